@@ -0,0 +1,338 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Export the blocks in the local database to a file, or import the blocks in
+//! such a file outside of the P2P sync.
+//!
+//! The file is a stream of RLP encoded blocks, each of them encoded by
+//! `Block::encode_with_pow_hash`. Blocks are ordered by epoch and the blocks
+//! in an epoch are in their execution order, so every block comes after its
+//! parent and referees.
+
+use super::helpers::{open_block_db, parse_arg};
+use blockgen::BlockGenerator;
+use cfxcore::{
+    BlockDataManager, ConsensusGraphTrait, NodeType, SynchronizationGraph,
+};
+use clap;
+use client::{
+    common::{initialize_common_modules, shutdown_handler, ClientComponents},
+    configuration::Configuration,
+};
+use parking_lot::{Condvar, Mutex};
+use primitives::Block;
+use rlp::{PayloadInfo, Rlp};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct ExportBlocks {
+    pub output: String,
+    pub from: u64,
+    pub to: Option<u64>,
+}
+
+impl ExportBlocks {
+    pub fn new(matches: &clap::ArgMatches) -> Result<Self, String> {
+        let from = parse_arg(matches, "from")?;
+        if from == 0 {
+            return Err(
+                "The genesis block is not exported, epochs start from 1".into(),
+            );
+        }
+        let to = match matches.value_of("to") {
+            Some(to) => Some(
+                to.parse()
+                    .map_err(|_| format!("Invalid value of to: {}", to))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            output: matches
+                .value_of("output")
+                .expect("CLI argument is required; qed")
+                .to_string(),
+            from,
+            to,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ImportBlocks {
+    pub file: String,
+    pub skip_pow_check: bool,
+}
+
+impl ImportBlocks {
+    pub fn new(matches: &clap::ArgMatches) -> Self {
+        Self {
+            file: matches
+                .value_of("file")
+                .expect("CLI argument is required; qed")
+                .to_string(),
+            skip_pow_check: matches.is_present("skip-pow-check"),
+        }
+    }
+}
+
+/// Writes the blocks of the executed epochs in `[from, to]` to the output
+/// file. If `to` is not given, the export stops at the first epoch that is
+/// not executed.
+pub fn export(
+    cmd: ExportBlocks, conf: &Configuration,
+) -> Result<String, String> {
+    let db_manager = open_block_db(conf)?;
+
+    let file = File::create(&cmd.output)
+        .map_err(|e| format!("Cannot create file: {:?}", e))?;
+    let mut writer = BufWriter::new(file);
+
+    let mut epoch = cmd.from;
+    let mut block_count = 0;
+    while cmd.to.map_or(true, |to| epoch <= to) {
+        let executed_hashes =
+            match db_manager.executed_epoch_set_hashes_from_db(epoch) {
+                Some(hashes) => hashes,
+                None if cmd.to.is_none() => break,
+                None => {
+                    return Err(format!(
+                        "Executed epoch {} is not found in database",
+                        epoch
+                    ))
+                }
+            };
+        let mut hashes = db_manager
+            .skipped_epoch_set_hashes_from_db(epoch)
+            .ok_or_else(|| {
+                format!("Skipped blocks of epoch {} are not found", epoch)
+            })?;
+        hashes.extend(executed_hashes);
+
+        for hash in hashes {
+            let block = db_manager.block_from_db(&hash).ok_or_else(|| {
+                format!("Block {:?} is not found in database", hash)
+            })?;
+            writer
+                .write_all(&block.encode_with_pow_hash())
+                .map_err(|e| format!("Cannot write block: {:?}", e))?;
+            block_count += 1;
+        }
+        epoch += 1;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| format!("Cannot flush file: {:?}", e))?
+        .sync_all()
+        .map_err(|e| format!("Cannot sync file: {:?}", e))?;
+
+    if epoch == cmd.from {
+        return Err(format!("Executed epoch {} is not found", cmd.from));
+    }
+    Ok(format!(
+        "Exported {} blocks of epochs [{}, {}] to {}",
+        block_count,
+        cmd.from,
+        epoch - 1,
+        cmd.output
+    ))
+}
+
+/// Inserts the blocks in the file to the sync graph with full verification,
+/// except the proof of work if `skip_pow_check` is set, and waits for the
+/// consensus graph to process them.
+///
+/// The node must be stopped. The P2P network is not started, so the blocks
+/// referring to PoS blocks unknown to the local PoS ledger stay pending.
+pub fn import(
+    cmd: ImportBlocks, mut conf: Configuration,
+) -> Result<String, String> {
+    let node_type = conf.node_type();
+    if node_type == NodeType::Light {
+        return Err("Blocks cannot be imported to a light node".into());
+    }
+    let mut reader = BufReader::new(
+        File::open(&cmd.file)
+            .map_err(|e| format!("Cannot open file: {:?}", e))?,
+    );
+
+    conf.skip_pow_hash_verification = cmd.skip_pow_check;
+    let exit = Arc::new((Mutex::new(false), Condvar::new()));
+    let (
+        machine,
+        secret_store,
+        genesis_accounts,
+        data_man,
+        pow,
+        pos_verifier,
+        txpool,
+        consensus,
+        sync_graph,
+        network,
+        common_impl,
+        accounts,
+        notifications,
+        pubsub,
+        eth_pubsub,
+        tokio_runtime,
+    ) = initialize_common_modules(&mut conf, exit, node_type)?;
+
+    let result =
+        insert_blocks(&mut reader, &data_man, &sync_graph, cmd.skip_pow_check);
+
+    while sync_graph.is_consensus_worker_busy() {
+        thread::sleep(Duration::from_millis(100));
+    }
+    consensus.wait_for_generation(&consensus.best_block_hash());
+    let best_epoch = consensus.best_epoch_number();
+
+    let client = ClientComponents::<BlockGenerator, _> {
+        data_manager_weak_ptr: Arc::downgrade(&data_man),
+        blockgen: None,
+        pos_handler: Some(pos_verifier),
+        other_components: (
+            machine,
+            secret_store,
+            genesis_accounts,
+            pow,
+            txpool,
+            consensus,
+            sync_graph,
+            network,
+            common_impl,
+            accounts,
+            notifications,
+            pubsub,
+            eth_pubsub,
+            tokio_runtime,
+        ),
+    };
+    drop(data_man);
+    shutdown_handler::shutdown(Box::new(client));
+
+    result.map(|(imported, skipped)| {
+        format!(
+            "Imported {} blocks and skipped {} known blocks, best epoch {}",
+            imported, skipped, best_epoch
+        )
+    })
+}
+
+/// Returns the number of the imported blocks and the skipped known blocks.
+fn insert_blocks(
+    reader: &mut impl Read, data_man: &BlockDataManager,
+    sync_graph: &SynchronizationGraph, skip_pow_check: bool,
+) -> Result<(usize, usize), String> {
+    // Continue with the blocks already in the database.
+    if data_man.terminals_from_db().is_some() {
+        sync_graph.recover_graph_from_db();
+        if !sync_graph.complete_filling_block_bodies() {
+            return Err("Some block bodies are missing in database".into());
+        }
+    }
+
+    let mut imported = 0;
+    let mut skipped = 0;
+    while let Some(raw) = read_rlp_item(reader)
+        .map_err(|e| format!("Cannot read file: {:?}", e))?
+    {
+        let mut block = Block::decode_with_pow_hash(&Rlp::new(&raw))
+            .map_err(|e| format!("Cannot decode block: {:?}", e))?;
+        let hash = block.hash();
+        data_man
+            .recover_block(&mut block)
+            .map_err(|e| format!("Cannot recover block {:?}: {:?}", hash, e))?;
+        // A filled `pow_hash` is trusted by the verification, so it is
+        // recomputed unless the file is trusted.
+        if !skip_pow_check {
+            block.block_header.pow_hash = None;
+        }
+
+        let (header_result, _) = sync_graph.insert_block_header(
+            &mut block.block_header,
+            true,  /* need_to_verify */
+            false, /* bench_mode */
+            false, /* insert_to_consensus */
+            true,  /* persistent */
+        );
+        if header_result.is_invalid() {
+            return Err(format!("Invalid block header {:?}", hash));
+        }
+        if !header_result.should_process_body() {
+            skipped += 1;
+            continue;
+        }
+
+        let block_result = sync_graph.insert_block(
+            block, true,  /* need_to_verify */
+            true,  /* persistent */
+            false, /* recover_from_db */
+        );
+        if !block_result.is_valid() {
+            return Err(format!("Invalid block {:?}", hash));
+        }
+        imported += 1;
+    }
+    Ok((imported, skipped))
+}
+
+/// Reads the next RLP item from the stream, or `None` at the end of it.
+fn read_rlp_item(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut item = vec![0u8];
+    match reader.read_exact(&mut item) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    // Long lists and strings carry the length of their payload length.
+    let length_of_length = match item[0] {
+        0xb8..=0xbf => item[0] - 0xb7,
+        0xf8..=0xff => item[0] - 0xf7,
+        _ => 0,
+    };
+    item.resize(1 + length_of_length as usize, 0);
+    reader.read_exact(&mut item[1..])?;
+
+    let total = PayloadInfo::from(&item)
+        .map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e))
+        })?
+        .total();
+    let header_len = item.len();
+    item.resize(total, 0);
+    reader.read_exact(&mut item[header_len..])?;
+    Ok(Some(item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_rlp_item;
+    use rlp::RlpStream;
+
+    #[test]
+    fn read_rlp_stream() {
+        let mut short = RlpStream::new_list(2);
+        short.append(&1u8).append(&"block");
+        let mut long = RlpStream::new_list(1);
+        long.append(&vec![7u8; 100]);
+        let (short, long) = (short.out().to_vec(), long.out().to_vec());
+
+        let mut stream = short.clone();
+        stream.extend_from_slice(&long);
+        let mut reader = &stream[..];
+        assert_eq!(read_rlp_item(&mut reader).unwrap(), Some(short));
+        assert_eq!(read_rlp_item(&mut reader).unwrap(), Some(long));
+        assert_eq!(read_rlp_item(&mut reader).unwrap(), None);
+
+        let mut truncated = &stream[..3];
+        assert!(read_rlp_item(&mut truncated).is_err());
+    }
+}
@@ -18,11 +18,15 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
+use cfxcore::{block_data_manager::db_manager::DBManager, pow::PowComputer};
 use cfxkey::Password;
+use client::configuration::Configuration;
 use rpassword::read_password;
 use std::{
     fs::File,
     io::{self, BufRead, BufReader, Write},
+    str::FromStr,
+    sync::Arc,
 };
 
 const PASSWORD_STDIN_ERROR: &str =
@@ -91,3 +95,28 @@ pub fn passwords_from_files(files: &[String]) -> Result<Vec<Password>, String> {
 	}).collect::<Result<Vec<Vec<Password>>, String>>();
     Ok(passwords?.into_iter().flatten().collect())
 }
+
+/// Parse the value of a required argument or an argument with default value.
+pub fn parse_arg<T: FromStr>(
+    matches: &clap::ArgMatches, name: &str,
+) -> Result<T, String> {
+    let value = matches
+        .value_of(name)
+        .expect("CLI argument is required or has a default value; qed");
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value of {}: {}", name, value))
+}
+
+/// Open the block database of a stopped node for reading.
+pub fn open_block_db(conf: &Configuration) -> Result<DBManager, String> {
+    if conf.raw_conf.block_db_type != "rocksdb" {
+        return Err("Only rocksdb block database is supported".into());
+    }
+
+    let (db_path, db_config) = conf.db_config();
+    let db = db::open_database(db_path.to_str().unwrap(), &db_config)
+        .map_err(|e| format!("Cannot open block database: {:?}", e))?;
+    let pow = Arc::new(PowComputer::new(conf.pow_config().use_octopus()));
+    Ok(DBManager::new_from_rocksdb(db, pow))
+}
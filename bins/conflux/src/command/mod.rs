@@ -3,5 +3,8 @@
 // See http://www.gnu.org/licenses/
 
 pub mod account;
+pub mod blocks;
 pub mod helpers;
+pub mod pivot_hint;
+pub mod pos_backup;
 pub mod rpc;
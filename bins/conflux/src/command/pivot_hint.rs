@@ -0,0 +1,162 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use super::helpers::{open_block_db, parse_arg};
+use cfx_types::H256;
+use cfxcore::{
+    block_data_manager::db_manager::DBManager,
+    consensus::pivot_hint::{
+        build_pivot_hint_file, PivotHint, PivotHintConfig, PivotHintParams,
+    },
+};
+use clap;
+use client::configuration::Configuration;
+use std::{path::Path, str::FromStr};
+
+#[derive(Debug, PartialEq)]
+pub enum PivotHintCmd {
+    Generate(GeneratePivotHint),
+    Verify(VerifyPivotHint),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GeneratePivotHint {
+    pub output: String,
+    pub range_max: u64,
+    pub page_interval: u32,
+    pub major_interval: u32,
+    pub minor_interval: u32,
+    pub minor_hash_length: u32,
+}
+
+impl GeneratePivotHint {
+    pub fn new(matches: &clap::ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            output: matches
+                .value_of("output")
+                .expect("CLI argument is required; qed")
+                .to_string(),
+            range_max: parse_arg(matches, "range-max")?,
+            page_interval: parse_arg(matches, "page-interval")?,
+            major_interval: parse_arg(matches, "major-interval")?,
+            minor_interval: parse_arg(matches, "minor-interval")?,
+            minor_hash_length: parse_arg(matches, "minor-hash-length")?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyPivotHint {
+    pub file: String,
+    pub checksum: Option<H256>,
+}
+
+impl VerifyPivotHint {
+    pub fn new(matches: &clap::ArgMatches) -> Result<Self, String> {
+        let checksum = matches
+            .value_of("checksum")
+            .map(|checksum| {
+                H256::from_str(checksum.trim_start_matches("0x"))
+                    .map_err(|e| format!("Invalid checksum: {:?}", e))
+            })
+            .transpose()?;
+        Ok(Self {
+            file: matches
+                .value_of("file")
+                .expect("CLI argument is required; qed")
+                .to_string(),
+            checksum,
+        })
+    }
+}
+
+pub fn execute(
+    cmd: PivotHintCmd, conf: &Configuration,
+) -> Result<String, String> {
+    let pivot_chain = LocalPivotChain::open(conf)?;
+    match cmd {
+        PivotHintCmd::Generate(generate_cmd) => {
+            generate(generate_cmd, &pivot_chain)
+        }
+        PivotHintCmd::Verify(verify_cmd) => verify(verify_cmd, &pivot_chain),
+    }
+}
+
+fn generate(
+    cmd: GeneratePivotHint, pivot_chain: &LocalPivotChain,
+) -> Result<String, String> {
+    let params = PivotHintParams {
+        range_max: cmd.range_max,
+        page_interval: cmd.page_interval,
+        major_interval: cmd.major_interval,
+        minor_interval: cmd.minor_interval,
+        minor_hash_length: cmd.minor_hash_length,
+    };
+    let checksum =
+        build_pivot_hint_file(Path::new(&cmd.output), &params, |height| {
+            pivot_chain.pivot_hash_at(height)
+        })?;
+
+    Ok(format!(
+        "Pivot hint file {} generated with range_max {}\npivot_hint_checksum: {:x}",
+        cmd.output, cmd.range_max, checksum
+    ))
+}
+
+fn verify(
+    cmd: VerifyPivotHint, pivot_chain: &LocalPivotChain,
+) -> Result<String, String> {
+    let checksum = PivotHint::file_checksum(&cmd.file)?;
+    if let Some(expected) = cmd.checksum {
+        if expected != checksum {
+            return Err(format!(
+                "Incorrect checksum, expected {:?}, got {:?}",
+                expected, checksum
+            ));
+        }
+    }
+
+    let pivot_hint =
+        PivotHint::new(&PivotHintConfig::new(&cmd.file, checksum))?;
+    pivot_hint.verify_all(|height| pivot_chain.pivot_hash_at(height))?;
+
+    Ok(format!(
+        "Pivot hint file {} matches the local pivot chain below height {}\npivot_hint_checksum: {:x}",
+        cmd.file,
+        pivot_hint.range_max(),
+        checksum
+    ))
+}
+
+/// The pivot chain of the executed epochs in the local block database.
+struct LocalPivotChain {
+    db_manager: DBManager,
+}
+
+impl LocalPivotChain {
+    fn open(conf: &Configuration) -> Result<Self, String> {
+        Ok(Self {
+            db_manager: open_block_db(conf)?,
+        })
+    }
+
+    fn pivot_hash_at(&self, height: u64) -> Result<H256, String> {
+        // The epoch set of the genesis block is not persisted.
+        if height == 0 {
+            let hash = self.pivot_hash_at(1)?;
+            let header =
+                self.db_manager.block_header_from_db(&hash).ok_or_else(
+                    || format!("Block {:?} is not found in database", hash),
+                )?;
+            return Ok(*header.parent_hash());
+        }
+
+        self.db_manager
+            .executed_epoch_set_hashes_from_db(height)
+            .and_then(|hashes| hashes.last().cloned())
+            .ok_or_else(|| {
+                format!("Executed epoch {} is not found in database", height)
+            })
+    }
+}
@@ -0,0 +1,364 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Back up the PoS ledger of a running node to a local directory, or restore
+//! the PoS ledger database from such a directory.
+//!
+//! The backups are taken from the backup service of the node, which is
+//! started with `pos_backup_service_address` set. A backup directory can be
+//! updated repeatedly: each run only adds the epoch endings and transactions
+//! committed since the last run and a state snapshot of the latest version.
+
+use anyhow::anyhow;
+use backup_cli::{
+    backup_types::{
+        epoch_ending::backup::{
+            EpochEndingBackupController, EpochEndingBackupOpt,
+        },
+        state_snapshot::backup::{
+            StateSnapshotBackupController, StateSnapshotBackupOpt,
+        },
+        transaction::backup::{
+            TransactionBackupController, TransactionBackupOpt,
+        },
+    },
+    coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt},
+    metadata::cache::{self, MetadataCacheOpt},
+    storage::{local_fs::LocalFs, BackupStorage},
+    utils::{
+        backup_service_client::BackupServiceClient, ConcurrentDownloadsOpt,
+        GlobalBackupOpt, GlobalRestoreOpt, GlobalRestoreOptions, RocksdbOpt,
+        TrustedWaypointOpt,
+    },
+};
+use clap;
+use client::configuration::Configuration;
+use diem_config::config::NodeConfig;
+use std::{
+    convert::TryInto, fs, future::Future, net::SocketAddr, path::PathBuf,
+    sync::Arc,
+};
+use tokio::runtime::Builder;
+
+// Same as the default of `db-backup`, so concurrent chunk downloads won't
+// take up too much memory.
+const MAX_CHUNK_SIZE: usize = 128 * 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub struct PosBackup {
+    pub dir: PathBuf,
+    pub address: Option<SocketAddr>,
+}
+
+impl PosBackup {
+    pub fn new(matches: &clap::ArgMatches) -> Result<Self, String> {
+        let address = match matches.value_of("address") {
+            Some(address) => Some(address.parse().map_err(|_| {
+                format!("Invalid value of address: {}", address)
+            })?),
+            None => None,
+        };
+        Ok(Self {
+            dir: matches
+                .value_of("dir")
+                .expect("CLI argument is required; qed")
+                .into(),
+            address,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PosRestore {
+    pub dir: PathBuf,
+    pub target_version: Option<u64>,
+}
+
+impl PosRestore {
+    pub fn new(matches: &clap::ArgMatches) -> Result<Self, String> {
+        let target_version = match matches.value_of("target-version") {
+            Some(version) => Some(version.parse().map_err(|_| {
+                format!("Invalid value of target-version: {}", version)
+            })?),
+            None => None,
+        };
+        Ok(Self {
+            dir: matches
+                .value_of("dir")
+                .expect("CLI argument is required; qed")
+                .into(),
+            target_version,
+        })
+    }
+}
+
+/// Backs up the PoS ledger up to the latest committed version through the
+/// backup service of a running node.
+pub fn backup(cmd: PosBackup, conf: &Configuration) -> Result<String, String> {
+    let address = cmd
+        .address
+        .or(conf.raw_conf.pos_backup_service_address)
+        .ok_or_else(|| {
+            "The backup service address is neither given nor configured by \
+             pos_backup_service_address"
+                .to_string()
+        })?;
+    fs::create_dir_all(&cmd.dir)
+        .map_err(|e| format!("Cannot create backup dir: {:?}", e))?;
+
+    block_on(async move {
+        let client =
+            Arc::new(BackupServiceClient::new(format!("http://{}", address)));
+        let db_state = client
+            .get_db_state()
+            .await?
+            .ok_or_else(|| anyhow!("PoS ledger is not bootstrapped"))?;
+        let storage: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(cmd.dir));
+        let backup_state = cache::sync_and_load(
+            &MetadataCacheOpt::new(None),
+            storage.clone(),
+            ConcurrentDownloadsOpt::default().get(),
+        )
+        .await?
+        .get_storage_state();
+        let global_opt = GlobalBackupOpt {
+            max_chunk_size: MAX_CHUNK_SIZE,
+        };
+
+        // The epoch ending ledger infos are backed up first, so the proofs of
+        // the other backups can always be verified with the same storage.
+        let start_epoch = backup_state
+            .latest_epoch_ending_epoch
+            .map_or(0, |epoch| epoch + 1);
+        if start_epoch < db_state.epoch {
+            EpochEndingBackupController::new(
+                EpochEndingBackupOpt {
+                    start_epoch,
+                    end_epoch: db_state.epoch,
+                },
+                global_opt.clone(),
+                client.clone(),
+                storage.clone(),
+            )
+            .run()
+            .await?;
+        }
+
+        let start_version = backup_state
+            .latest_transaction_version
+            .map_or(0, |version| version + 1);
+        if start_version <= db_state.committed_version {
+            TransactionBackupController::new(
+                TransactionBackupOpt {
+                    start_version,
+                    num_transactions: (db_state.committed_version + 1
+                        - start_version)
+                        as usize,
+                },
+                global_opt.clone(),
+                client.clone(),
+                storage.clone(),
+            )
+            .run()
+            .await?;
+        }
+
+        if backup_state.latest_state_snapshot_version
+            != Some(db_state.committed_version)
+        {
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt {
+                    version: db_state.committed_version,
+                },
+                global_opt,
+                client,
+                storage,
+            )
+            .run()
+            .await?;
+        }
+
+        Ok(format!(
+            "PoS ledger is backed up to version {} at epoch {}",
+            db_state.committed_version, db_state.epoch
+        ))
+    })
+}
+
+/// Restores the PoS ledger database of the node from the backups in a local
+/// directory. The node must be stopped.
+pub fn restore(
+    cmd: PosRestore, conf: &Configuration,
+) -> Result<String, String> {
+    let pos_config_path = conf
+        .raw_conf
+        .pos_config_path
+        .as_ref()
+        .ok_or_else(|| "No pos config!".to_string())?;
+    let mut pos_config = NodeConfig::load(pos_config_path)
+        .map_err(|e| format!("Failed to load node config: e={:?}", e))?;
+    pos_config.set_data_dir(pos_config.data_dir().to_path_buf());
+    let db_dir = pos_config.storage.dir();
+
+    block_on(async move {
+        // The ledger in the backups must start from the genesis of this
+        // chain.
+        let global_opt: GlobalRestoreOptions = GlobalRestoreOpt {
+            dry_run: false,
+            db_dir: Some(db_dir.clone()),
+            target_version: cmd.target_version,
+            trusted_waypoints: TrustedWaypointOpt {
+                trust_waypoint: vec![pos_config
+                    .base
+                    .waypoint
+                    .genesis_waypoint()],
+            },
+            rocksdb_opt: RocksdbOpt::default(),
+            concurernt_downloads: ConcurrentDownloadsOpt::default(),
+        }
+        .try_into()?;
+        RestoreCoordinator::new(
+            RestoreCoordinatorOpt {
+                metadata_cache_opt: MetadataCacheOpt::new(None),
+                replay_all: false,
+            },
+            global_opt,
+            Arc::new(LocalFs::new(cmd.dir)),
+        )
+        .run()
+        .await?;
+
+        Ok(format!("PoS ledger is restored to {:?}", db_dir))
+    })
+}
+
+fn block_on<F>(future: F) -> Result<String, String>
+where F: Future<Output = anyhow::Result<String>> {
+    Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create runtime: {:?}", e))?
+        .block_on(future)
+        .map_err(|e| format!("{:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PosBackup, PosRestore};
+    use clap::{load_yaml, App};
+
+    #[test]
+    fn parse_pos_backup() {
+        let yaml = load_yaml!("../cli.yaml");
+        let app = App::from_yaml(yaml);
+
+        let matches = app.clone().get_matches_from(vec![
+            "conflux",
+            "pos-backup",
+            "--dir",
+            "backups",
+            "--address",
+            "127.0.0.1:32012",
+        ]);
+        let cmd =
+            PosBackup::new(matches.subcommand_matches("pos-backup").unwrap());
+        assert_eq!(
+            cmd,
+            Ok(PosBackup {
+                dir: "backups".into(),
+                address: Some("127.0.0.1:32012".parse().unwrap()),
+            })
+        );
+
+        let matches = app.clone().get_matches_from(vec![
+            "conflux",
+            "pos-backup",
+            "--dir",
+            "backups",
+        ]);
+        let cmd =
+            PosBackup::new(matches.subcommand_matches("pos-backup").unwrap());
+        assert_eq!(
+            cmd,
+            Ok(PosBackup {
+                dir: "backups".into(),
+                address: None,
+            })
+        );
+
+        let matches = app.clone().get_matches_from(vec![
+            "conflux",
+            "pos-backup",
+            "--dir",
+            "backups",
+            "--address",
+            "localhost",
+        ]);
+        assert!(PosBackup::new(
+            matches.subcommand_matches("pos-backup").unwrap()
+        )
+        .is_err());
+
+        assert!(app
+            .get_matches_from_safe(vec!["conflux", "pos-backup"])
+            .is_err());
+    }
+
+    #[test]
+    fn parse_pos_restore() {
+        let yaml = load_yaml!("../cli.yaml");
+        let app = App::from_yaml(yaml);
+
+        let matches = app.clone().get_matches_from(vec![
+            "conflux",
+            "pos-restore",
+            "--dir",
+            "backups",
+            "--target-version",
+            "1000",
+        ]);
+        let cmd =
+            PosRestore::new(matches.subcommand_matches("pos-restore").unwrap());
+        assert_eq!(
+            cmd,
+            Ok(PosRestore {
+                dir: "backups".into(),
+                target_version: Some(1000),
+            })
+        );
+
+        let matches = app.clone().get_matches_from(vec![
+            "conflux",
+            "pos-restore",
+            "--dir",
+            "backups",
+        ]);
+        let cmd =
+            PosRestore::new(matches.subcommand_matches("pos-restore").unwrap());
+        assert_eq!(
+            cmd,
+            Ok(PosRestore {
+                dir: "backups".into(),
+                target_version: None,
+            })
+        );
+
+        let matches = app.clone().get_matches_from(vec![
+            "conflux",
+            "pos-restore",
+            "--dir",
+            "backups",
+            "--target-version",
+            "latest",
+        ]);
+        assert!(PosRestore::new(
+            matches.subcommand_matches("pos-restore").unwrap()
+        )
+        .is_err());
+
+        assert!(app
+            .get_matches_from_safe(vec!["conflux", "pos-restore"])
+            .is_err());
+    }
+}
@@ -17,7 +17,12 @@ use client::{
     full::FullClient,
     light::LightClient,
 };
-use command::account::{AccountCmd, ImportAccounts, ListAccounts, NewAccount};
+use command::{
+    account::{AccountCmd, ImportAccounts, ListAccounts, NewAccount},
+    blocks::{ExportBlocks, ImportBlocks},
+    pivot_hint::{GeneratePivotHint, PivotHintCmd, VerifyPivotHint},
+    pos_backup::{PosBackup, PosRestore},
+};
 use log::{info, LevelFilter};
 use log4rs::{
     append::{console::ConsoleAppender, file::FileAppender},
@@ -202,6 +207,51 @@ fn handle_sub_command(matches: &ArgMatches) -> Result<Option<String>, String> {
         return Ok(Some(execute_output));
     }
 
+    // pivot hint sub-commands
+    if let ("pivot-hint", Some(pivot_hint_matches)) = matches.subcommand() {
+        let pivot_hint_cmd = match pivot_hint_matches.subcommand() {
+            ("generate", Some(generate_matches)) => PivotHintCmd::Generate(
+                GeneratePivotHint::new(generate_matches)?,
+            ),
+            ("verify", Some(verify_matches)) => {
+                PivotHintCmd::Verify(VerifyPivotHint::new(verify_matches)?)
+            }
+            _ => unreachable!(),
+        };
+        let conf = Configuration::parse(matches)?;
+        let execute_output =
+            command::pivot_hint::execute(pivot_hint_cmd, &conf)?;
+        return Ok(Some(execute_output));
+    }
+
+    // block export and import sub-commands
+    if let ("export-blocks", Some(export_matches)) = matches.subcommand() {
+        let export_cmd = ExportBlocks::new(export_matches)?;
+        let conf = Configuration::parse(matches)?;
+        let execute_output = command::blocks::export(export_cmd, &conf)?;
+        return Ok(Some(execute_output));
+    }
+    if let ("import-blocks", Some(import_matches)) = matches.subcommand() {
+        let import_cmd = ImportBlocks::new(import_matches);
+        let conf = Configuration::parse(matches)?;
+        let execute_output = command::blocks::import(import_cmd, conf)?;
+        return Ok(Some(execute_output));
+    }
+
+    // PoS ledger backup and restore sub-commands
+    if let ("pos-backup", Some(backup_matches)) = matches.subcommand() {
+        let backup_cmd = PosBackup::new(backup_matches)?;
+        let conf = Configuration::parse(matches)?;
+        let execute_output = command::pos_backup::backup(backup_cmd, &conf)?;
+        return Ok(Some(execute_output));
+    }
+    if let ("pos-restore", Some(restore_matches)) = matches.subcommand() {
+        let restore_cmd = PosRestore::new(restore_matches)?;
+        let conf = Configuration::parse(matches)?;
+        let execute_output = command::pos_backup::restore(restore_cmd, &conf)?;
+        return Ok(Some(execute_output));
+    }
+
     // general RPC commands
     let mut subcmd_matches = matches;
     while let Some(m) = subcmd_matches.subcommand().1 {
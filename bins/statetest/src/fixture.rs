@@ -0,0 +1,105 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! The filled GeneralStateTests fixtures of the Ethereum tests repository.
+
+use cfx_types::{Address, H256, U256};
+use primitives::transaction::AccessList;
+use rustc_hex::FromHex;
+use serde::{de::Error, Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// A fixture file maps the test names to the tests.
+pub type StateTests = BTreeMap<String, StateTest>;
+
+#[derive(Debug, Deserialize)]
+pub struct StateTest {
+    pub env: Env,
+    pub pre: BTreeMap<Address, AccountState>,
+    pub transaction: Transaction,
+    /// The expected results of each fork.
+    pub post: BTreeMap<String, Vec<PostState>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Env {
+    pub current_coinbase: Address,
+    pub current_difficulty: U256,
+    pub current_gas_limit: U256,
+    pub current_number: U256,
+    pub current_timestamp: U256,
+    pub current_base_fee: Option<U256>,
+    /// The PREVRANDAO value after the merge.
+    pub current_random: Option<H256>,
+    pub previous_hash: Option<H256>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountState {
+    pub balance: U256,
+    #[serde(deserialize_with = "deserialize_hex_bytes")]
+    pub code: Vec<u8>,
+    pub nonce: U256,
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// The transaction is a template, and each post state picks its data, gas
+/// limit and value by `Indexes`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    #[serde(deserialize_with = "deserialize_hex_bytes_list")]
+    pub data: Vec<Vec<u8>>,
+    pub gas_limit: Vec<U256>,
+    pub value: Vec<U256>,
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub nonce: U256,
+    pub secret_key: H256,
+    /// Empty for contract creation.
+    pub to: String,
+    pub access_lists: Option<Vec<Option<AccessList>>>,
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostState {
+    /// The state root.
+    pub hash: H256,
+    /// The hash of the RLP encoded logs.
+    pub logs: H256,
+    pub indexes: Indexes,
+    pub expect_exception: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Indexes {
+    pub data: usize,
+    pub gas: usize,
+    pub value: usize,
+}
+
+fn parse_hex_bytes<E: Error>(s: &str) -> Result<Vec<u8>, E> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    s.from_hex()
+        .map_err(|e| E::custom(format!("Invalid hex bytes {}: {}", s, e)))
+}
+
+fn deserialize_hex_bytes<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where D: Deserializer<'de> {
+    parse_hex_bytes(&String::deserialize(deserializer)?)
+}
+
+fn deserialize_hex_bytes_list<'de, D>(
+    deserializer: D,
+) -> Result<Vec<Vec<u8>>, D::Error>
+where D: Deserializer<'de> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|s| parse_hex_bytes(s))
+        .collect()
+}
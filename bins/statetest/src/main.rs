@@ -0,0 +1,144 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Runs the filled GeneralStateTests of the Ethereum tests repository against
+//! the eSpace executor and reports the results of each fork, so the
+//! regressions of EVM equivalence can be tracked.
+//!
+//! ```text
+//! statetest [--fork <fork>] <path>...
+//! ```
+//!
+//! Each path is a fixture file or a directory searched recursively for the
+//! `.json` fixtures. The process exits with code 1 if any case fails.
+
+mod fixture;
+mod runner;
+mod trie;
+
+use clap::{App, Arg};
+use fixture::StateTests;
+use log::info;
+use runner::Outcome;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+#[derive(Default)]
+struct ForkSummary {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+fn main() {
+    env_logger::init();
+
+    let matches = App::new("statetest")
+        .about(
+            "Runs the Ethereum GeneralStateTests against the eSpace executor",
+        )
+        .arg(
+            Arg::with_name("fork")
+                .long("fork")
+                .value_name("FORK")
+                .help("Only runs the cases of the fork, e.g. Shanghai")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("path")
+                .value_name("PATH")
+                .help("The fixture files or directories")
+                .required(true)
+                .multiple(true),
+        )
+        .get_matches();
+    let fork_filter = matches.value_of("fork");
+
+    let mut files = vec![];
+    for path in matches.values_of("path").expect("required") {
+        if let Err(e) = collect_fixtures(Path::new(path), &mut files) {
+            eprintln!("Cannot read {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+
+    let mut summaries = BTreeMap::<String, ForkSummary>::new();
+    let mut failed = false;
+    for file in &files {
+        info!("Running {}", file.display());
+        let tests = match load_fixture(file) {
+            Ok(tests) => tests,
+            Err(e) => {
+                println!("FAIL {}: {}", file.display(), e);
+                failed = true;
+                continue;
+            }
+        };
+        for (name, test) in &tests {
+            for result in runner::run_test(test, fork_filter) {
+                let summary = summaries.entry(result.fork.clone()).or_default();
+                match result.outcome {
+                    Outcome::Passed => summary.passed += 1,
+                    Outcome::Skipped(_) => summary.skipped += 1,
+                    Outcome::Failed(reason) => {
+                        summary.failed += 1;
+                        failed = true;
+                        println!(
+                            "FAIL {} {} {} d{}g{}v{}: {}",
+                            file.display(),
+                            name,
+                            result.fork,
+                            result.indexes.data,
+                            result.indexes.gas,
+                            result.indexes.value,
+                            reason
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    for (fork, summary) in &summaries {
+        println!(
+            "{}: {} passed, {} failed, {} skipped",
+            fork, summary.passed, summary.failed, summary.skipped
+        );
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn collect_fixtures(
+    path: &Path, files: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_fixtures(&entry, files)?;
+        } else if entry.extension().map_or(false, |ext| ext == "json") {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn load_fixture(path: &Path) -> Result<StateTests, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid fixture: {}", e))
+}
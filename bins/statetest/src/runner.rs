@@ -0,0 +1,539 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Executes the state tests in eSpace and checks the post state root and the
+//! logs hash against the fixtures.
+
+use crate::{
+    fixture::{self, AccountState, Indexes, PostState, StateTest},
+    trie::sec_trie_root,
+};
+use cfx_executor::{
+    executive::{
+        execution_outcome::ToRepackError, ChargeCollateral, ExecutionError,
+        ExecutionOutcome, ExecutiveContext, TransactOptions, TransactSettings,
+        TxDropError,
+    },
+    machine::{Machine, VmFactory},
+    spec::CommonParams,
+    state::{CleanupMode, State},
+    substate::Substate,
+};
+use cfx_statedb::{InmemoryStorage, StateDb};
+use cfx_types::{
+    Address, AddressSpaceUtil, BigEndianHash, Space, SpaceMap, H256, U256,
+};
+use cfx_vm_types::Env;
+use cfxkey::Secret;
+use keccak_hash::keccak;
+use primitives::{
+    storage_key::SkipInputCheck,
+    transaction::{
+        eth_transaction::{
+            Eip1559Transaction, Eip155Transaction, Eip2930Transaction,
+            EthereumTransaction,
+        },
+        Action,
+    },
+    Account, EpochId, LogEntry, SignedTransaction, StorageKey,
+    StorageKeyWithSpace, StorageValue, Transaction,
+};
+use rlp::{Rlp, RlpStream};
+use std::{
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+};
+
+// The in-memory storage keeps the committed states by epoch id, the pre state
+// is shared by all the cases of a test.
+const PRE_STATE_EPOCH: EpochId = H256([0x01; 32]);
+const POST_STATE_EPOCH: EpochId = H256([0x02; 32]);
+
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+/// The result of a test with the transaction of `indexes` in `fork`.
+pub struct CaseResult {
+    pub fork: String,
+    pub indexes: Indexes,
+    pub outcome: Outcome,
+}
+
+pub fn run_test(
+    test: &StateTest, fork_filter: Option<&str>,
+) -> Vec<CaseResult> {
+    let pre_state = build_pre_state(&test.pre);
+
+    let mut results = vec![];
+    for (fork, posts) in &test.post {
+        if fork_filter.map_or(false, |filter| filter != fork) {
+            continue;
+        }
+        for post in posts {
+            let outcome = match (&pre_state, fork_params(fork)) {
+                (Err(e), _) => Outcome::Failed(e.clone()),
+                (_, None) => Outcome::Skipped("Unsupported fork".into()),
+                _ if test.transaction.blob_versioned_hashes.is_some() => {
+                    Outcome::Skipped("Blob transaction is not supported".into())
+                }
+                (Ok(()), Some(params)) => {
+                    // A panic of the executor only fails the case.
+                    match panic::catch_unwind(AssertUnwindSafe(|| {
+                        run_case(test, params, post)
+                    })) {
+                        Ok(Ok(())) => Outcome::Passed,
+                        Ok(Err(e)) => Outcome::Failed(e),
+                        Err(_) => Outcome::Failed("Executor panicked".into()),
+                    }
+                }
+            };
+            results.push(CaseResult {
+                fork: fork.clone(),
+                indexes: post.indexes,
+                outcome,
+            });
+        }
+    }
+    results
+}
+
+/// Maps the Ethereum forks to the Conflux upgrades bringing the same EVM
+/// changes. The forks before Berlin are not supported.
+fn fork_params(fork: &str) -> Option<CommonParams> {
+    let mut params = CommonParams::default();
+    let numbers = &mut params.transition_numbers;
+    match fork {
+        "Berlin" => {
+            params.transition_heights.cip1559 = u64::MAX;
+            numbers.cip119 = u64::MAX;
+            numbers.cancun_opcodes = u64::MAX;
+            numbers.cip144 = u64::MAX;
+        }
+        "London" | "Merge" | "Paris" => {
+            numbers.cip119 = u64::MAX;
+            numbers.cancun_opcodes = u64::MAX;
+            numbers.cip144 = u64::MAX;
+        }
+        "Shanghai" => {
+            numbers.cancun_opcodes = u64::MAX;
+            numbers.cip144 = u64::MAX;
+        }
+        "Cancun" => {}
+        _ => return None,
+    }
+    Some(params)
+}
+
+fn db_error<E: std::fmt::Debug>(e: E) -> String {
+    format!("Database error: {:?}", e)
+}
+
+fn build_pre_state(
+    pre: &BTreeMap<Address, AccountState>,
+) -> Result<(), String> {
+    let mut state =
+        State::new(StateDb::new_for_unit_test()).map_err(db_error)?;
+    let mut substate = Substate::new();
+    for (address, account) in pre {
+        let address = address.with_evm_space();
+        state
+            .add_balance(&address, &account.balance, CleanupMode::ForceCreate)
+            .map_err(db_error)?;
+        state
+            .set_nonce(&address, &account.nonce)
+            .map_err(db_error)?;
+        if !account.code.is_empty() {
+            state
+                .init_code(&address, account.code.clone(), Address::zero())
+                .map_err(db_error)?;
+        }
+        for (key, value) in &account.storage {
+            if value.is_zero() {
+                continue;
+            }
+            state
+                .set_storage(
+                    &address,
+                    H256::from_uint(key).0.to_vec(),
+                    *value,
+                    Address::zero(),
+                    &mut substate,
+                )
+                .map_err(db_error)?;
+        }
+    }
+    state.commit(PRE_STATE_EPOCH, None).map_err(db_error)?;
+    Ok(())
+}
+
+fn run_case(
+    test: &StateTest, params: CommonParams, post: &PostState,
+) -> Result<(), String> {
+    let env = make_env(&test.env, &params);
+    let chain_id = *env.chain_id.get(&Space::Ethereum).expect("exists");
+    let tx = make_transaction(&test.transaction, post.indexes, chain_id)?;
+    let machine = Machine::new_with_builtin(params, VmFactory::new(1024 * 32));
+    let spec = machine.spec(env.number, env.epoch_height);
+
+    let mut state =
+        State::new(StateDb::new_for_unit_test_with_epoch(&PRE_STATE_EPOCH))
+            .map_err(db_error)?;
+    let options = TransactOptions {
+        observer: (),
+        settings: TransactSettings {
+            charge_collateral: ChargeCollateral::Normal,
+            charge_gas: true,
+            check_base_price: true,
+            check_epoch_bound: false,
+        },
+    };
+    let outcome = ExecutiveContext::new(&mut state, &env, &machine, &spec)
+        .transact(&tx, options)
+        .map_err(db_error)?;
+
+    let executed = match (outcome, &post.expect_exception) {
+        (ExecutionOutcome::Finished(executed), None)
+        | (
+            ExecutionOutcome::ExecutionErrorBumpNonce(
+                ExecutionError::VmError(_),
+                executed,
+            ),
+            None,
+        ) => executed,
+        (ExecutionOutcome::Finished(_), Some(exception))
+        | (
+            ExecutionOutcome::ExecutionErrorBumpNonce(
+                ExecutionError::VmError(_),
+                _,
+            ),
+            Some(exception),
+        ) => {
+            return Err(format!(
+                "Expected exception {}, but the transaction is executed",
+                exception
+            ));
+        }
+        (outcome, Some(exception)) => {
+            return check_rejection(state, &outcome, exception);
+        }
+        (outcome, None) => {
+            return Err(format!("Transaction is rejected: {:?}", outcome));
+        }
+    };
+
+    // In Conflux the miner is rewarded at the end of the epoch, so reward
+    // the coinbase here as Ethereum does.
+    let reward = executed
+        .fee
+        .saturating_sub(executed.burnt_fee.unwrap_or_default());
+    state
+        .add_balance(
+            &env.author.with_evm_space(),
+            &reward,
+            CleanupMode::NoEmpty,
+        )
+        .map_err(db_error)?;
+
+    let logs = logs_hash(&executed.logs);
+    if logs != post.logs {
+        return Err(format!(
+            "Logs hash mismatch: expected {:?}, got {:?}",
+            post.logs, logs
+        ));
+    }
+    let root = post_state_root(state)?;
+    if root != post.hash {
+        return Err(format!(
+            "State root mismatch: expected {:?}, got {:?}",
+            post.hash, root
+        ));
+    }
+    Ok(())
+}
+
+/// The invalid transactions are not included in Ethereum, so the rejection
+/// must match the expected exception and leave the state unchanged.
+fn check_rejection(
+    state: State, outcome: &ExecutionOutcome, exception: &str,
+) -> Result<(), String> {
+    // Several exceptions may be acceptable, e.g.
+    // `TransactionException.INTRINSIC_GAS_TOO_LOW|TransactionException.
+    // INSUFFICIENT_ACCOUNT_FUNDS`.
+    let matched = exception.split('|').any(|exception| {
+        let exception = exception.trim();
+        let exception = exception
+            .strip_prefix("TransactionException.")
+            .unwrap_or(exception);
+        rejection_matches(exception, outcome)
+    });
+    if !matched {
+        return Err(format!(
+            "Expected exception {}, but the transaction is rejected: {:?}",
+            exception, outcome
+        ));
+    }
+
+    // Unlike Ethereum, Conflux charges the gas fee and bumps the nonce of a
+    // transaction that the sender cannot afford.
+    if let ExecutionOutcome::ExecutionErrorBumpNonce(..) = outcome {
+        return Ok(());
+    }
+    let root = post_state_root(state)?;
+    let pre_root = state_root(&PRE_STATE_EPOCH)?;
+    if root != pre_root {
+        return Err(format!(
+            "State is changed by the rejected transaction: expected {:?}, \
+             got {:?}",
+            pre_root, root
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the rejection of the executor is the counterpart of the exception.
+/// The exceptions without a counterpart, e.g. the ones checked before the
+/// execution in Conflux, are matched by any rejection.
+fn rejection_matches(exception: &str, outcome: &ExecutionOutcome) -> bool {
+    use ExecutionOutcome::*;
+    match exception {
+        "NONCE_MISMATCH_TOO_LOW" | "TR_NonceTooLow" => {
+            matches!(outcome, NotExecutedDrop(TxDropError::OldNonce(..)))
+        }
+        "NONCE_MISMATCH_TOO_HIGH" | "TR_NonceTooHigh" => matches!(
+            outcome,
+            NotExecutedToReconsiderPacking(ToRepackError::InvalidNonce { .. })
+        ),
+        "INSUFFICIENT_MAX_FEE_PER_GAS" | "TR_FeeCapLessThanBlocks" => matches!(
+            outcome,
+            NotExecutedToReconsiderPacking(
+                ToRepackError::NotEnoughBaseFee { .. }
+            )
+        ),
+        "INSUFFICIENT_ACCOUNT_FUNDS" | "TR_NoFunds" => matches!(
+            outcome,
+            ExecutionErrorBumpNonce(ExecutionError::NotEnoughCash { .. }, _)
+        ),
+        _ => matches!(
+            outcome,
+            NotExecutedDrop(_) | NotExecutedToReconsiderPacking(_)
+        ),
+    }
+}
+
+fn make_env(env: &fixture::Env, params: &CommonParams) -> Env {
+    let number = env.current_number.as_u64();
+    let base_fee = env.current_base_fee.unwrap_or_default();
+    Env {
+        chain_id: params.chain_id_map(number),
+        number,
+        author: env.current_coinbase,
+        timestamp: env.current_timestamp.as_u64(),
+        difficulty: env
+            .current_random
+            .map_or(env.current_difficulty, |random| random.into_uint()),
+        gas_limit: env.current_gas_limit,
+        last_hash: env.previous_hash.unwrap_or_default(),
+        epoch_height: number,
+        // The base fee is fully burnt in Ethereum.
+        base_gas_price: SpaceMap::new(U256::zero(), base_fee),
+        burnt_gas_price: SpaceMap::new(U256::zero(), base_fee),
+        ..Default::default()
+    }
+}
+
+fn make_transaction(
+    tx: &fixture::Transaction, indexes: Indexes, chain_id: u32,
+) -> Result<SignedTransaction, String> {
+    let data = tx
+        .data
+        .get(indexes.data)
+        .ok_or("Data index out of range")?
+        .clone();
+    let gas = *tx
+        .gas_limit
+        .get(indexes.gas)
+        .ok_or("Gas index out of range")?;
+    let value = *tx
+        .value
+        .get(indexes.value)
+        .ok_or("Value index out of range")?;
+    let action = if tx.to.is_empty() {
+        Action::Create
+    } else {
+        let to = tx.to.strip_prefix("0x").unwrap_or(&tx.to);
+        Action::Call(
+            to.parse()
+                .map_err(|_| format!("Invalid recipient: {}", tx.to))?,
+        )
+    };
+    let access_list = tx
+        .access_lists
+        .as_ref()
+        .and_then(|lists| lists.get(indexes.data).cloned().flatten());
+
+    let unsigned = match (tx.max_fee_per_gas, access_list) {
+        (Some(max_fee_per_gas), access_list) => {
+            EthereumTransaction::Eip1559(Eip1559Transaction {
+                chain_id,
+                nonce: tx.nonce,
+                max_priority_fee_per_gas: tx
+                    .max_priority_fee_per_gas
+                    .unwrap_or_default(),
+                max_fee_per_gas,
+                gas,
+                action,
+                value,
+                data,
+                access_list: access_list.unwrap_or_default(),
+            })
+        }
+        (None, Some(access_list)) => {
+            EthereumTransaction::Eip2930(Eip2930Transaction {
+                chain_id,
+                nonce: tx.nonce,
+                gas_price: tx.gas_price.ok_or("Gas price is missing")?,
+                gas,
+                action,
+                value,
+                data,
+                access_list,
+            })
+        }
+        (None, None) => EthereumTransaction::Eip155(Eip155Transaction {
+            nonce: tx.nonce,
+            gas_price: tx.gas_price.ok_or("Gas price is missing")?,
+            gas,
+            action,
+            value,
+            chain_id: Some(chain_id),
+            data,
+        }),
+    };
+    Ok(Transaction::Ethereum(unsigned).sign(&Secret::from(tx.secret_key)))
+}
+
+fn logs_hash(logs: &[LogEntry]) -> H256 {
+    let mut stream = RlpStream::new_list(logs.len());
+    for log in logs {
+        stream.begin_list(3);
+        stream.append(&log.address);
+        stream.append_list(&log.topics);
+        stream.append(&log.data);
+    }
+    keccak(stream.out())
+}
+
+/// Commits the state and computes the Ethereum state root of the eSpace
+/// accounts.
+fn post_state_root(state: State) -> Result<H256, String> {
+    state.commit(POST_STATE_EPOCH, None).map_err(db_error)?;
+    state_root(&POST_STATE_EPOCH)
+}
+
+/// Computes the Ethereum state root of the eSpace accounts committed in the
+/// epoch.
+fn state_root(epoch_id: &EpochId) -> Result<H256, String> {
+    let storage = InmemoryStorage::from_epoch_id(epoch_id)
+        .expect("The state is committed");
+
+    let mut accounts = BTreeMap::new();
+    let mut storages: BTreeMap<Address, Vec<(H256, U256)>> = BTreeMap::new();
+    for (key, value) in storage.iter() {
+        let key = StorageKeyWithSpace::from_key_bytes::<SkipInputCheck>(key);
+        if key.space != Space::Ethereum {
+            continue;
+        }
+        match key.key {
+            StorageKey::AccountKey(address) => {
+                let address = Address::from_slice(address);
+                let account = Account::new_from_rlp(address, &Rlp::new(value))
+                    .map_err(|e| format!("Invalid account: {:?}", e))?;
+                accounts.insert(address, account);
+            }
+            StorageKey::StorageKey {
+                address_bytes,
+                storage_key,
+            } => {
+                let entry: StorageValue = rlp::decode(value)
+                    .map_err(|e| format!("Invalid storage entry: {:?}", e))?;
+                if !entry.value.is_zero() {
+                    storages
+                        .entry(Address::from_slice(address_bytes))
+                        .or_default()
+                        .push((H256::from_slice(storage_key), entry.value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(sec_trie_root(accounts.into_iter().map(
+        |(address, account)| {
+            let storage_root = sec_trie_root(
+                storages
+                    .remove(&address)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(key, value)| (key, rlp::encode(&value))),
+            );
+            let mut stream = RlpStream::new_list(4);
+            stream.append(&account.nonce);
+            stream.append(&account.balance);
+            stream.append(&storage_root);
+            stream.append(&account.code_hash);
+            (address, stream.out())
+        },
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_test, Outcome};
+    use crate::{fixture::StateTests, load_fixture};
+    use std::path::Path;
+
+    fn value_transfer_tests() -> StateTests {
+        load_fixture(
+            &Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("test_data/value_transfer.json"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn run_fixture() {
+        for (name, test) in &value_transfer_tests() {
+            let results = run_test(test, None);
+            assert!(!results.is_empty());
+            for result in results {
+                match result.outcome {
+                    Outcome::Passed => {}
+                    Outcome::Failed(reason) | Outcome::Skipped(reason) => {
+                        panic!("{} {}: {}", name, result.fork, reason)
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unexpected_rejection() {
+        let mut tests = value_transfer_tests();
+        let test = tests.get_mut("nonceTooHigh").unwrap();
+        for post in test.post.values_mut().flatten() {
+            post.expect_exception =
+                Some("TransactionException.INSUFFICIENT_ACCOUNT_FUNDS".into());
+        }
+        let results = run_test(test, None);
+        assert!(!results.is_empty());
+        for result in results {
+            assert!(matches!(result.outcome, Outcome::Failed(_)));
+        }
+
+        assert!(run_test(test, Some("Cancun")).is_empty());
+    }
+}
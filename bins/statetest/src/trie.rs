@@ -0,0 +1,159 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! The root of the Merkle Patricia Trie of Ethereum, which is how the
+//! fixtures commit to the post state. The Conflux state is not organized this
+//! way, so the root is recomputed from the entries.
+
+use cfx_types::H256;
+use keccak_hash::keccak;
+use rlp::RlpStream;
+use std::cmp;
+
+/// The root of the secure trie, where the keys are hashed before insertion.
+pub fn sec_trie_root<I, K, V>(input: I) -> H256
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    trie_root(
+        input
+            .into_iter()
+            .map(|(k, v)| (keccak(k.as_ref()).0.to_vec(), v)),
+    )
+}
+
+pub fn trie_root<I, K, V>(input: I) -> H256
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    let mut input = input
+        .into_iter()
+        .map(|(k, v)| (to_nibbles(k.as_ref()), v))
+        .collect::<Vec<_>>();
+    input.sort_by(|a, b| a.0.cmp(&b.0));
+    input.dedup_by(|a, b| a.0 == b.0);
+
+    let mut stream = RlpStream::new();
+    build_node(&input, 0, &mut stream);
+    keccak(stream.out())
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Appends the node of the sorted `input`, whose keys share the first
+/// `prefix_len` nibbles.
+fn build_node<V: AsRef<[u8]>>(
+    input: &[(Vec<u8>, V)], prefix_len: usize, stream: &mut RlpStream,
+) {
+    if input.is_empty() {
+        stream.append_empty_data();
+        return;
+    }
+
+    let (key, value) = (&input[0].0, input[0].1.as_ref());
+    if input.len() == 1 {
+        stream.begin_list(2);
+        stream.append(&hex_prefix_encode(&key[prefix_len..], true));
+        stream.append(&value);
+        return;
+    }
+
+    let shared_len = input[1..].iter().fold(key.len(), |len, (k, _)| {
+        cmp::min(len, k.iter().zip(key).take_while(|(a, b)| a == b).count())
+    });
+    if shared_len > prefix_len {
+        // extension node
+        stream.begin_list(2);
+        stream.append(&hex_prefix_encode(&key[prefix_len..shared_len], false));
+        append_child(input, shared_len, stream);
+        return;
+    }
+
+    // branch node, the first key ends here if it has a value in this node
+    stream.begin_list(17);
+    let ends_here = key.len() == prefix_len;
+    let mut begin = if ends_here { 1 } else { 0 };
+    for nibble in 0..16 {
+        let len = input[begin..]
+            .iter()
+            .take_while(|(k, _)| k[prefix_len] == nibble)
+            .count();
+        if len == 0 {
+            stream.append_empty_data();
+        } else {
+            append_child(&input[begin..begin + len], prefix_len + 1, stream);
+        }
+        begin += len;
+    }
+    if ends_here {
+        stream.append(&value);
+    } else {
+        stream.append_empty_data();
+    }
+}
+
+/// Children shorter than 32 bytes are inlined, otherwise referred by hash.
+fn append_child<V: AsRef<[u8]>>(
+    input: &[(Vec<u8>, V)], prefix_len: usize, stream: &mut RlpStream,
+) {
+    let mut child = RlpStream::new();
+    build_node(input, prefix_len, &mut child);
+    let out = child.out();
+    if out.len() < 32 {
+        stream.append_raw(&out, 1);
+    } else {
+        stream.append(&keccak(&out));
+    }
+}
+
+fn hex_prefix_encode(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let odd = nibbles.len() % 2 == 1;
+    let mut flag = if leaf { 0x20 } else { 0x00 };
+    let rest = if odd {
+        flag |= 0x10 | nibbles[0];
+        &nibbles[1..]
+    } else {
+        nibbles
+    };
+    let mut encoded = vec![flag];
+    encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use keccak_hash::KECCAK_NULL_RLP;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_empty_trie() {
+        assert_eq!(
+            trie_root(Vec::<(Vec<u8>, Vec<u8>)>::new()),
+            KECCAK_NULL_RLP
+        );
+    }
+
+    #[test]
+    fn test_trie_root() {
+        let input = vec![
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ];
+        assert_eq!(
+            trie_root(input),
+            H256::from_str(
+                "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+            )
+            .unwrap()
+        );
+    }
+}
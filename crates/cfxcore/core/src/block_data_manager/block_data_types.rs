@@ -363,6 +363,25 @@ impl PosRewardInfo {
     }
 }
 
+/// A block with logs matching an entry of the log index. The logs are not
+/// located inside the block, as receipts are always loaded per block.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    RlpEncodable,
+    RlpDecodable,
+)]
+pub struct LogIndexPosition {
+    pub epoch: u64,
+    /// The index of the block in the executed epoch set.
+    pub block_index: u32,
+}
+
 pub fn db_encode_list<T>(list: &[T]) -> Vec<u8>
 where T: DatabaseEncodable {
     let mut rlp_stream = RlpStream::new();
@@ -390,3 +409,4 @@ impl_db_encoding_as_rlp!(EpochExecutionContext);
 impl_db_encoding_as_rlp!(BlockRewardResult);
 impl_db_encoding_as_rlp!(BlamedHeaderVerifiedRoots);
 impl_db_encoding_as_rlp!(PosRewardInfo);
+impl_db_encoding_as_rlp!(LogIndexPosition);
@@ -3,12 +3,12 @@ use crate::{
         db_decode_list, db_encode_list, BlamedHeaderVerifiedRoots,
         BlockExecutionResultWithEpoch, BlockRewardResult, BlockTracesWithEpoch,
         CheckpointHashes, DataVersionTuple, EpochExecutionContext,
-        LocalBlockInfo, PosRewardInfo,
+        LocalBlockInfo, LogIndexPosition, PosRewardInfo,
     },
     db::{
         COL_BLAMED_HEADER_VERIFIED_ROOTS, COL_BLOCKS, COL_BLOCK_TRACES,
-        COL_EPOCH_NUMBER, COL_HASH_BY_BLOCK_NUMBER, COL_MISC,
-        COL_REWARD_BY_POS_EPOCH, COL_TX_INDEX,
+        COL_EPOCH_NUMBER, COL_HASH_BY_BLOCK_NUMBER, COL_LOG_INDEX, COL_MISC,
+        COL_REWARD_BY_POS_EPOCH, COL_TX_INDEX, NUM_COLUMNS,
     },
     pow::PowComputer,
     verification::VerificationConfig,
@@ -25,7 +25,12 @@ use db::SystemDB;
 use malloc_size_of::{MallocSizeOf, MallocSizeOfOps};
 use primitives::{Block, BlockHeader, SignedTransaction, TransactionIndex};
 use rlp::Rlp;
-use std::{collections::HashMap, fs, path::Path, sync::Arc};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
@@ -39,6 +44,7 @@ const EPOCH_SKIPPED_BLOCK_SET_SUFFIX_BYTE: u8 = 7;
 const BLOCK_REWARD_RESULT_SUFFIX_BYTE: u8 = 8;
 const BLOCK_TERMINAL_KEY: &[u8] = b"block_terminals";
 const GC_PROGRESS_KEY: &[u8] = b"gc_progress";
+const LOG_INDEX_PROGRESS_KEY: &[u8] = b"log_index_progress";
 
 #[derive(Clone, Copy, Hash, Ord, PartialOrd, Eq, PartialEq, EnumIter)]
 enum DBTable {
@@ -50,6 +56,7 @@ enum DBTable {
     BlockTraces,
     HashByBlockNumber,
     RewardByPosEpoch,
+    LogIndex,
 }
 
 fn rocks_db_col(table: DBTable) -> u32 {
@@ -62,6 +69,7 @@ fn rocks_db_col(table: DBTable) -> u32 {
         DBTable::BlockTraces => COL_BLOCK_TRACES,
         DBTable::HashByBlockNumber => COL_HASH_BY_BLOCK_NUMBER,
         DBTable::RewardByPosEpoch => COL_REWARD_BY_POS_EPOCH,
+        DBTable::LogIndex => COL_LOG_INDEX,
     }
 }
 
@@ -75,12 +83,21 @@ fn sqlite_db_table(table: DBTable) -> String {
         DBTable::BlockTraces => "block_traces",
         DBTable::HashByBlockNumber => "hash_by_block_number",
         DBTable::RewardByPosEpoch => "reward_by_pos_epoch",
+        DBTable::LogIndex => "log_index",
     }
     .into()
 }
 
+/// The database holding the tables, which is needed to clear a table as a
+/// whole.
+enum DBBackend {
+    Rocksdb(Arc<SystemDB>),
+    Sqlite(PathBuf),
+}
+
 pub struct DBManager {
     table_db: HashMap<DBTable, Box<dyn KeyValueDbTrait<ValueType = Box<[u8]>>>>,
+    backend: DBBackend,
     pow: Arc<PowComputer>,
 }
 
@@ -98,7 +115,11 @@ impl DBManager {
                     as Box<dyn KeyValueDbTrait<ValueType = Box<[u8]>>>,
             );
         }
-        Self { table_db, pow }
+        Self {
+            table_db,
+            backend: DBBackend::Rocksdb(db),
+            pow,
+        }
     }
 }
 
@@ -113,15 +134,7 @@ impl DBManager {
             let (_, sqlite_db) = KvdbSqlite::open_or_create(
                 &db_path.join(table_str.as_str()), /* Use separate database
                                                     * for different table */
-                Arc::new(
-                    KvdbSqliteStatements::make_statements(
-                        &[&"value"],
-                        &[&"BLOB"],
-                        table_str.as_str(),
-                        false,
-                    )
-                    .unwrap(),
-                ),
+                sqlite_statements(table),
                 false, /* unsafe_mode */
             )
             .expect("Open sqlite failure");
@@ -131,10 +144,26 @@ impl DBManager {
                     as Box<dyn KeyValueDbTrait<ValueType = Box<[u8]>>>,
             );
         }
-        Self { table_db, pow }
+        Self {
+            table_db,
+            backend: DBBackend::Sqlite(db_path.to_path_buf()),
+            pow,
+        }
     }
 }
 
+fn sqlite_statements(table: DBTable) -> Arc<KvdbSqliteStatements> {
+    Arc::new(
+        KvdbSqliteStatements::make_statements(
+            &[&"value"],
+            &[&"BLOB"],
+            sqlite_db_table(table).as_str(),
+            false,
+        )
+        .unwrap(),
+    )
+}
+
 impl DBManager {
     pub fn insert_block_traces_to_db(
         &self, block_hash: &H256, block_traces: &BlockTracesWithEpoch,
@@ -505,6 +534,77 @@ impl DBManager {
         )
     }
 
+    pub fn insert_log_index_to_db(
+        &self, key: &[u8], positions: &Vec<LogIndexPosition>,
+    ) {
+        self.insert_encodable_list(DBTable::LogIndex, key, positions);
+    }
+
+    pub fn log_index_from_db(
+        &self, key: &[u8],
+    ) -> Option<Vec<LogIndexPosition>> {
+        self.load_decodable_list(DBTable::LogIndex, key)
+    }
+
+    pub fn remove_log_index_from_db(&self, key: &[u8]) {
+        self.remove_from_db(DBTable::LogIndex, key)
+    }
+
+    pub fn insert_log_index_progress_to_db(&self, next_to_index: u64) {
+        self.insert_encodable_val(
+            DBTable::Misc,
+            LOG_INDEX_PROGRESS_KEY,
+            &next_to_index,
+        );
+    }
+
+    pub fn log_index_progress_from_db(&self) -> Option<u64> {
+        self.load_decodable_val(DBTable::Misc, LOG_INDEX_PROGRESS_KEY)
+    }
+
+    pub fn remove_log_index_progress_from_db(&self) {
+        self.remove_from_db(DBTable::Misc, LOG_INDEX_PROGRESS_KEY)
+    }
+
+    /// Remove the whole log index together with its progress. The keys of the
+    /// index are not enumerable, so the table is recreated.
+    pub fn clear_log_index_in_db(&self) {
+        match &self.backend {
+            DBBackend::Rocksdb(db) => {
+                // Only the last column, which is the log index, can be
+                // dropped and added back.
+                let kvdb = db.key_value();
+                if kvdb.num_columns() == NUM_COLUMNS {
+                    kvdb.drop_column().expect("Drop log index column failure");
+                    kvdb.add_column().expect("Add log index column failure");
+                }
+            }
+            DBBackend::Sqlite(db_path) => {
+                let statements = sqlite_statements(DBTable::LogIndex);
+                let mut connection = KvdbSqlite::<Box<[u8]>>::open(
+                    db_path.join(sqlite_db_table(DBTable::LogIndex)),
+                    false, /* readonly */
+                    statements.clone(),
+                )
+                .ok()
+                .and_then(|db| db.into_connection())
+                .expect("Open sqlite failure");
+                KvdbSqlite::<Box<[u8]>>::drop_table(
+                    &mut connection,
+                    &statements,
+                )
+                .and_then(|_| {
+                    KvdbSqlite::<Box<[u8]>>::create_table(
+                        &mut connection,
+                        &statements,
+                    )
+                })
+                .expect("Recreate log index table failure");
+            }
+        }
+        self.remove_log_index_progress_from_db();
+    }
+
     /// The functions below are private utils used by the DBManager to access
     /// database
     fn insert_to_db(&self, table: DBTable, db_key: &[u8], value: Vec<u8>) {
@@ -0,0 +1,651 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! The persistent index from the addresses and the first topics of logs to the
+//! blocks containing them, so log filters constraining them do not need to
+//! check the blooms of every epoch in the range.
+//!
+//! The index is built from the persisted receipts of the pivot chain together
+//! with the database GC, and covers the executed epochs from the earliest one
+//! with execution results to `log_index_progress`. Only the epochs deep enough
+//! to be stable are indexed, the later ones are always scanned. The index is
+//! pruned together with the execution results.
+//!
+//! The blocks found in the index are only candidates which are still filtered
+//! with blooms and receipts, so an entry left by a pivot chain reorg can never
+//! produce a wrong log. A reorg deeper than the confirmation window reverts
+//! the progress to the fork height, so the epochs of the new pivot chain are
+//! scanned until they are executed and indexed again.
+
+use super::{BlockDataManager, LogIndexPosition};
+use cfx_types::{Space, H256};
+use primitives::{filter::LogFilterParams, LogEntry};
+use std::{
+    cmp::{max, min},
+    collections::{BTreeMap, BTreeSet},
+    sync::atomic::Ordering,
+};
+
+/// The number of consecutive epochs whose positions are stored under the same
+/// key.
+const EPOCHS_PER_LOG_INDEX_BUCKET: u64 = 1000;
+/// An epoch is indexed after the best epoch is this much higher than it.
+const LOG_INDEX_CONFIRMATION_EPOCH_COUNT: u64 = 100;
+/// The maximal number of epochs indexed in a round, so that building the index
+/// of the existing receipts does not block the database GC for long.
+const MAX_LOG_INDEX_EPOCHS_PER_ROUND: u64 = 1000;
+
+const ADDRESS_KEY_PREFIX: u8 = 0;
+const TOPIC_KEY_PREFIX: u8 = 1;
+
+type LogIndexEntries = BTreeMap<Vec<u8>, BTreeSet<LogIndexPosition>>;
+
+/// The blocks which may contain the logs matching a filter, according to the
+/// log index.
+pub struct LogIndexCandidates {
+    /// The epochs from this one are not indexed.
+    indexed_end: u64,
+    blocks: BTreeMap<u64, BTreeSet<usize>>,
+}
+
+impl LogIndexCandidates {
+    /// The epochs from the returned one are not indexed.
+    pub fn indexed_end(&self) -> u64 { self.indexed_end }
+
+    pub fn contains_epoch(&self, epoch: u64) -> bool {
+        epoch >= self.indexed_end || self.blocks.contains_key(&epoch)
+    }
+
+    /// `block_index` is the index of the block in the executed epoch set.
+    pub fn contains_block(&self, epoch: u64, block_index: usize) -> bool {
+        epoch >= self.indexed_end
+            || self
+                .blocks
+                .get(&epoch)
+                .map_or(false, |blocks| blocks.contains(&block_index))
+    }
+}
+
+impl BlockDataManager {
+    pub fn log_index_enabled(&self) -> bool { self.config.persist_log_index }
+
+    /// All the available epochs below the returned one are indexed.
+    pub fn log_index_progress(&self) -> u64 {
+        min(
+            *self.log_index_progress.lock(),
+            self.log_index_reverted_to.load(Ordering::Acquire),
+        )
+    }
+
+    /// Called when the pivot chain forks at `fork_height`. The index of the
+    /// reverted epochs misses the blocks of the new pivot chain, so they are
+    /// no longer considered indexed and are indexed again in the next update.
+    pub fn revert_log_index(&self, fork_height: u64) {
+        if !self.config.persist_log_index {
+            return;
+        }
+        self.log_index_reverted_to
+            .fetch_min(fork_height, Ordering::AcqRel);
+    }
+
+    /// Index the next stable epochs. The pruned epochs are skipped, so this
+    /// also builds the index of the existing receipts when the index is
+    /// enabled on an existing database.
+    pub fn update_log_index(&self, best_epoch: u64) {
+        if !self.config.persist_log_index {
+            return;
+        }
+        let mut progress = self.log_index_progress.lock();
+        let reverted_to =
+            self.log_index_reverted_to.swap(u64::MAX, Ordering::AcqRel);
+        if reverted_to < *progress {
+            info!(
+                "Log index reverted from {} to {} by pivot chain reorg",
+                *progress, reverted_to
+            );
+            *progress = reverted_to;
+            self.db_manager.insert_log_index_progress_to_db(reverted_to);
+        }
+        let start = max(*progress, self.earliest_epoch_with_execution_result());
+        let end = min(
+            best_epoch.saturating_sub(LOG_INDEX_CONFIRMATION_EPOCH_COUNT),
+            start + MAX_LOG_INDEX_EPOCHS_PER_ROUND,
+        );
+
+        let mut entries = LogIndexEntries::new();
+        let mut epoch = start;
+        while epoch < end {
+            let pivot_hash = {
+                let boundary = self.state_availability_boundary.read();
+                // The epochs reverted by a pivot chain reorg are indexed after
+                // they are executed again.
+                if epoch > boundary.upper_bound {
+                    break;
+                }
+                epoch
+                    .checked_sub(boundary.lower_bound)
+                    .and_then(|i| boundary.pivot_chain.get(i as usize))
+                    .cloned()
+            };
+            match self.epoch_log_index_entries(epoch, pivot_hash) {
+                Some(epoch_entries) => {
+                    for (key, positions) in epoch_entries {
+                        entries.entry(key).or_default().extend(positions);
+                    }
+                }
+                // The epochs before the synced snapshot are not executed
+                // locally.
+                None if epoch
+                    < self.state_availability_boundary.read().lower_bound => {}
+                None => break,
+            }
+            epoch += 1;
+        }
+        if epoch == start {
+            return;
+        }
+
+        for (key, positions) in entries {
+            let mut merged: BTreeSet<_> = self
+                .db_manager
+                .log_index_from_db(&key)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            merged.extend(positions);
+            self.db_manager
+                .insert_log_index_to_db(&key, &merged.into_iter().collect());
+        }
+        *progress = epoch;
+        self.db_manager.insert_log_index_progress_to_db(epoch);
+        debug!("Log index progress: {}", epoch);
+    }
+
+    /// Remove the index of the epoch whose execution results are garbage
+    /// collected with `base_epoch`. It must be called before the execution
+    /// results are removed.
+    pub(super) fn gc_log_index(&self, base_epoch: u64) {
+        if !self.config.persist_log_index {
+            return;
+        }
+        let defer_epochs = match self
+            .config
+            .additional_maintained_execution_result_epoch_count
+        {
+            Some(x) => x as u64,
+            None => return,
+        };
+        if base_epoch <= defer_epochs {
+            return;
+        }
+        let epoch_to_remove = base_epoch - defer_epochs;
+        let progress = self.log_index_progress.lock();
+        if epoch_to_remove >= *progress {
+            return;
+        }
+
+        let entries = match self.epoch_log_index_entries(
+            epoch_to_remove,
+            None, /* expected_pivot_hash */
+        ) {
+            Some(entries) => entries,
+            // The epoch is not executed locally and has no index.
+            None => return,
+        };
+        for key in entries.keys() {
+            let positions: Vec<_> = self
+                .db_manager
+                .log_index_from_db(key)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|position| position.epoch != epoch_to_remove)
+                .collect();
+            if positions.is_empty() {
+                self.db_manager.remove_log_index_from_db(key);
+            } else {
+                self.db_manager.insert_log_index_to_db(key, &positions);
+            }
+        }
+    }
+
+    /// Return `None` if the index is disabled or does not cover the range, or
+    /// if the filter constrains neither the addresses nor the first topics.
+    pub fn log_index_candidates(
+        &self, params: &LogFilterParams, from_epoch: u64, to_epoch: u64,
+    ) -> Option<LogIndexCandidates> {
+        if !self.config.persist_log_index {
+            return None;
+        }
+        let indexed_end = self.log_index_progress();
+        if from_epoch >= indexed_end {
+            return None;
+        }
+        let to_epoch = min(to_epoch, indexed_end - 1);
+
+        let address_positions = match &params.address {
+            Some(addresses) if !addresses.is_empty() => {
+                Some(self.log_index_positions(
+                    ADDRESS_KEY_PREFIX,
+                    params.space,
+                    addresses.iter().map(|address| address.as_bytes()),
+                    from_epoch,
+                    to_epoch,
+                ))
+            }
+            _ => None,
+        };
+        let topic_positions = match params.topics.get(0) {
+            Some(Some(topics)) if !topics.is_empty() => {
+                Some(self.log_index_positions(
+                    TOPIC_KEY_PREFIX,
+                    params.space,
+                    topics.iter().map(|topic| topic.as_bytes()),
+                    from_epoch,
+                    to_epoch,
+                ))
+            }
+            _ => None,
+        };
+        // A matching log has both the address and the topic, so they are in
+        // the same block.
+        let positions = match (address_positions, topic_positions) {
+            (Some(a), Some(t)) => a.intersection(&t).cloned().collect(),
+            (Some(positions), None) | (None, Some(positions)) => positions,
+            (None, None) => return None,
+        };
+
+        let mut blocks = BTreeMap::<u64, BTreeSet<usize>>::new();
+        for position in positions {
+            blocks
+                .entry(position.epoch)
+                .or_default()
+                .insert(position.block_index as usize);
+        }
+        Some(LogIndexCandidates {
+            indexed_end,
+            blocks,
+        })
+    }
+
+    fn log_index_positions<'a>(
+        &self, prefix: u8, space: Space,
+        values: impl Iterator<Item = &'a [u8]>, from_epoch: u64, to_epoch: u64,
+    ) -> BTreeSet<LogIndexPosition> {
+        let mut positions = BTreeSet::new();
+        for value in values {
+            for bucket in from_epoch / EPOCHS_PER_LOG_INDEX_BUCKET
+                ..=to_epoch / EPOCHS_PER_LOG_INDEX_BUCKET
+            {
+                let key = log_index_key(prefix, space, value, bucket);
+                positions.extend(
+                    self.db_manager
+                        .log_index_from_db(&key)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|position| {
+                            position.epoch >= from_epoch
+                                && position.epoch <= to_epoch
+                        }),
+                );
+            }
+        }
+        positions
+    }
+
+    /// Return `None` if the epoch or the receipts of its blocks under its
+    /// current pivot block are not in the database, or if the pivot block is
+    /// not `expected_pivot_hash` when it is given.
+    fn epoch_log_index_entries(
+        &self, epoch: u64, expected_pivot_hash: Option<H256>,
+    ) -> Option<LogIndexEntries> {
+        let mut entries = LogIndexEntries::new();
+        // The genesis block has no logs.
+        if epoch == 0 {
+            return Some(entries);
+        }
+        let epoch_hashes = self.executed_epoch_set_hashes_from_db(epoch)?;
+        let pivot_hash = *epoch_hashes.last()?;
+        if expected_pivot_hash.map_or(false, |hash| hash != pivot_hash) {
+            return None;
+        }
+        for (block_index, hash) in epoch_hashes.iter().enumerate() {
+            let block_receipts = self
+                .block_execution_result_by_hash_with_epoch(
+                    hash,
+                    &pivot_hash,
+                    false, /* update_pivot_assumption */
+                    false, /* update_cache */
+                )?
+                .block_receipts;
+            let position = LogIndexPosition {
+                epoch,
+                block_index: block_index as u32,
+            };
+            for log in block_receipts
+                .receipts
+                .iter()
+                .flat_map(|receipt| receipt.logs.iter())
+            {
+                for key in log_index_keys(epoch, log) {
+                    entries.entry(key).or_default().insert(position);
+                }
+            }
+        }
+        Some(entries)
+    }
+}
+
+fn log_index_keys(epoch: u64, log: &LogEntry) -> Vec<Vec<u8>> {
+    let bucket = epoch / EPOCHS_PER_LOG_INDEX_BUCKET;
+    let mut keys = vec![log_index_key(
+        ADDRESS_KEY_PREFIX,
+        log.space,
+        log.address.as_bytes(),
+        bucket,
+    )];
+    if let Some(topic) = log.topics.get(0) {
+        keys.push(log_index_key(
+            TOPIC_KEY_PREFIX,
+            log.space,
+            topic.as_bytes(),
+            bucket,
+        ));
+    }
+    keys
+}
+
+fn log_index_key(
+    prefix: u8, space: Space, value: &[u8], bucket: u64,
+) -> Vec<u8> {
+    let mut key = Vec::with_capacity(2 + value.len() + 8);
+    key.push(prefix);
+    key.push(match space {
+        Space::Native => 0,
+        Space::Ethereum => 1,
+    });
+    key.extend_from_slice(value);
+    key.extend_from_slice(&bucket.to_be_bytes());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{log_index_key, LogIndexCandidates, ADDRESS_KEY_PREFIX};
+    use crate::{
+        block_data_manager::{
+            db_manager::DBManager, BlockDataManager, DataManagerConfiguration,
+            DbType, LogIndexPosition,
+        },
+        pow::PowComputer,
+        sync::utils::initialize_data_manager_with_config,
+    };
+    use cfx_executor::machine::VmFactory;
+    use cfx_types::{Address, Space, H256, U256};
+    use primitives::{
+        filter::LogFilterParams, BlockReceipts, LogEntry, Receipt,
+    };
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
+    use tempdir::TempDir;
+
+    fn new_data_man(
+        dir: &TempDir, execution_result_defer_epochs: Option<usize>,
+    ) -> Arc<BlockDataManager> {
+        let mut config = DataManagerConfiguration::new(
+            false, /* persist_tx_index */
+            false, /* persist_block_number_index */
+            Duration::from_millis(300_000),
+            DbType::Rocksdb,
+        );
+        config.persist_log_index = true;
+        config.additional_maintained_execution_result_epoch_count =
+            execution_result_defer_epochs;
+        initialize_data_manager_with_config(
+            dir.path().to_str().unwrap(),
+            config,
+            Arc::new(PowComputer::new(true)),
+            VmFactory::new(1024 * 32),
+        )
+        .0
+    }
+
+    fn log(address: u64, topic: u64) -> LogEntry {
+        LogEntry {
+            address: Address::from_low_u64_be(address),
+            topics: vec![H256::from_low_u64_be(topic)],
+            data: vec![],
+            space: Space::Native,
+        }
+    }
+
+    /// Execute the epoch on the pivot chain with a block for each list of logs,
+    /// in which the last block is the pivot block. Different `fork`s give
+    /// different blocks.
+    fn insert_epoch(
+        data_man: &BlockDataManager, epoch: u64, fork: u64,
+        blocks: Vec<Vec<LogEntry>>,
+    ) {
+        let hashes: Vec<_> = (0..blocks.len() as u64)
+            .map(|i| H256::from_low_u64_be(fork << 48 | epoch << 8 | i))
+            .collect();
+        let pivot_hash = *hashes.last().unwrap();
+        for (hash, logs) in hashes.iter().zip(blocks) {
+            let block_receipts = BlockReceipts {
+                receipts: vec![Receipt {
+                    logs,
+                    ..Default::default()
+                }],
+                block_number: epoch,
+                secondary_reward: U256::zero(),
+                tx_execution_error_messages: vec![String::new()],
+            };
+            data_man.insert_block_execution_result(
+                *hash,
+                pivot_hash,
+                Arc::new(block_receipts),
+                true, /* persistent */
+            );
+        }
+        data_man.insert_executed_epoch_set_hashes_to_db(epoch, &hashes);
+
+        let mut boundary = data_man.state_availability_boundary.write();
+        boundary.pivot_chain.truncate(epoch as usize);
+        boundary.pivot_chain.push(pivot_hash);
+        boundary.upper_bound = epoch;
+    }
+
+    /// Insert the epochs from 1 to `end` (exclusive), with the logs of the
+    /// listed epochs and a block without logs in the others.
+    fn insert_epochs(
+        data_man: &BlockDataManager, end: u64,
+        mut epochs: BTreeMap<u64, Vec<Vec<LogEntry>>>,
+    ) {
+        for epoch in 1..end {
+            let blocks = epochs.remove(&epoch).unwrap_or(vec![vec![]]);
+            insert_epoch(data_man, epoch, 0, blocks);
+        }
+    }
+
+    fn candidates(
+        data_man: &BlockDataManager, address: Option<u64>, topic: Option<u64>,
+        from_epoch: u64, to_epoch: u64,
+    ) -> Option<LogIndexCandidates> {
+        let params = LogFilterParams {
+            address: address
+                .map(|address| vec![Address::from_low_u64_be(address)]),
+            topics: vec![topic.map(|topic| vec![H256::from_low_u64_be(topic)])],
+            ..Default::default()
+        };
+        data_man.log_index_candidates(&params, from_epoch, to_epoch)
+    }
+
+    fn blocks(candidates: &LogIndexCandidates) -> Vec<(u64, Vec<usize>)> {
+        candidates
+            .blocks
+            .iter()
+            .map(|(epoch, blocks)| (*epoch, blocks.iter().cloned().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_log_index_across_buckets() {
+        let dir = TempDir::new("log_index_buckets").unwrap();
+        let data_man = new_data_man(&dir, None);
+        insert_epochs(
+            &data_man,
+            1200,
+            vec![
+                (999, vec![vec![log(1, 10)], vec![log(2, 20)]]),
+                (1000, vec![vec![], vec![log(1, 20)]]),
+                (1050, vec![vec![log(1, 10)]]),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        // Only 1000 epochs are indexed in a round.
+        data_man.update_log_index(1201);
+        assert_eq!(data_man.log_index_progress(), 1000);
+        let c = candidates(&data_man, Some(1), None, 990, 1100).unwrap();
+        assert_eq!(c.indexed_end(), 1000);
+        assert_eq!(blocks(&c), vec![(999, vec![0])]);
+        assert!(!c.contains_epoch(998));
+        assert!(c.contains_block(999, 0) && !c.contains_block(999, 1));
+        // The epochs not indexed yet are always scanned.
+        assert!(c.contains_epoch(1000) && c.contains_block(1050, 1));
+
+        // The epochs at most 100 epochs lower than the best one are indexed.
+        data_man.update_log_index(1201);
+        assert_eq!(data_man.log_index_progress(), 1101);
+        let c = candidates(&data_man, Some(1), None, 0, 1200).unwrap();
+        assert_eq!(c.indexed_end(), 1101);
+        assert_eq!(
+            blocks(&c),
+            vec![(999, vec![0]), (1000, vec![1]), (1050, vec![0])]
+        );
+        let c = candidates(&data_man, Some(1), Some(10), 0, 1200).unwrap();
+        assert_eq!(blocks(&c), vec![(999, vec![0]), (1050, vec![0])]);
+        let c = candidates(&data_man, None, Some(20), 0, 1200).unwrap();
+        assert_eq!(blocks(&c), vec![(999, vec![1]), (1000, vec![1])]);
+        let c = candidates(&data_man, Some(1), None, 1000, 1049).unwrap();
+        assert_eq!(blocks(&c), vec![(1000, vec![1])]);
+        assert!(!c.contains_epoch(1050));
+
+        // The index is not used if it does not cover the range or the filter
+        // does not constrain the addresses or the first topics.
+        assert!(candidates(&data_man, Some(1), None, 1101, 1200).is_none());
+        assert!(candidates(&data_man, None, None, 0, 1200).is_none());
+    }
+
+    #[test]
+    fn test_log_index_reorg() {
+        let dir = TempDir::new("log_index_reorg").unwrap();
+        let data_man = new_data_man(&dir, None);
+        insert_epochs(
+            &data_man,
+            300,
+            vec![(150, vec![vec![log(1, 10)]])].into_iter().collect(),
+        );
+        data_man.update_log_index(400);
+        assert_eq!(data_man.log_index_progress(), 300);
+
+        // A fork above the indexed epochs does not change the index.
+        data_man.revert_log_index(350);
+        assert_eq!(data_man.log_index_progress(), 300);
+
+        // The pivot chain forks at epoch 150 with a new block.
+        data_man.revert_log_index(150);
+        assert_eq!(data_man.log_index_progress(), 150);
+        insert_epoch(
+            &data_man,
+            150,
+            1,
+            vec![vec![log(3, 30)], vec![log(1, 10)]],
+        );
+        let c = candidates(&data_man, Some(3), None, 0, 299).unwrap();
+        assert_eq!(c.indexed_end(), 150);
+        assert!(blocks(&c).is_empty());
+        // The new blocks are scanned until they are indexed.
+        assert!(c.contains_block(150, 0) && c.contains_block(150, 1));
+
+        // The epochs are indexed after they are executed again.
+        data_man.update_log_index(400);
+        assert_eq!(data_man.log_index_progress(), 151);
+        for epoch in 151..300 {
+            insert_epoch(&data_man, epoch, 1, vec![vec![]]);
+        }
+        data_man.update_log_index(400);
+        assert_eq!(data_man.log_index_progress(), 300);
+        assert_eq!(data_man.db_manager.log_index_progress_from_db(), Some(300));
+        let c = candidates(&data_man, Some(3), None, 0, 299).unwrap();
+        assert_eq!(blocks(&c), vec![(150, vec![0])]);
+        let c = candidates(&data_man, Some(1), Some(10), 0, 299).unwrap();
+        assert!(c.contains_block(150, 1));
+    }
+
+    #[test]
+    fn test_log_index_gc() {
+        let dir = TempDir::new("log_index_gc").unwrap();
+        let data_man = new_data_man(&dir, Some(10));
+        insert_epochs(
+            &data_man,
+            300,
+            vec![(5, vec![vec![log(1, 10)]]), (20, vec![vec![log(1, 10)]])]
+                .into_iter()
+                .collect(),
+        );
+        data_man.update_log_index(400);
+        assert_eq!(data_man.log_index_progress(), 300);
+        let key = log_index_key(
+            ADDRESS_KEY_PREFIX,
+            Space::Native,
+            Address::from_low_u64_be(1).as_bytes(),
+            0,
+        );
+
+        // The GC of base epoch 15 removes the execution results of epoch 5.
+        data_man.gc_log_index(15);
+        let c = candidates(&data_man, Some(1), None, 0, 299).unwrap();
+        assert_eq!(blocks(&c), vec![(20, vec![0])]);
+
+        // The key is removed with its last position.
+        data_man.gc_log_index(30);
+        assert!(candidates(&data_man, Some(1), None, 0, 299)
+            .unwrap()
+            .blocks
+            .is_empty());
+        assert!(data_man.db_manager.log_index_from_db(&key).is_none());
+    }
+
+    #[test]
+    fn test_clear_log_index() {
+        let positions = vec![LogIndexPosition {
+            epoch: 1,
+            block_index: 0,
+        }];
+        let check_clear = |db_manager: &DBManager| {
+            db_manager.insert_log_index_to_db(b"key", &positions);
+            db_manager.insert_log_index_progress_to_db(2);
+            db_manager.clear_log_index_in_db();
+            assert!(db_manager.log_index_from_db(b"key").is_none());
+            assert!(db_manager.log_index_progress_from_db().is_none());
+            // The index can be built again.
+            db_manager.insert_log_index_to_db(b"key", &positions);
+            assert_eq!(
+                db_manager.log_index_from_db(b"key"),
+                Some(positions.clone())
+            );
+        };
+
+        let dir = TempDir::new("log_index_clear").unwrap();
+        let data_man = new_data_man(&dir, None);
+        check_clear(&data_man.db_manager);
+
+        let dir = TempDir::new("log_index_clear_sqlite").unwrap();
+        let db_manager = DBManager::new_from_sqlite(
+            dir.path(),
+            Arc::new(PowComputer::new(true)),
+        );
+        check_clear(&db_manager);
+    }
+}
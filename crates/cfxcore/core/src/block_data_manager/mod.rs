@@ -27,12 +27,13 @@ use primitives::{
 use rlp::DecoderError;
 use std::{
     collections::{HashMap, HashSet},
-    sync::Arc,
+    sync::{atomic::AtomicU64, Arc},
 };
 use threadpool::ThreadPool;
 pub mod block_data_types;
 pub mod db_gc_manager;
 pub mod db_manager;
+pub mod log_index;
 pub mod tx_data_manager;
 use crate::{
     block_data_manager::{
@@ -144,6 +145,12 @@ pub struct BlockDataManager {
     cache_man: Arc<Mutex<CacheManager<CacheId>>>,
     pub target_difficulty_manager: TargetDifficultyManager,
     gc_progress: Arc<Mutex<GCProgress>>,
+    /// The next epoch to add to the log index. It also serializes the
+    /// updates of the log index.
+    log_index_progress: Mutex<u64>,
+    /// The lowest pivot chain fork height since the last update of the log
+    /// index, from which the index needs to be rebuilt.
+    log_index_reverted_to: AtomicU64,
 
     /// This maintains the boundary height of available state and commitments
     /// (executed but not deleted or in `ExecutionTaskQueue`).
@@ -196,6 +203,16 @@ impl BlockDataManager {
         };
         let previous_db_progress =
             db_manager.gc_progress_from_db().unwrap_or(0);
+        let log_index_progress = if config.persist_log_index {
+            db_manager.log_index_progress_from_db().unwrap_or(0)
+        } else {
+            // The log index is not maintained when it is disabled, so it is
+            // removed and rebuilt when it is enabled again.
+            if db_manager.log_index_progress_from_db().is_some() {
+                db_manager.clear_log_index_in_db();
+            }
+            0
+        };
 
         let data_man = Self {
             block_headers: RwLock::new(HashMap::new()),
@@ -237,6 +254,8 @@ impl BlockDataManager {
             gc_progress: Arc::new(Mutex::new(GCProgress::new(
                 previous_db_progress,
             ))),
+            log_index_progress: Mutex::new(log_index_progress),
+            log_index_reverted_to: AtomicU64::new(u64::MAX),
         };
 
         data_man.initialize_instance_id();
@@ -1639,7 +1658,9 @@ impl BlockDataManager {
             .config
             .additional_maintained_execution_result_epoch_count
         {
-            Some(defer) => self.gc_progress.lock().gc_end - defer as u64,
+            Some(defer) => {
+                self.gc_progress.lock().gc_end.saturating_sub(defer as u64)
+            }
             None => 0,
         }
     }
@@ -1741,6 +1762,8 @@ impl BlockDataManager {
         };
 
         gc_tx_index();
+        // The log index is located with the receipts, so it is removed first.
+        self.gc_log_index(base_epoch);
 
         self.gc_epoch_with_defer(
             base_epoch,
@@ -1805,6 +1828,7 @@ pub struct DataManagerConfiguration {
     pub additional_maintained_transaction_index_epoch_count: Option<usize>,
     pub checkpoint_gc_time_in_epoch_count: usize,
     pub strict_tx_index_gc: bool,
+    pub persist_log_index: bool,
 }
 
 impl MallocSizeOf for DataManagerConfiguration {
@@ -1828,6 +1852,7 @@ impl DataManagerConfiguration {
             additional_maintained_transaction_index_epoch_count: None,
             checkpoint_gc_time_in_epoch_count: 1,
             strict_tx_index_gc: true,
+            persist_log_index: false,
         }
     }
 }
@@ -2,9 +2,10 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
-use crate::UniqueId;
+use crate::{sync::SyncPhaseType, UniqueId};
 use cfx_types::H256;
 use parking_lot::RwLock;
+use primitives::SignedTransaction;
 use std::{collections::BTreeMap, sync::Arc, time::Duration};
 use tokio::{runtime, sync::mpsc, time::timeout};
 
@@ -112,6 +113,8 @@ pub struct Notifications {
     pub new_block_hashes: Arc<Channel<H256>>,
     pub epochs_ordered: Arc<Channel<(u64, Vec<H256>)>>,
     pub blame_verification_results: Arc<Channel<(u64, Option<u64>)>>, /* <height, witness> */
+    pub new_pending_transactions: Arc<Channel<Vec<Arc<SignedTransaction>>>>,
+    pub sync_phase_changes: Arc<Channel<SyncPhaseType>>,
 }
 
 impl Notifications {
@@ -122,6 +125,10 @@ impl Notifications {
             blame_verification_results: Arc::new(Channel::new(
                 "blame-verification-results",
             )),
+            new_pending_transactions: Arc::new(Channel::new(
+                "new-pending-transactions",
+            )),
+            sync_phase_changes: Arc::new(Channel::new("sync-phase-changes")),
         })
     }
 }
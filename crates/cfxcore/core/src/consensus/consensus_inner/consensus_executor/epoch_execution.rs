@@ -3,7 +3,9 @@ use std::{collections::BTreeSet, convert::From, sync::Arc};
 
 use alloy_rpc_types_trace::geth::GethDebugTracingOptions;
 use cfx_parameters::genesis::GENESIS_ACCOUNT_ADDRESS;
-use geth_tracer::{GethTraceWithHash, GethTracer, TxExecContext};
+use geth_tracer::{
+    check_tracing_options, GethTraceWithHash, GethTracer, TxExecContext,
+};
 use pow_types::StakingEvent;
 
 use cfx_statedb::{Error as DbErrorKind, Result as DbResult};
@@ -274,7 +276,7 @@ impl ConsensusExecutionHandler {
         let spec = machine.spec(env.number, env.epoch_height);
 
         let options = TransactOptions {
-            observer: self.make_observer(transaction, block_context),
+            observer: self.make_observer(transaction, rpc_index, block_context),
             settings: TransactSettings::all_checks(),
         };
 
@@ -350,13 +352,9 @@ impl ConsensusExecutionHandler {
     }
 
     fn make_observer(
-        &self, transaction: &Arc<SignedTransaction>,
+        &self, transaction: &Arc<SignedTransaction>, rpc_index: usize,
         block_context: &BlockProcessContext,
     ) -> Observer {
-        use alloy_rpc_types_trace::geth::{
-            GethDebugBuiltInTracerType::*, GethDebugTracerType::BuiltInTracer,
-        };
-
         let mut observer = if self.config.executive_trace {
             Observer::with_tracing()
         } else {
@@ -368,24 +366,24 @@ impl ConsensusExecutionHandler {
         {
             let need_trace =
                 task.tx_hash.map_or(true, |hash| transaction.hash() == hash);
-            let support_tracer = matches!(
-                task.opts.tracer,
-                Some(BuiltInTracer(
-                    FourByteTracer | CallTracer | PreStateTracer | NoopTracer
-                )) | None
-            );
+            let support_tracer = check_tracing_options(&task.opts).is_ok();
             let tx_gas_limit = transaction.gas_limit().as_u64();
+            let pivot_block = block_context.epoch_context.pivot_block;
+            // eSpace blocks are identified by the pivot block of the epoch
+            let block_hash = match transaction.space() {
+                Space::Native => block_context.block.hash(),
+                Space::Ethereum => pivot_block.hash(),
+            };
 
             if need_trace && support_tracer {
                 observer.geth_tracer = Some(GethTracer::new(
                     TxExecContext {
                         tx_gas_limit,
-                        block_height: block_context
-                            .epoch_context
-                            .pivot_block
-                            .block_header
-                            .height(),
+                        block_height: pivot_block.block_header.height(),
                         block_number: block_context.block_number,
+                        block_hash,
+                        tx_hash: transaction.hash(),
+                        tx_index: rpc_index,
                     },
                     Arc::clone(&self.machine),
                     task.opts.clone(),
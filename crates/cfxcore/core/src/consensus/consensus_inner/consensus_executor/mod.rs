@@ -3,6 +3,8 @@
 // See http://www.gnu.org/licenses/
 
 mod epoch_execution;
+mod overrides;
+mod simulation;
 
 use core::convert::TryFrom;
 use std::{
@@ -29,7 +31,7 @@ use cfx_parameters::consensus::*;
 use cfx_statedb::{Result as DbResult, StateDb};
 use cfx_storage::{
     defaults::DEFAULT_EXECUTION_PREFETCH_THREADS, StateIndex,
-    StorageManagerTrait,
+    StorageManagerTrait, StorageStateTrait,
 };
 use cfx_types::{
     address_util::AddressUtil, AddressSpaceUtil, AllChainID, BigEndianHash,
@@ -69,12 +71,17 @@ use cfx_executor::{
         StateCommitResult,
     },
 };
+use cfx_rpc_eth_types::EvmOverrides;
 use cfx_vm_types::{Env, Spec};
 use geth_tracer::GethTraceWithHash;
 
 use alloy_rpc_types_trace::geth::GethDebugTracingOptions;
 
-use self::epoch_execution::{GethTask, VirtualCall};
+pub use self::simulation::{VirtualBlock, VirtualBlockOutcome};
+use self::{
+    epoch_execution::{GethTask, VirtualCall},
+    overrides::{apply_block_overrides, apply_state_overrides},
+};
 
 lazy_static! {
     static ref CONSENSIS_EXECUTION_TIMER: Arc<dyn Meter> =
@@ -636,9 +643,27 @@ impl ConsensusExecutor {
 
     pub fn call_virtual(
         &self, tx: &SignedTransaction, epoch_id: &H256, epoch_size: usize,
-        request: EstimateRequest,
+        request: EstimateRequest, overrides: EvmOverrides,
+    ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
+        self.handler
+            .call_virtual(tx, epoch_id, epoch_size, request, overrides)
+    }
+
+    pub fn call_virtual_on_storage(
+        &self, tx: &SignedTransaction, epoch_id: &H256, epoch_size: usize,
+        request: EstimateRequest, storage: Box<dyn StorageStateTrait>,
     ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
-        self.handler.call_virtual(tx, epoch_id, epoch_size, request)
+        self.handler
+            .call_virtual_on_storage(tx, epoch_id, epoch_size, request, storage)
+    }
+
+    pub fn call_virtual_bundle(
+        &self, epoch_id: &H256, epoch_size: usize, space: Space,
+        blocks: Vec<VirtualBlock>, validation: bool,
+    ) -> CoreResult<Vec<VirtualBlockOutcome>> {
+        self.handler.call_virtual_bundle(
+            epoch_id, epoch_size, space, blocks, validation,
+        )
     }
 
     pub fn collect_blocks_geth_trace(
@@ -1578,8 +1603,74 @@ impl ConsensusExecutionHandler {
 
     pub fn call_virtual(
         &self, tx: &SignedTransaction, epoch_id: &H256, epoch_size: usize,
-        request: EstimateRequest,
+        request: EstimateRequest, overrides: EvmOverrides,
+    ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
+        self.call_virtual_inner(
+            tx, epoch_id, epoch_size, request, overrides, None,
+        )
+    }
+
+    /// Executes a virtual transaction like `call_virtual`, but reads the
+    /// state of the epoch from `storage` instead of the local storage
+    /// manager. Used by light nodes, which do not keep the state.
+    pub fn call_virtual_on_storage(
+        &self, tx: &SignedTransaction, epoch_id: &H256, epoch_size: usize,
+        request: EstimateRequest, storage: Box<dyn StorageStateTrait>,
+    ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
+        self.call_virtual_inner(
+            tx,
+            epoch_id,
+            epoch_size,
+            request,
+            Default::default(),
+            Some(storage),
+        )
+    }
+
+    fn call_virtual_inner(
+        &self, tx: &SignedTransaction, epoch_id: &H256, epoch_size: usize,
+        request: EstimateRequest, overrides: EvmOverrides,
+        storage: Option<Box<dyn StorageStateTrait>>,
     ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
+        let (mut state, mut env) = self.virtual_call_context(
+            epoch_id,
+            epoch_size,
+            tx.space(),
+            storage,
+        )?;
+        env.gas_limit = tx.gas().clone();
+        let spec = self.machine.spec(env.number, env.epoch_height);
+        self.verify_virtual_transaction(tx, &env, &spec)?;
+
+        if let Some(state_overrides) = &overrides.state {
+            apply_state_overrides(&mut state, state_overrides, tx.space())?;
+        }
+        // The hardfork rules are decided before the block overrides, which
+        // only change what the executed code observes.
+        if let Some(block_overrides) = &overrides.block {
+            apply_block_overrides(&mut env, block_overrides, tx.space(), |x| {
+                state.burnt_gas_price(x)
+            });
+        }
+        let mut ex = EstimationContext::new(
+            &mut state,
+            &env,
+            self.machine.as_ref(),
+            &spec,
+        );
+
+        let r = ex.transact_virtual(tx.clone(), request);
+        trace!("Execution result {:?}", r);
+        Ok(r?)
+    }
+
+    /// Loads the state after executing the given epoch and the environment of
+    /// the next block, on top of which virtual calls are executed. The state
+    /// is read from `storage` if it is given.
+    fn virtual_call_context(
+        &self, epoch_id: &H256, epoch_size: usize, space: Space,
+        storage: Option<Box<dyn StorageStateTrait>>,
+    ) -> CoreResult<(State, Env)> {
         let best_block_header = self.data_man.block_header_by_hash(epoch_id);
         if best_block_header.is_none() {
             bail!("invalid epoch id");
@@ -1599,35 +1690,27 @@ impl ConsensusExecutionHandler {
             Some(v) => v.start_block_number + epoch_size as u64,
             None => bail!("cannot obtain the execution context. Database is potentially corrupted!"),
         };
-        let spec = self.machine.spec(start_block_number, block_height);
-        let transitions = &self.machine.params().transition_heights;
 
-        invalid_params_check(
-            "tx",
-            self.verification_config.verify_transaction_common(
-                tx,
-                AllChainID::fake_for_virtual(tx.chain_id().unwrap_or(1)),
-                block_height,
-                transitions,
-                VerifyTxMode::Local(VerifyTxLocalMode::Full, &spec),
-            ),
-        )?;
-
-        let state_space = match tx.space() {
-            Space::Native => None,
-            Space::Ethereum => Some(Space::Ethereum),
+        let state = match storage {
+            Some(storage) => State::new(StateDb::new(storage))?,
+            None => {
+                let state_space = match space {
+                    Space::Native => None,
+                    Space::Ethereum => Some(Space::Ethereum),
+                };
+                self.get_state_by_epoch_id_and_space(
+                    epoch_id,
+                    best_block_header.height(),
+                    state_space,
+                )?
+            }
         };
-        let mut state = self.get_state_by_epoch_id_and_space(
-            epoch_id,
-            best_block_header.height(),
-            state_space,
-        )?;
 
         let time_stamp = best_block_header.timestamp();
 
         let miner = {
             let mut address = H160::random();
-            if tx.space() == Space::Native {
+            if space == Space::Native {
                 address.set_user_account_type_bits();
             }
             address
@@ -1645,7 +1728,7 @@ impl ConsensusExecutionHandler {
             difficulty: Default::default(),
             accumulated_gas_used: U256::zero(),
             last_hash: epoch_id.clone(),
-            gas_limit: tx.gas().clone(),
+            gas_limit: best_block_header.gas_limit().clone(),
             epoch_height: block_height,
             pos_view: pos_view_number,
             finalized_epoch: pivot_decision_epoch,
@@ -1655,17 +1738,25 @@ impl ConsensusExecutionHandler {
             base_gas_price,
             burnt_gas_price,
         };
-        let spec = self.machine.spec(env.number, env.epoch_height);
-        let mut ex = EstimationContext::new(
-            &mut state,
-            &env,
-            self.machine.as_ref(),
-            &spec,
-        );
+        Ok((state, env))
+    }
 
-        let r = ex.transact_virtual(tx.clone(), request);
-        trace!("Execution result {:?}", r);
-        Ok(r?)
+    fn verify_virtual_transaction(
+        &self, tx: &SignedTransaction, env: &Env, spec: &Spec,
+    ) -> CoreResult<()> {
+        let transitions = &self.machine.params().transition_heights;
+
+        invalid_params_check(
+            "tx",
+            self.verification_config.verify_transaction_common(
+                tx,
+                AllChainID::fake_for_virtual(tx.chain_id().unwrap_or(1)),
+                env.epoch_height,
+                transitions,
+                VerifyTxMode::Local(VerifyTxLocalMode::Full, spec),
+            ),
+        )?;
+        Ok(())
     }
 
     /// Execute transactions in the blocks to collect traces.
@@ -0,0 +1,97 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Applies the state and block overrides of a virtual call. The overrides
+//! only change the in-memory `State` and `Env` of the call, which are
+//! dropped afterwards, so the persistent storage is never touched.
+
+use crate::errors::{invalid_params, Result as CoreResult};
+use cfx_executor::state::State;
+use cfx_rpc_eth_types::{BlockOverrides, StateOverride};
+use cfx_types::{AddressSpaceUtil, BigEndianHash, Space, U256};
+use cfx_util_macros::bail;
+use cfx_vm_types::Env;
+
+pub fn apply_state_overrides(
+    state: &mut State, overrides: &StateOverride, space: Space,
+) -> CoreResult<()> {
+    for (address, account) in overrides {
+        let address = address.with_space(space);
+
+        if account.state.is_some() && account.state_diff.is_some() {
+            bail!(invalid_params(
+                "state_overrides",
+                format!(
+                    "account {:?} has both 'state' and 'stateDiff'",
+                    address.address
+                ),
+            ));
+        }
+
+        if let Some(balance) = account.balance {
+            state.override_balance(&address, balance)?;
+        }
+        if let Some(nonce) = account.nonce {
+            state.set_nonce(&address, &U256::from(nonce.as_u64()))?;
+        }
+        if let Some(code) = &account.code {
+            state.override_code(&address, code.0.clone())?;
+        }
+        if let Some(storage) = &account.state {
+            state.clear_storage_for_override(&address)?;
+            for (key, value) in storage {
+                state.override_storage(
+                    &address,
+                    key.as_bytes().to_vec(),
+                    value.into_uint(),
+                )?;
+            }
+        }
+        if let Some(storage) = &account.state_diff {
+            for (key, value) in storage {
+                state.override_storage(
+                    &address,
+                    key.as_bytes().to_vec(),
+                    value.into_uint(),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies the block overrides to `env`. The number is the epoch number for
+/// eSpace and the block number for Core Space, as observed by the `NUMBER`
+/// opcode. `burnt_gas_price` computes the burnt part of an overridden base
+/// fee.
+pub fn apply_block_overrides(
+    env: &mut Env, overrides: &BlockOverrides, space: Space,
+    burnt_gas_price: impl Fn(U256) -> U256,
+) {
+    if let Some(number) = overrides.number {
+        match space {
+            Space::Native => env.number = number.as_u64(),
+            Space::Ethereum => env.epoch_height = number.as_u64(),
+        }
+    }
+    if let Some(difficulty) = overrides.difficulty {
+        env.difficulty = difficulty;
+    }
+    if let Some(random) = overrides.random {
+        env.difficulty = random.into_uint();
+    }
+    if let Some(time) = overrides.time {
+        env.timestamp = time.as_u64();
+    }
+    if let Some(gas_limit) = overrides.gas_limit {
+        env.gas_limit = gas_limit.as_u64().into();
+    }
+    if let Some(coinbase) = overrides.coinbase {
+        env.author = coinbase;
+    }
+    if let Some(base_fee) = overrides.base_fee {
+        env.base_gas_price[space] = base_fee;
+        env.burnt_gas_price = env.base_gas_price.map_all(burnt_gas_price);
+    }
+}
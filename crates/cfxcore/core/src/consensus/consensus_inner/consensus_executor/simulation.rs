@@ -0,0 +1,221 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use super::{
+    overrides::{apply_block_overrides, apply_state_overrides},
+    ConsensusExecutionHandler,
+};
+use crate::errors::Result as CoreResult;
+use cfx_execute_helper::estimation::{EstimateRequest, EstimationContext};
+use cfx_executor::executive::ExecutionOutcome;
+use cfx_rpc_eth_types::EvmOverrides;
+use cfx_types::{Space, H256};
+use cfx_vm_types::Env;
+use primitives::SignedTransaction;
+
+/// A block of virtual transactions, which are executed in sequence after its
+/// overrides are applied.
+pub struct VirtualBlock {
+    pub transactions: Vec<(SignedTransaction, EstimateRequest)>,
+    pub overrides: EvmOverrides,
+}
+
+/// The outcomes of the transactions in a `VirtualBlock`, together with the
+/// block environment they are executed in.
+pub struct VirtualBlockOutcome {
+    pub env: Env,
+    pub outcomes: Vec<ExecutionOutcome>,
+}
+
+impl ConsensusExecutionHandler {
+    /// Executes blocks of virtual transactions on top of the state of the
+    /// given epoch. All the transactions share one `State`, so each of them
+    /// observes the state changes of the previous ones. The state is
+    /// dropped afterwards and never committed.
+    ///
+    /// The simulated blocks follow the epoch one by one, but the hardfork
+    /// rules are always the ones of the first simulated block.
+    pub fn call_virtual_bundle(
+        &self, epoch_id: &H256, epoch_size: usize, space: Space,
+        blocks: Vec<VirtualBlock>, validation: bool,
+    ) -> CoreResult<Vec<VirtualBlockOutcome>> {
+        let (mut state, base_env) =
+            self.virtual_call_context(epoch_id, epoch_size, space, None)?;
+        let spec = self.machine.spec(base_env.number, base_env.epoch_height);
+
+        let mut answer = Vec::with_capacity(blocks.len());
+        for (idx, block) in blocks.into_iter().enumerate() {
+            let mut env = base_env.clone();
+            env.number += idx as u64;
+            env.epoch_height += idx as u64;
+            env.timestamp += idx as u64;
+
+            if let Some(state_overrides) = &block.overrides.state {
+                apply_state_overrides(&mut state, state_overrides, space)?;
+            }
+            if let Some(block_overrides) = &block.overrides.block {
+                apply_block_overrides(&mut env, block_overrides, space, |x| {
+                    state.burnt_gas_price(x)
+                });
+            }
+
+            let mut outcomes = Vec::with_capacity(block.transactions.len());
+            for (tx, request) in block.transactions {
+                self.verify_virtual_transaction(&tx, &base_env, &spec)?;
+
+                let outcome = EstimationContext::new(
+                    &mut state,
+                    &env,
+                    self.machine.as_ref(),
+                    &spec,
+                )
+                .transact_virtual_in_bundle(tx, request, validation)?;
+                trace!("Execution result {:?}", outcome);
+
+                if let Some(executed) = outcome.try_as_executed() {
+                    env.accumulated_gas_used += executed.gas_used;
+                }
+                outcomes.push(outcome);
+            }
+            answer.push(VirtualBlockOutcome { env, outcomes });
+        }
+        Ok(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VirtualBlock;
+    use crate::{
+        block_data_manager::{DataManagerConfiguration, DbType},
+        keylib::{Generator, KeyPair, Random},
+        pow::PowComputer,
+        sync::utils::{
+            initialize_data_manager_with_genesis_accounts,
+            initialize_synchronization_graph_with_data_manager,
+        },
+    };
+    use cfx_execute_helper::estimation::EstimateRequest;
+    use cfx_executor::{executive::ExecutionOutcome, machine::VmFactory};
+    use cfx_types::{Address, AddressSpaceUtil, Space, U256};
+    use primitives::{
+        transaction::native_transaction::NativeTransaction, Action,
+        EpochNumber, SignedTransaction, Transaction,
+    };
+    use std::{collections::HashMap, sync::Arc, time::Duration};
+
+    fn transfer(
+        sender: &KeyPair, receiver: Address, value: U256,
+    ) -> SignedTransaction {
+        let tx: Transaction = NativeTransaction {
+            nonce: U256::zero(),
+            gas_price: U256::one(),
+            gas: U256::from(21000),
+            action: Action::Call(receiver),
+            value,
+            storage_limit: 0,
+            epoch_height: 0,
+            chain_id: 1,
+            data: Vec::new(),
+        }
+        .into();
+        tx.sign(sender.secret())
+    }
+
+    #[test]
+    fn test_call_virtual_bundle() {
+        let dir = tempdir::TempDir::new("call_virtual_bundle").unwrap();
+        let alice = Random.generate().unwrap();
+        let bob = Random.generate().unwrap();
+        let carol = Random.generate().unwrap();
+
+        // only alice is funded at genesis
+        let genesis_accounts: HashMap<_, _> =
+            [(alice.address().with_native_space(), U256::exp10(18))]
+                .into_iter()
+                .collect();
+        let vm = VmFactory::new(1024 * 32);
+        let pow = Arc::new(PowComputer::new(true));
+        let (data_man, _) = initialize_data_manager_with_genesis_accounts(
+            dir.path().to_str().unwrap(),
+            DataManagerConfiguration::new(
+                false,
+                false,
+                Duration::from_millis(300_000),
+                DbType::Rocksdb,
+            ),
+            pow.clone(),
+            vm.clone(),
+            genesis_accounts,
+        );
+        let (_, consensus) = initialize_synchronization_graph_with_data_manager(
+            data_man, 1, 1, 1, 1, 50000, pow, vm,
+        );
+
+        // bob can only pay for his transfer with the funds sent by alice
+        let request = EstimateRequest {
+            has_sender: true,
+            has_gas_limit: true,
+            has_gas_price: true,
+            has_nonce: true,
+            has_storage_limit: true,
+            collect_access_list: false,
+        };
+        let bundle = || {
+            vec![
+                VirtualBlock {
+                    transactions: vec![(
+                        transfer(&alice, bob.address(), U256::exp10(17)),
+                        request,
+                    )],
+                    overrides: Default::default(),
+                },
+                VirtualBlock {
+                    transactions: vec![(
+                        transfer(&bob, carol.address(), U256::exp10(16)),
+                        request,
+                    )],
+                    overrides: Default::default(),
+                },
+            ]
+        };
+
+        // nothing is committed, so the bundle succeeds again with the same
+        // nonces
+        for _ in 0..2 {
+            let blocks = consensus
+                .call_virtual_bundle(
+                    EpochNumber::Number(0),
+                    Space::Native,
+                    bundle(),
+                    true, /* validation */
+                )
+                .unwrap();
+            assert_eq!(blocks.len(), 2);
+            assert_eq!(blocks[1].env.number, blocks[0].env.number + 1);
+            for block in &blocks {
+                assert_eq!(block.outcomes.len(), 1);
+                assert!(matches!(
+                    block.outcomes[0],
+                    ExecutionOutcome::Finished(_)
+                ));
+                assert_eq!(block.env.accumulated_gas_used, U256::from(21000));
+            }
+        }
+
+        // without the transfer of alice, bob can not pay
+        let blocks = consensus
+            .call_virtual_bundle(
+                EpochNumber::Number(0),
+                Space::Native,
+                bundle().split_off(1),
+                true, /* validation */
+            )
+            .unwrap();
+        assert!(!matches!(
+            blocks[0].outcomes[0],
+            ExecutionOutcome::Finished(_)
+        ));
+    }
+}
@@ -1358,6 +1358,10 @@ impl ConsensusNewBlockHandler {
             fork_at,
             &inner.arena[inner.get_pivot_block_arena_index(fork_at - 1)].hash,
         );
+        if pivot_changed && !extend_pivot {
+            // The log index does not have the blocks of the new pivot chain.
+            self.data_man.revert_log_index(fork_at);
+        }
 
         // Now compute last_pivot_in_block and update pivot_metadata.
         // Note that we need to do this for partially invalid blocks to
@@ -23,11 +23,16 @@ pub use crate::consensus::{
 };
 use crate::{
     block_data_manager::{
-        BlockDataManager, BlockExecutionResultWithEpoch, DataVersionTuple,
+        log_index::LogIndexCandidates, BlockDataManager,
+        BlockExecutionResultWithEpoch, DataVersionTuple,
     },
     consensus::{
         consensus_inner::{
-            consensus_executor::ConsensusExecutionConfiguration, StateBlameInfo,
+            consensus_executor::{
+                ConsensusExecutionConfiguration, VirtualBlock,
+                VirtualBlockOutcome,
+            },
+            StateBlameInfo,
         },
         pos_handler::PosVerifier,
     },
@@ -62,6 +67,7 @@ use cfx_parameters::{
     },
 };
 use cfx_rpc_cfx_types::PhantomBlock;
+use cfx_rpc_eth_types::EvmOverrides;
 use cfx_statedb::StateDb;
 use cfx_storage::{
     state::StateTrait, state_manager::StateManagerTrait, StorageState,
@@ -90,6 +96,7 @@ use std::{
     any::Any,
     cmp::{max, min},
     collections::HashSet,
+    ops::RangeInclusive,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -944,7 +951,7 @@ impl ConsensusGraph {
 
     fn filter_single_epoch<'a>(
         &'a self, filter: &'a LogFilter, bloom_possibilities: &'a Vec<Bloom>,
-        epoch: u64,
+        epoch: u64, log_index: Option<&LogIndexCandidates>,
     ) -> Result<Vec<LocalizedLogEntry>, FilterError> {
         // retrieve epoch hashes and pivot hash
         let mut epoch_hashes =
@@ -952,6 +959,16 @@ impl ConsensusGraph {
 
         let pivot_hash = *epoch_hashes.last().expect("Epoch set not empty");
 
+        // skip the blocks without matching logs in the log index
+        if let Some(log_index) = log_index {
+            epoch_hashes = epoch_hashes
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| log_index.contains_block(epoch, *i))
+                .map(|(_, hash)| hash)
+                .collect();
+        }
+
         // process hashes in reverse order
         epoch_hashes.reverse();
 
@@ -988,7 +1005,8 @@ impl ConsensusGraph {
 
     fn filter_epoch_batch(
         &self, filter: &LogFilter, bloom_possibilities: &Vec<Bloom>,
-        epochs: Vec<u64>, consistency_check_data: &mut Option<(u64, H256)>,
+        epochs: Vec<u64>, log_index: Option<&LogIndexCandidates>,
+        consistency_check_data: &mut Option<(u64, H256)>,
     ) -> Result<Vec<LocalizedLogEntry>, FilterError> {
         // lock so that we have a consistent view during this batch
         let inner = self.inner.read();
@@ -1018,7 +1036,14 @@ impl ConsensusGraph {
 
         let epoch_batch_logs = epochs
             .into_par_iter() // process each epoch of this batch in parallel
-            .map(|e| self.filter_single_epoch(filter, bloom_possibilities, e))
+            .map(|e| {
+                self.filter_single_epoch(
+                    filter,
+                    bloom_possibilities,
+                    e,
+                    log_index,
+                )
+            })
             .collect::<Result<Vec<Vec<LocalizedLogEntry>>, FilterError>>()?; // short-circuit on error
 
         Ok(epoch_batch_logs.into_iter().flatten().collect())
@@ -1027,7 +1052,7 @@ impl ConsensusGraph {
     pub fn get_log_filter_epoch_range(
         &self, from_epoch: EpochNumber, to_epoch: EpochNumber,
         check_range: bool,
-    ) -> Result<impl Iterator<Item = u64>, FilterError> {
+    ) -> Result<RangeInclusive<u64>, FilterError> {
         // lock so that we have a consistent view
         let _inner = self.inner.read_recursive();
 
@@ -1062,7 +1087,7 @@ impl ConsensusGraph {
             }
         }
 
-        return Ok((from_epoch..=to_epoch).rev());
+        return Ok(from_epoch..=to_epoch);
     }
 
     pub fn get_trace_filter_epoch_range(
@@ -1095,6 +1120,47 @@ impl ConsensusGraph {
     fn filter_logs_by_epochs(
         &self, from_epoch: EpochNumber, to_epoch: EpochNumber,
         filter: &LogFilter, blocks_to_skip: HashSet<H256>, check_range: bool,
+    ) -> Result<Vec<LocalizedLogEntry>, FilterError> {
+        let epochs =
+            self.get_log_filter_epoch_range(from_epoch, to_epoch, check_range)?;
+
+        // the epochs without matching logs in the log index are skipped, the
+        // epochs not covered by the index are always filtered
+        let log_index = self.data_man.log_index_candidates(
+            filter,
+            *epochs.start(),
+            *epochs.end(),
+        );
+
+        let logs = self.filter_logs_by_epoch_range(
+            filter,
+            epochs.clone(),
+            &blocks_to_skip,
+            log_index.as_ref(),
+        )?;
+
+        // a pivot chain reorg during filtering may revert the index of the
+        // filtered epochs, which does not have the blocks of the new pivot
+        // chain, so the blooms of all the blocks are checked instead
+        match log_index {
+            Some(log_index)
+                if self.data_man.log_index_progress()
+                    < log_index.indexed_end() =>
+            {
+                self.filter_logs_by_epoch_range(
+                    filter,
+                    epochs,
+                    &blocks_to_skip,
+                    None,
+                )
+            }
+            _ => Ok(logs),
+        }
+    }
+
+    fn filter_logs_by_epoch_range(
+        &self, filter: &LogFilter, epochs: RangeInclusive<u64>,
+        blocks_to_skip: &HashSet<H256>, log_index: Option<&LogIndexCandidates>,
     ) -> Result<Vec<LocalizedLogEntry>, FilterError> {
         let bloom_possibilities = filter.bloom_possibilities();
 
@@ -1102,9 +1168,10 @@ impl ConsensusGraph {
         // that we can check whether it changed between batches
         let mut consistency_check_data: Option<(u64, H256)> = None;
 
-        let mut logs = self
+        let mut logs = epochs
             // iterate over epochs in reverse order
-            .get_log_filter_epoch_range(from_epoch, to_epoch, check_range)?
+            .rev()
+            .filter(|e| log_index.map_or(true, |c| c.contains_epoch(*e)))
             // we process epochs in each batch in parallel
             // but batches are processed one-by-one
             .chunks(self.config.get_logs_epoch_batch_size)
@@ -1114,6 +1181,7 @@ impl ConsensusGraph {
                     &filter,
                     &bloom_possibilities,
                     epochs.into_iter().collect(),
+                    log_index,
                     &mut consistency_check_data,
                 )
             })
@@ -1429,19 +1497,48 @@ impl ConsensusGraph {
 
     pub fn call_virtual(
         &self, tx: &SignedTransaction, epoch: EpochNumber,
-        request: EstimateRequest,
+        request: EstimateRequest, overrides: EvmOverrides,
+    ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
+        let (epoch_id, epoch_size) = self.virtual_call_epoch(epoch)?;
+        self.executor
+            .call_virtual(tx, &epoch_id, epoch_size, request, overrides)
+    }
+
+    /// Executes a virtual transaction against the state of the epoch with
+    /// pivot block `epoch_id` and `epoch_size` blocks, read from `storage`.
+    /// Unlike `call_virtual`, the epoch does not need to be executed locally.
+    pub fn call_virtual_on_storage(
+        &self, tx: &SignedTransaction, epoch_id: &H256, epoch_size: usize,
+        request: EstimateRequest, storage: Box<dyn StateTrait>,
     ) -> CoreResult<(ExecutionOutcome, EstimateExt)> {
+        self.executor
+            .call_virtual_on_storage(tx, epoch_id, epoch_size, request, storage)
+    }
+
+    /// Executes blocks of virtual transactions in sequence against the stated
+    /// epoch, see `ConsensusExecutionHandler::call_virtual_bundle`.
+    pub fn call_virtual_bundle(
+        &self, epoch: EpochNumber, space: Space, blocks: Vec<VirtualBlock>,
+        validation: bool,
+    ) -> CoreResult<Vec<VirtualBlockOutcome>> {
+        let (epoch_id, epoch_size) = self.virtual_call_epoch(epoch)?;
+        self.executor.call_virtual_bundle(
+            &epoch_id, epoch_size, space, blocks, validation,
+        )
+    }
+
+    /// Returns the pivot hash and the number of blocks of the epoch to
+    /// execute virtual calls against.
+    fn virtual_call_epoch(
+        &self, epoch: EpochNumber,
+    ) -> CoreResult<(H256, usize)> {
         // only allow to call against stated epoch
         self.validate_stated_epoch(&epoch)?;
-        let (epoch_id, epoch_size) = if let Ok(v) =
-            self.get_block_hashes_by_epoch(epoch)
-        {
-            (v.last().expect("pivot block always exist").clone(), v.len())
+        if let Ok(v) = self.get_block_hashes_by_epoch(epoch) {
+            Ok((v.last().expect("pivot block always exist").clone(), v.len()))
         } else {
             bail!("cannot get block hashes in the specified epoch, maybe it does not exist?");
-        };
-        self.executor
-            .call_virtual(tx, &epoch_id, epoch_size, request)
+        }
     }
 
     pub fn collect_epoch_geth_trace(
@@ -2484,7 +2581,8 @@ impl ConsensusGraphTrait for ConsensusGraph {
         self.txpool.set_ready();
         self.txpool
             .notify_new_best_info(self.best_info.read_recursive().clone())
-            .expect("No DB error")
+            .expect("No DB error");
+        self.txpool.replay_journal();
     }
 
     /// Reset the information in consensus graph with only checkpoint
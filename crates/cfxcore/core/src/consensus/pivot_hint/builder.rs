@@ -0,0 +1,97 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use crate::hash::{keccak, H256};
+
+use super::{
+    header::{PivotHintHeader, HEADER_LENGTH},
+    PivotHintParams,
+};
+
+/// Builds a pivot hint file from a trusted pivot chain.
+///
+/// # Arguments
+/// * `file_path` - Path of the pivot hint file to write
+/// * `params` - Parameters of the pivot hint file
+/// * `pivot_hash_at` - Callback to retrieve the pivot block hash at specified
+///   height, for every height below `range_max` which is a multiple of
+///   `minor_interval`
+///
+/// # Returns
+/// Returns the keccak hash of the Page Digests Part, which is the checksum to
+/// load the file with.
+pub fn build_pivot_hint_file(
+    file_path: &Path, params: &PivotHintParams,
+    mut pivot_hash_at: impl FnMut(u64) -> Result<H256, String>,
+) -> Result<H256, String> {
+    let header = PivotHintHeader::new(
+        params.minor_interval as u64,
+        params.major_interval as u64,
+        params.page_interval as u64,
+        params.range_max,
+        params.minor_hash_length as usize,
+    )?;
+
+    let file = File::create(file_path)
+        .map_err(|e| format!("Cannot create file: {:?}", e))?;
+    let mut writer = BufWriter::new(file);
+    writer
+        .write_all(&header.to_raw())
+        .map_err(|e| format!("Cannot write header: {:?}", e))?;
+
+    // The page digests are only known after all the pages are built, so
+    // their space is reserved here and filled in at last.
+    let page_number = header.page_number();
+    writer
+        .write_all(&vec![0u8; page_number * 32])
+        .map_err(|e| format!("Cannot write page digests: {:?}", e))?;
+
+    let mut raw_page_digests = Vec::with_capacity(page_number * 32);
+    for page_number in 0..page_number as u64 {
+        let page_content =
+            build_page(&header, page_number, &mut pivot_hash_at)?;
+        raw_page_digests.extend_from_slice(keccak(&page_content).as_bytes());
+        writer
+            .write_all(&page_content)
+            .map_err(|e| format!("Cannot write page: {:?}", e))?;
+    }
+
+    writer
+        .seek(SeekFrom::Start(HEADER_LENGTH as u64))
+        .map_err(|e| format!("Cannot seek to page digests: {:?}", e))?;
+    writer
+        .write_all(&raw_page_digests)
+        .map_err(|e| format!("Cannot write page digests: {:?}", e))?;
+    writer
+        .into_inner()
+        .map_err(|e| format!("Cannot flush file: {:?}", e))?
+        .sync_all()
+        .map_err(|e| format!("Cannot sync file: {:?}", e))?;
+
+    Ok(keccak(&raw_page_digests))
+}
+
+fn build_page(
+    header: &PivotHintHeader, page_number: u64,
+    pivot_hash_at: &mut impl FnMut(u64) -> Result<H256, String>,
+) -> Result<Vec<u8>, String> {
+    let mut major_section = Vec::with_capacity(header.major_section_bytes());
+    let mut minor_section = Vec::with_capacity(header.minor_section_bytes());
+
+    let start_height = page_number * header.page_interval;
+    for page_offset in
+        (0..header.page_interval).step_by(header.minor_interval as usize)
+    {
+        let hash = pivot_hash_at(start_height + page_offset)?;
+        if page_offset % header.major_interval == 0 {
+            major_section.extend_from_slice(hash.as_bytes());
+        }
+        minor_section.extend_from_slice(&hash[..header.minor_hash_length]);
+    }
+
+    major_section.append(&mut minor_section);
+    Ok(major_section)
+}
@@ -18,3 +18,23 @@ impl PivotHintConfig {
         }
     }
 }
+
+/// Parameters of a pivot hint file to build. See the module document for the
+/// constraints between them.
+#[derive(Clone, Copy, Debug)]
+pub struct PivotHintParams {
+    /// Upper bound (exclusive) of block heights for stored hashes
+    pub range_max: u64,
+
+    /// Number of consecutive blocks in each page
+    pub page_interval: u32,
+
+    /// Interval of heights with full hashes recorded
+    pub major_interval: u32,
+
+    /// Interval of heights with hash prefixes recorded
+    pub minor_interval: u32,
+
+    /// Length of the recorded hash prefixes
+    pub minor_hash_length: u32,
+}
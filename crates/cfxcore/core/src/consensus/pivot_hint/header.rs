@@ -38,6 +38,35 @@ impl PivotHintHeader {
         let range_max = read_u64(&mut reader).unwrap();
         let minor_hash_length = read_u32(&mut reader).unwrap() as usize;
 
+        let header = PivotHintHeader::new(
+            minor_interval,
+            major_interval,
+            page_interval,
+            range_max,
+            minor_hash_length,
+        )?;
+
+        let page_bytes = read_u32(&mut reader).unwrap() as usize;
+        if header.page_bytes() != page_bytes {
+            return Err("Inconsistent page bytes".into());
+        }
+        assert!(reader.is_empty());
+
+        Ok(header)
+    }
+
+    pub fn new(
+        minor_interval: u64, major_interval: u64, page_interval: u64,
+        range_max: u64, minor_hash_length: usize,
+    ) -> Result<Self, String> {
+        if minor_interval == 0 || major_interval == 0 || page_interval == 0 {
+            return Err("Inconsistent header params: zero interval".into());
+        }
+
+        if page_interval > u32::MAX as u64 {
+            return Err("Inconsistent header params: page_interval".into());
+        }
+
         if major_interval % minor_interval != 0 {
             return Err("Inconsistent header params: major_interval".into());
         }
@@ -50,21 +79,28 @@ impl PivotHintHeader {
             return Err("Inconsistent header params: range_max".into());
         }
 
-        let header = PivotHintHeader {
+        if minor_hash_length > 32 {
+            return Err("Inconsistent header params: minor_hash_length".into());
+        }
+
+        Ok(PivotHintHeader {
             major_interval,
             minor_interval,
             minor_hash_length,
             range_max,
             page_interval,
-        };
-
-        let page_bytes = read_u32(&mut reader).unwrap() as usize;
-        if header.page_bytes() != page_bytes {
-            return Err("Inconsistent page bytes".into());
-        }
-        assert!(reader.is_empty());
+        })
+    }
 
-        Ok(header)
+    pub fn to_raw(&self) -> [u8; HEADER_LENGTH] {
+        let mut raw = Vec::with_capacity(HEADER_LENGTH);
+        raw.extend_from_slice(&(self.minor_interval as u32).to_le_bytes());
+        raw.extend_from_slice(&(self.major_interval as u32).to_le_bytes());
+        raw.extend_from_slice(&(self.page_interval as u32).to_le_bytes());
+        raw.extend_from_slice(&self.range_max.to_le_bytes());
+        raw.extend_from_slice(&(self.minor_hash_length as u32).to_le_bytes());
+        raw.extend_from_slice(&(self.page_bytes() as u32).to_le_bytes());
+        raw.try_into().expect("header length is fixed")
     }
 
     pub fn major_section_bytes(&self) -> usize {
@@ -53,14 +53,22 @@
 //!    Checksum
 //! 3. Keep Page Digests in memory
 //! 4. Verify each page against Page Digests when loading to prevent corruption
+//!
+//! # Building Process
+//! [build_pivot_hint_file] writes a pivot hint file from the pivot chain of a
+//! fully synced node, and returns the Pivot Hint Checksum to be configured as
+//! `pivot_hint_checksum`. [PivotHint::verify_all] checks every record of an
+//! existing file against such a pivot chain.
 
+mod builder;
 mod config;
 mod header;
 mod page;
 #[cfg(test)]
 mod tests;
 
-pub use config::PivotHintConfig;
+pub use builder::build_pivot_hint_file;
+pub use config::{PivotHintConfig, PivotHintParams};
 use header::{PivotHintHeader, HEADER_LENGTH};
 use page::PivotHintPage;
 
@@ -111,17 +119,8 @@ impl PivotHint {
     /// * Header parsing errors
     /// * Checksum mismatch
     pub fn new(conf: &PivotHintConfig) -> Result<Self, String> {
-        let mut file = File::open(&conf.file_path)
-            .map_err(|e| format!("Cannot open file: {:?}", e))?;
-        let mut raw_header = [0u8; HEADER_LENGTH];
-        file.read_exact(&mut raw_header)
-            .map_err(|e| format!("Cannot load header: {:?}", e))?;
-        let header = PivotHintHeader::from_raw(raw_header)
-            .map_err(|e| format!("Cannot parse and check header: {}", e))?;
-
-        let mut raw_page_digests = vec![0u8; header.page_number() * 32];
-        file.read_exact(&mut raw_page_digests)
-            .map_err(|e| format!("Cannot load page digests: {:?}", e))?;
+        let (header, raw_page_digests) =
+            Self::load_header_and_page_digests(&conf.file_path)?;
         let file_checksum = keccak(&raw_page_digests);
         if file_checksum != conf.checksum {
             return Err("Incorrect checksum".into());
@@ -141,6 +140,67 @@ impl PivotHint {
         })
     }
 
+    /// Computes the Pivot Hint Checksum of a pivot hint file, i.e. the keccak
+    /// hash of its Page Digests Part.
+    pub fn file_checksum(file_path: &str) -> Result<H256, String> {
+        let (_, raw_page_digests) =
+            Self::load_header_and_page_digests(file_path)?;
+        Ok(keccak(&raw_page_digests))
+    }
+
+    fn load_header_and_page_digests(
+        file_path: &str,
+    ) -> Result<(PivotHintHeader, Vec<u8>), String> {
+        let mut file = File::open(file_path)
+            .map_err(|e| format!("Cannot open file: {:?}", e))?;
+        let mut raw_header = [0u8; HEADER_LENGTH];
+        file.read_exact(&mut raw_header)
+            .map_err(|e| format!("Cannot load header: {:?}", e))?;
+        let header = PivotHintHeader::from_raw(raw_header)
+            .map_err(|e| format!("Cannot parse and check header: {}", e))?;
+
+        let mut raw_page_digests = vec![0u8; header.page_number() * 32];
+        file.read_exact(&mut raw_page_digests)
+            .map_err(|e| format!("Cannot load page digests: {:?}", e))?;
+        Ok((header, raw_page_digests))
+    }
+
+    /// Checks every record in the pivot hint file against a trusted pivot
+    /// chain, e.g. the one in the local database of a fully synced node.
+    ///
+    /// # Arguments
+    /// * `pivot_hash_at` - Callback to retrieve the pivot block hash at
+    ///   specified height
+    ///
+    /// # Errors
+    /// * Page loading errors
+    /// * Errors from `pivot_hash_at`
+    /// * The first height where the records mismatch the pivot chain
+    pub fn verify_all(
+        &self, mut pivot_hash_at: impl FnMut(u64) -> Result<H256, String>,
+    ) -> Result<(), String> {
+        for page_number in 0..self.header.page_number() as u64 {
+            let page = self.load_page(page_number).map_err(|e| {
+                format!("Cannot load page {}: {}", page_number, e)
+            })?;
+
+            let start_height = page_number * self.header.page_interval;
+            for page_offset in (0..self.header.page_interval)
+                .step_by(self.header.minor_interval as usize)
+            {
+                let height = start_height + page_offset;
+                let hash = pivot_hash_at(height)?;
+                if !page.check_hash_at_height(page_offset, hash) {
+                    return Err(format!(
+                        "Pivot hint mismatches at height {}, local hash {:?}",
+                        height, hash
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Validates if switching to a target branch is allowed based on pivot hint
     /// records.
     ///
@@ -204,6 +264,8 @@ impl PivotHint {
 
     pub fn is_active(&self) -> bool { self.active.load(Ordering::Acquire) }
 
+    pub fn range_max(&self) -> u64 { self.header.range_max }
+
     fn check_hash(&self, height: u64, hash: H256) -> bool {
         let page_number = height / self.header.page_interval;
         let page_offset = height % self.header.page_interval;
@@ -6,9 +6,11 @@ use std::{
     str::FromStr,
 };
 
-use crate::hash::H256;
+use crate::hash::{keccak, H256};
 
-use super::{PivotHint, PivotHintConfig};
+use super::{
+    build_pivot_hint_file, PivotHint, PivotHintConfig, PivotHintParams,
+};
 
 pub struct TestHashFile {
     file: File,
@@ -86,3 +88,51 @@ fn test_pivot_hint() {
         assert!(pivot_hint.is_active());
     }
 }
+
+#[test]
+fn test_build_pivot_hint() {
+    let dir = tempdir::TempDir::new("pivot_hint").unwrap();
+    let file_path = dir.path().join("pivot_hint.bin");
+    let params = PivotHintParams {
+        range_max: 10_000,
+        page_interval: 1_000,
+        major_interval: 100,
+        minor_interval: 5,
+        minor_hash_length: 4,
+    };
+    let pivot_hash_at = |height: u64| keccak(height.to_le_bytes());
+
+    let checksum = build_pivot_hint_file(&file_path, &params, |height| {
+        Ok(pivot_hash_at(height))
+    })
+    .unwrap();
+    let file_path = file_path.to_str().unwrap();
+    assert_eq!(PivotHint::file_checksum(file_path).unwrap(), checksum);
+
+    let pivot_hint =
+        PivotHint::new(&PivotHintConfig::new(file_path, checksum)).unwrap();
+    assert_eq!(pivot_hint.range_max(), 10_000);
+    pivot_hint
+        .verify_all(|height| Ok(pivot_hash_at(height)))
+        .unwrap();
+
+    let err = pivot_hint
+        .verify_all(|height| {
+            let mut hash = pivot_hash_at(height);
+            if height == 5_005 {
+                hash.0[0] ^= 0x80;
+            }
+            Ok(hash)
+        })
+        .unwrap_err();
+    assert!(err.contains("height 5005"));
+
+    assert!(pivot_hint.allow_extend(3_100, pivot_hash_at(3_100)));
+    assert!(!pivot_hint.allow_extend(3_100, H256::zero()));
+    assert!(pivot_hint.allow_extend(3_105, pivot_hash_at(3_105)));
+    assert!(!pivot_hint.allow_extend(3_105, H256::zero()));
+    assert!(pivot_hint.allow_extend(10_000, H256::zero()));
+
+    let config = PivotHintConfig::new(file_path, H256::zero());
+    assert!(PivotHint::new(&config).is_err());
+}
@@ -51,7 +51,7 @@ use diem_types::{
 use network::NetworkService;
 use parking_lot::Mutex;
 use pos_ledger_db::PosLedgerDB;
-use std::{fs, io::Read, path::PathBuf};
+use std::{fs, io::Read, net::SocketAddr, path::PathBuf};
 
 pub type PosVerifier = PosHandler;
 
@@ -230,6 +230,7 @@ impl PosHandler {
                 .expect("not initialized"),
             test_command_receiver,
             self.hsb_protocol_handler.clone().expect("set in new"),
+            self.conf.backup_service_address,
         );
         debug!("PoS initialized");
         let pos_connection = PosConnection::new(
@@ -640,6 +641,9 @@ pub struct PosConfiguration {
     pub pos_initial_nodes_path: String,
     pub vrf_proposal_threshold: U256,
     pub pos_state_config: PosStateConfig,
+    /// The address of the backup service of the PoS ledger. The service is
+    /// not started if it's `None`.
+    pub backup_service_address: Option<SocketAddr>,
 }
 
 fn diem_hash_to_h256(h: &HashValue) -> PosBlockId { H256::from(h.as_ref()) }
@@ -9,7 +9,9 @@ use crate::{
 };
 use cfx_internal_common::ChainIdParamsOneChainInner;
 use cfx_types::{H160, H256};
-use network::{node_table::NodeId, NetworkContext, UpdateNodeOperation};
+use network::{
+    node_table::NodeId, NetworkContext, ReputationChange, UpdateNodeOperation,
+};
 use parking_lot::Mutex;
 use primitives::{account::AccountError, filter::FilterError, StateRoot};
 use rlp::DecoderError;
@@ -162,6 +164,7 @@ pub fn handle(
     let mut disconnect = true;
     let reason = format!("{}", e);
     let mut op = None;
+    let mut change = None;
 
     // NOTE: do not use wildcard; this way, the compiler
     // will help covering all the cases.
@@ -201,13 +204,22 @@ pub fn handle(
 
 
         Error::GenesisMismatch{..}
-        | Error::InvalidHeader
-        | Error::ChainIdMismatch{..}
-        | Error::UnexpectedMessage{..}
-        | Error::UnexpectedPeerType{..} => op = Some(UpdateNodeOperation::Failure),
+        | Error::ChainIdMismatch{..} => op = Some(UpdateNodeOperation::Failure),
+
+        Error::InvalidHeader => {
+            op = Some(UpdateNodeOperation::Failure);
+            change = Some(ReputationChange::InvalidBlock);
+        }
+
+        Error::UnexpectedMessage{..}
+        | Error::UnexpectedPeerType{..} => {
+            op = Some(UpdateNodeOperation::Failure);
+            change = Some(ReputationChange::ProtocolViolation);
+        }
 
         Error::UnexpectedResponse{..} => {
-            op = Some(UpdateNodeOperation::Demotion)
+            op = Some(UpdateNodeOperation::Demotion);
+            change = Some(ReputationChange::UselessResponse);
         }
 
         Error::InvalidBloom{..}
@@ -224,7 +236,10 @@ pub fn handle(
         | Error::InvalidWitnessRoot{..}
         | Error::AlreadyThrottled(_)
         | Error::Decoder(_)
-        | Error::AccountError(_) => op = Some(UpdateNodeOperation::Remove),
+        | Error::AccountError(_) => {
+            op = Some(UpdateNodeOperation::Remove);
+            change = Some(ReputationChange::ProtocolViolation);
+        }
 
         Error::Throttled(_, resp) => {
             disconnect = false;
@@ -243,6 +258,7 @@ pub fn handle(
 
             network::Error::MessageDeprecated{..} => {
                 op = Some(UpdateNodeOperation::Failure);
+                change = Some(ReputationChange::ProtocolViolation);
                 error!(
                     "Peer sent us a deprecated message {:?}. Either it's a bug \
                     in protocol version maintenance or the peer is malicious.",
@@ -262,7 +278,8 @@ pub fn handle(
             | network::Error::Throttling(_) => disconnect = false,
 
             network::Error::BadProtocol | network::Error::Decoder(_) => {
-                op = Some(UpdateNodeOperation::Remove)
+                op = Some(UpdateNodeOperation::Remove);
+                change = Some(ReputationChange::ProtocolViolation);
             }
 
             network::Error::SocketIo(_) | network::Error::Msg(_) => {
@@ -277,6 +294,13 @@ pub fn handle(
         // }
     };
 
+    if let Some(change) = change {
+        // already disconnected if banned
+        if io.report_peer(peer, change, reason.as_str()) {
+            disconnect = false;
+        }
+    }
+
     if disconnect {
         io.disconnect_peer(peer, op, reason.as_str());
     }
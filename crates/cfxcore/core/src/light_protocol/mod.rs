@@ -9,6 +9,7 @@ mod handler;
 mod message;
 mod provider;
 pub mod query_service;
+mod state_storage;
 
 use network::{service::ProtocolVersion, ProtocolId};
 
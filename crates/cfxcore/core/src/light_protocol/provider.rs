@@ -48,6 +48,7 @@ use cfx_types::H256;
 use diem_types::validator_config::{ConsensusPublicKey, ConsensusVRFPublicKey};
 use io::TimerToken;
 use malloc_size_of_derive::MallocSizeOf as DeriveMallocSizeOf;
+use metrics::{register_meter_with_group, Meter};
 use network::{
     node_table::NodeId, service::ProtocolVersion,
     throttling::THROTTLING_SERVICE, NetworkContext, NetworkProtocolHandler,
@@ -67,6 +68,16 @@ use throttling::token_bucket::{ThrottleResult, TokenBucketManager};
 
 const CHECK_PEER_HEARTBEAT_TIMER: TimerToken = 0;
 
+lazy_static! {
+    static ref THROTTLED_METER: Arc<dyn Meter> =
+        register_meter_with_group("ingress_throttling", "light::throttled");
+    static ref ALREADY_THROTTLED_METER: Arc<dyn Meter> =
+        register_meter_with_group(
+            "ingress_throttling",
+            "light::already_throttled"
+        );
+}
+
 #[derive(DeriveMallocSizeOf)]
 pub struct Provider {
     pub protocol_version: ProtocolVersion,
@@ -983,6 +994,7 @@ impl Provider {
         match result {
             ThrottleResult::Success => Ok(()),
             ThrottleResult::Throttled(wait_time) => {
+                THROTTLED_METER.mark(1);
                 let throttled = Throttled {
                     msg_id: msg.msg_id(),
                     wait_time_nanos: wait_time.as_nanos() as u64,
@@ -992,6 +1004,7 @@ impl Provider {
                 bail!(Error::Throttled(msg.msg_name(), throttled))
             }
             ThrottleResult::AlreadyThrottled => {
+                ALREADY_THROTTLED_METER.mark(1);
                 bail!(Error::AlreadyThrottled(msg.msg_name()))
             }
         }
@@ -9,6 +9,7 @@ use crate::{
         common::{FullPeerFilter, LedgerInfo},
         handler::sync::TxInfoValidated,
         message::msgid,
+        state_storage::LightStateStorage,
         Error as LightError, Handler as LightHandler, LightNodeConfiguration,
         LIGHT_PROTOCOL_ID, LIGHT_PROTOCOL_VERSION,
     },
@@ -16,7 +17,13 @@ use crate::{
     ConsensusGraph, Notifications,
 };
 use cfx_addr::Network;
-use cfx_executor::state::COMMISSION_PRIVILEGE_SPECIAL_KEY;
+use cfx_execute_helper::{
+    estimation::{EstimateExt, EstimateRequest},
+    phantom_tx::build_bloom_and_recover_phantom,
+};
+use cfx_executor::{
+    executive::ExecutionOutcome, state::COMMISSION_PRIVILEGE_SPECIAL_KEY,
+};
 use cfx_parameters::{
     consensus::DEFERRED_STATE_EPOCH_COUNT,
     internal_contract_addresses::SPONSOR_WHITELIST_CONTROL_CONTRACT_ADDRESS,
@@ -27,22 +34,24 @@ use cfx_parameters::{
         TRANSACTION_COUNT_PER_BLOCK_WATER_LINE_MEDIUM,
     },
 };
+use cfx_rpc_cfx_types::PhantomBlock;
 use cfx_statedb::global_params::{self, GlobalParamKey};
 use cfx_types::{
-    address_util::AddressUtil, AllChainID, BigEndianHash, Bloom, H160, H256,
-    KECCAK_EMPTY_BLOOM, U256,
+    address_util::AddressUtil, AddressSpaceUtil, AddressWithSpace, AllChainID,
+    BigEndianHash, Bloom, Space, H160, H256, KECCAK_EMPTY_BLOOM, U256,
 };
 use futures::{
     future::{self, Either},
-    stream, try_join, StreamExt, TryStreamExt,
+    stream, try_join, StreamExt, TryFutureExt, TryStreamExt,
 };
+use keccak_hash::KECCAK_EMPTY;
 use network::{service::ProtocolVersion, NetworkContext, NetworkService};
 use primitives::{
     filter::{FilterError, LogFilter},
     log_entry::{LocalizedLogEntry, LogEntry},
     Account, Block, BlockReceipts, CodeInfo, DepositList, EpochNumber, Receipt,
     SignedTransaction, StorageKey, StorageRoot, StorageValue, TransactionIndex,
-    VoteStakeList,
+    TransactionStatus, VoteStakeList,
 };
 use rlp::Rlp;
 use std::{collections::BTreeSet, future::Future, sync::Arc, time::Duration};
@@ -127,7 +136,7 @@ impl QueryService {
             .expect("Unable to access network service")
     }
 
-    async fn retrieve_state_entry_raw(
+    pub(super) async fn retrieve_state_entry_raw(
         &self, epoch: u64, key: Vec<u8>,
     ) -> Result<Option<Vec<u8>>, LightError> {
         trace!(
@@ -361,21 +370,21 @@ impl QueryService {
         }
     }
 
-    fn account_key(address: &H160) -> Vec<u8> {
-        StorageKey::new_account_key(&address)
-            .with_native_space()
+    fn account_key(address: &AddressWithSpace) -> Vec<u8> {
+        StorageKey::new_account_key(&address.address)
+            .with_space(address.space)
             .to_key_bytes()
     }
 
-    fn code_key(address: &H160, code_hash: &H256) -> Vec<u8> {
-        StorageKey::new_code_key(&address, &code_hash)
-            .with_native_space()
+    fn code_key(address: &AddressWithSpace, code_hash: &H256) -> Vec<u8> {
+        StorageKey::new_code_key(&address.address, &code_hash)
+            .with_space(address.space)
             .to_key_bytes()
     }
 
-    fn storage_key(address: &H160, position: &[u8]) -> Vec<u8> {
-        StorageKey::new_storage_key(&address, &position)
-            .with_native_space()
+    fn storage_key(address: &AddressWithSpace, position: &[u8]) -> Vec<u8> {
+        StorageKey::new_storage_key(&address.address, &position)
+            .with_space(address.space)
             .to_key_bytes()
     }
 
@@ -395,15 +404,31 @@ impl QueryService {
         &self, epoch: EpochNumber, address: H160,
     ) -> Result<Option<Account>, LightError> {
         debug!("get_account epoch={:?} address={:?}", epoch, address);
+        self.get_account_with_space(epoch, address.with_native_space())
+            .await
+    }
 
+    /// Get the account of `address` in the eSpace state.
+    pub async fn get_eth_account(
+        &self, epoch: EpochNumber, address: H160,
+    ) -> Result<Option<Account>, LightError> {
+        debug!("get_eth_account epoch={:?} address={:?}", epoch, address);
+        self.get_account_with_space(epoch, address.with_evm_space())
+            .await
+    }
+
+    async fn get_account_with_space(
+        &self, epoch: EpochNumber, address: AddressWithSpace,
+    ) -> Result<Option<Account>, LightError> {
         let epoch = self.get_height_from_epoch_number(epoch)?;
         let key = Self::account_key(&address);
 
         match self.retrieve_state_entry_raw(epoch, key).await? {
             None => Ok(None),
-            Some(rlp) => {
-                Ok(Some(Account::new_from_rlp(address, &Rlp::new(&rlp))?))
-            }
+            Some(rlp) => Ok(Some(Account::new_from_rlp(
+                address.address,
+                &Rlp::new(&rlp),
+            )?)),
         }
     }
 
@@ -433,6 +458,23 @@ impl QueryService {
             return Ok(None);
         }
 
+        self.get_code_with_space(epoch, address.with_native_space())
+            .await
+    }
+
+    /// Get the code of `address` in the eSpace state. The eSpace addresses
+    /// carry no type bits, so the peers are always queried.
+    pub async fn get_eth_code(
+        &self, epoch: EpochNumber, address: H160,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        debug!("get_eth_code epoch={:?} address={:?}", epoch, address);
+        self.get_code_with_space(epoch, address.with_evm_space())
+            .await
+    }
+
+    async fn get_code_with_space(
+        &self, epoch: EpochNumber, address: AddressWithSpace,
+    ) -> Result<Option<Vec<u8>>, Error> {
         let epoch = self.get_height_from_epoch_number(epoch)?;
         let key = Self::account_key(&address);
 
@@ -442,12 +484,17 @@ impl QueryService {
             Ok(Some(rlp)) => {
                 account_result_to_rpc_result(
                     "address",
-                    Account::new_from_rlp(address, &Rlp::new(&rlp)),
+                    Account::new_from_rlp(address.address, &Rlp::new(&rlp)),
                 )?
                 .code_hash
             }
         };
 
+        // accounts without code have no code entry
+        if code_hash == KECCAK_EMPTY {
+            return Ok(None);
+        }
+
         let key = Self::code_key(&address, &code_hash);
 
         match self.retrieve_state_entry::<CodeInfo>(epoch, key).await? {
@@ -471,7 +518,29 @@ impl QueryService {
             "get_storage epoch={:?} address={:?} position={:?}",
             epoch, address, position
         );
+        self.get_storage_with_space(
+            epoch,
+            address.with_native_space(),
+            position,
+        )
+        .await
+    }
+
+    /// Get the storage entry of `address` at `position` in the eSpace state.
+    pub async fn get_eth_storage(
+        &self, epoch: EpochNumber, address: H160, position: H256,
+    ) -> Result<Option<H256>, LightError> {
+        debug!(
+            "get_eth_storage epoch={:?} address={:?} position={:?}",
+            epoch, address, position
+        );
+        self.get_storage_with_space(epoch, address.with_evm_space(), position)
+            .await
+    }
 
+    async fn get_storage_with_space(
+        &self, epoch: EpochNumber, address: AddressWithSpace, position: H256,
+    ) -> Result<Option<H256>, LightError> {
         let epoch = self.get_height_from_epoch_number(epoch)?;
         let key = Self::storage_key(&address, &position.0);
 
@@ -499,7 +568,7 @@ impl QueryService {
             pos.extend_from_slice(COMMISSION_PRIVILEGE_SPECIAL_KEY.as_bytes());
 
             let key = Self::storage_key(
-                &SPONSOR_WHITELIST_CONTROL_CONTRACT_ADDRESS,
+                &SPONSOR_WHITELIST_CONTROL_CONTRACT_ADDRESS.with_native_space(),
                 &pos,
             );
 
@@ -513,7 +582,7 @@ impl QueryService {
             pos.extend_from_slice(user.as_bytes());
 
             let key = Self::storage_key(
-                &SPONSOR_WHITELIST_CONTROL_CONTRACT_ADDRESS,
+                &SPONSOR_WHITELIST_CONTROL_CONTRACT_ADDRESS.with_native_space(),
                 &pos,
             );
 
@@ -598,6 +667,39 @@ impl QueryService {
         ])
     }
 
+    /// Executes a virtual transaction against the state of `epoch`. The
+    /// state entries accessed by the execution are retrieved one by one and
+    /// verified, so the execution may take a while.
+    pub async fn call_virtual(
+        self: &Arc<Self>, tx: SignedTransaction, epoch: EpochNumber,
+        request: EstimateRequest,
+    ) -> Result<(ExecutionOutcome, EstimateExt), Error> {
+        debug!("call_virtual tx={:?} epoch={:?}", tx, epoch);
+
+        let epoch = self.get_height_from_epoch_number(epoch)?;
+        let hashes = self.ledger.block_hashes_in(epoch)?;
+        let pivot = *hashes.last().expect("epoch should not be empty");
+        let storage = Box::new(LightStateStorage::new(self.clone(), epoch));
+
+        // the executor accesses the storage synchronously
+        let consensus = self.consensus.clone();
+        tokio::task::spawn_blocking(move || {
+            consensus
+                .as_any()
+                .downcast_ref::<ConsensusGraph>()
+                .expect("downcast should succeed")
+                .call_virtual_on_storage(
+                    &tx,
+                    &pivot,
+                    hashes.len(),
+                    request,
+                    storage,
+                )
+        })
+        .await
+        .map_err(|e| format!("Virtual call aborted: {}", e))?
+    }
+
     pub async fn get_tx_info(&self, hash: H256) -> Result<TxInfo, LightError> {
         debug!("get_tx_info hash={:?}", hash);
 
@@ -685,6 +787,191 @@ impl QueryService {
         .await
     }
 
+    /// Build the eSpace block of `epoch` from the verified receipts and
+    /// transactions of its blocks, the same way as full nodes do in
+    /// `ConsensusGraph::get_phantom_block_by_number`. The phantom transactions
+    /// of cross-space calls are recovered from the receipt logs. The execution
+    /// errors and the traces cannot be verified, so the errors are left empty
+    /// and no traces are included.
+    pub async fn get_phantom_block(
+        &self, epoch: u64,
+    ) -> Result<PhantomBlock, LightError> {
+        debug!("get_phantom_block epoch={:?}", epoch);
+
+        let hashes = self
+            .ledger
+            .block_hashes_in(epoch)
+            .map_err(|e| format!("{}", e))?;
+
+        let data_man = self.consensus.get_data_manager();
+        let mut headers = Vec::with_capacity(hashes.len());
+        for hash in &hashes {
+            match data_man.block_header_by_hash(hash) {
+                Some(header) => headers.push(header),
+                None => bail!(LightError::InternalError(format!(
+                    "Header {:?} not found",
+                    hash
+                ))),
+            }
+        }
+
+        let pivot_header = match headers.last() {
+            Some(header) => (**header).clone(),
+            None => bail!(LightError::InternalError(
+                "Inconsistent state: empty epoch".into()
+            )),
+        };
+
+        let mut phantom_block = PhantomBlock {
+            pivot_header,
+            transactions: vec![],
+            receipts: vec![],
+            errors: vec![],
+            bloom: Bloom::zero(),
+            traces: vec![],
+            total_gas_limit: U256::zero(),
+        };
+
+        // the genesis block has no receipts
+        if epoch == 0 {
+            return Ok(phantom_block);
+        }
+
+        let (_, epoch_receipts) = self.retrieve_receipts(epoch).await?;
+        let epoch_txs = future::try_join_all(
+            hashes.iter().map(|hash| self.retrieve_block_txs(*hash)),
+        )
+        .await?;
+
+        if epoch_receipts.len() != hashes.len() {
+            bail!(LightError::InternalError(
+                "Inconsistent state: blocks and receipts length mismatch"
+                    .into()
+            ));
+        }
+
+        let params = &self
+            .consensus
+            .as_any()
+            .downcast_ref::<ConsensusGraph>()
+            .expect("downcast should succeed")
+            .params;
+
+        let evm_chain_id = self
+            .consensus
+            .get_config()
+            .chain_id
+            .read()
+            .get_chain_id(epoch)
+            .in_evm_space();
+
+        let mut accumulated_gas_used = U256::zero();
+
+        for ((header, txs), block_receipts) in
+            headers.iter().zip(epoch_txs).zip(epoch_receipts)
+        {
+            let gas_used_offset = accumulated_gas_used;
+
+            phantom_block.total_gas_limit += header.espace_gas_limit(
+                params.can_pack_evm_transaction(header.height()),
+            );
+
+            let receipts = block_receipts.receipts;
+
+            if txs.len() != receipts.len() {
+                bail!(LightError::InternalError(
+                    "Inconsistent state: transactions and receipts length mismatch"
+                        .into()
+                ));
+            }
+
+            for (tx, receipt) in txs.into_iter().zip(receipts) {
+                match tx.space() {
+                    Space::Ethereum => {
+                        // we do not return non-executed transaction
+                        if receipt.outcome_status == TransactionStatus::Skipped
+                        {
+                            continue;
+                        }
+
+                        accumulated_gas_used =
+                            gas_used_offset + receipt.accumulated_gas_used;
+
+                        phantom_block.bloom.accrue_bloom(&receipt.log_bloom);
+                        phantom_block.receipts.push(Receipt {
+                            accumulated_gas_used,
+                            ..receipt
+                        });
+                        phantom_block.transactions.push(Arc::new(tx));
+                        phantom_block.errors.push("".into());
+                    }
+                    Space::Native => {
+                        // note: failing transactions will not produce any
+                        // phantom txs
+                        if receipt.outcome_status != TransactionStatus::Success
+                        {
+                            continue;
+                        }
+
+                        let (phantom_txs, _) = build_bloom_and_recover_phantom(
+                            &receipt.logs[..],
+                            tx.hash(),
+                        );
+
+                        for p in phantom_txs {
+                            phantom_block.transactions.push(Arc::new(
+                                p.clone().into_eip155(evm_chain_id),
+                            ));
+
+                            // note: phantom txs consume no gas
+                            let phantom_receipt =
+                                p.into_receipt(accumulated_gas_used);
+
+                            phantom_block
+                                .bloom
+                                .accrue_bloom(&phantom_receipt.log_bloom);
+                            phantom_block.receipts.push(phantom_receipt);
+                            phantom_block.errors.push("".into());
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(phantom_block)
+    }
+
+    /// Find the eSpace transaction `hash` in the eSpace block of its epoch,
+    /// see [`Self::get_phantom_block`]. Returns the block and the index of the
+    /// transaction in it, or `None` if the transaction is not an executed
+    /// eSpace transaction.
+    pub async fn get_eth_tx_info(
+        &self, hash: H256,
+    ) -> Result<Option<(PhantomBlock, usize)>, LightError> {
+        debug!("get_eth_tx_info hash={:?}", hash);
+
+        let TxInfo {
+            tx, maybe_epoch, ..
+        } = self.get_tx_info(hash).await?;
+
+        if tx.space() != Space::Ethereum {
+            return Ok(None);
+        }
+
+        let epoch = match maybe_epoch {
+            Some(epoch) => epoch,
+            None => return Ok(None),
+        };
+
+        let phantom_block = self.get_phantom_block(epoch).await?;
+
+        Ok(phantom_block
+            .transactions
+            .iter()
+            .position(|tx| tx.hash() == hash)
+            .map(|idx| (phantom_block, idx)))
+    }
+
     /// Apply filter to all logs within a receipt.
     /// NOTE: `log.transaction_hash` is not known at this point,
     /// so this field has to be filled later on.
@@ -778,12 +1065,16 @@ impl QueryService {
         &self,
     ) -> Result<AllChainID, FilterError> {
         let epoch_number = self.get_latest_verifiable_epoch_number()?;
-        Ok(self
-            .consensus
+        Ok(self.get_chain_id(epoch_number))
+    }
+
+    /// Returns the chain id in effect at `epoch`.
+    pub fn get_chain_id(&self, epoch: u64) -> AllChainID {
+        self.consensus
             .get_config()
             .chain_id
             .read()
-            .get_chain_id(epoch_number))
+            .get_chain_id(epoch)
     }
 
     pub fn get_latest_verifiable_epoch_number(
@@ -1024,7 +1315,329 @@ impl QueryService {
         Ok(matching)
     }
 
+    /// Apply `filter` to the logs of the eSpace blocks, see
+    /// [`Self::get_phantom_block`]. The log indices are the ones within the
+    /// eSpace block of the epoch.
+    pub async fn get_eth_logs(
+        &self, filter: LogFilter,
+    ) -> Result<Vec<LocalizedLogEntry>, LightError> {
+        debug!("get_eth_logs filter = {:?}", filter);
+
+        // eSpace blocks correspond to epochs, so no block filter is needed
+        let (epochs, _) = self
+            .get_filter_epochs(&filter)
+            .map_err(|e| format!("{}", e))?;
+
+        debug!("Executing filter on epochs {:?}", epochs);
+
+        // the epoch blooms also cover the eSpace logs of cross-space calls
+        let blooms = filter.bloom_possibilities();
+
+        let bloom_match = move |block_log_bloom: &Bloom| {
+            blooms
+                .iter()
+                .any(|bloom| block_log_bloom.contains_bloom(bloom))
+        };
+
+        let mut stream = Box::pin(
+            stream::iter(epochs)
+                .map(|epoch| self.retrieve_bloom(epoch))
+                .buffered(LOG_FILTERING_LOOKAHEAD)
+                .try_filter_map(move |(epoch, bloom)| {
+                    future::ready(Ok(bloom_match(&bloom).then_some(epoch)))
+                })
+                .map(|res| match res {
+                    Err(e) => Either::Left(future::err(e)),
+                    Ok(epoch) => Either::Right(
+                        self.get_phantom_block(epoch)
+                            .map_ok(move |block| (epoch, block)),
+                    ),
+                })
+                .buffered(LOG_FILTERING_LOOKAHEAD),
+        );
+
+        let limit = self
+            .consensus
+            .get_config()
+            .get_logs_filter_max_limit
+            .unwrap_or(::std::usize::MAX - 1)
+            + 1;
+
+        // epochs are processed in reverse order
+        let mut matching = vec![];
+
+        while let Some((epoch, block)) = stream.try_next().await? {
+            let epoch_logs =
+                Self::filter_eth_block_logs(epoch, &block, &filter);
+            matching.extend(epoch_logs.into_iter().rev());
+
+            if matching.len() >= limit {
+                matching.truncate(limit);
+                break;
+            }
+        }
+
+        matching.reverse();
+        debug!("Collected matching eSpace logs = {:?}", matching);
+        Ok(matching)
+    }
+
+    /// Collect the logs in the eSpace block of `epoch` that match `filter`.
+    fn filter_eth_block_logs(
+        epoch: u64, block: &PhantomBlock, filter: &LogFilter,
+    ) -> Vec<LocalizedLogEntry> {
+        let block_hash = block.pivot_header.hash();
+        let mut logs = vec![];
+        let mut log_index = 0;
+
+        for (transaction_index, (tx, receipt)) in
+            block.transactions.iter().zip(&block.receipts).enumerate()
+        {
+            for (transaction_log_index, entry) in
+                receipt.logs.iter().enumerate()
+            {
+                if filter.matches(entry) {
+                    logs.push(LocalizedLogEntry {
+                        block_hash,
+                        epoch_number: epoch,
+                        entry: entry.clone(),
+                        log_index: log_index + transaction_log_index,
+                        transaction_hash: tx.hash(),
+                        transaction_index,
+                        transaction_log_index,
+                    });
+                }
+            }
+            log_index += receipt.logs.len();
+        }
+
+        logs
+    }
+
     pub fn get_network_type(&self) -> &Network {
         self.network.get_network_type()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::QueryService;
+    use cfx_rpc_cfx_types::PhantomBlock;
+    use cfx_storage::{
+        new_storage_manager_for_testing, state_manager::StateManagerTrait,
+        StateIndex,
+    };
+    use cfx_types::{
+        AddressSpaceUtil, BigEndianHash, Bloom, Space, H160, H256, U256,
+    };
+    use cfxkey::{Generator, Random};
+    use primitives::{
+        filter::{LogFilter, LogFilterParams},
+        log_entry::LogEntry,
+        transaction::Eip155Transaction,
+        Account, BlockHeaderBuilder, EpochId, EpochNumber, Receipt, StorageKey,
+        StorageValue, Transaction, TransactionStatus,
+    };
+    use rlp::Rlp;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_eth_state_entry_keys() {
+        let address = H160::from_low_u64_be(0x1234);
+        let eth_address = address.with_evm_space();
+        let native_address = address.with_native_space();
+        let position = H256::from_low_u64_be(7);
+
+        let account_key = QueryService::account_key(&eth_address);
+        let storage_key = QueryService::storage_key(&eth_address, &position.0);
+        assert_ne!(account_key, QueryService::account_key(&native_address));
+        assert_ne!(
+            storage_key,
+            QueryService::storage_key(&native_address, &position.0)
+        );
+
+        // write the entries the same as the eSpace execution
+        let storage_manager = new_storage_manager_for_testing();
+        let mut state = storage_manager.get_state_for_genesis_write();
+        let account = Account::new_empty_with_balance(
+            &eth_address,
+            &U256::from(100),
+            &U256::from(3),
+        );
+        state
+            .set(
+                StorageKey::new_account_key(&address).with_evm_space(),
+                rlp::encode(&account).into(),
+            )
+            .unwrap();
+        let storage_value = StorageValue {
+            value: U256::from(42),
+            owner: None,
+        };
+        state
+            .set(
+                StorageKey::new_storage_key(&address, position.as_bytes())
+                    .with_evm_space(),
+                rlp::encode(&storage_value).into(),
+            )
+            .unwrap();
+        let mut epoch_id = EpochId::default();
+        epoch_id.as_bytes_mut()[0] = 1;
+        state.compute_state_root().unwrap();
+        let root = state.commit(epoch_id).unwrap();
+
+        let state = storage_manager
+            .get_state_no_commit(
+                StateIndex::new_for_readonly(&epoch_id, &root),
+                /* try_open = */ false,
+                None,
+            )
+            .unwrap()
+            .unwrap();
+
+        // the proofs of the eSpace entries are verified with the keys
+        // requested by the light node, but not with the Core space keys
+        let (value, proof) = state
+            .get_with_proof(
+                StorageKey::new_account_key(&address).with_evm_space(),
+            )
+            .unwrap();
+        let value = value.unwrap();
+        assert!(proof.is_valid_kv(
+            &account_key,
+            Some(&*value),
+            root.state_root.clone(),
+            None
+        ));
+        assert!(!proof.is_valid_kv(
+            &QueryService::account_key(&native_address),
+            Some(&*value),
+            root.state_root.clone(),
+            None
+        ));
+        let decoded =
+            Account::new_from_rlp(address, &Rlp::new(&value)).unwrap();
+        assert_eq!(decoded.balance, U256::from(100));
+        assert_eq!(decoded.nonce, U256::from(3));
+
+        let (value, proof) = state
+            .get_with_proof(
+                StorageKey::new_storage_key(&address, position.as_bytes())
+                    .with_evm_space(),
+            )
+            .unwrap();
+        let value = value.unwrap();
+        assert!(proof.is_valid_kv(
+            &storage_key,
+            Some(&*value),
+            root.state_root.clone(),
+            None
+        ));
+        assert!(!proof.is_valid_kv(
+            &QueryService::storage_key(&native_address, &position.0),
+            Some(&*value),
+            root.state_root.clone(),
+            None
+        ));
+        let decoded: StorageValue = rlp::decode(&value).unwrap();
+        assert_eq!(H256::from_uint(&decoded.value), H256::from_low_u64_be(42));
+    }
+
+    fn receipt(logs: Vec<LogEntry>) -> Receipt {
+        Receipt {
+            accumulated_gas_used: U256::zero(),
+            gas_fee: U256::zero(),
+            gas_sponsor_paid: false,
+            logs,
+            outcome_status: TransactionStatus::Success,
+            log_bloom: Bloom::zero(),
+            storage_sponsor_paid: false,
+            storage_collateralized: vec![],
+            storage_released: vec![],
+            burnt_gas_fee: None,
+        }
+    }
+
+    fn eth_log(address: H160, topic: H256) -> LogEntry {
+        LogEntry {
+            address,
+            topics: vec![topic],
+            data: vec![],
+            space: Space::Ethereum,
+        }
+    }
+
+    #[test]
+    fn test_filter_eth_block_logs() {
+        let (a1, a2) = (H160::from_low_u64_be(1), H160::from_low_u64_be(2));
+        let (t1, t2) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+
+        let keypair = Random.generate().unwrap();
+        let transactions: Vec<_> = (0..2)
+            .map(|nonce| {
+                Arc::new(
+                    Transaction::from(Eip155Transaction {
+                        nonce: U256::from(nonce),
+                        chain_id: Some(1),
+                        ..Default::default()
+                    })
+                    .sign(keypair.secret()),
+                )
+            })
+            .collect();
+        let block = PhantomBlock {
+            pivot_header: BlockHeaderBuilder::new().with_height(5).build(),
+            transactions: transactions.clone(),
+            receipts: vec![
+                receipt(vec![eth_log(a1, t1), eth_log(a2, t1)]),
+                receipt(vec![eth_log(a1, t2), eth_log(a1, t1)]),
+            ],
+            errors: vec![String::new(); 2],
+            bloom: Bloom::zero(),
+            traces: vec![],
+            total_gas_limit: U256::zero(),
+        };
+
+        let filter = LogFilter::EpochLogFilter {
+            from_epoch: EpochNumber::Number(5),
+            to_epoch: EpochNumber::Number(5),
+            params: LogFilterParams {
+                address: Some(vec![a1]),
+                topics: vec![Some(vec![t1]), None, None, None],
+                trusted: false,
+                space: Space::Ethereum,
+            },
+        };
+
+        let logs = QueryService::filter_eth_block_logs(5, &block, &filter);
+        assert_eq!(logs.len(), 2);
+        assert!(logs
+            .iter()
+            .all(|log| log.block_hash == block.pivot_header.hash()
+                && log.epoch_number == 5));
+        assert_eq!(
+            (logs[0].transaction_index, logs[0].transaction_log_index),
+            (0, 0)
+        );
+        assert_eq!(logs[0].log_index, 0);
+        assert_eq!(logs[0].transaction_hash, transactions[0].hash());
+        assert_eq!(
+            (logs[1].transaction_index, logs[1].transaction_log_index),
+            (1, 1)
+        );
+        // the log indices count the logs of the previous transactions
+        assert_eq!(logs[1].log_index, 3);
+        assert_eq!(logs[1].transaction_hash, transactions[1].hash());
+
+        // the Core space logs are not included
+        let mut native_filter = filter.clone();
+        if let LogFilter::EpochLogFilter { ref mut params, .. } = native_filter
+        {
+            params.space = Space::Native;
+        }
+        assert!(
+            QueryService::filter_eth_block_logs(5, &block, &native_filter)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+// `LightStateStorage` is a storage backend for executing virtual transactions
+// on light nodes. It retrieves each state entry from the full nodes on its
+// first access, verified against the state root of the epoch. The changes of
+// the execution are kept in memory and can never be committed.
+
+use crate::light_protocol::{Error as LightError, QueryService};
+use cfx_internal_common::StateRootWithAuxInfo;
+use cfx_storage::{
+    Error as StorageError, MptKeyValue, Result as StorageResult,
+    StorageStateTrait,
+};
+use futures::future::{FutureExt, LocalBoxFuture};
+use primitives::{EpochId, StorageKeyWithSpace};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::runtime::Handle;
+
+/// The source of the state entries read by `LightStateStorage`.
+pub trait StateEntryProvider: Send + Sync {
+    /// Retrieves the raw value of the state entry `key` at `epoch`.
+    fn retrieve_entry(
+        &self, epoch: u64, key: Vec<u8>,
+    ) -> LocalBoxFuture<'_, Result<Option<Vec<u8>>, LightError>>;
+}
+
+impl StateEntryProvider for QueryService {
+    fn retrieve_entry(
+        &self, epoch: u64, key: Vec<u8>,
+    ) -> LocalBoxFuture<'_, Result<Option<Vec<u8>>, LightError>> {
+        self.retrieve_state_entry_raw(epoch, key).boxed_local()
+    }
+}
+
+pub struct LightStateStorage {
+    query: Arc<dyn StateEntryProvider>,
+
+    // the epoch whose state is read
+    epoch: u64,
+
+    // the runtime driving the retrievals, as the storage is accessed
+    // synchronously by the executor
+    runtime: Handle,
+
+    // entries set (`Some`) or deleted (`None`) during the execution
+    modified: BTreeMap<Vec<u8>, Option<Box<[u8]>>>,
+}
+
+impl LightStateStorage {
+    /// Must be called inside a tokio runtime, and the storage must only be
+    /// accessed outside of it, e.g. in `spawn_blocking`.
+    pub fn new(query: Arc<dyn StateEntryProvider>, epoch: u64) -> Self {
+        LightStateStorage {
+            query,
+            epoch,
+            runtime: Handle::current(),
+            modified: BTreeMap::new(),
+        }
+    }
+}
+
+impl StorageStateTrait for LightStateStorage {
+    fn get(
+        &self, access_key: StorageKeyWithSpace,
+    ) -> StorageResult<Option<Box<[u8]>>> {
+        let key = access_key.to_key_bytes();
+        if let Some(value) = self.modified.get(&key) {
+            return Ok(value.clone());
+        }
+
+        let value = self
+            .runtime
+            .block_on(self.query.retrieve_entry(self.epoch, key))
+            .map_err(|e| StorageError::Msg(format!("{}", e)))?;
+        Ok(value.map(Into::into))
+    }
+
+    fn set(
+        &mut self, access_key: StorageKeyWithSpace, value: Box<[u8]>,
+    ) -> StorageResult<()> {
+        self.modified.insert(access_key.to_key_bytes(), Some(value));
+        Ok(())
+    }
+
+    fn delete(&mut self, access_key: StorageKeyWithSpace) -> StorageResult<()> {
+        self.modified.insert(access_key.to_key_bytes(), None);
+        Ok(())
+    }
+
+    fn delete_test_only(
+        &mut self, access_key: StorageKeyWithSpace,
+    ) -> StorageResult<Option<Box<[u8]>>> {
+        let value = self.get(access_key)?;
+        self.delete(access_key)?;
+        Ok(value)
+    }
+
+    // the light protocol can only retrieve single entries
+    fn delete_all(
+        &mut self, _access_key_prefix: StorageKeyWithSpace,
+    ) -> StorageResult<Option<Vec<MptKeyValue>>> {
+        Err(StorageError::Msg(
+            "Iterating the state is not supported on light nodes".into(),
+        ))
+    }
+
+    fn read_all(
+        &mut self, _access_key_prefix: StorageKeyWithSpace,
+    ) -> StorageResult<Option<Vec<MptKeyValue>>> {
+        Err(StorageError::Msg(
+            "Iterating the state is not supported on light nodes".into(),
+        ))
+    }
+
+    fn compute_state_root(&mut self) -> StorageResult<StateRootWithAuxInfo> {
+        Err(StorageError::Msg(
+            "The state of light nodes can not be committed".into(),
+        ))
+    }
+
+    fn get_state_root(&self) -> StorageResult<StateRootWithAuxInfo> {
+        Err(StorageError::Msg(
+            "The state of light nodes can not be committed".into(),
+        ))
+    }
+
+    fn commit(
+        &mut self, _epoch: EpochId,
+    ) -> StorageResult<StateRootWithAuxInfo> {
+        Err(StorageError::Msg(
+            "The state of light nodes can not be committed".into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LightStateStorage, StateEntryProvider};
+    use crate::{
+        block_data_manager::DbType,
+        keylib::{Generator, Random},
+        light_protocol::Error as LightError,
+        sync::utils::initialize_synchronization_graph,
+    };
+    use cfx_execute_helper::estimation::EstimateRequest;
+    use cfx_executor::executive::ExecutionOutcome;
+    use cfx_storage::StorageStateTrait;
+    use cfx_types::{Address, AddressSpaceUtil, U256};
+    use futures::future::{FutureExt, LocalBoxFuture};
+    use parking_lot::Mutex;
+    use primitives::{
+        transaction::native_transaction::NativeTransaction, Account, Action,
+        StorageKey, Transaction,
+    };
+    use std::{collections::HashMap, fs, sync::Arc};
+    use tokio::runtime::Runtime;
+
+    #[derive(Default)]
+    struct MockProvider {
+        entries: HashMap<Vec<u8>, Vec<u8>>,
+        requests: Mutex<Vec<(u64, Vec<u8>)>>,
+        fail: bool,
+    }
+
+    impl StateEntryProvider for MockProvider {
+        fn retrieve_entry(
+            &self, epoch: u64, key: Vec<u8>,
+        ) -> LocalBoxFuture<'_, Result<Option<Vec<u8>>, LightError>> {
+            self.requests.lock().push((epoch, key.clone()));
+            let result = if self.fail {
+                Err(LightError::Timeout("no response".into()))
+            } else {
+                Ok(self.entries.get(&key).cloned())
+            };
+            async move { result }.boxed_local()
+        }
+    }
+
+    fn new_storage(
+        runtime: &Runtime, provider: Arc<MockProvider>, epoch: u64,
+    ) -> LightStateStorage {
+        let _guard = runtime.enter();
+        LightStateStorage::new(provider, epoch)
+    }
+
+    #[test]
+    fn test_light_state_storage() {
+        let runtime = Runtime::new().unwrap();
+        let (address, other_address) = (Address::random(), Address::random());
+        let key = StorageKey::new_account_key(&address).with_native_space();
+        let other_key =
+            StorageKey::new_account_key(&other_address).with_native_space();
+
+        let mut provider = MockProvider::default();
+        provider.entries.insert(key.to_key_bytes(), vec![1, 2, 3]);
+        let provider = Arc::new(provider);
+        let mut storage = new_storage(&runtime, provider.clone(), 5);
+
+        // entries are retrieved at the epoch of the storage
+        assert_eq!(storage.get(key).unwrap(), Some(vec![1, 2, 3].into()));
+        assert_eq!(storage.get(other_key).unwrap(), None);
+        assert_eq!(
+            *provider.requests.lock(),
+            vec![(5, key.to_key_bytes()), (5, other_key.to_key_bytes())]
+        );
+
+        // changes are kept in memory and shadow the retrieved entries
+        storage.set(other_key, vec![4].into()).unwrap();
+        storage.delete(key).unwrap();
+        assert_eq!(storage.get(other_key).unwrap(), Some(vec![4].into()));
+        assert_eq!(storage.get(key).unwrap(), None);
+        assert_eq!(provider.requests.lock().len(), 2);
+
+        // the state can be neither iterated nor committed
+        assert!(storage.read_all(key).is_err());
+        assert!(storage.delete_all(key).is_err());
+        assert!(storage.compute_state_root().is_err());
+        assert!(storage.get_state_root().is_err());
+        assert!(storage.commit(Default::default()).is_err());
+
+        // retrieval errors are reported as storage errors
+        let provider = Arc::new(MockProvider {
+            fail: true,
+            ..Default::default()
+        });
+        let storage = new_storage(&runtime, provider, 5);
+        assert!(storage.get(key).is_err());
+    }
+
+    #[test]
+    fn test_call_virtual_on_storage() {
+        let db_dir = "./light_state_storage_test.db/";
+        {
+            let (_, consensus, _, genesis) = initialize_synchronization_graph(
+                db_dir,
+                1,
+                1,
+                1,
+                1,
+                50000,
+                DbType::Rocksdb,
+            );
+
+            let sender = Random.generate().unwrap();
+            let sender_address = sender.address().with_native_space();
+            let sender_key =
+                StorageKey::new_account_key(&sender_address.address)
+                    .with_native_space()
+                    .to_key_bytes();
+            let account = Account::new_empty_with_balance(
+                &sender_address,
+                &U256::from(1_000_000_000),
+                &U256::zero(),
+            );
+
+            let mut provider = MockProvider::default();
+            provider
+                .entries
+                .insert(sender_key.clone(), rlp::encode(&account).to_vec());
+            let provider = Arc::new(provider);
+            let runtime = Runtime::new().unwrap();
+            let storage = new_storage(&runtime, provider.clone(), 0);
+
+            let tx: Transaction = NativeTransaction {
+                nonce: U256::zero(),
+                gas_price: U256::one(),
+                gas: U256::from(21000),
+                action: Action::Call(Address::random()),
+                value: U256::from(100),
+                storage_limit: 0,
+                epoch_height: 0,
+                chain_id: 1,
+                data: Vec::new(),
+            }
+            .into();
+            let tx = tx.sign(sender.secret());
+            let request = EstimateRequest {
+                has_sender: true,
+                has_gas_limit: true,
+                has_gas_price: true,
+                has_nonce: true,
+                has_storage_limit: true,
+                collect_access_list: false,
+            };
+
+            // the sender's balance is only known to the storage backend
+            let (outcome, _) = consensus
+                .call_virtual_on_storage(
+                    &tx,
+                    &genesis.hash(),
+                    1,
+                    request,
+                    Box::new(storage),
+                )
+                .unwrap();
+            assert!(matches!(outcome, ExecutionOutcome::Finished(_)));
+            assert!(provider
+                .requests
+                .lock()
+                .iter()
+                .any(|(epoch, key)| *epoch == 0 && *key == sender_key));
+        }
+
+        fs::remove_dir_all(db_dir).unwrap();
+    }
+}
@@ -30,6 +30,7 @@ use crate::{
     sync::ProtocolConfiguration,
 };
 
+use backup_service::start_backup_service;
 use cached_pos_ledger_db::CachedPosLedgerDB;
 use consensus_types::db::FakeLedgerBlockDB;
 use diem_config::{config::NodeConfig, utils::get_genesis_txn};
@@ -57,6 +58,7 @@ use pow_types::FakePowHandler;
 use std::{
     boxed::Box,
     fs,
+    net::SocketAddr,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -83,6 +85,7 @@ pub struct PosDropHandle {
     _mempool: Runtime,
     _state_sync_bootstrapper: StateSyncBootstrapper,
     _consensus_runtime: Runtime,
+    _backup_service: Option<Runtime>,
 }
 
 pub fn start_pos_consensus(
@@ -94,6 +97,7 @@ pub fn start_pos_consensus(
     mempool_network_receiver: MemPoolNetworkReceivers,
     test_command_receiver: channel::Receiver<TestCommand>,
     hsb_protocol: Arc<HotStuffSynchronizationProtocol>,
+    backup_service_address: Option<SocketAddr>,
 ) -> PosDropHandle {
     crash_handler::setup_panic_handler();
 
@@ -160,6 +164,7 @@ pub fn start_pos_consensus(
         mempool_network_receiver,
         test_command_receiver,
         hsb_protocol,
+        backup_service_address,
     )
 }
 
@@ -189,6 +194,7 @@ pub fn setup_pos_environment(
     mempool_network_receiver: MemPoolNetworkReceivers,
     test_command_receiver: channel::Receiver<TestCommand>,
     hsb_protocol: Arc<HotStuffSynchronizationProtocol>,
+    backup_service_address: Option<SocketAddr>,
 ) -> PosDropHandle {
     // TODO(lpl): Handle port conflict.
     // let metrics_port = node_config.debug_interface.metrics_server_port;
@@ -249,6 +255,13 @@ pub fn setup_pos_environment(
         instant.elapsed().as_millis()
     );
 
+    // The backup service only reads the committed ledger, so it can serve
+    // the backup tools while the node is running.
+    let backup_service = backup_service_address.map(|address| {
+        diem_info!("Start PoS backup service at {}", address);
+        start_backup_service(address, pos_ledger_db.clone())
+    });
+
     instant = Instant::now();
     let chunk_executor = setup_chunk_executor(db_rw.clone());
     debug!(
@@ -349,6 +362,7 @@ pub fn setup_pos_environment(
         stopped,
         _state_sync_bootstrapper: state_sync_bootstrapper,
         _mempool: mempool,
+        _backup_service: backup_service,
         pos_ledger_db,
         cached_db: db_with_cache,
         consensus_db,
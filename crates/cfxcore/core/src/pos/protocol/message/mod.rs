@@ -10,9 +10,10 @@ pub mod epoch_retrieval;
 pub mod mempool_sync_msg;
 pub mod proposal;
 pub mod sync_info;
+pub mod throttled;
 pub mod vote;
 
-use super::HSB_PROTOCOL_VERSION;
+use super::{HSB_PROTOCOL_VERSION, HSB_PROTO_V1, HSB_PROTO_V2};
 
 use crate::{
     message::{
@@ -30,6 +31,7 @@ use consensus_types::{
 };
 use diem_types::epoch_change::EpochChangeProof;
 use network::service::ProtocolVersion;
+use throttled::Throttled;
 
 // FIXME: A temporary workaround by avoiding msg_id overlapping
 // with SynchronizationProtocolHandler msg_id.
@@ -43,6 +45,7 @@ build_msgid! {
     EPOCH_RETRIEVAL = 0x56
     CONSENSUS_MSG = 0x57
     MEMPOOL_SYNC_MSG = 0x58
+    THROTTLED = 0x59
     INVALID = 0xff
 }
 
@@ -104,48 +107,34 @@ macro_rules! build_msg_impl_with_request_id_and_serde_serialization {
 }
 
 build_msg_impl_with_serde_serialization_generic! {ProposalMsg, msgid::PROPOSAL, "ProposalMessage"}
-mark_msg_version_bound!(
-    ProposalMsg,
-    HSB_PROTOCOL_VERSION,
-    HSB_PROTOCOL_VERSION
-);
+mark_msg_version_bound!(ProposalMsg, HSB_PROTO_V1, HSB_PROTOCOL_VERSION);
 build_msg_impl_with_serde_serialization_generic! {BlockRetrievalRpcResponse, msgid::BLOCK_RETRIEVAL_RESPONSE, "BlockRetrievalResponseMessage"}
 mark_msg_version_bound!(
     BlockRetrievalRpcResponse,
-    HSB_PROTOCOL_VERSION,
+    HSB_PROTO_V1,
     HSB_PROTOCOL_VERSION
 );
 build_msg_impl_with_serde_serialization! {VoteMsg, msgid::VOTE, "VoteMessage"}
-mark_msg_version_bound!(VoteMsg, HSB_PROTOCOL_VERSION, HSB_PROTOCOL_VERSION);
+mark_msg_version_bound!(VoteMsg, HSB_PROTO_V1, HSB_PROTOCOL_VERSION);
 build_msg_impl_with_serde_serialization! {SyncInfo, msgid::SYNC_INFO, "SyncInfoMessage"}
-mark_msg_version_bound!(SyncInfo, HSB_PROTOCOL_VERSION, HSB_PROTOCOL_VERSION);
+mark_msg_version_bound!(SyncInfo, HSB_PROTO_V1, HSB_PROTOCOL_VERSION);
 build_msg_impl_with_serde_serialization! {EpochChangeProof, msgid::EPOCH_CHANGE, "EpochChangeMessage"}
-mark_msg_version_bound!(
-    EpochChangeProof,
-    HSB_PROTOCOL_VERSION,
-    HSB_PROTOCOL_VERSION
-);
+mark_msg_version_bound!(EpochChangeProof, HSB_PROTO_V1, HSB_PROTOCOL_VERSION);
 build_msg_impl_with_serde_serialization! {ConsensusMsg, msgid::CONSENSUS_MSG, "ConsensusMsg"}
-mark_msg_version_bound!(
-    ConsensusMsg,
-    HSB_PROTOCOL_VERSION,
-    HSB_PROTOCOL_VERSION
-);
+mark_msg_version_bound!(ConsensusMsg, HSB_PROTO_V1, HSB_PROTOCOL_VERSION);
 build_msg_impl_with_serde_serialization! {EpochRetrievalRequest, msgid::EPOCH_RETRIEVAL, "EpochRetrievalMessage"}
 mark_msg_version_bound!(
     EpochRetrievalRequest,
-    HSB_PROTOCOL_VERSION,
+    HSB_PROTO_V1,
     HSB_PROTOCOL_VERSION
 );
 build_msg_impl_with_request_id_and_serde_serialization! {BlockRetrievalRpcRequest, msgid::BLOCK_RETRIEVAL, "BlockRetrievalMessage"}
 mark_msg_version_bound!(
     BlockRetrievalRpcRequest,
-    HSB_PROTOCOL_VERSION,
+    HSB_PROTO_V1,
     HSB_PROTOCOL_VERSION
 );
 build_msg_impl_with_serde_serialization! {MempoolSyncMsg, msgid::MEMPOOL_SYNC_MSG, "MempoolSyncMsg"}
-mark_msg_version_bound!(
-    MempoolSyncMsg,
-    HSB_PROTOCOL_VERSION,
-    HSB_PROTOCOL_VERSION
-);
+mark_msg_version_bound!(MempoolSyncMsg, HSB_PROTO_V1, HSB_PROTOCOL_VERSION);
+build_msg_impl_with_serde_serialization! {Throttled, msgid::THROTTLED, "ThrottledMessage"}
+mark_msg_version_bound!(Throttled, HSB_PROTO_V2, HSB_PROTOCOL_VERSION);
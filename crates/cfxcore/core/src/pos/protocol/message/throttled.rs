@@ -0,0 +1,42 @@
+// Copyright 2019-2020 Conflux Foundation. All rights reserved.
+// TreeGraph is free software and distributed under Apache License 2.0.
+// See https://www.apache.org/licenses/LICENSE-2.0
+
+use crate::{
+    message::{MsgId, RequestId},
+    pos::protocol::sync_protocol::{Context, Handleable},
+    sync::Error,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Response to the messages throttled by the ingress token buckets of the
+/// peer.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Throttled {
+    pub msg_id: MsgId,
+    pub wait_time_nanos: u64,
+    // fail the request fast instead of waiting for timeout
+    pub request_id: Option<RequestId>,
+}
+
+impl Handleable for Throttled {
+    fn handle(self, ctx: &Context) -> Result<(), Error> {
+        debug!(
+            "message throttled by peer, peer = {:?}, msg_id = {}, wait_time = {:?}",
+            ctx.peer,
+            self.msg_id,
+            Duration::from_nanos(self.wait_time_nanos)
+        );
+
+        if let Some(request_id) = self.request_id {
+            let mut request = ctx.match_request(request_id)?;
+            request.request.notify_error(Error::Msg(format!(
+                "request throttled by peer, wait_time = {:?}",
+                Duration::from_nanos(self.wait_time_nanos)
+            )));
+        }
+
+        Ok(())
+    }
+}
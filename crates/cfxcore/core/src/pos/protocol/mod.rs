@@ -11,4 +11,7 @@ pub mod sync_protocol;
 use network::{service::ProtocolVersion, ProtocolId};
 
 pub const HSB_PROTOCOL_ID: ProtocolId = *b"hsb"; // HotStuff Synchronization Protocol
-pub const HSB_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion(1);
+pub const HSB_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion(2);
+
+pub const HSB_PROTO_V1: ProtocolVersion = ProtocolVersion(1);
+pub const HSB_PROTO_V2: ProtocolVersion = ProtocolVersion(2);
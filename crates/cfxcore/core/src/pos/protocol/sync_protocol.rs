@@ -19,10 +19,12 @@ use diem_types::{
     validator_config::{ConsensusPublicKey, ConsensusVRFPublicKey},
 };
 use io::TimerToken;
+use metrics::{register_meter_with_group, Meter};
 use network::{
     node_table::NodeId, service::ProtocolVersion, NetworkContext,
     NetworkProtocolHandler, NetworkService, UpdateNodeOperation,
 };
+use throttling::token_bucket::{ThrottleResult, TokenBucketManager};
 
 use crate::{
     message::{Message, MsgId},
@@ -35,6 +37,7 @@ use crate::{
             message::{
                 block_retrieval::BlockRetrievalRpcRequest,
                 block_retrieval_response::BlockRetrievalRpcResponse, msgid,
+                throttled::Throttled,
             },
             network_event::NetworkEvent,
             request_manager::{
@@ -42,17 +45,32 @@ use crate::{
             },
         },
     },
-    sync::{Error, ProtocolConfiguration, CHECK_RPC_REQUEST_TIMER},
+    sync::{
+        message::Throttled as SyncThrottled, Error, ProtocolConfiguration,
+        CHECK_RPC_REQUEST_TIMER,
+    },
 };
 
 use super::{HSB_PROTOCOL_ID, HSB_PROTOCOL_VERSION};
 
+lazy_static! {
+    static ref THROTTLED_METER: Arc<dyn Meter> =
+        register_meter_with_group("ingress_throttling", "pos::throttled");
+    static ref ALREADY_THROTTLED_METER: Arc<dyn Meter> =
+        register_meter_with_group(
+            "ingress_throttling",
+            "pos::already_throttled"
+        );
+}
+
 #[derive(Default)]
 pub struct PeerState {
     id: NodeId,
     peer_hash: H256,
     // TODO(lpl): Only keep AccountAddress?
     pos_public_key: Option<(ConsensusPublicKey, ConsensusVRFPublicKey)>,
+    // ingress token buckets of messages
+    throttling: TokenBucketManager,
 }
 
 impl PeerState {
@@ -64,6 +82,7 @@ impl PeerState {
             id,
             peer_hash,
             pos_public_key,
+            throttling: TokenBucketManager::default(),
         }
     }
 
@@ -310,9 +329,20 @@ impl HotStuffSynchronizationProtocol {
             Error::Throttled(_, msg) => {
                 disconnect = false;
 
-                if let Err(e) = msg.send(io, peer) {
-                    error!("failed to send throttled packet: {:?}", e);
-                    disconnect = true;
+                let throttled = Throttled {
+                    msg_id: msg.msg_id,
+                    wait_time_nanos: msg.wait_time_nanos,
+                    request_id: msg.request_id,
+                };
+                match throttled.send(io, peer) {
+                    Ok(_) => {}
+                    // peers of old versions do not support it, and the
+                    // message is just dropped
+                    Err(network::Error::SendUnsupportedMessage { .. }) => {}
+                    Err(e) => {
+                        error!("failed to send throttled packet: {:?}", e);
+                        disconnect = true;
+                    }
                 }
             }
             Error::Decoder(_) => op = Some(UpdateNodeOperation::Remove),
@@ -437,6 +467,7 @@ pub fn handle_serialized_message(
         msgid::EPOCH_CHANGE => handle_message::<EpochChangeProof>(ctx, msg)?,
         msgid::CONSENSUS_MSG => handle_message::<ConsensusMsg>(ctx, msg)?,
         msgid::MEMPOOL_SYNC_MSG => handle_message::<MempoolSyncMsg>(ctx, msg)?,
+        msgid::THROTTLED => handle_message::<Throttled>(ctx, msg)?,
         _ => return Ok(false),
     }
     Ok(true)
@@ -454,7 +485,7 @@ where M: Deserialize<'a> + Handleable + Message {
         ctx.peer_hash, msg_id, msg_name, req_id,
     );
 
-    // FIXME: add throttling.
+    throttle(ctx, &msg)?;
 
     if let Err(e) = msg.handle(ctx) {
         info!(
@@ -468,6 +499,40 @@ where M: Deserialize<'a> + Handleable + Message {
     Ok(())
 }
 
+/// Acquire tokens from the ingress token bucket of the message, which is
+/// configured in the `pos_protocol` section of the throttling file.
+fn throttle<M: Message>(ctx: &Context, msg: &M) -> Result<(), Error> {
+    let peer = match ctx.manager.peers.get(&ctx.peer_hash) {
+        Some(peer) => peer,
+        None => return Ok(()),
+    };
+
+    let bucket = match peer.read().throttling.get(msg.msg_name()) {
+        Some(bucket) => bucket,
+        None => return Ok(()),
+    };
+
+    let result = bucket.lock().throttle_default();
+
+    match result {
+        ThrottleResult::Success => Ok(()),
+        ThrottleResult::Throttled(wait_time) => {
+            THROTTLED_METER.mark(1);
+            let throttled = SyncThrottled {
+                msg_id: msg.msg_id(),
+                wait_time_nanos: wait_time.as_nanos() as u64,
+                request_id: msg.get_request_id(),
+            };
+
+            Err(Error::Throttled(msg.msg_name(), throttled))
+        }
+        ThrottleResult::AlreadyThrottled => {
+            ALREADY_THROTTLED_METER.mark(1);
+            Err(Error::AlreadyThrottled(msg.msg_name()))
+        }
+    }
+}
+
 impl NetworkProtocolHandler for HotStuffSynchronizationProtocol {
     fn minimum_supported_version(&self) -> ProtocolVersion {
         ProtocolVersion(0)
@@ -553,6 +618,18 @@ impl NetworkProtocolHandler for HotStuffSynchronizationProtocol {
                 let mut state = state.write();
                 state.id = *node_id;
                 state.peer_hash = peer_hash;
+                if let Some(ref file) =
+                    self.protocol_config.throttling_config_file
+                {
+                    // the section is optional for the throttling files
+                    // created before the PoS protocol is throttled
+                    state.throttling =
+                        TokenBucketManager::load(file, Some("pos_protocol"))
+                            .unwrap_or_else(|e| {
+                                debug!("PoS protocol not throttled: {}", e);
+                                TokenBucketManager::default()
+                            });
+                }
                 self.request_manager.on_peer_connected(node_id);
             } else {
                 warn!(
@@ -670,3 +747,163 @@ impl From<anyhow::Error> for Error {
         Error::InternalError(format!("{}", error)).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{message::SendQueuePriority, pos::protocol::HSB_PROTO_V1};
+    use futures::{FutureExt, StreamExt};
+    use network::{HandlerWorkType, ProtocolId};
+    use parking_lot::Mutex;
+    use std::time::Duration;
+    use throttling::token_bucket::TokenBucket;
+
+    struct TestNetworkContext {
+        peer_protocol_version: ProtocolVersion,
+        sent: Mutex<Vec<Vec<u8>>>,
+        disconnected: Mutex<Vec<NodeId>>,
+    }
+
+    impl TestNetworkContext {
+        fn new(peer_protocol_version: ProtocolVersion) -> Self {
+            TestNetworkContext {
+                peer_protocol_version,
+                sent: Default::default(),
+                disconnected: Default::default(),
+            }
+        }
+    }
+
+    impl NetworkContext for TestNetworkContext {
+        fn get_protocol(&self) -> ProtocolId { HSB_PROTOCOL_ID }
+
+        fn get_peer_connection_origin(
+            &self, _node_id: &NodeId,
+        ) -> Option<bool> {
+            Some(true)
+        }
+
+        fn send(
+            &self, _node_id: &NodeId, msg: Vec<u8>,
+            min_protocol_version: ProtocolVersion,
+            _version_valid_till: ProtocolVersion, _priority: SendQueuePriority,
+        ) -> Result<(), network::Error> {
+            if min_protocol_version > self.peer_protocol_version {
+                return Err(network::Error::SendUnsupportedMessage {
+                    protocol: HSB_PROTOCOL_ID,
+                    msg_id: msg[msg.len() - 1] as u16,
+                    peer_protocol_version: Some(self.peer_protocol_version),
+                    min_supported_version: Some(min_protocol_version),
+                });
+            }
+            self.sent.lock().push(msg);
+            Ok(())
+        }
+
+        fn disconnect_peer(
+            &self, node_id: &NodeId, _op: Option<UpdateNodeOperation>,
+            _reason: &str,
+        ) {
+            self.disconnected.lock().push(*node_id);
+        }
+
+        fn report_peer(
+            &self, _node_id: &NodeId, _change: network::ReputationChange,
+            _reason: &str,
+        ) -> bool {
+            false
+        }
+
+        fn register_timer(
+            &self, _token: TimerToken, _delay: Duration,
+        ) -> Result<(), network::Error> {
+            Ok(())
+        }
+
+        fn dispatch_work(&self, _work_type: HandlerWorkType) {}
+
+        fn insert_peer_node_tag(
+            &self, _peer: NodeId, _key: &str, _value: &str,
+        ) {
+        }
+
+        fn is_peer_self(&self, _node_id: &NodeId) -> bool { false }
+
+        fn self_node_id(&self) -> NodeId { NodeId::default() }
+    }
+
+    fn mempool_sync_msg(id: u8) -> Vec<u8> {
+        MempoolSyncMsg::BroadcastTransactionsResponse {
+            request_id: vec![id],
+            retry: false,
+            backoff: false,
+        }
+        .encode()
+    }
+
+    #[test]
+    fn test_throttled_message() {
+        let (consensus_network_task, _) = ConsensusNetworkTask::new();
+        let (mempool_network_task, mut mempool_network_receivers) =
+            MempoolNetworkTask::new();
+        let protocol = HotStuffSynchronizationProtocol::new(
+            H256::random(),
+            consensus_network_task,
+            mempool_network_task,
+            ProtocolConfiguration::default(),
+        );
+
+        // one message allowed per second
+        let peer = NodeId::random();
+        protocol.peers.insert(keccak(&peer), peer, None);
+        protocol
+            .peers
+            .get(&keccak(&peer))
+            .unwrap()
+            .write()
+            .throttling
+            .register(
+                "MempoolSyncMsg".into(),
+                "1,1,1,1,5".parse::<TokenBucket>().unwrap(),
+            );
+
+        let io = TestNetworkContext::new(HSB_PROTOCOL_VERSION);
+        protocol.on_message(&io, &peer, &mempool_sync_msg(1));
+        protocol.on_message(&io, &peer, &mempool_sync_msg(2));
+
+        // the throttled message is dropped
+        let mut received = Vec::new();
+        while let Some(Some((_, msg))) = mempool_network_receivers
+            .mempool_sync_message
+            .next()
+            .now_or_never()
+        {
+            received.push(msg);
+        }
+        assert_eq!(received.len(), 1);
+        match &received[0] {
+            MempoolSyncMsg::BroadcastTransactionsResponse {
+                request_id,
+                ..
+            } => assert_eq!(request_id, &vec![1]),
+            msg => panic!("unexpected message {:?}", msg),
+        }
+
+        // and the peer is notified
+        let sent = io.sent.lock();
+        assert_eq!(sent.len(), 1);
+        let (id, payload) = sent[0].split_last().unwrap();
+        assert_eq!(*id as MsgId, msgid::THROTTLED);
+        let throttled: Throttled = bcs::from_bytes(payload).unwrap();
+        assert_eq!(throttled.msg_id, msgid::MEMPOOL_SYNC_MSG);
+        assert!(throttled.wait_time_nanos > 0);
+        assert_eq!(throttled.request_id, None);
+        assert!(io.disconnected.lock().is_empty());
+
+        // peers of the old protocol version are not notified or disconnected
+        let io = TestNetworkContext::new(HSB_PROTO_V1);
+        protocol.on_message(&io, &peer, &mempool_sync_msg(3));
+        assert!(io.sent.lock().is_empty());
+        assert!(io.disconnected.lock().is_empty());
+    }
+}
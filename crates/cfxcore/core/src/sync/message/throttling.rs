@@ -9,10 +9,24 @@ use crate::{
         Error,
     },
 };
+use metrics::{register_meter_with_group, Meter};
 use rlp_derive::{RlpDecodable, RlpEncodable};
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use throttling::token_bucket::ThrottleResult;
 
+lazy_static! {
+    static ref THROTTLED_METER: Arc<dyn Meter> =
+        register_meter_with_group("ingress_throttling", "sync::throttled");
+    static ref ALREADY_THROTTLED_METER: Arc<dyn Meter> =
+        register_meter_with_group(
+            "ingress_throttling",
+            "sync::already_throttled"
+        );
+}
+
 // TODO: It seems better to distinguish request, response, and different kind of
 // TODO: requests, as here in this class it tries to resend request to another
 // TODO: peer. This class is implemented to all Message type. But the resend
@@ -71,6 +85,7 @@ impl<T: Message> Throttle for T {
         match result {
             ThrottleResult::Success => Ok(()),
             ThrottleResult::Throttled(wait_time) => {
+                THROTTLED_METER.mark(1);
                 let throttled = Throttled {
                     msg_id: self.msg_id(),
                     wait_time_nanos: wait_time.as_nanos() as u64,
@@ -80,6 +95,7 @@ impl<T: Message> Throttle for T {
                 Err(Error::Throttled(self.msg_name(), throttled).into())
             }
             ThrottleResult::AlreadyThrottled => {
+                ALREADY_THROTTLED_METER.mark(1);
                 Err(Error::AlreadyThrottled(self.msg_name()).into())
             }
         }
@@ -17,7 +17,7 @@ use malloc_size_of::MallocSizeOf;
 use malloc_size_of_derive::MallocSizeOf as DeriveMallocSizeOf;
 use network::{
     node_table::NodeId, Error as NetworkError, NetworkContext,
-    UpdateNodeOperation,
+    ReputationChange, UpdateNodeOperation,
 };
 use parking_lot::Mutex;
 use std::{
@@ -163,11 +163,15 @@ impl RequestHandler {
         let mut timeout_requests = Vec::new();
         let mut peers_to_disconnect = HashSet::new();
         let mut peers_to_send_pending_requests = HashSet::new();
+        // Penalize each peer once per check, however many of its requests
+        // time out together.
+        let mut timeout_peers = HashSet::new();
         for sync_req in self.get_timeout_sync_requests() {
             if let Ok(mut req) =
                 self.match_request(&sync_req.peer_id, sync_req.request_id)
             {
                 let peer_id = sync_req.peer_id.clone();
+                timeout_peers.insert(peer_id);
                 if let Some(request_container) =
                     self.peers.lock().get_mut(&peer_id)
                 {
@@ -185,6 +189,17 @@ impl RequestHandler {
                 debug!("Timeout a removed request {:?}", sync_req);
             }
         }
+        for peer_id in timeout_peers {
+            // Peers banned due to low reputation are disconnected already.
+            if io.report_peer(
+                &peer_id,
+                ReputationChange::RequestTimeout,
+                "request timeout", /* reason */
+            ) {
+                peers_to_disconnect.remove(&peer_id);
+                peers_to_send_pending_requests.remove(&peer_id);
+            }
+        }
         let op = if self.protocol_config.demote_peer_for_timeout {
             Some(UpdateNodeOperation::Demotion)
         } else {
@@ -556,3 +571,121 @@ impl PartialEq for TimedSyncRequests {
         self.timeout_time == other.timeout_time
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::SendQueuePriority, sync::message::GetBlockHashesByEpoch,
+    };
+    use network::{
+        service::ProtocolVersion, HandlerWorkType, ProtocolId, TimerToken,
+    };
+    use std::thread;
+
+    #[derive(Default)]
+    struct TestNetworkContext {
+        reported: Mutex<Vec<(NodeId, ReputationChange)>>,
+        disconnected: Mutex<Vec<NodeId>>,
+    }
+
+    impl NetworkContext for TestNetworkContext {
+        fn get_protocol(&self) -> ProtocolId { *b"cfx" }
+
+        fn get_peer_connection_origin(
+            &self, _node_id: &NodeId,
+        ) -> Option<bool> {
+            Some(true)
+        }
+
+        fn send(
+            &self, _node_id: &NodeId, _msg: Vec<u8>,
+            _min_protocol_version: ProtocolVersion,
+            _version_valid_till: ProtocolVersion, _priority: SendQueuePriority,
+        ) -> Result<(), NetworkError> {
+            Ok(())
+        }
+
+        fn disconnect_peer(
+            &self, node_id: &NodeId, _op: Option<UpdateNodeOperation>,
+            _reason: &str,
+        ) {
+            self.disconnected.lock().push(*node_id);
+        }
+
+        fn report_peer(
+            &self, node_id: &NodeId, change: ReputationChange, _reason: &str,
+        ) -> bool {
+            self.reported.lock().push((*node_id, change));
+            false
+        }
+
+        fn register_timer(
+            &self, _token: TimerToken, _delay: Duration,
+        ) -> Result<(), NetworkError> {
+            Ok(())
+        }
+
+        fn dispatch_work(&self, _work_type: HandlerWorkType) {}
+
+        fn insert_peer_node_tag(
+            &self, _peer: NodeId, _key: &str, _value: &str,
+        ) {
+        }
+
+        fn is_peer_self(&self, _node_id: &NodeId) -> bool { false }
+
+        fn self_node_id(&self) -> NodeId { NodeId::default() }
+    }
+
+    fn send_requests(
+        handler: &RequestHandler, io: &dyn NetworkContext, peer: NodeId,
+        count: u64,
+    ) {
+        for epoch in 0..count {
+            let request = Box::new(GetBlockHashesByEpoch {
+                request_id: 0,
+                epochs: vec![epoch],
+            });
+            assert!(handler
+                .send_request(io, Some(peer), request, None)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_batch_timeout_reported_once() {
+        // `headers_request_timeout` is zero, so that all the requests time
+        // out at the next check.
+        let config = ProtocolConfiguration {
+            max_inflight_request_count: 10,
+            timeout_observing_period_s: 60,
+            max_allowed_timeout_in_observing_period: 10,
+            ..Default::default()
+        };
+        let handler = RequestHandler::new(&config);
+        let io = TestNetworkContext::default();
+
+        let congested = NodeId::random();
+        let normal = NodeId::random();
+        handler.add_peer(congested);
+        handler.add_peer(normal);
+        send_requests(&handler, &io, congested, 5);
+        send_requests(&handler, &io, normal, 1);
+
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(handler.process_timeout_requests(&io).len(), 6);
+
+        let reported = io.reported.lock().clone();
+        assert_eq!(reported.len(), 2);
+        assert!(
+            reported.contains(&(congested, ReputationChange::RequestTimeout))
+        );
+        assert!(reported.contains(&(normal, ReputationChange::RequestTimeout)));
+        assert!(io.disconnected.lock().is_empty());
+
+        // Nothing is reported if no request times out.
+        assert!(handler.process_timeout_requests(&io).is_empty());
+        assert_eq!(io.reported.lock().len(), 2);
+    }
+}
@@ -3,6 +3,7 @@
 // See http://www.gnu.org/licenses/
 
 use crate::{
+    channel::Channel,
     sync::{
         message::DynamicCapability,
         state::{SnapshotChunkSync, Status},
@@ -101,6 +102,9 @@ impl SynchronizationPhaseManagerInner {
 
 pub struct SynchronizationPhaseManager {
     inner: RwLock<SynchronizationPhaseManagerInner>,
+    /// Every phase change is sent to this channel for the `syncing` PubSub
+    /// subscriptions.
+    sync_phase_changes: Arc<Channel<SyncPhaseType>>,
 }
 
 impl SynchronizationPhaseManager {
@@ -114,6 +118,7 @@ impl SynchronizationPhaseManager {
             inner: RwLock::new(SynchronizationPhaseManagerInner::new(
                 initial_phase_type,
             )),
+            sync_phase_changes: sync_graph.sync_phase_changes.clone(),
         };
 
         sync_manager.register_phase(Arc::new(
@@ -158,6 +163,7 @@ impl SynchronizationPhaseManager {
         sync_handler: &SynchronizationProtocolHandler,
     ) {
         self.inner.write().change_phase_to(phase_type);
+        self.sync_phase_changes.send(phase_type);
         let current_phase = self.get_current_phase();
         current_phase.start(io, sync_handler);
     }
@@ -37,7 +37,8 @@ use metrics::{register_meter_with_group, Meter, MeterTimer};
 use network::{
     node_table::NodeId, service::ProtocolVersion,
     throttling::THROTTLING_SERVICE, Error as NetworkError, HandlerWorkType,
-    NetworkContext, NetworkProtocolHandler, UpdateNodeOperation,
+    NetworkContext, NetworkProtocolHandler, ReputationChange,
+    UpdateNodeOperation,
 };
 use parking_lot::{Mutex, RwLock};
 use primitives::{Block, BlockHeader, EpochId, SignedTransaction};
@@ -602,19 +603,28 @@ impl SynchronizationProtocolHandler {
         let reason = format!("{}", e);
         let error_reason = format!("{:?}", e);
         let mut op = None;
+        let mut change = None;
 
         // NOTE, DO NOT USE WILDCARD IN THE FOLLOWING MATCH STATEMENT!
         // COMPILER WILL HELP TO FIND UNHANDLED ERROR CASES.
         match e {
-            Error::InvalidBlock => op = Some(UpdateNodeOperation::Failure),
+            Error::InvalidBlock => {
+                op = Some(UpdateNodeOperation::Failure);
+                change = Some(ReputationChange::InvalidBlock);
+            }
             Error::InvalidGetBlockTxn(_) => {
-                op = Some(UpdateNodeOperation::Demotion)
+                op = Some(UpdateNodeOperation::Demotion);
+                change = Some(ReputationChange::UselessResponse);
+            }
+            Error::InvalidStatus(_) => {
+                op = Some(UpdateNodeOperation::Demotion);
+                change = Some(ReputationChange::ProtocolViolation);
             }
-            Error::InvalidStatus(_) => op = Some(UpdateNodeOperation::Demotion),
             Error::InvalidMessageFormat => {
                 // TODO: Shall we blacklist a node when the message format is
                 // wrong? maybe it's a different version of sync protocol?
-                op = Some(UpdateNodeOperation::Remove)
+                op = Some(UpdateNodeOperation::Remove);
+                change = Some(ReputationChange::ProtocolViolation);
             }
             Error::UnknownPeer => {
                 warn = false;
@@ -623,7 +633,8 @@ impl SynchronizationProtocolHandler {
             // TODO handle the unexpected response case (timeout or real invalid
             // message type)
             Error::UnexpectedResponse => {
-                op = Some(UpdateNodeOperation::Demotion)
+                op = Some(UpdateNodeOperation::Demotion);
+                change = Some(ReputationChange::UselessResponse);
             }
             Error::RequestNotFound => {
                 disconnect = false;
@@ -634,16 +645,25 @@ impl SynchronizationProtocolHandler {
                 warn = false;
             }
             Error::TooManyTrans => {}
-            Error::InvalidTimestamp => op = Some(UpdateNodeOperation::Demotion),
+            Error::InvalidTimestamp => {
+                op = Some(UpdateNodeOperation::Demotion);
+                change = Some(ReputationChange::ProtocolViolation);
+            }
             Error::InvalidSnapshotManifest(_) => {
-                op = Some(UpdateNodeOperation::Demotion)
+                op = Some(UpdateNodeOperation::Demotion);
+                change = Some(ReputationChange::UselessResponse);
             }
             Error::InvalidSnapshotChunk(_) => {
-                op = Some(UpdateNodeOperation::Demotion)
+                op = Some(UpdateNodeOperation::Demotion);
+                change = Some(ReputationChange::UselessResponse);
+            }
+            Error::EmptySnapshotChunk => {
+                disconnect = false;
+                change = Some(ReputationChange::UselessResponse);
             }
-            Error::EmptySnapshotChunk => disconnect = false,
             Error::AlreadyThrottled(_) => {
-                op = Some(UpdateNodeOperation::Remove)
+                op = Some(UpdateNodeOperation::Remove);
+                change = Some(ReputationChange::ProtocolViolation);
             }
             Error::Throttled(_, msg) => {
                 disconnect = false;
@@ -653,7 +673,10 @@ impl SynchronizationProtocolHandler {
                     disconnect = true;
                 }
             }
-            Error::Decoder(_) => op = Some(UpdateNodeOperation::Remove),
+            Error::Decoder(_) => {
+                op = Some(UpdateNodeOperation::Remove);
+                change = Some(ReputationChange::ProtocolViolation);
+            }
             Error::Io(_) => disconnect = false,
             Error::Network(kind) => match kind {
                 network::Error::SendUnsupportedMessage { .. } => {
@@ -665,6 +688,7 @@ impl SynchronizationProtocolHandler {
 
                 network::Error::MessageDeprecated { .. } => {
                     op = Some(UpdateNodeOperation::Failure);
+                    change = Some(ReputationChange::ProtocolViolation);
                     error!(
                         "Peer sent us a deprecated message {:?}. Either it's a bug \
                         in protocol version maintenance or the peer is malicious.",
@@ -676,11 +700,13 @@ impl SynchronizationProtocolHandler {
                 network::Error::AddressResolve(_) => disconnect = false,
                 network::Error::Auth => disconnect = false,
                 network::Error::BadProtocol => {
-                    op = Some(UpdateNodeOperation::Remove)
+                    op = Some(UpdateNodeOperation::Remove);
+                    change = Some(ReputationChange::ProtocolViolation);
                 }
                 network::Error::BadAddr => disconnect = false,
                 network::Error::Decoder(_) => {
-                    op = Some(UpdateNodeOperation::Remove)
+                    op = Some(UpdateNodeOperation::Remove);
+                    change = Some(ReputationChange::ProtocolViolation);
                 }
                 network::Error::Expired => disconnect = false,
                 network::Error::Disconnect(_) => disconnect = false,
@@ -701,7 +727,8 @@ impl SynchronizationProtocolHandler {
             Error::RpcCancelledByDisconnection => {}
             Error::RpcTimeout => {}
             Error::UnexpectedMessage(_) => {
-                op = Some(UpdateNodeOperation::Remove)
+                op = Some(UpdateNodeOperation::Remove);
+                change = Some(ReputationChange::ProtocolViolation);
             }
             Error::NotSupported(_) => disconnect = false,
         }
@@ -718,6 +745,13 @@ impl SynchronizationProtocolHandler {
             );
         }
 
+        if let Some(change) = change {
+            // already disconnected if banned
+            if io.report_peer(peer, change, reason.as_str()) {
+                disconnect = false;
+            }
+        }
+
         if disconnect {
             io.disconnect_peer(peer, op, reason.as_str());
         }
@@ -1595,10 +1629,10 @@ impl SynchronizationProtocolHandler {
     }
 
     fn gc(&self) {
+        let best_epoch = self.graph.consensus.best_epoch_number();
         self.graph.data_man.cache_gc();
-        self.graph
-            .data_man
-            .database_gc(self.graph.consensus.best_epoch_number())
+        self.graph.data_man.database_gc(best_epoch);
+        self.graph.data_man.update_log_index(best_epoch);
     }
 
     fn log_statistics(&self) { self.graph.log_statistics(); }
@@ -15,8 +15,8 @@ use cfx_parameters::{
 };
 use cfx_storage::{StorageConfiguration, StorageManager};
 use cfx_types::{
-    address_util::AddressUtil, Address, AddressSpaceUtil, AllChainID, H256,
-    U256,
+    address_util::AddressUtil, Address, AddressSpaceUtil, AddressWithSpace,
+    AllChainID, H256, U256,
 };
 use diem_config::keys::ConfigKey;
 use diem_crypto::Uniform;
@@ -109,6 +109,46 @@ pub fn create_simple_block(
 
 pub fn initialize_data_manager(
     db_dir: &str, dbtype: DbType, pow: Arc<PowComputer>, vm: VmFactory,
+) -> (Arc<BlockDataManager>, Arc<Block>) {
+    initialize_data_manager_with_config(
+        db_dir,
+        DataManagerConfiguration::new(
+            false,                          /* do not persist transaction
+                                             * address */
+            false, /* do not persist block number index */
+            Duration::from_millis(300_000), /* max cached tx count */
+            dbtype,
+        ),
+        pow,
+        vm,
+    )
+}
+
+pub fn initialize_data_manager_with_config(
+    db_dir: &str, config: DataManagerConfiguration, pow: Arc<PowComputer>,
+    vm: VmFactory,
+) -> (Arc<BlockDataManager>, Arc<Block>) {
+    let mut genesis_accounts = HashMap::new();
+    genesis_accounts.insert(
+        Address::from_str("1000000000000000000000000000000000000008")
+            .unwrap()
+            .with_native_space(),
+        U256::from(0),
+    );
+    initialize_data_manager_with_genesis_accounts(
+        db_dir,
+        config,
+        pow,
+        vm,
+        genesis_accounts,
+    )
+}
+
+/// Like `initialize_data_manager_with_config`, but the genesis state holds
+/// `genesis_accounts` with their balances.
+pub fn initialize_data_manager_with_genesis_accounts(
+    db_dir: &str, config: DataManagerConfiguration, pow: Arc<PowComputer>,
+    vm: VmFactory, genesis_accounts: HashMap<AddressWithSpace, U256>,
 ) -> (Arc<BlockDataManager>, Arc<Block>) {
     let ledger_db = db::open_database(
         db_dir,
@@ -137,14 +177,6 @@ pub fn initialize_data_manager(
         .expect("Failed to initialize storage."),
     );
 
-    let mut genesis_accounts = HashMap::new();
-    genesis_accounts.insert(
-        Address::from_str("1000000000000000000000000000000000000008")
-            .unwrap()
-            .with_native_space(),
-        U256::from(0),
-    );
-
     let machine = Arc::new(Machine::new_with_builtin(Default::default(), vm));
 
     let genesis_block = Arc::new(genesis_block(
@@ -168,13 +200,7 @@ pub fn initialize_data_manager(
         ledger_db.clone(),
         storage_manager,
         worker_thread_pool,
-        DataManagerConfiguration::new(
-            false,                          /* do not persist transaction
-                                             * address */
-            false, /* do not persist block number index */
-            Duration::from_millis(300_000), /* max cached tx count */
-            dbtype,
-        ),
+        config,
         pow,
     ));
     (data_man, genesis_block)
@@ -199,6 +225,7 @@ pub fn initialize_synchronization_graph_with_data_manager(
             pos_initial_nodes_path: "".to_string(),
             vrf_proposal_threshold: Default::default(),
             pos_state_config: Default::default(),
+            backup_service_address: None,
         },
         u64::MAX,
     ));
@@ -209,15 +236,18 @@ pub fn initialize_synchronization_graph_with_data_manager(
         MAX_BLOCK_SIZE_IN_BYTES,
         TRANSACTION_DEFAULT_EPOCH_BOUND,
         TXPOOL_DEFAULT_NONCE_BITS,
+        true, /* verify_pow_hash */
         machine.clone(),
         pos_verifier.clone(),
     );
 
+    let notifications = Notifications::init();
     let txpool = Arc::new(TransactionPool::new(
         TxPoolConfig::default(),
         verification_config.clone(),
         data_man.clone(),
         machine.clone(),
+        notifications.clone(),
     ));
     let statistics = Arc::new(Statistics::new());
 
@@ -237,7 +267,6 @@ pub fn initialize_synchronization_graph_with_data_manager(
         enable_state_expose: false,
         is_consortium: false,
     };
-    let notifications = Notifications::init();
     let consensus = Arc::new(ConsensusGraph::new(
         ConsensusConfig {
             chain_id: ChainIdParamsInner::new_simple(AllChainID::new(1, 1)),
@@ -0,0 +1,469 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! The on-disk journal of the transaction pool, so the pending transactions
+//! survive a restart.
+//!
+//! The journal is an RLP list of `[is_local, transaction]` entries. It keeps
+//! the local transactions still in the pool except the private ones and, if
+//! configured, all the other ones. It is written periodically and when the pool
+//! is dropped, always to a temporary file first, which is synced to the disk
+//! before it is renamed, and the directory is synced after the rename, so a
+//! crash never leaves a partial journal. The previous journals are kept as
+//! `<path>.1`, `<path>.2` and so on.
+//!
+//! The journal is replayed once the node enters the normal sync phase, through
+//! the normal insertion, so the stale transactions are dropped by the nonce
+//! and readiness checks.
+
+use super::TransactionPool;
+use parking_lot::Mutex;
+use primitives::{SignedTransaction, TransactionWithSignature};
+use rlp::{PayloadInfo, Rlp, RlpStream};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// The number of transactions inserted at a time when replaying the journal.
+const REPLAY_BATCH_SIZE: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub struct TxPoolJournalConfig {
+    pub path: PathBuf,
+    /// Also journal the transactions received from the peers.
+    pub journal_all: bool,
+    pub interval: Duration,
+    /// The transactions exceeding this size in bytes are not journaled, the
+    /// local transactions are journaled first.
+    pub max_size: usize,
+    /// The number of the previous journals kept.
+    pub rotations: usize,
+}
+
+pub(super) struct TxPoolJournal {
+    config: TxPoolJournalConfig,
+    /// The journal is only written after it is replayed, so the one of the
+    /// last run is not overwritten before the pool is filled.
+    replayed: AtomicBool,
+    /// Serializes the writes of the journal.
+    write_lock: Mutex<()>,
+}
+
+impl TxPoolJournal {
+    pub fn new(config: TxPoolJournalConfig) -> Self {
+        TxPoolJournal {
+            config,
+            replayed: AtomicBool::new(false),
+            write_lock: Mutex::new(()),
+        }
+    }
+}
+
+impl TransactionPool {
+    /// Starts the thread writing the journal periodically. The thread stops
+    /// after the pool is dropped.
+    pub fn start_journal_thread(self: &Arc<Self>) {
+        let interval = match &self.journal {
+            Some(journal) => journal.config.interval,
+            None => return,
+        };
+        let pool = Arc::downgrade(self);
+        thread::Builder::new()
+            .name("txpool_journal".into())
+            .spawn(move || loop {
+                thread::sleep(interval);
+                match pool.upgrade() {
+                    Some(pool) => pool.write_journal(),
+                    None => break,
+                }
+            })
+            .expect("Transaction pool journal thread start fails");
+    }
+
+    /// Inserts the transactions of the journal into the pool. Only the first
+    /// call takes effect.
+    pub fn replay_journal(&self) {
+        let journal = match &self.journal {
+            Some(journal) => journal,
+            None => return,
+        };
+        if journal.replayed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let entries = match read_latest_journal(&journal.config) {
+            Some(entries) => entries,
+            None => return,
+        };
+        let total = entries.len();
+        let mut local = Vec::new();
+        let mut remote = Vec::new();
+        for (is_local, tx) in entries {
+            if is_local {
+                local.push(tx);
+            } else {
+                remote.push(tx);
+            }
+        }
+
+        let mut inserted = 0;
+        for chunk in local.chunks(REPLAY_BATCH_SIZE) {
+            inserted +=
+                self.insert_new_local_transactions(chunk.to_vec()).0.len();
+        }
+        for chunk in remote.chunks(REPLAY_BATCH_SIZE) {
+            inserted += self.insert_new_transactions(chunk.to_vec()).0.len();
+        }
+        info!(
+            "Replayed transaction pool journal: {} of {} transactions inserted",
+            inserted, total
+        );
+    }
+
+    /// Writes the journal if it is enabled and has been replayed.
+    pub fn write_journal(&self) {
+        let journal = match &self.journal {
+            Some(journal) => journal,
+            None => return,
+        };
+        if !journal.replayed.load(Ordering::SeqCst) {
+            return;
+        }
+        let _write_lock = journal.write_lock.lock();
+
+        let entries = {
+            let inner = self.inner.read();
+            // The private transactions would be propagated after replayed.
+            let mut entries: Vec<_> = inner
+                .local_transactions()
+                .into_iter()
+                .filter(|tx| !inner.is_private_transaction(&tx.hash()))
+                .map(|tx| (true, tx))
+                .collect();
+            if journal.config.journal_all {
+                entries.extend(
+                    inner
+                        .content(None)
+                        .1
+                        .into_iter()
+                        .filter(|tx| !inner.is_local_transaction(&tx.hash()))
+                        .map(|tx| (false, tx)),
+                );
+            }
+            entries
+        };
+
+        match write_journal_file(&journal.config, &entries) {
+            Ok(count) => {
+                debug!("Transaction pool journal written: {} txs", count)
+            }
+            Err(e) => warn!("Failed to write transaction pool journal: {}", e),
+        }
+    }
+}
+
+impl Drop for TransactionPool {
+    fn drop(&mut self) { self.write_journal(); }
+}
+
+/// Returns the number of the written transactions.
+fn write_journal_file(
+    config: &TxPoolJournalConfig, entries: &[(bool, Arc<SignedTransaction>)],
+) -> Result<usize, String> {
+    let mut encoded_entries = Vec::new();
+    let mut size = 0;
+    for (is_local, tx) in entries {
+        let mut entry = RlpStream::new_list(2);
+        entry.append(is_local).append(&tx.transaction);
+        let entry = entry.out();
+        size += entry.len();
+        if size > config.max_size {
+            break;
+        }
+        encoded_entries.push(entry);
+    }
+
+    let mut stream = RlpStream::new_list(encoded_entries.len());
+    for entry in &encoded_entries {
+        stream.append_raw(entry, 1);
+    }
+
+    let dir = match config.path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let tmp_path = with_suffix(&config.path, "tmp");
+    let mut tmp_file = File::create(&tmp_path).map_err(|e| e.to_string())?;
+    tmp_file
+        .write_all(&stream.out())
+        .and_then(|_| tmp_file.sync_all())
+        .map_err(|e| e.to_string())?;
+    if config.path.exists() {
+        rotate(&config.path, config.rotations)?;
+    }
+    fs::rename(&tmp_path, &config.path).map_err(|e| e.to_string())?;
+    sync_dir(dir)?;
+    Ok(encoded_entries.len())
+}
+
+/// Syncs the entries of `dir`, so the renames in it survive a crash.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> Result<(), String> {
+    File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| e.to_string())
+}
+
+/// The directories can not be opened as files on the other platforms.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> Result<(), String> { Ok(()) }
+
+/// Shifts `<path>` to `<path>.1`, `<path>.1` to `<path>.2` and so on, and
+/// drops the one beyond `rotations`.
+fn rotate(path: &Path, rotations: usize) -> Result<(), String> {
+    if rotations == 0 {
+        return fs::remove_file(path).map_err(|e| e.to_string());
+    }
+    for i in (1..rotations).rev() {
+        let from = with_suffix(path, &i.to_string());
+        if from.exists() {
+            fs::rename(&from, with_suffix(path, &(i + 1).to_string()))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    fs::rename(path, with_suffix(path, "1")).map_err(|e| e.to_string())
+}
+
+/// Reads the latest readable journal, or `None` if there is none.
+fn read_latest_journal(
+    config: &TxPoolJournalConfig,
+) -> Option<Vec<(bool, TransactionWithSignature)>> {
+    let paths = std::iter::once(config.path.clone()).chain(
+        (1..=config.rotations)
+            .map(|i| with_suffix(&config.path, &i.to_string())),
+    );
+    for path in paths {
+        if !path.exists() {
+            continue;
+        }
+        match read_journal_file(&path) {
+            Ok(entries) => return Some(entries),
+            Err(e) => warn!(
+                "Failed to read transaction pool journal {:?}: {}",
+                path, e
+            ),
+        }
+    }
+    None
+}
+
+fn read_journal_file(
+    path: &Path,
+) -> Result<Vec<(bool, TransactionWithSignature)>, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let rlp = Rlp::new(&bytes);
+    let valid = rlp.is_list()
+        && PayloadInfo::from(&bytes)
+            .map_or(false, |payload| payload.total() == bytes.len());
+    if !valid {
+        return Err("Invalid journal".into());
+    }
+    rlp.iter()
+        .map(|entry| Ok((entry.val_at(0)?, entry.val_at(1)?)))
+        .collect::<Result<_, rlp::DecoderError>>()
+        .map_err(|e| format!("{:?}", e))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(suffix);
+    path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_journal_file, read_latest_journal, with_suffix,
+        write_journal_file, TxPoolJournalConfig,
+    };
+    use crate::{
+        block_data_manager::{DataManagerConfiguration, DbType},
+        keylib::{Generator, KeyPair, Random},
+        pow::PowComputer,
+        sync::utils::{
+            initialize_data_manager_with_genesis_accounts,
+            initialize_synchronization_graph_with_data_manager,
+        },
+        transaction_pool::{TransactionPool, TxPoolConfig},
+        ConsensusGraphTrait, Notifications,
+    };
+    use cfx_executor::machine::VmFactory;
+    use cfx_types::{
+        address_util::AddressUtil, Address, AddressSpaceUtil, U256,
+    };
+    use primitives::{
+        transaction::native_transaction::NativeTransaction, Action,
+        SignedTransaction, Transaction,
+    };
+    use std::{collections::HashMap, sync::Arc, time::Duration};
+
+    fn new_test_tx(nonce: usize) -> Arc<SignedTransaction> {
+        let sender = Random.generate().unwrap();
+        new_test_tx_from(&sender, nonce, 1)
+    }
+
+    fn new_test_tx_from(
+        sender: &KeyPair, nonce: usize, chain_id: u32,
+    ) -> Arc<SignedTransaction> {
+        let mut receiver = Address::random();
+        receiver.set_user_account_type_bits();
+        let tx: Transaction = NativeTransaction {
+            nonce: U256::from(nonce),
+            gas_price: U256::from(1),
+            gas: U256::from(21000),
+            action: Action::Call(receiver),
+            value: U256::from(1),
+            storage_limit: 0,
+            epoch_height: 0,
+            chain_id,
+            data: Vec::new(),
+        }
+        .into();
+        Arc::new(tx.sign(sender.secret()))
+    }
+
+    #[test]
+    fn test_write_and_rotate_journal() {
+        let dir = tempdir::TempDir::new("txpool_journal").unwrap();
+        let mut config = TxPoolJournalConfig {
+            path: dir.path().join("journal.rlp"),
+            journal_all: true,
+            interval: Duration::from_secs(1),
+            max_size: 1 << 20,
+            rotations: 1,
+        };
+        let txs: Vec<_> = (0..3).map(new_test_tx).collect();
+
+        let entries = vec![(true, txs[0].clone()), (false, txs[1].clone())];
+        assert_eq!(write_journal_file(&config, &entries).unwrap(), 2);
+        let journal = read_journal_file(&config.path).unwrap();
+        assert_eq!(journal.len(), 2);
+        assert_eq!((journal[0].0, journal[0].1.hash()), (true, txs[0].hash()));
+        assert_eq!((journal[1].0, journal[1].1.hash()), (false, txs[1].hash()));
+
+        // The previous journal is kept, and the one before it is dropped.
+        let entries = vec![(true, txs[2].clone())];
+        write_journal_file(&config, &entries).unwrap();
+        write_journal_file(&config, &entries).unwrap();
+        assert_eq!(read_journal_file(&config.path).unwrap().len(), 1);
+        assert_eq!(
+            read_journal_file(&with_suffix(&config.path, "1"))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(!with_suffix(&config.path, "2").exists());
+
+        // A corrupted journal falls back to the previous one.
+        std::fs::write(&config.path, b"corrupted").unwrap();
+        assert_eq!(read_latest_journal(&config).unwrap().len(), 1);
+
+        // The entries beyond the size limit are not written.
+        config.max_size = 1;
+        assert_eq!(write_journal_file(&config, &entries).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_replay_journal() {
+        let dir = tempdir::TempDir::new("txpool_journal").unwrap();
+        let journal_config = TxPoolJournalConfig {
+            path: dir.path().join("journal.rlp"),
+            journal_all: true,
+            interval: Duration::from_secs(1),
+            max_size: 1 << 20,
+            rotations: 1,
+        };
+
+        let senders: Vec<_> =
+            (0..2).map(|_| Random.generate().unwrap()).collect();
+        let genesis_accounts: HashMap<_, _> = senders
+            .iter()
+            .map(|sender| {
+                (sender.address().with_native_space(), U256::exp10(18))
+            })
+            .collect();
+        let vm = VmFactory::new(1024 * 32);
+        let pow = Arc::new(PowComputer::new(true));
+        let (data_man, _) = initialize_data_manager_with_genesis_accounts(
+            dir.path().join("db").to_str().unwrap(),
+            DataManagerConfiguration::new(
+                false,
+                false,
+                Duration::from_millis(300_000),
+                DbType::Rocksdb,
+            ),
+            pow.clone(),
+            vm.clone(),
+            genesis_accounts,
+        );
+        let (_, consensus) = initialize_synchronization_graph_with_data_manager(
+            data_man.clone(),
+            1,
+            1,
+            1,
+            1,
+            50000,
+            pow,
+            vm,
+        );
+        let new_pool = || {
+            let pool = TransactionPool::new(
+                TxPoolConfig {
+                    journal: Some(journal_config.clone()),
+                    ..Default::default()
+                },
+                consensus.get_tx_pool().verification_config.clone(),
+                data_man.clone(),
+                consensus.get_tx_pool().machine(),
+                Notifications::init(),
+            );
+            pool.notify_new_best_info(consensus.best_info()).unwrap();
+            pool
+        };
+
+        let chain_id = consensus.best_chain_id().in_native_space();
+        let local_tx = new_test_tx_from(&senders[0], 0, chain_id);
+        let remote_tx = new_test_tx_from(&senders[1], 0, chain_id);
+        {
+            // Nothing to replay, but the journal is written from now on.
+            let pool = new_pool();
+            pool.replay_journal();
+            let local = vec![local_tx.transaction.clone()];
+            assert_eq!(pool.insert_new_local_transactions(local).0.len(), 1);
+            let remote = vec![remote_tx.transaction.clone()];
+            assert_eq!(pool.insert_new_transactions(remote).0.len(), 1);
+            // The journal is written when the pool is dropped.
+        }
+        assert_eq!(read_journal_file(&journal_config.path).unwrap().len(), 2);
+
+        let pool = new_pool();
+        assert!(pool.get_transaction(&local_tx.hash()).is_none());
+        pool.replay_journal();
+        assert!(pool.get_transaction(&local_tx.hash()).is_some());
+        assert!(pool.get_transaction(&remote_tx.hash()).is_some());
+        let inner = pool.inner.read();
+        assert!(inner.is_local_transaction(&local_tx.hash()));
+        assert!(!inner.is_local_transaction(&remote_tx.hash()));
+    }
+}
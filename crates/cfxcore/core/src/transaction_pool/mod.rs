@@ -5,22 +5,28 @@
 mod account_cache;
 mod error;
 mod garbage_collector;
+mod journal;
 mod nonce_pool;
 mod transaction_pool_inner;
 
-pub use self::transaction_pool_inner::TransactionStatus;
+pub use self::{
+    journal::TxPoolJournalConfig, transaction_pool_inner::TransactionStatus,
+};
 use crate::{
     block_data_manager::BlockDataManager,
+    channel::Channel,
     consensus::BestInformation,
     transaction_pool::{
         nonce_pool::TxWithReadyInfo, transaction_pool_inner::PendingReason,
     },
     verification::{VerificationConfig, VerifyTxLocalMode, VerifyTxMode},
+    Notifications,
 };
 use account_cache::AccountCache;
 use cfx_executor::{
     machine::Machine, spec::TransitionsEpochHeight, state::State,
 };
+pub use cfx_packing_pool::PackingStrategyKind;
 use cfx_parameters::{
     block::{
         cspace_block_gas_limit_after_cip1559, espace_block_gas_limit,
@@ -36,6 +42,7 @@ use cfx_types::{
 };
 use cfx_vm_types::Spec;
 pub use error::TransactionPoolError;
+use journal::TxPoolJournal;
 use malloc_size_of::{MallocSizeOf, MallocSizeOfOps};
 use metrics::{
     register_meter_with_group, Gauge, GaugeUsize, Lock, Meter, MeterTimer,
@@ -49,7 +56,7 @@ use primitives::{
 };
 use std::{
     cmp::{max, min},
-    collections::{hash_map::HashMap, BTreeSet},
+    collections::{hash_map::HashMap, BTreeSet, HashSet},
     mem,
     ops::DerefMut,
     sync::{
@@ -102,6 +109,15 @@ pub struct TxPoolConfig {
     pub max_packing_batch_gas_limit: u64,
     pub max_packing_batch_size: usize,
     pub packing_pool_degree: u8,
+    pub packing_strategy: PackingStrategyKind,
+    pub journal: Option<TxPoolJournalConfig>,
+    /// The transactions sent from these addresses are treated as local, like
+    /// the ones submitted through the local RPC. Local transactions are not
+    /// garbage collected before executed and skip the minimum gas price.
+    pub local_addresses: HashSet<Address>,
+    /// The number of epochs a privately submitted transaction stays in the
+    /// pool.
+    pub private_tx_lifetime_epochs: u64,
 }
 
 impl MallocSizeOf for TxPoolConfig {
@@ -121,44 +137,25 @@ impl Default for TxPoolConfig {
             max_packing_batch_size: 20,
             max_packing_batch_gas_limit: DEFAULT_TARGET_BLOCK_GAS_LIMIT / 10,
             packing_pool_degree: 4,
+            packing_strategy: PackingStrategyKind::default(),
             target_block_gas_limit: DEFAULT_TARGET_BLOCK_GAS_LIMIT,
+            journal: None,
+            local_addresses: HashSet::new(),
+            private_tx_lifetime_epochs: 100,
         }
     }
 }
 
 impl TxPoolConfig {
+    pub fn is_local_address(&self, address: &Address) -> bool {
+        self.local_addresses.contains(address)
+    }
+
+    /// The minimum gas price is not checked for the local transactions.
     pub fn check_gas_price_and_limit(
-        &self, tx: &TransactionWithSignature,
+        &self, tx: &TransactionWithSignature, local: bool,
     ) -> Result<(), TransactionPoolError> {
-        // If the actual block gas limit is less than the miners' preference,
-        // the miner chooses the actual limit to ensure compatibility with other
-        // nodes. If the actual block gas limit exceeds the miners'
-        // preference, the miner adheres to their own settings since this does
-        // not result in incompatibility with others.
-        let half_block_gas_limit = std::cmp::min(
-            *self.half_block_gas_limit.read(),
-            U256::from(self.target_block_gas_limit),
-        );
-
-        // The current implementation is designed for after the activation of
-        // CIP-1559. However, it is also compatible with the system before
-        // CIP-1559 was activated, although there are some minor behavioral
-        // differences.
-        let block_gas_target = half_block_gas_limit;
-
-        let min_tx_price = match tx.space() {
-            Space::Native => self.min_native_tx_price,
-            Space::Ethereum => self.min_eth_tx_price,
-        };
-
-        let space_gas_target: U256 = match tx.space() {
-            Space::Native => {
-                cspace_block_gas_limit_after_cip1559(block_gas_target)
-            }
-            Space::Ethereum => {
-                espace_block_gas_limit_of_enabled_block(block_gas_target)
-            }
-        };
+        let space_gas_target = self.space_gas_target(tx.space());
 
         let space_gas_limit = space_gas_target * 2;
         let max_tx_gas = if self.allow_gas_over_half_block {
@@ -168,7 +165,6 @@ impl TxPoolConfig {
         };
 
         let tx_gas = *tx.gas();
-        let tx_gas_price = *tx.gas_price();
 
         if tx_gas > max_tx_gas {
             warn!(
@@ -182,9 +178,25 @@ impl TxPoolConfig {
             });
         }
 
+        if local {
+            Ok(())
+        } else {
+            self.check_min_gas_price(tx)
+        }
+    }
+
+    pub fn check_min_gas_price(
+        &self, tx: &TransactionWithSignature,
+    ) -> Result<(), TransactionPoolError> {
+        let min_tx_price = match tx.space() {
+            Space::Native => self.min_native_tx_price,
+            Space::Ethereum => self.min_eth_tx_price,
+        };
+        let tx_gas_price = *tx.gas_price();
+
         let minimum_price = compute_next_price(
-            space_gas_target,
-            tx_gas,
+            self.space_gas_target(tx.space()),
+            *tx.gas(),
             min_tx_price.into(),
             min_tx_price.into(),
         );
@@ -200,6 +212,33 @@ impl TxPoolConfig {
 
         Ok(())
     }
+
+    fn space_gas_target(&self, space: Space) -> U256 {
+        // If the actual block gas limit is less than the miners' preference,
+        // the miner chooses the actual limit to ensure compatibility with other
+        // nodes. If the actual block gas limit exceeds the miners'
+        // preference, the miner adheres to their own settings since this does
+        // not result in incompatibility with others.
+        let half_block_gas_limit = std::cmp::min(
+            *self.half_block_gas_limit.read(),
+            U256::from(self.target_block_gas_limit),
+        );
+
+        // The current implementation is designed for after the activation of
+        // CIP-1559. However, it is also compatible with the system before
+        // CIP-1559 was activated, although there are some minor behavioral
+        // differences.
+        let block_gas_target = half_block_gas_limit;
+
+        match space {
+            Space::Native => {
+                cspace_block_gas_limit_after_cip1559(block_gas_target)
+            }
+            Space::Ethereum => {
+                espace_block_gas_limit_of_enabled_block(block_gas_target)
+            }
+        }
+    }
 }
 
 pub struct TransactionPool {
@@ -214,6 +253,11 @@ pub struct TransactionPool {
     recycle_tx_requests: Mutex<Vec<Arc<SignedTransaction>>>,
     machine: Arc<Machine>,
 
+    /// Channel used to send the newly inserted transactions to PubSub.
+    new_pending_transactions: Arc<Channel<Vec<Arc<SignedTransaction>>>>,
+
+    journal: Option<TxPoolJournal>,
+
     /// If it's `false`, operations on the tx pool will be ignored to save
     /// memory/CPU cost.
     ready_for_mining: AtomicBool,
@@ -246,6 +290,7 @@ impl TransactionPool {
     pub fn new(
         config: TxPoolConfig, verification_config: VerificationConfig,
         data_man: Arc<BlockDataManager>, machine: Arc<Machine>,
+        notifications: Arc<Notifications>,
     ) -> Self {
         let genesis_hash = data_man.true_genesis.hash();
         let inner = TransactionPoolInner::new(
@@ -253,6 +298,7 @@ impl TransactionPool {
             config.max_packing_batch_gas_limit as usize,
             config.max_packing_batch_size,
             config.packing_pool_degree,
+            config.packing_strategy,
         );
         let best_executed_state = Mutex::new(
             Self::best_executed_state(
@@ -264,6 +310,7 @@ impl TransactionPool {
             )
             .expect("The genesis state is guaranteed to exist."),
         );
+        let journal = config.journal.clone().map(TxPoolJournal::new);
         TransactionPool {
             config,
             verification_config,
@@ -275,6 +322,10 @@ impl TransactionPool {
             set_tx_requests: Mutex::new(Default::default()),
             recycle_tx_requests: Mutex::new(Default::default()),
             machine,
+            new_pending_transactions: notifications
+                .new_pending_transactions
+                .clone(),
+            journal,
             ready_for_mining: AtomicBool::new(false),
         }
     }
@@ -422,7 +473,41 @@ impl TransactionPool {
     /// cannot be inserted to the tx pool, it will be included in the returned
     /// `failure` and will not be propagated.
     pub fn insert_new_transactions(
-        &self, mut transactions: Vec<TransactionWithSignature>,
+        &self, transactions: Vec<TransactionWithSignature>,
+    ) -> (
+        Vec<Arc<SignedTransaction>>,
+        HashMap<H256, TransactionPoolError>,
+    ) {
+        self.insert_transactions(transactions, false, false)
+    }
+
+    /// Inserts the transactions submitted through the local RPC, which are
+    /// treated as local transactions.
+    pub fn insert_new_local_transactions(
+        &self, transactions: Vec<TransactionWithSignature>,
+    ) -> (
+        Vec<Arc<SignedTransaction>>,
+        HashMap<H256, TransactionPoolError>,
+    ) {
+        self.insert_transactions(transactions, true, false)
+    }
+
+    /// Inserts the transactions submitted privately through the local RPC.
+    /// They are local transactions which are never propagated, and are only
+    /// packed by the local block generator. They are removed from the pool if
+    /// not executed in `private_tx_lifetime_epochs` epochs.
+    pub fn insert_new_private_transactions(
+        &self, transactions: Vec<TransactionWithSignature>,
+    ) -> (
+        Vec<Arc<SignedTransaction>>,
+        HashMap<H256, TransactionPoolError>,
+    ) {
+        self.insert_transactions(transactions, true, true)
+    }
+
+    fn insert_transactions(
+        &self, mut transactions: Vec<TransactionWithSignature>, local: bool,
+        private: bool,
     ) -> (
         Vec<Arc<SignedTransaction>>,
         HashMap<H256, TransactionPoolError>,
@@ -447,6 +532,11 @@ impl TransactionPool {
         // height check, it may also become valid and invalid back and forth.
         let vm_spec = self.machine.spec(best_block_number, best_height);
         let transitions = &self.machine.params().transition_heights;
+        // The senders are unknown before the public keys are recovered, so the
+        // minimum gas price is checked after that if some addresses are
+        // local.
+        let check_price_later =
+            !local && !self.config.local_addresses.is_empty();
 
         // filter out invalid transactions.
         let mut index = 0;
@@ -458,6 +548,7 @@ impl TransactionPool {
                 best_height,
                 transitions,
                 &vm_spec,
+                local || check_price_later,
             ) {
                 Ok(_) => index += 1,
                 Err(e) => {
@@ -490,6 +581,17 @@ impl TransactionPool {
                         continue;
                     }
 
+                    let local =
+                        local || self.config.is_local_address(&tx.sender());
+                    if check_price_later && !local {
+                        if let Err(e) =
+                            self.config.check_min_gas_price(&tx.transaction)
+                        {
+                            failure.insert(tx.hash(), e);
+                            continue;
+                        }
+                    }
+
                     if let Err(e) = self.add_transaction_with_readiness_check(
                         &mut *inner,
                         &account_cache,
@@ -504,9 +606,18 @@ impl TransactionPool {
                         failure.insert(tx.hash(), e);
                         continue;
                     }
+                    if local {
+                        inner.mark_local_transaction(&tx.hash());
+                    }
 
                     passed_transactions.push(tx.clone());
-                    if to_prop.len() < inner.capacity() {
+                    if private {
+                        inner.mark_private_transaction(
+                            &tx.hash(),
+                            best_height
+                                + self.config.private_tx_lifetime_epochs,
+                        );
+                    } else if to_prop.len() < inner.capacity() {
                         to_prop.entry(tx.hash).or_insert(tx);
                     }
                 }
@@ -531,6 +642,7 @@ impl TransactionPool {
         INSERT_TXS_SUCCESS_TPS.mark(passed_transactions.len());
         INSERT_TXS_FAILURE_TPS.mark(failure.len());
 
+        self.notify_new_pending_transactions(&passed_transactions);
         (passed_transactions, failure)
     }
 
@@ -577,6 +689,7 @@ impl TransactionPool {
                 best_height,
                 transitions,
                 &vm_spec,
+                self.config.is_local_address(&tx.sender()),
             ) {
                 Ok(_) => index += 1,
                 Err(e) => {
@@ -630,6 +743,9 @@ impl TransactionPool {
                     failure.insert(tx.hash(), e);
                     continue;
                 }
+                if self.config.is_local_address(&tx.sender()) {
+                    inner.mark_local_transaction(&tx.hash());
+                }
                 passed_transactions.push(tx.clone());
                 if !to_prop.contains_key(&tx.hash) {
                     to_prop.insert(tx.hash, tx);
@@ -645,15 +761,28 @@ impl TransactionPool {
         INSERT_TXS_SUCCESS_TPS.mark(passed_transactions.len());
         INSERT_TXS_FAILURE_TPS.mark(failure.len());
 
+        self.notify_new_pending_transactions(&passed_transactions);
         (passed_transactions, failure)
     }
 
+    fn notify_new_pending_transactions(
+        &self, transactions: &Vec<Arc<SignedTransaction>>,
+    ) {
+        if !transactions.is_empty()
+            && self.new_pending_transactions.num_subscriptions() > 0
+        {
+            self.new_pending_transactions.send(transactions.clone());
+        }
+    }
+
     /// verify transactions based on the rules that have nothing to do with
     /// readiness
+    /// The minimum gas price is not checked if `skip_min_gas_price` is set.
     fn verify_transaction_tx_pool(
         &self, transaction: &TransactionWithSignature, basic_check: bool,
         chain_id: AllChainID, best_height: u64,
         transitions: &TransitionsEpochHeight, spec: &Spec,
+        skip_min_gas_price: bool,
     ) -> Result<(), TransactionPoolError> {
         let _timer = MeterTimer::time_func(TX_POOL_VERIFY_TIMER.as_ref());
         let mode = VerifyTxMode::Local(VerifyTxLocalMode::MaybeLater, spec);
@@ -680,7 +809,8 @@ impl TransactionPool {
         // best info is initialized here.
 
         // check transaction gas limit
-        self.config.check_gas_price_and_limit(transaction)?;
+        self.config
+            .check_gas_price_and_limit(transaction, skip_min_gas_price)?;
 
         Ok(())
     }
@@ -916,6 +1046,27 @@ impl TransactionPool {
         )
     }
 
+    pub fn is_local_transaction(&self, tx_hash: &H256) -> bool {
+        self.inner.read().is_local_transaction(tx_hash)
+    }
+
+    pub fn is_private_transaction(&self, tx_hash: &H256) -> bool {
+        self.inner.read().is_private_transaction(tx_hash)
+    }
+
+    pub fn total_private(&self) -> usize { self.inner.read().total_private() }
+
+    /// Returns the local transactions in the pool, of `address` if given.
+    pub fn local_transactions(
+        &self, address: Option<Address>,
+    ) -> Vec<Arc<SignedTransaction>> {
+        let mut txs = self.inner.read().local_transactions();
+        if let Some(address) = address {
+            txs.retain(|tx| tx.sender() == address);
+        }
+        txs
+    }
+
     /// content retrieves the ready and deferred transactions.
     pub fn content(
         &self, address: Option<Address>,
@@ -940,6 +1091,7 @@ impl TransactionPool {
         let account_cache = self.get_best_state_account_cache();
         let mut inner = self.inner.write_with_metric(&NOTIFY_BEST_INFO_LOCK);
         let inner = inner.deref_mut();
+        inner.remove_expired_private_transactions(best_info.best_epoch_number);
 
         while let Some(tx) = set_tx_buffer.pop() {
             let tx_hash = tx.hash();
@@ -982,6 +1134,7 @@ impl TransactionPool {
                 best_height,
                 transitions,
                 &vm_spec,
+                self.config.is_local_address(&tx.sender()),
             ) {
                 warn!(
                     "Recycled transaction {:?} discarded due to not passing verification {}.",
@@ -7,7 +7,9 @@ use super::{
 
 use crate::verification::{PackingCheckResult, VerificationConfig};
 use cfx_executor::machine::Machine;
-use cfx_packing_pool::{PackingPool, PackingPoolConfig};
+use cfx_packing_pool::{
+    PackingPool, PackingPoolConfig, PackingStrategy, PackingStrategyKind,
+};
 use cfx_parameters::{
     block::cspace_block_gas_limit_after_cip1559,
     consensus_internal::ELASTICITY_MULTIPLIER,
@@ -32,7 +34,7 @@ use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
 use rlp::*;
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -79,16 +81,22 @@ struct DeferredPool {
     /// Store transactions that are ready to be packed for each address, and
     /// implements random sampling logic.
     packing_pool: SpaceMap<PackingPool<Arc<SignedTransaction>>>,
+    /// Decides the order in which the transactions in `packing_pool` are
+    /// packed.
+    packing_strategy: PackingStrategyKind,
 }
 
 impl DeferredPool {
-    fn new(config: PackingPoolConfig) -> Self {
+    fn new(
+        config: PackingPoolConfig, packing_strategy: PackingStrategyKind,
+    ) -> Self {
         DeferredPool {
             buckets: Default::default(),
             packing_pool: SpaceMap::new(
                 PackingPool::new(config),
                 PackingPool::new(config),
             ),
+            packing_strategy,
         }
     }
 
@@ -101,6 +109,7 @@ impl DeferredPool {
                 PackingPool::new(config),
                 PackingPool::new(config),
             ),
+            packing_strategy: PackingStrategyKind::default(),
         }
     }
 
@@ -167,11 +176,21 @@ impl DeferredPool {
         let mut rest_size_limit = block_size_limit;
         let mut rest_gas_limit = block_gas_limit;
 
-        'all: for (_, sender_txs, _) in self
-            .packing_pool
-            .in_space_mut(space)
-            .tx_sampler(&mut rng, block_gas_limit.into())
-        {
+        // A packing strategy may offer a sender again with its following
+        // transactions, which can only be packed after all the previous ones.
+        let mut stopped_senders = HashSet::new();
+
+        let packing_strategy = self.packing_strategy;
+        'all: for (sender, sender_txs, _) in packing_strategy.packing_order(
+            self.packing_pool.in_space(space),
+            &mut rng,
+            block_gas_limit,
+            tx_min_price,
+        ) {
+            if stopped_senders.contains(&sender) {
+                continue;
+            }
+            let packed_before = to_pack_txs.len();
             'sender: for tx in sender_txs.iter() {
                 if tx.gas_price() < &tx_min_price {
                     break 'sender;
@@ -216,6 +235,9 @@ impl DeferredPool {
                     break 'all;
                 }
             }
+            if to_pack_txs.len() - packed_before < sender_txs.len() {
+                stopped_senders.insert(sender);
+            }
         }
 
         // Maybe we can remove to drop txs from deferred pool. But removing them
@@ -312,6 +334,25 @@ impl DeferredPool {
         ret
     }
 
+    /// Removes a transaction. The ready transactions of the sender from its
+    /// nonce are removed from the packing pool, so the readiness of the sender
+    /// must be recalculated.
+    fn remove(
+        &mut self, addr: &AddressWithSpace, nonce: &U256,
+    ) -> Option<TxWithReadyInfo> {
+        let bucket = self.buckets.get_mut(addr)?;
+        let ret = bucket.remove(nonce);
+        if bucket.is_empty() {
+            self.buckets.remove(addr);
+            self.packing_pool.in_space_mut(addr.space).remove(*addr);
+        } else {
+            self.packing_pool
+                .in_space_mut(addr.space)
+                .split_off_suffix(*addr, nonce);
+        }
+        ret
+    }
+
     #[inline]
     fn get_lowest_nonce(&self, addr: &AddressWithSpace) -> Option<&U256> {
         Some(self.get_lowest_nonce_tx(addr)?.nonce())
@@ -494,6 +535,12 @@ impl DeferredPool {
 pub struct TransactionSet {
     inner: HashMap<H256, Arc<SignedTransaction>>,
     count: SpaceMap<usize>,
+    /// The transactions submitted through the local RPC or sent from the
+    /// local addresses, which are not garbage collected while unexecuted.
+    local: HashSet<H256>,
+    /// The privately submitted transactions, which are never propagated, with
+    /// the epochs after which they are removed.
+    private: HashMap<H256, u64>,
 }
 
 impl TransactionSet {
@@ -524,12 +571,34 @@ impl TransactionSet {
         if let Some(ref tx) = res {
             *self.count.in_space_mut(tx.space()) -= 1;
         }
+        self.local.remove(tx_hash);
+        self.private.remove(tx_hash);
         res
     }
 
+    fn mark_local(&mut self, tx_hash: &H256) {
+        if self.inner.contains_key(tx_hash) {
+            self.local.insert(*tx_hash);
+        }
+    }
+
+    fn is_local(&self, tx_hash: &H256) -> bool { self.local.contains(tx_hash) }
+
+    fn mark_private(&mut self, tx_hash: &H256, expire_epoch: u64) {
+        if self.inner.contains_key(tx_hash) {
+            self.private.insert(*tx_hash, expire_epoch);
+        }
+    }
+
+    fn is_private(&self, tx_hash: &H256) -> bool {
+        self.private.contains_key(tx_hash)
+    }
+
     fn clear(&mut self) {
         self.inner.clear();
         self.count.apply_all(|x| *x = 0);
+        self.local.clear();
+        self.private.clear();
     }
 }
 
@@ -556,6 +625,7 @@ impl TransactionPoolInner {
     pub fn new(
         capacity: usize, max_packing_batch_gas_limit: usize,
         max_packing_batch_size: usize, packing_pool_degree: u8,
+        packing_strategy: PackingStrategyKind,
     ) -> Self {
         let config = PackingPoolConfig::new(
             max_packing_batch_gas_limit.into(),
@@ -566,7 +636,7 @@ impl TransactionPoolInner {
             capacity,
             total_received_count: 0,
             unpacked_transaction_count: 0,
-            deferred_pool: DeferredPool::new(config),
+            deferred_pool: DeferredPool::new(config, packing_strategy),
             ready_nonces_and_balances: HashMap::new(),
             garbage_collector: SpaceMap::default(),
             txs: TransactionSet::default(),
@@ -574,7 +644,9 @@ impl TransactionPoolInner {
     }
 
     #[cfg(test)]
-    pub fn new_for_test() -> Self { Self::new(50_000, 3_000_000, 50, 4) }
+    pub fn new_for_test() -> Self {
+        Self::new(50_000, 3_000_000, 50, 4, PackingStrategyKind::default())
+    }
 
     pub fn clear(&mut self) {
         self.deferred_pool.clear();
@@ -617,6 +689,76 @@ impl TransactionPoolInner {
         self.txs.get(tx_hash).map(|x| x.clone())
     }
 
+    /// Marks a transaction in the pool as local, so it is not garbage
+    /// collected before executed.
+    pub fn mark_local_transaction(&mut self, tx_hash: &H256) {
+        self.txs.mark_local(tx_hash);
+    }
+
+    pub fn is_local_transaction(&self, tx_hash: &H256) -> bool {
+        self.txs.is_local(tx_hash)
+    }
+
+    pub fn local_transactions(&self) -> Vec<Arc<SignedTransaction>> {
+        self.txs
+            .local
+            .iter()
+            .filter_map(|hash| self.txs.get(hash).cloned())
+            .collect()
+    }
+
+    /// Marks a local transaction in the pool as private, so it is never
+    /// propagated and is removed after `expire_epoch`.
+    pub fn mark_private_transaction(
+        &mut self, tx_hash: &H256, expire_epoch: u64,
+    ) {
+        self.txs.mark_private(tx_hash, expire_epoch);
+    }
+
+    pub fn is_private_transaction(&self, tx_hash: &H256) -> bool {
+        self.txs.is_private(tx_hash)
+    }
+
+    pub fn total_private(&self) -> usize { self.txs.private.len() }
+
+    /// Removes the private transactions expired before `best_epoch`. The
+    /// following transactions of the same senders are kept, but they are not
+    /// ready until the nonce gaps are filled.
+    pub fn remove_expired_private_transactions(&mut self, best_epoch: u64) {
+        let expired: Vec<H256> = self
+            .txs
+            .private
+            .iter()
+            .filter(|(_, expire_epoch)| **expire_epoch < best_epoch)
+            .map(|(hash, _)| *hash)
+            .collect();
+        for hash in expired {
+            let tx = match self.txs.get(&hash) {
+                Some(tx) => tx.clone(),
+                None => continue,
+            };
+            let sender = tx.sender();
+            if let Some(removed) =
+                self.deferred_pool.remove(&sender, tx.nonce())
+            {
+                if !removed.is_already_packed() {
+                    self.unpacked_transaction_count =
+                        self.unpacked_transaction_count.saturating_sub(1);
+                }
+            }
+            self.txs.remove(&hash);
+            if self.deferred_pool.contain_address(&sender) {
+                let (nonce, balance) = self
+                    .get_local_nonce_and_balance(&sender)
+                    .unwrap_or((0.into(), 0.into()));
+                self.recalculate_readiness(&sender, nonce, balance);
+            } else {
+                self.ready_nonces_and_balances.remove(&sender);
+            }
+            debug!("private tx {:?} expired", hash);
+        }
+    }
+
     pub fn get_by_address2nonce(
         &self, address: AddressWithSpace, nonce: U256,
     ) -> Option<Arc<SignedTransaction>> {
@@ -647,7 +789,7 @@ impl TransactionPoolInner {
     pub fn collect_garbage(&mut self, new_tx: &SignedTransaction) {
         let space = new_tx.space();
         let count_before_gc = self.total_deferred(Some(space));
-        let mut skipped_self_node = None;
+        let mut skipped_nodes = Vec::new();
         while self.is_full(space)
             && !self.garbage_collector.in_space(space).is_empty()
         {
@@ -665,15 +807,20 @@ impl TransactionPoolInner {
             // no unconditional garbage collection to conduct and we need to
             // check if we should replace one unexecuted tx.
             if victim.count == 0 {
-                if *victim_address == new_tx.sender() {
+                let is_local = self
+                    .deferred_pool
+                    .get_lowest_nonce_tx(victim_address)
+                    .map_or(false, |tx| self.txs.is_local(&tx.hash()));
+                if *victim_address == new_tx.sender() || is_local {
                     // We do not GC a not-executed transaction from the same
-                    // sender, so save it and try another account.
+                    // sender or a local one, so save it and try another
+                    // account.
                     let (victim_address, victim) = self
                         .garbage_collector
                         .in_space_mut(space)
                         .pop()
                         .unwrap();
-                    skipped_self_node = Some((victim_address, victim));
+                    skipped_nodes.push((victim_address, victim));
                     continue;
                 } else if victim.has_ready_tx
                     && victim.first_tx_gas_price >= *new_tx.gas_price()
@@ -755,7 +902,7 @@ impl TransactionPoolInner {
 
         // Insert back skipped nodes to keep `garbage_collector`
         // unchanged.
-        if let Some((addr, node)) = skipped_self_node {
+        for (addr, node) in skipped_nodes {
             self.garbage_collector.in_space_mut(space).insert(
                 &addr,
                 node.count,
@@ -850,7 +997,11 @@ impl TransactionPoolInner {
                             0
                         });
                 }
-                self.txs.remove(&replaced_tx.hash());
+                // A recycled or packed transaction replaces itself, and keeps
+                // its local and private marks.
+                if replaced_tx.hash() != transaction.hash() {
+                    self.txs.remove(&replaced_tx.hash());
+                }
                 self.txs.insert(transaction.hash(), transaction.clone());
                 if !packed {
                     self.unpacked_transaction_count += 1;
@@ -1507,7 +1658,8 @@ mod tests {
     };
 
     use super::{
-        DeferredPool, InsertResult, TransactionPoolInner, TxWithReadyInfo,
+        DeferredPool, InsertResult, PackingStrategyKind, TransactionPoolInner,
+        TxWithReadyInfo,
     };
     use crate::keylib::{Generator, KeyPair, Random};
     use cfx_executor::{
@@ -1848,6 +2000,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_local_transactions_not_garbage_collected() {
+        let mut pool = TransactionPoolInner::new(
+            2,
+            3_000_000,
+            50,
+            4,
+            PackingStrategyKind::default(),
+        );
+        let local_sender = Random.generate().unwrap();
+        let remote_sender = Random.generate().unwrap();
+
+        // Without the exemption, the local transaction with the lowest gas
+        // price would be the victim.
+        let local_tx =
+            new_test_tx(&local_sender, 0, 10, 21000, 0, Space::Native);
+        let remote_tx =
+            new_test_tx(&remote_sender, 0, 20, 21000, 0, Space::Native);
+        pool.insert_transaction_for_test(local_tx.clone(), U256::zero());
+        pool.insert_transaction_for_test(remote_tx.clone(), U256::zero());
+        pool.mark_local_transaction(&local_tx.hash());
+        assert!(pool.is_local_transaction(&local_tx.hash()));
+
+        let new_tx = new_test_tx(
+            &Random.generate().unwrap(),
+            0,
+            30,
+            21000,
+            0,
+            Space::Native,
+        );
+        assert!(matches!(
+            pool.insert_transaction_for_test(new_tx.clone(), U256::zero()),
+            InsertResult::NewAdded
+        ));
+        assert!(pool.get(&local_tx.hash()).is_some());
+        assert!(pool.get(&remote_tx.hash()).is_none());
+        assert!(pool.get(&new_tx.hash()).is_some());
+
+        // A pool full of local transactions rejects the new ones.
+        pool.mark_local_transaction(&new_tx.hash());
+        let another_tx = new_test_tx(
+            &Random.generate().unwrap(),
+            0,
+            40,
+            21000,
+            0,
+            Space::Native,
+        );
+        assert!(matches!(
+            pool.insert_transaction_for_test(another_tx, U256::zero()),
+            InsertResult::Failed(_)
+        ));
+        assert_eq!(pool.local_transactions().len(), 2);
+    }
+
+    #[test]
+    fn test_remove_expired_private_transactions() {
+        let mut pool = TransactionPoolInner::new_for_test();
+        let sender = Random.generate().unwrap();
+        let txs: Vec<_> = (0..3)
+            .map(|nonce| {
+                new_test_tx(&sender, nonce, 10, 21000, 0, Space::Native)
+            })
+            .collect();
+        for tx in &txs {
+            pool.insert_transaction_for_test(tx.clone(), U256::zero());
+        }
+        pool.mark_local_transaction(&txs[1].hash());
+        pool.mark_private_transaction(&txs[1].hash(), 10);
+        assert!(pool.is_private_transaction(&txs[1].hash()));
+        assert_eq!(pool.total_private(), 1);
+
+        pool.remove_expired_private_transactions(10);
+        assert_eq!(pool.total_deferred(None), 3);
+
+        // The following transaction is kept but no longer ready.
+        pool.remove_expired_private_transactions(11);
+        assert_eq!(pool.total_deferred(None), 2);
+        assert_eq!(pool.total_private(), 0);
+        assert!(pool.get(&txs[1].hash()).is_none());
+        assert!(!pool.is_local_transaction(&txs[1].hash()));
+        let sender = txs[0].sender();
+        let ready: Vec<_> = pool
+            .deferred_pool
+            .ready_transactions_by_address(sender)
+            .map_or(vec![], |txs| txs.to_vec());
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].hash(), txs[0].hash());
+    }
+
+    #[test]
+    fn test_recycled_private_transaction_stays_private() {
+        let mut pool = TransactionPoolInner::new_for_test();
+        let sender = Random.generate().unwrap();
+        let tx = new_test_tx(&sender, 0, 10, 21000, 0, Space::Native);
+        pool.insert_transaction_for_test(tx.clone(), U256::zero());
+        pool.mark_local_transaction(&tx.hash());
+        pool.mark_private_transaction(&tx.hash(), 10);
+
+        // The transactions of a reverted block are re-inserted with `force`.
+        assert!(matches!(
+            pool.insert_transaction_for_test(tx.clone(), U256::zero()),
+            InsertResult::Updated(_)
+        ));
+        assert!(pool.is_private_transaction(&tx.hash()));
+        assert!(pool.is_local_transaction(&tx.hash()));
+        assert_eq!(pool.total_deferred(None), 1);
+
+        // A replacement with another transaction drops the marks.
+        let replacement = new_test_tx(&sender, 0, 20, 21000, 0, Space::Native);
+        assert!(matches!(
+            pool.insert_transaction_for_test(replacement.clone(), U256::zero()),
+            InsertResult::Updated(_)
+        ));
+        assert!(!pool.is_private_transaction(&tx.hash()));
+        assert!(!pool.is_private_transaction(&replacement.hash()));
+        assert_eq!(pool.total_private(), 0);
+        assert_eq!(pool.total_deferred(None), 1);
+    }
+
     #[test]
     fn test_pack_eip1559_transactions() {
         let mut pool = TransactionPoolInner::new_for_test();
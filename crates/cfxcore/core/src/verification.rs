@@ -39,6 +39,7 @@ use unexpected::{Mismatch, OutOfBounds};
 #[derive(Clone)]
 pub struct VerificationConfig {
     pub verify_timestamp: bool,
+    pub verify_pow_hash: bool,
     pub referee_bound: usize,
     pub max_block_size_in_bytes: usize,
     pub transaction_epoch_bound: u64,
@@ -223,7 +224,8 @@ impl VerificationConfig {
     pub fn new(
         test_mode: bool, referee_bound: usize, max_block_size_in_bytes: usize,
         transaction_epoch_bound: u64, tx_pool_nonce_bits: usize,
-        machine: Arc<Machine>, pos_verifier: Arc<PosVerifier>,
+        verify_pow_hash: bool, machine: Arc<Machine>,
+        pos_verifier: Arc<PosVerifier>,
     ) -> Self {
         let max_nonce = if tx_pool_nonce_bits < 256 {
             Some((U256::one() << tx_pool_nonce_bits) - 1)
@@ -233,6 +235,7 @@ impl VerificationConfig {
         if test_mode {
             VerificationConfig {
                 verify_timestamp: false,
+                verify_pow_hash,
                 referee_bound,
                 max_block_size_in_bytes,
                 transaction_epoch_bound,
@@ -243,6 +246,7 @@ impl VerificationConfig {
         } else {
             VerificationConfig {
                 verify_timestamp: true,
+                verify_pow_hash,
                 referee_bound,
                 max_block_size_in_bytes,
                 transaction_epoch_bound,
@@ -405,8 +409,11 @@ impl VerificationConfig {
             }
         }
 
-        // verify POW
-        self.verify_pow(pow, header)?;
+        // verify POW, unless the headers come from a trusted source and their
+        // `pow_hash` is already filled.
+        if self.verify_pow_hash || header.pow_hash.is_none() {
+            self.verify_pow(pow, header)?;
+        }
 
         // A block will be invalid if it has more than REFEREE_BOUND referees
         if header.referee_hashes().len() > self.referee_bound {
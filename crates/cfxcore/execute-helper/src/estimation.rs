@@ -9,7 +9,10 @@ use cfx_executor::{
 use solidity_abi::string_revert_reason_decode;
 
 use super::observer::{
-    exec_tracer::ErrorUnwind, gasman::GasLimitEstimation, Observer,
+    access_list::{exclude_precompiles, AccessListKey},
+    exec_tracer::ErrorUnwind,
+    gasman::GasLimitEstimation,
+    Observer,
 };
 use cfx_parameters::{consensus::ONE_CFX_IN_DRIP, staking::*};
 use cfx_statedb::Result as DbResult;
@@ -17,7 +20,9 @@ use cfx_types::{
     address_util::AddressUtil, Address, AddressSpaceUtil, Space, U256,
 };
 use cfx_vm_types::{self as vm, Env, Spec};
-use primitives::{transaction::Action, SignedTransaction, Transaction};
+use primitives::{
+    transaction::Action, AccessList, SignedTransaction, Transaction,
+};
 use std::{
     cmp::{max, min},
     fmt::Display,
@@ -33,6 +38,11 @@ enum SponsoredType {
 pub struct EstimateExt {
     pub estimated_gas_limit: U256,
     pub estimated_storage_limit: u64,
+    /// Addresses and storage slots touched by the transaction, excluding the
+    /// precompiles, and the sender and the recipient unless their storage is
+    /// accessed. Only collected if requested by
+    /// `EstimateRequest::collect_access_list`.
+    pub access_list: Option<AccessList>,
 }
 
 pub struct EstimationContext<'a> {
@@ -156,6 +166,7 @@ impl<'a> EstimationContext<'a> {
                                 executed, &tx,
                             ),
                             estimated_storage_limit: storage_limit(executed),
+                            access_list: self.access_list(executed, &tx),
                         }
                     }
                     ExecutionOutcome::Finished(_) => unreachable!(),
@@ -172,6 +183,53 @@ impl<'a> EstimationContext<'a> {
         )
     }
 
+    /// Executes a transaction of a bundle and keeps its state changes, so
+    /// that the following transactions of the bundle observe them. Unlike
+    /// `transact_virtual`, the gas limit is not estimated.
+    ///
+    /// With `validation`, the sender, the nonce, the balance and the base fee
+    /// are checked like executing a real transaction. A transaction which is
+    /// not executed leaves the state untouched.
+    pub fn transact_virtual_in_bundle(
+        &mut self, mut tx: SignedTransaction, request: EstimateRequest,
+        validation: bool,
+    ) -> DbResult<ExecutionOutcome> {
+        if let Some((outcome, _)) = self.check_cip130(&tx, &request) {
+            return Ok(outcome);
+        }
+
+        let saved = self.state.save();
+        let settings = if validation {
+            if !request.has_nonce {
+                *tx.nonce_mut() = self.state.nonce(&tx.sender())?;
+            }
+            TransactSettings {
+                charge_collateral: ChargeCollateral::EstimateSender,
+                charge_gas: true,
+                check_epoch_bound: false,
+                check_base_price: true,
+            }
+        } else {
+            self.process_estimate_request(&mut tx, &request)?;
+            request.transact_settings(ChargeCollateral::EstimateSender)
+        };
+        let options = TransactOptions {
+            observer: Observer::virtual_call(),
+            settings,
+        };
+
+        let outcome = self.as_executive().transact(&tx, options)?;
+        match outcome.try_as_executed() {
+            Some(executed) => {
+                if let Some(burnt_fee) = executed.burnt_fee {
+                    self.state.burn_by_cip1559(burnt_fee);
+                }
+            }
+            None => self.state.restore(saved),
+        }
+        Ok(outcome)
+    }
+
     fn check_cip130(
         &self, tx: &SignedTransaction, request: &EstimateRequest,
     ) -> Option<(ExecutionOutcome, EstimateExt)> {
@@ -290,6 +348,19 @@ impl<'a> EstimationContext<'a> {
         ))
     }
 
+    fn access_list(
+        &self, executed: &Executed, tx: &SignedTransaction,
+    ) -> Option<AccessList> {
+        let builtins = match tx.space() {
+            Space::Native => self.machine.builtins(),
+            Space::Ethereum => self.machine.builtins_evm(),
+        };
+        let access_list = executed.ext_result.get::<AccessListKey>()?;
+        Some(exclude_precompiles(access_list, |address| {
+            builtins.contains_key(address)
+        }))
+    }
+
     fn enact_executed_by_estimation_request(
         &self, tx: SignedTransaction, mut executed: Executed,
         overwrite_storage_limit: Option<u64>, request: &EstimateRequest,
@@ -300,6 +371,7 @@ impl<'a> EstimationContext<'a> {
         let estimation = EstimateExt {
             estimated_storage_limit,
             estimated_gas_limit,
+            access_list: self.access_list(&executed, &tx),
         };
 
         let gas_sponsored_contract_if_eligible_sender = self
@@ -451,6 +523,7 @@ pub struct EstimateRequest {
     pub has_gas_price: bool,
     pub has_nonce: bool,
     pub has_storage_limit: bool,
+    pub collect_access_list: bool,
 }
 
 impl EstimateRequest {
@@ -471,16 +544,24 @@ impl EstimateRequest {
         }
     }
 
+    fn observer(self) -> Observer {
+        if self.collect_access_list {
+            Observer::virtual_call_with_access_list()
+        } else {
+            Observer::virtual_call()
+        }
+    }
+
     fn first_pass_options(self) -> TransactOptions<Observer> {
         TransactOptions {
-            observer: Observer::virtual_call(),
+            observer: self.observer(),
             settings: self.transact_settings(ChargeCollateral::EstimateSender),
         }
     }
 
     pub fn second_pass_options(self) -> TransactOptions<Observer> {
         TransactOptions {
-            observer: Observer::virtual_call(),
+            observer: self.observer(),
             settings: self.transact_settings(ChargeCollateral::EstimateSponsor),
         }
     }
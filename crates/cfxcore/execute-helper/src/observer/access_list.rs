@@ -0,0 +1,308 @@
+use cfx_executor::observer::{
+    CallTracer, CheckpointTracer, DrainTrace, InternalTransferTracer,
+    OpcodeTracer, StorageTracer,
+};
+use cfx_types::{Address, BigEndianHash, H256, U256};
+use cfx_vm_types::{ActionParams, InterpreterInfo};
+use primitives::{AccessList, AccessListItem};
+use revm::interpreter::opcode;
+use std::collections::{BTreeMap, BTreeSet};
+use typemap::ShareDebugMap;
+
+/// Records the addresses and storage slots touched during execution, which
+/// make up an EIP-2930 access list.
+///
+/// Slots accessed in reverted frames are kept, since they are still loaded
+/// when the transaction is executed.
+#[derive(Default)]
+pub struct AccessListTracer {
+    touched: BTreeMap<Address, BTreeSet<H256>>,
+    /// The sender and the recipient of the transaction, which are warm
+    /// before the execution starts.
+    sender: Option<Address>,
+    recipient: Option<Address>,
+}
+
+impl AccessListTracer {
+    fn record_frame(&mut self, params: &ActionParams) {
+        if self.recipient.is_none() {
+            self.sender = Some(params.sender);
+            self.recipient = Some(params.address);
+        }
+    }
+
+    fn touch_address(&mut self, address: Address) {
+        self.touched.entry(address).or_default();
+    }
+
+    fn touch_slot(&mut self, address: Address, key: &U256) {
+        self.touched
+            .entry(address)
+            .or_default()
+            .insert(H256::from_uint(key));
+    }
+
+    /// The sender and the recipient are only kept if their storage slots are
+    /// accessed.
+    pub fn drain(self) -> AccessList {
+        let warm = [self.sender, self.recipient];
+        self.touched
+            .into_iter()
+            .filter(|(address, keys)| {
+                !keys.is_empty() || !warm.contains(&Some(*address))
+            })
+            .map(|(address, keys)| AccessListItem {
+                address,
+                storage_keys: keys.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+impl DrainTrace for AccessListTracer {
+    fn drain_trace(self, map: &mut ShareDebugMap) {
+        map.insert::<AccessListKey>(self.drain());
+    }
+}
+
+/// The access list of an execution, which still includes the precompiles.
+pub struct AccessListKey;
+
+impl typemap::Key for AccessListKey {
+    type Value = AccessList;
+}
+
+/// Remove the precompiles from an access list, which are always warm.
+pub fn exclude_precompiles(
+    access_list: &AccessList, is_precompile: impl Fn(&Address) -> bool,
+) -> AccessList {
+    access_list
+        .iter()
+        .filter(|item| !is_precompile(&item.address))
+        .cloned()
+        .collect()
+}
+
+impl CallTracer for AccessListTracer {
+    fn record_call(&mut self, params: &ActionParams) {
+        self.record_frame(params);
+        self.touch_address(params.address);
+        self.touch_address(params.code_address);
+    }
+
+    fn record_create(&mut self, params: &ActionParams) {
+        self.record_frame(params);
+        self.touch_address(params.address);
+    }
+}
+
+impl OpcodeTracer for AccessListTracer {
+    fn do_trace_opcode(&self, enabled: &mut bool) { *enabled |= true; }
+
+    fn step(&mut self, interp: &dyn InterpreterInfo) {
+        let stack = interp.stack();
+        // The top of the stack is the last element.
+        let peek = |n: usize| stack.len().checked_sub(n + 1).map(|i| stack[i]);
+
+        match interp.current_opcode() {
+            opcode::SLOAD | opcode::SSTORE => {
+                if let Some(key) = peek(0) {
+                    self.touch_slot(interp.contract_address(), &key);
+                }
+            }
+            opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::EXTCODESIZE
+            | opcode::BALANCE
+            | opcode::SELFDESTRUCT => {
+                if let Some(address) = peek(0) {
+                    self.touch_address(Address::from(H256::from_uint(
+                        &address,
+                    )));
+                }
+            }
+            opcode::CALL
+            | opcode::CALLCODE
+            | opcode::DELEGATECALL
+            | opcode::STATICCALL => {
+                if let Some(address) = peek(1) {
+                    self.touch_address(Address::from(H256::from_uint(
+                        &address,
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl CheckpointTracer for AccessListTracer {}
+impl InternalTransferTracer for AccessListTracer {}
+impl StorageTracer for AccessListTracer {}
+
+#[cfg(test)]
+mod tests {
+    use super::{exclude_precompiles, AccessListTracer};
+    use cfx_executor::observer::{CallTracer, OpcodeTracer};
+    use cfx_types::{Address, BigEndianHash, H256, U256};
+    use cfx_vm_types::{ActionParams, InterpreterInfo};
+    use primitives::AccessListItem;
+    use revm::interpreter::opcode;
+
+    struct Step {
+        opcode: u8,
+        stack: Vec<U256>,
+        address: Address,
+    }
+
+    impl InterpreterInfo for Step {
+        fn gas_remainning(&self) -> U256 { U256::zero() }
+
+        fn program_counter(&self) -> u64 { 0 }
+
+        fn current_opcode(&self) -> u8 { self.opcode }
+
+        fn opcode(&self, _pc: u64) -> Option<u8> { Some(self.opcode) }
+
+        fn mem(&self) -> &Vec<u8> { unimplemented!() }
+
+        fn stack(&self) -> &Vec<U256> { &self.stack }
+
+        fn return_stack(&self) -> &Vec<usize> { unimplemented!() }
+
+        fn contract_address(&self) -> Address { self.address }
+    }
+
+    fn address(n: u64) -> Address { Address::from_low_u64_be(n) }
+
+    fn word(address: Address) -> U256 { H256::from(address).into_uint() }
+
+    fn call_params(sender: Address, address: Address) -> ActionParams {
+        ActionParams {
+            sender,
+            address,
+            code_address: address,
+            ..Default::default()
+        }
+    }
+
+    fn step(
+        tracer: &mut AccessListTracer, opcode: u8, address: Address,
+        stack: Vec<U256>,
+    ) {
+        tracer.step(&Step {
+            opcode,
+            stack,
+            address,
+        });
+    }
+
+    #[test]
+    fn test_collect_slots_and_call_targets() {
+        let (sender, contract) = (address(0x1000), address(0x2000));
+        let (callee, balance_of) = (address(0x3000), address(0x4000));
+
+        let mut tracer = AccessListTracer::default();
+        tracer.record_call(&call_params(sender, contract));
+
+        // the key is on the top of the stack
+        step(
+            &mut tracer,
+            opcode::SLOAD,
+            contract,
+            vec![U256::from(9), 1.into()],
+        );
+        step(
+            &mut tracer,
+            opcode::SSTORE,
+            contract,
+            vec![3.into(), 2.into()],
+        );
+        // the callee is the second item on the top of the stack
+        step(
+            &mut tracer,
+            opcode::STATICCALL,
+            contract,
+            vec![0.into(), word(callee), 10000.into()],
+        );
+        step(
+            &mut tracer,
+            opcode::BALANCE,
+            contract,
+            vec![word(balance_of)],
+        );
+
+        // the callee accesses its own storage
+        tracer.record_call(&call_params(contract, callee));
+        step(&mut tracer, opcode::SLOAD, callee, vec![5.into()]);
+
+        assert_eq!(
+            tracer.drain(),
+            vec![
+                AccessListItem {
+                    address: contract,
+                    storage_keys: vec![
+                        H256::from_low_u64_be(1),
+                        H256::from_low_u64_be(2)
+                    ],
+                },
+                AccessListItem {
+                    address: callee,
+                    storage_keys: vec![H256::from_low_u64_be(5)],
+                },
+                AccessListItem {
+                    address: balance_of,
+                    storage_keys: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exclude_warm_addresses() {
+        let (sender, recipient) = (address(0x1000), address(0x2000));
+        let (other, precompile) = (address(0x3000), address(0x1));
+
+        // the recipient without storage access, the sender and the
+        // precompiles are excluded
+        let mut tracer = AccessListTracer::default();
+        tracer.record_call(&call_params(sender, recipient));
+        step(&mut tracer, opcode::BALANCE, recipient, vec![word(sender)]);
+        step(
+            &mut tracer,
+            opcode::CALL,
+            recipient,
+            vec![0.into(), word(precompile), 10000.into()],
+        );
+        tracer.record_call(&call_params(recipient, precompile));
+        step(
+            &mut tracer,
+            opcode::EXTCODESIZE,
+            recipient,
+            vec![word(other)],
+        );
+
+        let access_list =
+            exclude_precompiles(&tracer.drain(), |a| *a == precompile);
+        assert_eq!(
+            access_list,
+            vec![AccessListItem {
+                address: other,
+                storage_keys: vec![],
+            }]
+        );
+
+        // the recipient is kept if its storage is accessed
+        let mut tracer = AccessListTracer::default();
+        tracer.record_call(&call_params(sender, recipient));
+        step(&mut tracer, opcode::SLOAD, recipient, vec![7.into()]);
+
+        assert_eq!(
+            tracer.drain(),
+            vec![AccessListItem {
+                address: recipient,
+                storage_keys: vec![H256::from_low_u64_be(7)],
+            }]
+        );
+    }
+}
@@ -1,7 +1,9 @@
+pub mod access_list;
 pub mod exec_tracer;
 pub mod gasman;
 mod utils;
 
+use access_list::AccessListTracer;
 use exec_tracer::ExecTracer;
 use gasman::GasMan;
 
@@ -20,6 +22,7 @@ pub struct Observer {
     pub tracer: Option<ExecTracer>,
     pub gas_man: Option<GasMan>,
     pub geth_tracer: Option<GethTracer>,
+    pub access_list: Option<AccessListTracer>,
 }
 
 impl Observer {
@@ -28,6 +31,7 @@ impl Observer {
             tracer: Some(ExecTracer::default()),
             gas_man: None,
             geth_tracer: None,
+            access_list: None,
         }
     }
 
@@ -36,6 +40,7 @@ impl Observer {
             tracer: None,
             gas_man: None,
             geth_tracer: None,
+            access_list: None,
         }
     }
 
@@ -44,6 +49,16 @@ impl Observer {
             tracer: Some(ExecTracer::default()),
             gas_man: Some(GasMan::default()),
             geth_tracer: None,
+            access_list: None,
+        }
+    }
+
+    pub fn virtual_call_with_access_list() -> Self {
+        Observer {
+            tracer: Some(ExecTracer::default()),
+            gas_man: Some(GasMan::default()),
+            geth_tracer: None,
+            access_list: Some(AccessListTracer::default()),
         }
     }
 
@@ -55,6 +70,7 @@ impl Observer {
             tracer: None,
             gas_man: None,
             geth_tracer: Some(GethTracer::new(tx_exec_context, machine, opts)),
+            access_list: None,
         }
     }
 }
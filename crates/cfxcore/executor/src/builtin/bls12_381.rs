@@ -0,0 +1,434 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! BLS12-381 curve operations of EIP-2537, built on `blst`.
+//!
+//! A base field element is encoded as 64 bytes, i.e., its 48-byte big endian
+//! representation left padded with zeros. An element of the quadratic
+//! extension is encoded as `c0 ++ c1`. A point is encoded as `x ++ y`, and the
+//! point at infinity is encoded as all zeros.
+
+use blst::{
+    blst_bendian_from_fp, blst_final_exp, blst_fp, blst_fp12, blst_fp12_is_one,
+    blst_fp12_mul, blst_fp12_one, blst_fp2, blst_fp_from_bendian,
+    blst_map_to_g1, blst_map_to_g2, blst_miller_loop, blst_p1,
+    blst_p1_add_or_double, blst_p1_affine, blst_p1_affine_in_g1,
+    blst_p1_affine_is_inf, blst_p1_affine_on_curve, blst_p1_from_affine,
+    blst_p1_to_affine, blst_p1s_mult_pippenger,
+    blst_p1s_mult_pippenger_scratch_sizeof, blst_p2, blst_p2_add_or_double,
+    blst_p2_affine, blst_p2_affine_in_g2, blst_p2_affine_is_inf,
+    blst_p2_affine_on_curve, blst_p2_from_affine, blst_p2_to_affine,
+    blst_p2s_mult_pippenger, blst_p2s_mult_pippenger_scratch_sizeof,
+    blst_scalar, blst_scalar_from_bendian, limb_t,
+};
+use hex_literal::hex;
+use std::{mem::size_of, ptr};
+
+use super::Error;
+
+const FP_LENGTH: usize = 48;
+const PADDED_FP_LENGTH: usize = 64;
+const PADDED_FP2_LENGTH: usize = 2 * PADDED_FP_LENGTH;
+pub const G1_LENGTH: usize = 2 * PADDED_FP_LENGTH;
+pub const G2_LENGTH: usize = 2 * PADDED_FP2_LENGTH;
+const SCALAR_LENGTH: usize = 32;
+pub const G1_MSM_PAIR_LENGTH: usize = G1_LENGTH + SCALAR_LENGTH;
+pub const G2_MSM_PAIR_LENGTH: usize = G2_LENGTH + SCALAR_LENGTH;
+pub const PAIRING_PAIR_LENGTH: usize = G1_LENGTH + G2_LENGTH;
+
+/// The big endian representation of the base field modulus.
+const MODULUS: [u8; FP_LENGTH] = hex!(
+    "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab"
+);
+
+/// Adds two G1 points. The points are not required to be in the subgroup.
+pub fn g1_add(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.len() != 2 * G1_LENGTH {
+        return Err(Error("Invalid input length for G1 addition"));
+    }
+    let a = read_g1(&input[..G1_LENGTH], false)?;
+    let b = read_g1(&input[G1_LENGTH..], false)?;
+
+    let mut sum = blst_p1::default();
+    unsafe {
+        let mut p = blst_p1::default();
+        blst_p1_from_affine(&mut p, &a);
+        let mut q = blst_p1::default();
+        blst_p1_from_affine(&mut q, &b);
+        blst_p1_add_or_double(&mut sum, &p, &q);
+    }
+    Ok(encode_g1(&sum))
+}
+
+/// Multi-scalar multiplication of G1 points in the subgroup, computed with
+/// the Pippenger algorithm which the gas discount is priced for.
+pub fn g1_msm(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.is_empty() || input.len() % G1_MSM_PAIR_LENGTH != 0 {
+        return Err(Error("Invalid input length for G1 MSM"));
+    }
+
+    let pairs = input.len() / G1_MSM_PAIR_LENGTH;
+    let mut points = Vec::with_capacity(pairs);
+    let mut scalars = Vec::with_capacity(pairs * SCALAR_LENGTH);
+    for pair in input.chunks(G1_MSM_PAIR_LENGTH) {
+        let point = read_g1(&pair[..G1_LENGTH], true)?;
+        // The point at infinity contributes nothing to the sum.
+        if unsafe { blst_p1_affine_is_inf(&point) } {
+            continue;
+        }
+        points.push(point);
+        scalars.extend_from_slice(&read_scalar(&pair[G1_LENGTH..]).b);
+    }
+
+    let mut sum = blst_p1::default();
+    if !points.is_empty() {
+        unsafe {
+            let mut scratch = vec![
+                limb_t::default();
+                blst_p1s_mult_pippenger_scratch_sizeof(
+                    points.len()
+                ) / size_of::<limb_t>()
+            ];
+            // A null second pointer marks contiguous arrays.
+            let points_ptrs = [points.as_ptr(), ptr::null()];
+            let scalars_ptrs = [scalars.as_ptr(), ptr::null()];
+            blst_p1s_mult_pippenger(
+                &mut sum,
+                points_ptrs.as_ptr(),
+                points.len(),
+                scalars_ptrs.as_ptr(),
+                8 * SCALAR_LENGTH,
+                scratch.as_mut_ptr(),
+            );
+        }
+    }
+    Ok(encode_g1(&sum))
+}
+
+/// Adds two G2 points. The points are not required to be in the subgroup.
+pub fn g2_add(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.len() != 2 * G2_LENGTH {
+        return Err(Error("Invalid input length for G2 addition"));
+    }
+    let a = read_g2(&input[..G2_LENGTH], false)?;
+    let b = read_g2(&input[G2_LENGTH..], false)?;
+
+    let mut sum = blst_p2::default();
+    unsafe {
+        let mut p = blst_p2::default();
+        blst_p2_from_affine(&mut p, &a);
+        let mut q = blst_p2::default();
+        blst_p2_from_affine(&mut q, &b);
+        blst_p2_add_or_double(&mut sum, &p, &q);
+    }
+    Ok(encode_g2(&sum))
+}
+
+/// Multi-scalar multiplication of G2 points in the subgroup, computed with
+/// the Pippenger algorithm.
+pub fn g2_msm(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.is_empty() || input.len() % G2_MSM_PAIR_LENGTH != 0 {
+        return Err(Error("Invalid input length for G2 MSM"));
+    }
+
+    let pairs = input.len() / G2_MSM_PAIR_LENGTH;
+    let mut points = Vec::with_capacity(pairs);
+    let mut scalars = Vec::with_capacity(pairs * SCALAR_LENGTH);
+    for pair in input.chunks(G2_MSM_PAIR_LENGTH) {
+        let point = read_g2(&pair[..G2_LENGTH], true)?;
+        if unsafe { blst_p2_affine_is_inf(&point) } {
+            continue;
+        }
+        points.push(point);
+        scalars.extend_from_slice(&read_scalar(&pair[G2_LENGTH..]).b);
+    }
+
+    let mut sum = blst_p2::default();
+    if !points.is_empty() {
+        unsafe {
+            let mut scratch = vec![
+                limb_t::default();
+                blst_p2s_mult_pippenger_scratch_sizeof(
+                    points.len()
+                ) / size_of::<limb_t>()
+            ];
+            let points_ptrs = [points.as_ptr(), ptr::null()];
+            let scalars_ptrs = [scalars.as_ptr(), ptr::null()];
+            blst_p2s_mult_pippenger(
+                &mut sum,
+                points_ptrs.as_ptr(),
+                points.len(),
+                scalars_ptrs.as_ptr(),
+                8 * SCALAR_LENGTH,
+                scratch.as_mut_ptr(),
+            );
+        }
+    }
+    Ok(encode_g2(&sum))
+}
+
+/// Checks whether the product of the pairings of the (G1, G2) pairs is the
+/// identity. Returns 1 as a 32-byte word if it is, otherwise 0.
+pub fn pairing(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.is_empty() || input.len() % PAIRING_PAIR_LENGTH != 0 {
+        return Err(Error("Invalid input length for pairing"));
+    }
+
+    let mut acc: blst_fp12 = unsafe { *blst_fp12_one() };
+    for pair in input.chunks(PAIRING_PAIR_LENGTH) {
+        let p = read_g1(&pair[..G1_LENGTH], true)?;
+        let q = read_g2(&pair[G1_LENGTH..], true)?;
+        // The pairing with the point at infinity is the identity.
+        if unsafe { blst_p1_affine_is_inf(&p) || blst_p2_affine_is_inf(&q) } {
+            continue;
+        }
+        unsafe {
+            let mut loop_result = *blst_fp12_one();
+            blst_miller_loop(&mut loop_result, &q, &p);
+            let prev = acc;
+            blst_fp12_mul(&mut acc, &prev, &loop_result);
+        }
+    }
+
+    let mut result = acc;
+    unsafe { blst_final_exp(&mut result, &acc) };
+    let mut output = vec![0u8; 32];
+    if unsafe { blst_fp12_is_one(&result) } {
+        output[31] = 1;
+    }
+    Ok(output)
+}
+
+/// Maps a base field element to a G1 point in the subgroup.
+pub fn map_fp_to_g1(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.len() != PADDED_FP_LENGTH {
+        return Err(Error("Invalid input length for mapping to G1"));
+    }
+    let u = read_fp(input)?;
+
+    let mut p = blst_p1::default();
+    unsafe { blst_map_to_g1(&mut p, &u, std::ptr::null()) };
+    Ok(encode_g1(&p))
+}
+
+/// Maps an element of the quadratic extension to a G2 point in the subgroup.
+pub fn map_fp2_to_g2(input: &[u8]) -> Result<Vec<u8>, Error> {
+    if input.len() != PADDED_FP2_LENGTH {
+        return Err(Error("Invalid input length for mapping to G2"));
+    }
+    let u = read_fp2(input)?;
+
+    let mut p = blst_p2::default();
+    unsafe { blst_map_to_g2(&mut p, &u, std::ptr::null()) };
+    Ok(encode_g2(&p))
+}
+
+fn read_fp(input: &[u8]) -> Result<blst_fp, Error> {
+    let (padding, repr) = input.split_at(PADDED_FP_LENGTH - FP_LENGTH);
+    if padding.iter().any(|b| *b != 0) {
+        return Err(Error("Invalid padding of field element"));
+    }
+    // Both are big endian of the same length.
+    if repr >= &MODULUS[..] {
+        return Err(Error("Field element is not less than the modulus"));
+    }
+
+    let mut fp = blst_fp::default();
+    unsafe { blst_fp_from_bendian(&mut fp, repr.as_ptr()) };
+    Ok(fp)
+}
+
+fn read_fp2(input: &[u8]) -> Result<blst_fp2, Error> {
+    Ok(blst_fp2 {
+        fp: [
+            read_fp(&input[..PADDED_FP_LENGTH])?,
+            read_fp(&input[PADDED_FP_LENGTH..])?,
+        ],
+    })
+}
+
+fn read_g1(
+    input: &[u8], subgroup_check: bool,
+) -> Result<blst_p1_affine, Error> {
+    let p = blst_p1_affine {
+        x: read_fp(&input[..PADDED_FP_LENGTH])?,
+        y: read_fp(&input[PADDED_FP_LENGTH..])?,
+    };
+    // Both checks pass for the point at infinity.
+    if unsafe { !blst_p1_affine_on_curve(&p) } {
+        return Err(Error("G1 point is not on the curve"));
+    }
+    if subgroup_check && unsafe { !blst_p1_affine_in_g1(&p) } {
+        return Err(Error("G1 point is not in the subgroup"));
+    }
+    Ok(p)
+}
+
+fn read_g2(
+    input: &[u8], subgroup_check: bool,
+) -> Result<blst_p2_affine, Error> {
+    let p = blst_p2_affine {
+        x: read_fp2(&input[..PADDED_FP2_LENGTH])?,
+        y: read_fp2(&input[PADDED_FP2_LENGTH..])?,
+    };
+    if unsafe { !blst_p2_affine_on_curve(&p) } {
+        return Err(Error("G2 point is not on the curve"));
+    }
+    if subgroup_check && unsafe { !blst_p2_affine_in_g2(&p) } {
+        return Err(Error("G2 point is not in the subgroup"));
+    }
+    Ok(p)
+}
+
+/// The scalar is not required to be less than the subgroup order.
+fn read_scalar(input: &[u8]) -> blst_scalar {
+    let mut scalar = blst_scalar::default();
+    unsafe { blst_scalar_from_bendian(&mut scalar, input.as_ptr()) };
+    scalar
+}
+
+fn write_fp(fp: &blst_fp, output: &mut [u8]) {
+    unsafe {
+        blst_bendian_from_fp(
+            output[PADDED_FP_LENGTH - FP_LENGTH..].as_mut_ptr(),
+            fp,
+        )
+    };
+}
+
+/// The point at infinity is converted to (0, 0) by `blst`.
+fn encode_g1(p: &blst_p1) -> Vec<u8> {
+    let mut affine = blst_p1_affine::default();
+    unsafe { blst_p1_to_affine(&mut affine, p) };
+
+    let mut output = vec![0u8; G1_LENGTH];
+    write_fp(&affine.x, &mut output[..PADDED_FP_LENGTH]);
+    write_fp(&affine.y, &mut output[PADDED_FP_LENGTH..]);
+    output
+}
+
+fn encode_g2(p: &blst_p2) -> Vec<u8> {
+    let mut affine = blst_p2_affine::default();
+    unsafe { blst_p2_to_affine(&mut affine, p) };
+
+    let mut output = vec![0u8; G2_LENGTH];
+    for (i, fp) in affine.x.fp.iter().chain(affine.y.fp.iter()).enumerate() {
+        write_fp(
+            fp,
+            &mut output[i * PADDED_FP_LENGTH..(i + 1) * PADDED_FP_LENGTH],
+        );
+    }
+    output
+}
+
+/// The discounts of G1 MSM in permille by the number of pairs, and the last
+/// one applies to any larger number.
+pub const G1_MSM_DISCOUNT_TABLE: [u16; 128] = [
+    1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682,
+    677, 673, 669, 665, 661, 658, 654, 651, 648, 645, 642, 640, 637, 635, 632,
+    630, 627, 625, 623, 621, 619, 617, 615, 613, 611, 609, 608, 606, 604, 603,
+    601, 599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585, 584, 582, 581,
+    580, 579, 577, 576, 575, 574, 573, 572, 570, 569, 568, 567, 566, 565, 564,
+    563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549,
+    548, 547, 547, 546, 545, 544, 543, 542, 541, 540, 540, 539, 538, 537, 536,
+    536, 535, 534, 533, 532, 532, 531, 530, 529, 528, 528, 527, 526, 525, 525,
+    524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// The discounts of G2 MSM in permille by the number of pairs.
+pub const G2_MSM_DISCOUNT_TABLE: [u16; 128] = [
+    1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732,
+    724, 717, 711, 704, 699, 693, 688, 683, 679, 674, 670, 666, 663, 659, 655,
+    652, 649, 646, 643, 640, 637, 634, 632, 629, 627, 624, 622, 620, 618, 615,
+    613, 611, 609, 607, 606, 604, 602, 600, 598, 597, 595, 593, 592, 590, 589,
+    587, 586, 584, 583, 582, 580, 579, 578, 576, 575, 574, 573, 571, 570, 569,
+    568, 567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553,
+    552, 552, 551, 550, 549, 548, 547, 546, 545, 545, 544, 543, 542, 541, 541,
+    540, 539, 538, 537, 537, 536, 535, 535, 534, 533, 532, 532, 531, 530, 530,
+    529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_hex::FromHex;
+    use serde::Deserialize;
+    use std::{fs, path::Path};
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Vector {
+        name: String,
+        input: String,
+        /// Absent for the failure cases.
+        expected: Option<String>,
+    }
+
+    /// Runs the test vectors of EIP-2537 in `test_data/eip2537`. The gas in
+    /// the vectors is not checked, as the prices have changed since.
+    fn run_vectors(file: &str, f: fn(&[u8]) -> Result<Vec<u8>, Error>) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data/eip2537")
+            .join(file);
+        let vectors: Vec<Vector> =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert!(!vectors.is_empty());
+
+        for vector in vectors {
+            let input: Vec<u8> = vector.input.from_hex().unwrap();
+            match vector.expected {
+                Some(expected) => assert_eq!(
+                    f(&input).expect(&vector.name),
+                    expected.from_hex::<Vec<u8>>().unwrap(),
+                    "{}",
+                    vector.name
+                ),
+                None => assert!(f(&input).is_err(), "{}", vector.name),
+            }
+        }
+    }
+
+    #[test]
+    fn test_g1_add() {
+        run_vectors("add_G1_bls.json", g1_add);
+        run_vectors("fail-add_G1_bls.json", g1_add);
+    }
+
+    #[test]
+    fn test_g1_msm() {
+        run_vectors("multiexp_G1_bls.json", g1_msm);
+        run_vectors("fail-multiexp_G1_bls.json", g1_msm);
+        // A multiplication is a multi-scalar multiplication of one pair.
+        run_vectors("mul_G1_bls.json", g1_msm);
+        run_vectors("fail-mul_G1_bls.json", g1_msm);
+    }
+
+    #[test]
+    fn test_g2_add() {
+        run_vectors("add_G2_bls.json", g2_add);
+        run_vectors("fail-add_G2_bls.json", g2_add);
+    }
+
+    #[test]
+    fn test_g2_msm() {
+        run_vectors("multiexp_G2_bls.json", g2_msm);
+        run_vectors("fail-multiexp_G2_bls.json", g2_msm);
+        run_vectors("mul_G2_bls.json", g2_msm);
+        run_vectors("fail-mul_G2_bls.json", g2_msm);
+    }
+
+    #[test]
+    fn test_pairing() {
+        run_vectors("pairing_check_bls.json", pairing);
+        run_vectors("fail-pairing_check_bls.json", pairing);
+    }
+
+    #[test]
+    fn test_map_to_curve() {
+        run_vectors("map_fp_to_G1_bls.json", map_fp_to_g1);
+        run_vectors("fail-map_fp_to_G1_bls.json", map_fp_to_g1);
+        run_vectors("map_fp2_to_G2_bls.json", map_fp2_to_g2);
+        run_vectors("fail-map_fp2_to_G2_bls.json", map_fp2_to_g2);
+    }
+}
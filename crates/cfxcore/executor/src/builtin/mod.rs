@@ -21,9 +21,11 @@
 //! Standard built-in contracts.
 
 mod blake2f;
+mod bls12_381;
 mod ethereum_trusted_setup_points;
 mod executable;
 mod kzg_point_evaluations;
+mod p256_verify;
 
 pub use executable::BuiltinExec;
 
@@ -225,6 +227,70 @@ impl Pricer for Blake2FPricer {
     }
 }
 
+/// Pricing for the BLS12-381 multi-scalar multiplications (EIP-2537): the cost
+/// of `k` multiplications is discounted by the k-th entry of the table in
+/// permille, and the last entry applies to any larger `k`.
+pub(crate) struct Bls12MsmPricer {
+    pair_length: usize,
+    multiplication_cost: u64,
+    discount_table: &'static [u16],
+}
+
+impl Bls12MsmPricer {
+    pub(crate) fn g1() -> Self {
+        Bls12MsmPricer {
+            pair_length: bls12_381::G1_MSM_PAIR_LENGTH,
+            multiplication_cost: 12_000,
+            discount_table: &bls12_381::G1_MSM_DISCOUNT_TABLE,
+        }
+    }
+
+    pub(crate) fn g2() -> Self {
+        Bls12MsmPricer {
+            pair_length: bls12_381::G2_MSM_PAIR_LENGTH,
+            multiplication_cost: 22_500,
+            discount_table: &bls12_381::G2_MSM_DISCOUNT_TABLE,
+        }
+    }
+}
+
+impl Pricer for Bls12MsmPricer {
+    fn cost(&self, input: &[u8]) -> U256 {
+        let k = input.len() / self.pair_length;
+        // `execute()` will bail on the empty input.
+        if k == 0 {
+            return U256::zero();
+        }
+        let discount =
+            self.discount_table[min(k, self.discount_table.len()) - 1];
+        U256::from(k)
+            * U256::from(self.multiplication_cost)
+            * U256::from(discount)
+            / U256::from(1000)
+    }
+}
+
+/// A BLS12-381 pairing pricing model. This computes a price using a base cost
+/// and a cost per pair.
+pub(crate) struct Bls12PairingPricer {
+    base: usize,
+    pair: usize,
+}
+
+impl Bls12PairingPricer {
+    pub(crate) fn new(base: usize, pair: usize) -> Self {
+        Bls12PairingPricer { base, pair }
+    }
+}
+
+impl Pricer for Bls12PairingPricer {
+    fn cost(&self, input: &[u8]) -> U256 {
+        U256::from(self.base)
+            + U256::from(self.pair)
+                * U256::from(input.len() / bls12_381::PAIRING_PAIR_LENGTH)
+    }
+}
+
 /// Pricing scheme, execution definition, and activation block for a built-in
 /// contract.
 ///
@@ -280,6 +346,14 @@ pub fn builtin_factory(name: &str) -> Box<dyn Impl> {
         "alt_bn128_pairing" => Box::new(Bn128PairingImpl) as Box<dyn Impl>,
         "blake2_f" => Box::new(Blake2FImpl) as Box<dyn Impl>,
         "kzg_point_eval" => Box::new(KzgPointEval) as Box<dyn Impl>,
+        "bls12_g1_add" => Box::new(Bls12G1AddImpl) as Box<dyn Impl>,
+        "bls12_g1_msm" => Box::new(Bls12G1MsmImpl) as Box<dyn Impl>,
+        "bls12_g2_add" => Box::new(Bls12G2AddImpl) as Box<dyn Impl>,
+        "bls12_g2_msm" => Box::new(Bls12G2MsmImpl) as Box<dyn Impl>,
+        "bls12_pairing" => Box::new(Bls12PairingImpl) as Box<dyn Impl>,
+        "bls12_map_fp_to_g1" => Box::new(Bls12MapFpToG1Impl) as Box<dyn Impl>,
+        "bls12_map_fp2_to_g2" => Box::new(Bls12MapFp2ToG2Impl) as Box<dyn Impl>,
+        "p256_verify" => Box::new(P256VerifyImpl) as Box<dyn Impl>,
         _ => panic!("invalid builtin name: {}", name),
     }
 }
@@ -332,6 +406,30 @@ struct Blake2FImpl;
 #[allow(dead_code)]
 struct KzgPointEval;
 
+#[derive(Debug)]
+struct Bls12G1AddImpl;
+
+#[derive(Debug)]
+struct Bls12G1MsmImpl;
+
+#[derive(Debug)]
+struct Bls12G2AddImpl;
+
+#[derive(Debug)]
+struct Bls12G2MsmImpl;
+
+#[derive(Debug)]
+struct Bls12PairingImpl;
+
+#[derive(Debug)]
+struct Bls12MapFpToG1Impl;
+
+#[derive(Debug)]
+struct Bls12MapFp2ToG2Impl;
+
+#[derive(Debug)]
+struct P256VerifyImpl;
+
 impl Impl for Identity {
     fn execute(
         &self, input: &[u8], output: &mut BytesRef,
@@ -787,11 +885,89 @@ impl Impl for KzgPointEval {
         Ok(())
     }
 }
+
+impl Impl for Bls12G1AddImpl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::g1_add(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for Bls12G1MsmImpl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::g1_msm(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for Bls12G2AddImpl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::g2_add(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for Bls12G2MsmImpl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::g2_msm(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for Bls12PairingImpl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::pairing(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for Bls12MapFpToG1Impl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::map_fp_to_g1(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for Bls12MapFp2ToG2Impl {
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        output.write(0, &bls12_381::map_fp2_to_g2(input)?);
+        Ok(())
+    }
+}
+
+impl Impl for P256VerifyImpl {
+    /// Writes 1 as a 32-byte word for a valid signature, and nothing
+    /// otherwise. An invalid signature is not an error.
+    fn execute(
+        &self, input: &[u8], output: &mut BytesRef,
+    ) -> Result<(), Error> {
+        if p256_verify::verify(input) {
+            let mut buf = [0u8; 32];
+            buf[31] = 1;
+            output.write(0, &buf);
+        }
+        Ok(())
+    }
+}
 #[cfg(test)]
 mod tests {
     use super::{
-        builtin_factory, modexp as me, Blake2FPricer, Builtin, Linear,
-        ModexpPricer, Pricer,
+        builtin_factory, modexp as me, Blake2FPricer, Bls12MsmPricer,
+        Bls12PairingPricer, Builtin, Linear, ModexpPricer, Pricer,
     };
     use cfx_bytes::BytesRef;
     use cfx_types::U256;
@@ -1350,6 +1526,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bls12_msm_cost() {
+        let g1 = Bls12MsmPricer::g1();
+        assert_eq!(g1.cost(&[]), U256::zero());
+        assert_eq!(g1.cost(&[0u8; 160]), U256::from(12_000));
+        assert_eq!(g1.cost(&[0u8; 320]), U256::from(2 * 12_000 * 949 / 1000));
+        // the discount is capped for more than 128 pairs
+        assert_eq!(
+            g1.cost(&vec![0u8; 160 * 200]),
+            U256::from(200 * 12_000 * 519 / 1000)
+        );
+
+        let g2 = Bls12MsmPricer::g2();
+        assert_eq!(g2.cost(&[0u8; 288]), U256::from(22_500));
+        assert_eq!(
+            g2.cost(&[0u8; 288 * 3]),
+            U256::from(3 * 22_500 * 923 / 1000)
+        );
+    }
+
+    #[test]
+    fn bls12_pairing_cost() {
+        let pricer = Bls12PairingPricer::new(37_700, 32_600);
+        assert_eq!(
+            pricer.cost(&[0u8; 384 * 2]),
+            U256::from(37_700 + 2 * 32_600)
+        );
+    }
+
+    #[test]
+    fn p256_verify() {
+        use super::p256_verify::tests::{INVALID, VALID};
+
+        let f = builtin_factory("p256_verify");
+        let mut expected = vec![0u8; 32];
+        expected[31] = 1;
+        for input in VALID {
+            let input: Vec<u8> = FromHex::from_hex(input).unwrap();
+            let mut output = Vec::new();
+            f.execute(&input[..], &mut BytesRef::Flexible(&mut output))
+                .expect("Builtin should not fail");
+            assert_eq!(output, expected);
+        }
+
+        // an invalid signature is not an error, but returns nothing
+        for input in INVALID {
+            let input: Vec<u8> = FromHex::from_hex(input).unwrap();
+            let mut output = Vec::new();
+            f.execute(&input[..], &mut BytesRef::Flexible(&mut output))
+                .expect("Builtin should not fail");
+            assert!(output.is_empty());
+        }
+    }
+
     #[test]
     fn blake2f_cost() {
         let f = blake2f_builtin();
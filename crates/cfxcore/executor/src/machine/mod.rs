@@ -7,8 +7,8 @@ mod vm_factory;
 use super::builtin::Builtin;
 use crate::{
     builtin::{
-        builtin_factory, AltBn128PairingPricer, Blake2FPricer, Linear,
-        ModexpPricer,
+        builtin_factory, AltBn128PairingPricer, Blake2FPricer, Bls12MsmPricer,
+        Bls12PairingPricer, Linear, ModexpPricer,
     },
     internal_contract::InternalContractMap,
     spec::CommonParams,
@@ -202,5 +202,69 @@ fn new_builtin_map(
             params.transition_numbers.cip144,
         ),
     );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(11)),
+        Builtin::new(
+            Box::new(Linear::new(375, 0)),
+            builtin_factory("bls12_g1_add"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(12)),
+        Builtin::new(
+            Box::new(Bls12MsmPricer::g1()),
+            builtin_factory("bls12_g1_msm"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(13)),
+        Builtin::new(
+            Box::new(Linear::new(600, 0)),
+            builtin_factory("bls12_g2_add"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(14)),
+        Builtin::new(
+            Box::new(Bls12MsmPricer::g2()),
+            builtin_factory("bls12_g2_msm"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(15)),
+        Builtin::new(
+            Box::new(Bls12PairingPricer::new(37_700, 32_600)),
+            builtin_factory("bls12_pairing"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(16)),
+        Builtin::new(
+            Box::new(Linear::new(5500, 0)),
+            builtin_factory("bls12_map_fp_to_g1"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(17)),
+        Builtin::new(
+            Box::new(Linear::new(23_800, 0)),
+            builtin_factory("bls12_map_fp2_to_g2"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
+    btree.insert(
+        Address::from(H256::from_low_u64_be(0x100)),
+        Builtin::new(
+            Box::new(Linear::new(3450, 0)),
+            builtin_factory("p256_verify"),
+            params.transition_numbers.crypto_builtins,
+        ),
+    );
     btree
 }
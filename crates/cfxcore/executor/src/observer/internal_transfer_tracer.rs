@@ -6,7 +6,7 @@ use self::AddressPocket::*;
 use impl_tools::autoimpl;
 use impl_trait_for_tuples::impl_for_tuples;
 
-#[impl_for_tuples(3)]
+#[impl_for_tuples(4)]
 #[autoimpl(for<T: trait + ?Sized> &mut T)]
 #[allow(unused_variables)]
 /// This trait is used by executive to build traces.
@@ -115,6 +115,9 @@ pub struct TransitionsBlockNumber {
     pub cip144: BlockNumber,
     /// CIP-145: Fix Receipts upon `NotEnoughBalance` Error
     pub cip145: BlockNumber,
+    /// EIP-2537: Precompile for BLS12-381 Curve Operations
+    /// RIP-7212: Precompile for secp256r1 Curve Support
+    pub crypto_builtins: BlockNumber,
 }
 
 #[derive(Default, Debug, Clone)]
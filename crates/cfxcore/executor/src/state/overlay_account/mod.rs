@@ -30,6 +30,10 @@ mod ext_fields;
 /// utilized in checkpoint logic to create and manage account instances.
 mod factory;
 
+/// Implements functions of an `OverlayAccount` for overriding its fields and
+/// storage entries in virtual calls.
+mod overrides;
+
 /// Implements functions of an `OverlayAccount` related to the sponsor
 /// mechanism.
 mod sponsor;
@@ -0,0 +1,43 @@
+use cfx_bytes::Bytes;
+use cfx_types::U256;
+use primitives::StorageValue;
+
+use super::OverlayAccount;
+
+impl OverlayAccount {
+    pub fn override_balance(&mut self, balance: U256) {
+        self.balance = balance;
+    }
+
+    /// Replace the code of the account, the code owner is kept unchanged if
+    /// the account already has code.
+    pub fn override_code(&mut self, code: Bytes) {
+        let owner = self
+            .code
+            .as_ref()
+            .map_or(self.address.address, |info| info.owner);
+        self.init_code(code, owner);
+    }
+
+    /// Regard all the storage entries on db as deleted, so only the entries
+    /// overridden later are visible.
+    pub fn clear_storage_for_override(&mut self) {
+        self.storage_write_cache.write().clear();
+        self.storage_read_cache.write().clear();
+        self.pending_db_clear = true;
+    }
+
+    /// Put a storage entry into the read cache, as if it is loaded from db.
+    /// So it doesn't change the storage collateral.
+    pub fn override_storage(&mut self, key: Vec<u8>, value: U256) {
+        let owner = if self.should_have_owner(&key) && !value.is_zero() {
+            Some(self.address.address)
+        } else {
+            None
+        };
+        self.storage_write_cache.write().remove(&key);
+        self.storage_read_cache
+            .write()
+            .insert(key, StorageValue { owner, value });
+    }
+}
@@ -27,6 +27,9 @@ mod commit;
 /// Implements access functions global statistic variables of `State`.
 mod global_statistics;
 
+/// Implements functions for overriding accounts of `State` in virtual calls.
+mod overrides;
+
 /// Implements functions for the PoS rewarding of `State`.
 mod pos;
 
@@ -0,0 +1,43 @@
+use super::State;
+use cfx_bytes::Bytes;
+use cfx_statedb::Result as DbResult;
+use cfx_types::{AddressWithSpace, U256};
+
+/// Overrides of account fields and storage entries for virtual calls (e.g.
+/// the state override of `eth_call`). They only modify the in-memory cache,
+/// bypassing the collateral accounting, and must be applied before any
+/// checkpoint is made. A `State` with overrides must never be committed.
+impl State {
+    pub fn override_balance(
+        &mut self, address: &AddressWithSpace, balance: U256,
+    ) -> DbResult<()> {
+        self.write_account_or_new_lock(address)?
+            .override_balance(balance);
+        Ok(())
+    }
+
+    pub fn override_code(
+        &mut self, address: &AddressWithSpace, code: Bytes,
+    ) -> DbResult<()> {
+        self.write_account_or_new_lock(address)?.override_code(code);
+        Ok(())
+    }
+
+    /// Clear all the storage entries of an account, used to replace the whole
+    /// storage with `override_storage`.
+    pub fn clear_storage_for_override(
+        &mut self, address: &AddressWithSpace,
+    ) -> DbResult<()> {
+        self.write_account_or_new_lock(address)?
+            .clear_storage_for_override();
+        Ok(())
+    }
+
+    pub fn override_storage(
+        &mut self, address: &AddressWithSpace, key: Vec<u8>, value: U256,
+    ) -> DbResult<()> {
+        self.write_account_or_new_lock(address)?
+            .override_storage(key, value);
+        Ok(())
+    }
+}
@@ -1025,6 +1025,49 @@ fn create_contract_fail_previous_storage() {
     // TODO(69): checking ownership
 }
 
+#[test]
+fn override_account_and_storage() {
+    let mut state = get_state_for_genesis_write();
+    let mut a = Address::from_low_u64_be(1000);
+    a.set_contract_type_bits();
+    let a_s = a.with_native_space();
+    let k1 = u256_to_vec(&U256::from(1));
+    let k2 = u256_to_vec(&U256::from(2));
+
+    let mut substate = Substate::new();
+    state.new_contract_with_code(&a_s, U256::zero()).unwrap();
+    state
+        .set_storage(&a_s, k1.clone(), U256::from(1), a, &mut substate)
+        .unwrap();
+    state
+        .set_storage(&a_s, k2.clone(), U256::from(2), a, &mut substate)
+        .unwrap();
+    state
+        .commit_for_test(BigEndianHash::from_uint(&U256::from(1)))
+        .unwrap();
+    state = get_state_by_epoch_id(&BigEndianHash::from_uint(&U256::from(1)));
+
+    state.override_balance(&a_s, U256::from(100)).unwrap();
+    state.set_nonce(&a_s, &U256::from(5)).unwrap();
+    state.override_code(&a_s, vec![0x56, 0x78]).unwrap();
+    state
+        .override_storage(&a_s, k1.clone(), U256::from(10))
+        .unwrap();
+    assert_eq!(state.balance(&a_s).unwrap(), U256::from(100));
+    assert_eq!(state.nonce(&a_s).unwrap(), U256::from(5));
+    assert_eq!(state.code_hash(&a_s).unwrap(), keccak(vec![0x56, 0x78]));
+    assert_eq!(state.storage_at(&a_s, &k1).unwrap(), U256::from(10));
+    assert_eq!(state.storage_at(&a_s, &k2).unwrap(), U256::from(2));
+    assert_eq!(state.collateral_for_storage(&a).unwrap(), U256::zero());
+
+    state.clear_storage_for_override(&a_s).unwrap();
+    state
+        .override_storage(&a_s, k2.clone(), U256::from(20))
+        .unwrap();
+    assert_eq!(state.storage_at(&a_s, &k1).unwrap(), U256::zero());
+    assert_eq!(state.storage_at(&a_s, &k2).unwrap(), U256::from(20));
+}
+
 #[test]
 fn test_automatic_collateral_normal_account() {
     let mut state = get_state_for_genesis_write();
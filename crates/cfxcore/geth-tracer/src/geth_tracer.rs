@@ -1,6 +1,7 @@
 use crate::{
     config::TracingInspectorConfig,
     fourbyte::FourByteInspector,
+    native::{native_tracer, NativeTracer},
     tracing_inspector::TracingInspector,
     types::{LogCallOrder, TxExecContext},
     utils::{to_alloy_address, to_alloy_h256, to_alloy_u256},
@@ -8,8 +9,8 @@ use crate::{
 use alloy_primitives::{Address, Bytes, LogData};
 use alloy_rpc_types_trace::geth::{
     CallConfig, GethDebugBuiltInTracerType, GethDebugBuiltInTracerType::*,
-    GethDebugTracerType, GethDebugTracingOptions, GethTrace, NoopFrame,
-    PreStateConfig,
+    GethDebugTracerConfig, GethDebugTracerType, GethDebugTracingOptions,
+    GethTrace, MuxFrame, NoopFrame, PreStateConfig,
 };
 use cfx_executor::{
     machine::Machine,
@@ -42,6 +43,10 @@ pub struct GethTracer {
     opts: GethDebugTracingOptions,
     // gas stack, used to trace gas_spent in call_result/create_result
     pub gas_stack: Vec<u64>,
+    //
+    tx_exec_context: TxExecContext,
+    // tracers driven by the muxTracer, each one records the whole execution
+    mux_tracers: Vec<(GethDebugBuiltInTracerType, GethTracer)>,
 }
 
 impl GethTracer {
@@ -49,7 +54,7 @@ impl GethTracer {
         tx_exec_context: TxExecContext, machine: Arc<Machine>,
         opts: GethDebugTracingOptions,
     ) -> Self {
-        let TxExecContext { tx_gas_limit, .. } = tx_exec_context;
+        let tx_gas_limit = tx_exec_context.tx_gas_limit;
         let config = match opts.tracer {
             Some(GethDebugTracerType::BuiltInTracer(builtin_tracer)) => {
                 match builtin_tracer {
@@ -74,20 +79,58 @@ impl GethTracer {
                     }
                 }
             }
-            Some(GethDebugTracerType::JsTracer(_)) => {
-                TracingInspectorConfig::none()
+            Some(GethDebugTracerType::JsTracer(ref name)) => {
+                match native_tracer(name) {
+                    Some(tracer) => {
+                        tracer.inspector_config(&opts.tracer_config)
+                    }
+                    None => TracingInspectorConfig::none(),
+                }
             }
             None => TracingInspectorConfig::from_geth_config(&opts.config),
         };
 
+        let mux_tracers = match opts.tracer {
+            Some(GethDebugTracerType::BuiltInTracer(MuxTracer)) => opts
+                .tracer_config
+                .clone()
+                .into_mux_config()
+                .expect("should success")
+                .0
+                .into_iter()
+                .map(|(tracer_type, tracer_config)| {
+                    let opts = GethDebugTracingOptions {
+                        tracer: Some(GethDebugTracerType::BuiltInTracer(
+                            tracer_type,
+                        )),
+                        tracer_config: tracer_config.unwrap_or_default(),
+                        ..opts.clone()
+                    };
+                    let tracer = GethTracer::new(
+                        tx_exec_context.clone(),
+                        Arc::clone(&machine),
+                        opts,
+                    );
+                    (tracer_type, tracer)
+                })
+                .collect(),
+            _ => vec![],
+        };
+
         Self {
-            inner: TracingInspector::new(config, machine, tx_exec_context),
+            inner: TracingInspector::new(
+                config,
+                machine,
+                tx_exec_context.clone(),
+            ),
             fourbyte_inspector: FourByteInspector::new(),
             tx_gas_limit,
             depth: 0,
             gas_left: tx_gas_limit,
             opts,
             gas_stack: Vec::new(),
+            tx_exec_context,
+            mux_tracers,
         }
     }
 
@@ -98,7 +141,7 @@ impl GethTracer {
                     Some(builtin_tracer)
                 }
                 GethDebugTracerType::JsTracer(_) => {
-                    // not supported
+                    // not supported unless it is a native tracer
                     Some(NoopTracer)
                 }
             },
@@ -114,13 +157,34 @@ impl GethTracer {
         self.opts.tracer_config.clone().into_pre_state_config().ok()
     }
 
+    fn native_tracer(&self) -> Option<&'static dyn NativeTracer> {
+        match &self.opts.tracer {
+            Some(GethDebugTracerType::JsTracer(name)) => native_tracer(name),
+            _ => None,
+        }
+    }
+
     pub fn is_fourbyte_tracer(&self) -> bool {
         self.tracer_type() == Some(FourByteTracer)
     }
 
+    pub fn is_mux_tracer(&self) -> bool {
+        self.tracer_type() == Some(MuxTracer)
+    }
+
     pub fn gas_used(&self) -> u64 { self.tx_gas_limit - self.gas_left }
 
-    pub fn drain(self) -> GethTrace {
+    pub fn drain(self) -> Result<GethTrace, String> {
+        if let Some(tracer) = self.native_tracer() {
+            let trace = tracer.build_trace(
+                &self.inner.traces.arena,
+                &self.tx_exec_context,
+                self.gas_used(),
+                &self.opts.tracer_config,
+            )?;
+            return Ok(GethTrace::JS(trace));
+        }
+
         let trace = match self.tracer_type() {
             Some(t) => match t {
                 FourByteTracer => self.fourbyte_inspector.drain(),
@@ -146,9 +210,17 @@ impl GethTracer {
                         .unwrap();
                     GethTrace::PreStateTracer(frame)
                 }
-                NoopTracer | MuxTracer => {
-                    GethTrace::NoopTracer(NoopFrame::default())
+                MuxTracer => {
+                    let frame = self
+                        .mux_tracers
+                        .into_iter()
+                        .map(|(tracer_type, tracer)| {
+                            Ok((tracer_type, tracer.drain()?))
+                        })
+                        .collect::<Result<_, String>>()?;
+                    GethTrace::MuxTracer(MuxFrame(frame))
                 }
+                NoopTracer => GethTrace::NoopTracer(NoopFrame::default()),
             },
             None => {
                 let gas_used = self.gas_used();
@@ -167,20 +239,68 @@ impl GethTracer {
             }
         };
 
-        trace
+        Ok(trace)
+    }
+}
+
+/// Validates the tracing options before any transaction is executed, so
+/// that a `GethTracer` can be built from them.
+pub fn check_tracing_options(
+    opts: &GethDebugTracingOptions,
+) -> Result<(), String> {
+    match &opts.tracer {
+        Some(GethDebugTracerType::BuiltInTracer(MuxTracer)) => {
+            let mux_config = opts
+                .tracer_config
+                .clone()
+                .into_mux_config()
+                .map_err(|e| e.to_string())?;
+            for (tracer_type, tracer_config) in mux_config.0 {
+                if tracer_type == MuxTracer {
+                    return Err("nested muxTracer is not supported".into());
+                }
+                check_builtin_tracer_config(
+                    tracer_type,
+                    &tracer_config.unwrap_or_default(),
+                )?;
+            }
+            Ok(())
+        }
+        Some(GethDebugTracerType::BuiltInTracer(tracer_type)) => {
+            check_builtin_tracer_config(*tracer_type, &opts.tracer_config)
+        }
+        Some(GethDebugTracerType::JsTracer(name)) => {
+            match native_tracer(name) {
+                Some(tracer) => tracer.check_config(&opts.tracer_config),
+                None => Err("JS tracer is not supported".into()),
+            }
+        }
+        None => Ok(()),
     }
 }
 
+fn check_builtin_tracer_config(
+    tracer_type: GethDebugBuiltInTracerType, config: &GethDebugTracerConfig,
+) -> Result<(), String> {
+    let result = match tracer_type {
+        CallTracer => config.clone().into_call_config().map(|_| ()),
+        PreStateTracer => config.clone().into_pre_state_config().map(|_| ()),
+        FourByteTracer | NoopTracer | MuxTracer => Ok(()),
+    };
+    result.map_err(|e| e.to_string())
+}
+
 impl DrainTrace for GethTracer {
     fn drain_trace(self, map: &mut typemap::ShareDebugMap) {
         map.insert::<GethTraceKey>(self.drain());
     }
 }
 
+/// The trace of a transaction, or the error if the tracer fails to build it.
 pub struct GethTraceKey;
 
 impl typemap::Key for GethTraceKey {
-    type Value = GethTrace;
+    type Value = Result<GethTrace, String>;
 }
 
 impl CheckpointTracer for GethTracer {}
@@ -191,6 +311,13 @@ impl StorageTracer for GethTracer {}
 
 impl CallTracer for GethTracer {
     fn record_call(&mut self, params: &ActionParams) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.record_call(params);
+            }
+            return;
+        }
+
         if self.is_fourbyte_tracer() {
             self.fourbyte_inspector.record_call(params);
             return;
@@ -242,6 +369,13 @@ impl CallTracer for GethTracer {
     }
 
     fn record_call_result(&mut self, result: &FrameResult) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.record_call_result(result);
+            }
+            return;
+        }
+
         if self.is_fourbyte_tracer() {
             return;
         }
@@ -275,6 +409,13 @@ impl CallTracer for GethTracer {
     }
 
     fn record_create(&mut self, params: &ActionParams) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.record_create(params);
+            }
+            return;
+        }
+
         if self.is_fourbyte_tracer() {
             return;
         }
@@ -309,6 +450,13 @@ impl CallTracer for GethTracer {
     }
 
     fn record_create_result(&mut self, result: &FrameResult) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.record_create_result(result);
+            }
+            return;
+        }
+
         if self.is_fourbyte_tracer() {
             return;
         }
@@ -352,18 +500,36 @@ impl CallTracer for GethTracer {
 
 impl OpcodeTracer for GethTracer {
     fn do_trace_opcode(&self, enabled: &mut bool) {
+        for (_, tracer) in &self.mux_tracers {
+            tracer.do_trace_opcode(enabled);
+        }
+
         if self.inner.config.record_steps {
             *enabled |= true;
         }
     }
 
     fn initialize_interp(&mut self, gas_limit: cfx_types::U256) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.initialize_interp(gas_limit);
+            }
+            return;
+        }
+
         self.inner
             .gas_inspector
             .set_gas_remainning(gas_limit.as_u64());
     }
 
     fn step(&mut self, interp: &dyn InterpreterInfo) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.step(interp);
+            }
+            return;
+        }
+
         self.inner
             .gas_inspector
             .set_gas_remainning(interp.gas_remainning().as_u64());
@@ -374,6 +540,13 @@ impl OpcodeTracer for GethTracer {
     }
 
     fn step_end(&mut self, interp: &dyn InterpreterInfo) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.step_end(interp);
+            }
+            return;
+        }
+
         let remainning = interp.gas_remainning().as_u64();
         let last_gas_cost = self
             .inner
@@ -390,9 +563,16 @@ impl OpcodeTracer for GethTracer {
     }
 
     fn log(
-        &mut self, _address: &cfx_types::Address,
-        topics: &Vec<cfx_types::H256>, data: &[u8],
+        &mut self, address: &cfx_types::Address, topics: &Vec<cfx_types::H256>,
+        data: &[u8],
     ) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.log(address, topics, data);
+            }
+            return;
+        }
+
         if self.inner.config.record_logs {
             let trace_idx = self.inner.last_trace_idx();
             let trace = &mut self.inner.traces.arena[trace_idx];
@@ -405,9 +585,16 @@ impl OpcodeTracer for GethTracer {
     }
 
     fn selfdestruct(
-        &mut self, _contract: &cfx_types::Address, target: &cfx_types::Address,
-        _value: cfx_types::U256,
+        &mut self, contract: &cfx_types::Address, target: &cfx_types::Address,
+        value: cfx_types::U256,
     ) {
+        if self.is_mux_tracer() {
+            for (_, tracer) in &mut self.mux_tracers {
+                tracer.selfdestruct(contract, target, value);
+            }
+            return;
+        }
+
         if self.is_fourbyte_tracer() {
             return;
         }
@@ -415,7 +602,8 @@ impl OpcodeTracer for GethTracer {
         let trace_idx = self.inner.last_trace_idx();
         let trace = &mut self.inner.traces.arena[trace_idx].trace;
         trace.selfdestruct_refund_target =
-            Some(to_alloy_address(*target as H160))
+            Some(to_alloy_address(*target as H160));
+        trace.selfdestruct_transferred_value = Some(to_alloy_u256(value));
     }
 }
 
@@ -5,6 +5,7 @@ mod fourbyte;
 mod gas;
 mod geth_builder;
 mod geth_tracer;
+mod native;
 mod tracing_inspector;
 mod types;
 mod utils;
@@ -13,7 +14,7 @@ use arena::CallTraceArena;
 use config::TracingInspectorConfig;
 use geth_builder::GethTraceBuilder;
 
-pub use geth_tracer::{GethTraceKey, GethTracer};
+pub use geth_tracer::{check_tracing_options, GethTraceKey, GethTracer};
 pub use types::{GethTraceWithHash, TxExecContext};
 pub use utils::{
     from_alloy_address, to_alloy_address, to_alloy_h256, to_alloy_u256,
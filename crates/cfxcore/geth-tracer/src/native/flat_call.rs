@@ -0,0 +1,451 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! The `flatCallTracer`, which reports the call traces of a transaction as a
+//! flat list in the style of parity's `trace_transaction`, compatible with
+//! geth's tracer of the same name.
+
+use super::NativeTracer;
+use crate::{
+    config::{TraceStyle, TracingInspectorConfig},
+    types::CallTraceNode,
+    utils::to_alloy_h256,
+    TxExecContext,
+};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_trace::geth::GethDebugTracerConfig;
+use revm::interpreter::InstructionResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct FlatCallConfig {
+    /// Reports the errors in parity style, e.g. `Reverted` instead of
+    /// `execution reverted`
+    convert_parity_errors: bool,
+    /// Includes the calls to precompiles without value transfer
+    include_precompiles: bool,
+}
+
+impl FlatCallConfig {
+    fn parse(config: &GethDebugTracerConfig) -> Result<Self, String> {
+        if config.is_null() {
+            return Ok(Self::default());
+        }
+        serde_json::from_value(config.0.clone()).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FlatCallFrame {
+    action: FlatCallAction,
+    block_hash: B256,
+    block_number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<FlatCallResult>,
+    subtraces: usize,
+    trace_address: Vec<usize>,
+    transaction_hash: B256,
+    transaction_position: usize,
+    #[serde(rename = "type")]
+    typ: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum FlatCallAction {
+    Call(CallAction),
+    Create(CreateAction),
+    Suicide(SuicideAction),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallAction {
+    call_type: String,
+    from: Address,
+    gas: U256,
+    input: Bytes,
+    to: Address,
+    value: U256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateAction {
+    creation_method: String,
+    from: Address,
+    gas: U256,
+    init: Bytes,
+    value: U256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SuicideAction {
+    address: Address,
+    balance: U256,
+    refund_address: Address,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum FlatCallResult {
+    Call {
+        #[serde(rename = "gasUsed")]
+        gas_used: U256,
+        output: Bytes,
+    },
+    Create {
+        address: Address,
+        code: Bytes,
+        #[serde(rename = "gasUsed")]
+        gas_used: U256,
+    },
+}
+
+pub(crate) struct FlatCallTracer;
+
+impl FlatCallTracer {
+    /// Appends the frames of `node` and its sub calls in depth-first order.
+    fn flatten(
+        nodes: &[CallTraceNode], node: &CallTraceNode, gas_used: u64,
+        trace_address: Vec<usize>, context: &TxExecContext, style: TraceStyle,
+        frames: &mut Vec<FlatCallFrame>,
+    ) {
+        let trace = &node.trace;
+        let subtraces = node.children.len() + node.is_selfdestruct() as usize;
+
+        let (action, result, typ) = if trace.kind.is_any_create() {
+            let action = FlatCallAction::Create(CreateAction {
+                creation_method: trace.kind.to_string().to_lowercase(),
+                from: trace.caller,
+                gas: U256::from(trace.gas_limit),
+                init: trace.data.clone(),
+                value: trace.value,
+            });
+            let result = FlatCallResult::Create {
+                address: trace.address,
+                code: trace.output.clone(),
+                gas_used: U256::from(gas_used),
+            };
+            (action, result, "create")
+        } else {
+            let action = FlatCallAction::Call(CallAction {
+                call_type: trace.kind.to_string().to_lowercase(),
+                from: trace.caller,
+                gas: U256::from(trace.gas_limit),
+                input: trace.data.clone(),
+                to: trace.address,
+                value: trace.value,
+            });
+            let result = FlatCallResult::Call {
+                gas_used: U256::from(gas_used),
+                output: trace.output.clone(),
+            };
+            (action, result, "call")
+        };
+
+        // the output of a reverted call holds the revert reason, otherwise
+        // there is no result for a failed call
+        let error = trace.as_error_msg(style);
+        let result = (error.is_none()
+            || trace.status == InstructionResult::Revert)
+            .then_some(result);
+
+        frames.push(Self::frame(
+            action,
+            error,
+            result,
+            subtraces,
+            trace_address.clone(),
+            typ,
+            context,
+        ));
+
+        for (idx, child) in node.children.iter().enumerate() {
+            let child = &nodes[*child];
+            let mut child_address = trace_address.clone();
+            child_address.push(idx);
+            Self::flatten(
+                nodes,
+                child,
+                child.trace.gas_used,
+                child_address,
+                context,
+                style,
+                frames,
+            );
+        }
+
+        if let Some(refund_address) = trace.selfdestruct_refund_target {
+            let mut child_address = trace_address;
+            child_address.push(node.children.len());
+            let action = FlatCallAction::Suicide(SuicideAction {
+                address: trace.address,
+                balance: trace
+                    .selfdestruct_transferred_value
+                    .unwrap_or_default(),
+                refund_address,
+            });
+            frames.push(Self::frame(
+                action,
+                None,
+                None,
+                0,
+                child_address,
+                "suicide",
+                context,
+            ));
+        }
+    }
+
+    fn frame(
+        action: FlatCallAction, error: Option<String>,
+        result: Option<FlatCallResult>, subtraces: usize,
+        trace_address: Vec<usize>, typ: &'static str, context: &TxExecContext,
+    ) -> FlatCallFrame {
+        FlatCallFrame {
+            action,
+            block_hash: to_alloy_h256(context.block_hash),
+            block_number: context.block_height,
+            error,
+            result,
+            subtraces,
+            trace_address,
+            transaction_hash: to_alloy_h256(context.tx_hash),
+            transaction_position: context.tx_index,
+            typ,
+        }
+    }
+}
+
+impl NativeTracer for FlatCallTracer {
+    fn name(&self) -> &'static str { "flatCallTracer" }
+
+    fn check_config(
+        &self, config: &GethDebugTracerConfig,
+    ) -> Result<(), String> {
+        FlatCallConfig::parse(config).map(|_| ())
+    }
+
+    fn inspector_config(
+        &self, config: &GethDebugTracerConfig,
+    ) -> TracingInspectorConfig {
+        let config = FlatCallConfig::parse(config).unwrap_or_default();
+        TracingInspectorConfig::none()
+            .set_exclude_precompile_calls(!config.include_precompiles)
+    }
+
+    fn build_trace(
+        &self, nodes: &[CallTraceNode], context: &TxExecContext, gas_used: u64,
+        config: &GethDebugTracerConfig,
+    ) -> Result<serde_json::Value, String> {
+        let config = FlatCallConfig::parse(config)?;
+        let style = if config.convert_parity_errors {
+            TraceStyle::Parity
+        } else {
+            TraceStyle::Geth
+        };
+
+        let mut frames = Vec::with_capacity(nodes.len());
+        if let Some(root) = nodes.first() {
+            Self::flatten(
+                nodes,
+                root,
+                gas_used,
+                vec![],
+                context,
+                style,
+                &mut frames,
+            );
+        }
+        serde_json::to_value(frames).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CallKind, CallTrace};
+    use cfx_types::H256;
+    use serde_json::json;
+
+    fn node(
+        idx: usize, parent: Option<usize>, children: Vec<usize>,
+        trace: CallTrace,
+    ) -> CallTraceNode {
+        CallTraceNode {
+            parent,
+            children,
+            idx,
+            trace,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flatten_call_traces() {
+        let caller = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let created = Address::with_last_byte(3);
+
+        let nodes = vec![
+            node(
+                0,
+                None,
+                vec![1, 2],
+                CallTrace {
+                    caller,
+                    address: contract,
+                    kind: CallKind::Call,
+                    gas_limit: 100000,
+                    gas_used: 50000,
+                    status: InstructionResult::Return,
+                    ..Default::default()
+                },
+            ),
+            node(
+                1,
+                Some(0),
+                vec![],
+                CallTrace {
+                    depth: 1,
+                    caller: contract,
+                    address: created,
+                    kind: CallKind::Create2,
+                    gas_limit: 30000,
+                    gas_used: 20000,
+                    output: Bytes::from(vec![0x60]),
+                    status: InstructionResult::Return,
+                    ..Default::default()
+                },
+            ),
+            node(
+                2,
+                Some(0),
+                vec![],
+                CallTrace {
+                    depth: 1,
+                    caller: contract,
+                    address: created,
+                    kind: CallKind::StaticCall,
+                    gas_limit: 10000,
+                    gas_used: 10000,
+                    status: InstructionResult::OutOfGas,
+                    ..Default::default()
+                },
+            ),
+        ];
+        let context = TxExecContext {
+            tx_gas_limit: 100000,
+            block_number: 10,
+            block_height: 5,
+            block_hash: H256::repeat_byte(0xbb),
+            tx_hash: H256::repeat_byte(0xaa),
+            tx_index: 1,
+        };
+
+        let trace = FlatCallTracer
+            .build_trace(
+                &nodes,
+                &context,
+                60000,
+                &GethDebugTracerConfig::default(),
+            )
+            .unwrap();
+        let frames = trace.as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+
+        assert_eq!(frames[0]["type"], json!("call"));
+        assert_eq!(frames[0]["action"]["callType"], json!("call"));
+        assert_eq!(frames[0]["result"]["gasUsed"], json!("0xea60"));
+        assert_eq!(frames[0]["subtraces"], json!(2));
+        assert_eq!(frames[0]["traceAddress"], json!([]));
+        assert_eq!(frames[0]["blockNumber"], json!(5));
+        assert_eq!(frames[0]["transactionPosition"], json!(1));
+
+        assert_eq!(frames[1]["type"], json!("create"));
+        assert_eq!(frames[1]["action"]["creationMethod"], json!("create2"));
+        assert_eq!(frames[1]["result"]["address"], json!(created));
+        assert_eq!(frames[1]["result"]["code"], json!("0x60"));
+        assert_eq!(frames[1]["traceAddress"], json!([0]));
+
+        assert_eq!(frames[2]["action"]["callType"], json!("staticcall"));
+        assert_eq!(frames[2]["error"], json!("out of gas"));
+        assert!(frames[2].get("result").is_none());
+        assert_eq!(frames[2]["traceAddress"], json!([1]));
+
+        let config = GethDebugTracerConfig(json!({
+            "convertParityErrors": true
+        }));
+        let trace = FlatCallTracer
+            .build_trace(&nodes, &context, 60000, &config)
+            .unwrap();
+        assert_eq!(trace[2]["error"], json!("Out of gas"));
+
+        let config = GethDebugTracerConfig(json!({ "foo": true }));
+        assert!(FlatCallTracer.check_config(&config).is_err());
+        assert!(FlatCallTracer
+            .build_trace(&nodes, &context, 60000, &config)
+            .is_err());
+    }
+
+    #[test]
+    fn flatten_selfdestruct() {
+        let caller = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+        let beneficiary = Address::with_last_byte(4);
+
+        // The call transfers 1 while the contract holds 1000 when destroyed.
+        let nodes = vec![node(
+            0,
+            None,
+            vec![],
+            CallTrace {
+                caller,
+                address: contract,
+                kind: CallKind::Call,
+                value: U256::from(1),
+                gas_limit: 100000,
+                status: InstructionResult::Return,
+                selfdestruct_refund_target: Some(beneficiary),
+                selfdestruct_transferred_value: Some(U256::from(1000)),
+                ..Default::default()
+            },
+        )];
+        let context = TxExecContext {
+            tx_gas_limit: 100000,
+            block_number: 10,
+            block_height: 5,
+            block_hash: H256::repeat_byte(0xbb),
+            tx_hash: H256::repeat_byte(0xaa),
+            tx_index: 0,
+        };
+
+        let trace = FlatCallTracer
+            .build_trace(
+                &nodes,
+                &context,
+                30000,
+                &GethDebugTracerConfig::default(),
+            )
+            .unwrap();
+        let frames = trace.as_array().unwrap();
+        assert_eq!(frames.len(), 2);
+
+        assert_eq!(frames[0]["action"]["value"], json!("0x1"));
+        assert_eq!(frames[0]["subtraces"], json!(1));
+
+        assert_eq!(frames[1]["type"], json!("suicide"));
+        assert_eq!(frames[1]["action"]["address"], json!(contract));
+        assert_eq!(frames[1]["action"]["balance"], json!("0x3e8"));
+        assert_eq!(frames[1]["action"]["refundAddress"], json!(beneficiary));
+        assert_eq!(frames[1]["traceAddress"], json!([0]));
+        assert!(frames[1].get("result").is_none());
+    }
+}
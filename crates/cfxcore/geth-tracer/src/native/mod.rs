@@ -0,0 +1,52 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Native tracers selected by name, e.g. `{"tracer": "flatCallTracer"}`.
+//!
+//! Tracer names unknown to `GethDebugBuiltInTracerType` are parsed as
+//! JavaScript tracers, so they are looked up in [NATIVE_TRACERS] before being
+//! rejected. A new tracer only needs to implement [NativeTracer] and be
+//! registered there; the RPC dispatch picks it up without changes.
+
+mod flat_call;
+
+use crate::{
+    config::TracingInspectorConfig, types::CallTraceNode, TxExecContext,
+};
+use alloy_rpc_types_trace::geth::GethDebugTracerConfig;
+
+pub(crate) use flat_call::FlatCallTracer;
+
+/// A tracer built on the call traces recorded by the `TracingInspector`.
+pub(crate) trait NativeTracer: Sync {
+    /// The name used to select the tracer in the tracing options.
+    fn name(&self) -> &'static str;
+
+    /// Validates the tracer config before any transaction is executed.
+    fn check_config(
+        &self, config: &GethDebugTracerConfig,
+    ) -> Result<(), String>;
+
+    /// Returns what the inspector should record for this tracer.
+    fn inspector_config(
+        &self, config: &GethDebugTracerConfig,
+    ) -> TracingInspectorConfig;
+
+    /// Builds the trace of a transaction from the recorded call traces.
+    fn build_trace(
+        &self, nodes: &[CallTraceNode], context: &TxExecContext, gas_used: u64,
+        config: &GethDebugTracerConfig,
+    ) -> Result<serde_json::Value, String>;
+}
+
+/// All the registered native tracers.
+static NATIVE_TRACERS: &[&dyn NativeTracer] = &[&FlatCallTracer];
+
+/// Returns the native tracer registered under `name`.
+pub(crate) fn native_tracer(name: &str) -> Option<&'static dyn NativeTracer> {
+    NATIVE_TRACERS
+        .iter()
+        .find(|tracer| tracer.name() == name)
+        .copied()
+}
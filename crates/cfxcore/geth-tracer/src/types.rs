@@ -69,6 +69,9 @@ pub struct CallTrace {
     /// [InstructionResult::SelfDestruct] There's an edge case where a new
     /// created contract is immediately selfdestructed.
     pub selfdestruct_refund_target: Option<Address>,
+    /// The balance transferred to the refund target by the selfdestruct,
+    /// which is only set if a selfdestruct was executed.
+    pub selfdestruct_transferred_value: Option<U256>,
     /// The kind of call this is
     pub kind: CallKind,
     /// The value transferred in the call
@@ -606,7 +609,7 @@ impl AsRef<[u8]> for RecordedMemory {
 }
 
 pub struct GethTraceWithHash {
-    pub trace: GethTrace,
+    pub trace: Result<GethTrace, String>,
     pub tx_hash: H256,
     pub space: Space,
 }
@@ -616,6 +619,10 @@ pub struct TxExecContext {
     pub tx_gas_limit: u64,
     pub block_number: BlockNumber,
     pub block_height: BlockHeight,
+    pub block_hash: H256,
+    pub tx_hash: H256,
+    /// The index of the transaction among the ones of its space in the block
+    pub tx_index: usize,
 }
 
 #[cfg(feature = "serde")]
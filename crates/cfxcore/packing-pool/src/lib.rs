@@ -15,6 +15,7 @@ mod packing_batch;
 mod pool;
 mod pool_config;
 mod sample;
+mod strategy;
 mod transaction;
 mod treapmap_config;
 mod weight;
@@ -24,4 +25,8 @@ pub use packing_batch::PackingBatch;
 pub use pool::PackingPool;
 pub use pool_config::PackingPoolConfig;
 pub use sample::{SampleTag, TxSampler};
+pub use strategy::{
+    FairShare, PackingItem, PackingStrategy, PackingStrategyKind,
+    PriorityFeeOrdering, RandomPacking,
+};
 pub use transaction::PackingPoolTransaction;
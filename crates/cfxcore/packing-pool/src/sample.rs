@@ -17,6 +17,11 @@ pub enum SampleTag {
     CandidateAddress,
     /// Transaction was picked during the Remaining Transactions Phase.
     PriceDesc,
+    /// Transaction was picked by
+    /// [`PriorityFeeOrdering`][crate::PriorityFeeOrdering].
+    PriorityFee,
+    /// Transaction was picked by [`FairShare`][crate::FairShare].
+    FairShare,
 }
 
 /// An iterator for sampling transactions from a packing pool.
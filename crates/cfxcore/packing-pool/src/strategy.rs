@@ -0,0 +1,300 @@
+use std::{cmp::Reverse, str::FromStr};
+
+use cfx_types::U256;
+use malloc_size_of_derive::MallocSizeOf;
+use rand::RngCore;
+
+use crate::{
+    sample::SampleTag, transaction::PackingPoolTransaction, PackingPool,
+};
+
+/// The items of a packing order: a sender, a list of its transactions with
+/// continuous nonces, and the phase in which they were selected.
+pub type PackingItem<'a, TX> =
+    (<TX as PackingPoolTransaction>::Sender, &'a [TX], SampleTag);
+
+/// A policy deciding the order in which the transactions in a
+/// [`PackingPool`] are offered to the block packer.
+///
+/// A sender may appear several times in the order, each time with the
+/// transactions following the ones of its previous appearance. Once the packer
+/// stops at a transaction of a sender, it must skip the later appearances of
+/// that sender, otherwise the packed nonces would not be continuous.
+pub trait PackingStrategy {
+    /// Returns the packing order of the transactions in `pool`.
+    /// `block_gas_limit` is the gas limit available for the pool, and
+    /// `base_price` is the base price of the block being packed.
+    fn packing_order<'a, 'b, TX: PackingPoolTransaction, R: RngCore>(
+        &self, pool: &'a PackingPool<TX>, rng: &'b mut R,
+        block_gas_limit: U256, base_price: U256,
+    ) -> Box<dyn Iterator<Item = PackingItem<'a, TX>> + 'b>
+    where
+        'a: 'b;
+}
+
+/// The random packing algorithm, see [`TxSampler`][crate::TxSampler].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomPacking;
+
+impl PackingStrategy for RandomPacking {
+    fn packing_order<'a, 'b, TX: PackingPoolTransaction, R: RngCore>(
+        &self, pool: &'a PackingPool<TX>, rng: &'b mut R,
+        block_gas_limit: U256, _base_price: U256,
+    ) -> Box<dyn Iterator<Item = PackingItem<'a, TX>> + 'b>
+    where
+        'a: 'b,
+    {
+        Box::new(pool.tx_sampler(rng, block_gas_limit))
+    }
+}
+
+/// Outputs the senders in descending order of the priority gas price of their
+/// first transaction under the base price, the ties are broken by the gas
+/// price.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityFeeOrdering;
+
+impl PackingStrategy for PriorityFeeOrdering {
+    fn packing_order<'a, 'b, TX: PackingPoolTransaction, R: RngCore>(
+        &self, pool: &'a PackingPool<TX>, _rng: &'b mut R,
+        _block_gas_limit: U256, base_price: U256,
+    ) -> Box<dyn Iterator<Item = PackingItem<'a, TX>> + 'b>
+    where
+        'a: 'b,
+    {
+        // The pool is iterated in descending order of the gas price, and the
+        // sort is stable.
+        let mut batches: Vec<&'a [TX]> = pool.iter().collect();
+        batches
+            .sort_by_key(|txs| Reverse(txs[0].priority_gas_price(&base_price)));
+        Box::new(
+            batches
+                .into_iter()
+                .map(|txs| (txs[0].sender(), txs, SampleTag::PriorityFee)),
+        )
+    }
+}
+
+/// Outputs one transaction of each sender per round, so a sender with many
+/// transactions cannot take the place of the other ones. In each round, the
+/// senders are in descending order of the gas price of their first
+/// transaction.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FairShare;
+
+impl PackingStrategy for FairShare {
+    fn packing_order<'a, 'b, TX: PackingPoolTransaction, R: RngCore>(
+        &self, pool: &'a PackingPool<TX>, _rng: &'b mut R,
+        _block_gas_limit: U256, _base_price: U256,
+    ) -> Box<dyn Iterator<Item = PackingItem<'a, TX>> + 'b>
+    where
+        'a: 'b,
+    {
+        Box::new(RoundRobinIter {
+            batches: pool.iter().collect(),
+            round: 0,
+            next: 0,
+        })
+    }
+}
+
+struct RoundRobinIter<'a, TX: PackingPoolTransaction> {
+    /// The senders having a transaction in the current round.
+    batches: Vec<&'a [TX]>,
+    /// The index of the transactions output in the current round.
+    round: usize,
+    /// The next sender to output in the current round.
+    next: usize,
+}
+
+impl<'a, TX: PackingPoolTransaction> Iterator for RoundRobinIter<'a, TX> {
+    type Item = PackingItem<'a, TX>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.batches.len() {
+            let round = self.round + 1;
+            self.batches.retain(|txs| txs.len() > round);
+            self.round = round;
+            self.next = 0;
+        }
+        let txs = *self.batches.get(self.next)?;
+        self.next += 1;
+        Some((
+            txs[0].sender(),
+            &txs[self.round..self.round + 1],
+            SampleTag::FairShare,
+        ))
+    }
+}
+
+/// The built-in packing strategies, selected by the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, MallocSizeOf)]
+pub enum PackingStrategyKind {
+    /// [`RandomPacking`]
+    Random,
+    /// [`PriorityFeeOrdering`]
+    PriorityFee,
+    /// [`FairShare`]
+    FairShare,
+}
+
+impl Default for PackingStrategyKind {
+    fn default() -> Self { PackingStrategyKind::Random }
+}
+
+impl FromStr for PackingStrategyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(PackingStrategyKind::Random),
+            "priority_fee" => Ok(PackingStrategyKind::PriorityFee),
+            "fair_share" => Ok(PackingStrategyKind::FairShare),
+            _ => Err(format!(
+                "Unknown packing strategy {:?}, expected one of \"random\", \
+                 \"priority_fee\" and \"fair_share\"",
+                s
+            )),
+        }
+    }
+}
+
+impl PackingStrategy for PackingStrategyKind {
+    fn packing_order<'a, 'b, TX: PackingPoolTransaction, R: RngCore>(
+        &self, pool: &'a PackingPool<TX>, rng: &'b mut R,
+        block_gas_limit: U256, base_price: U256,
+    ) -> Box<dyn Iterator<Item = PackingItem<'a, TX>> + 'b>
+    where
+        'a: 'b,
+    {
+        match self {
+            PackingStrategyKind::Random => RandomPacking.packing_order(
+                pool,
+                rng,
+                block_gas_limit,
+                base_price,
+            ),
+            PackingStrategyKind::PriorityFee => PriorityFeeOrdering
+                .packing_order(pool, rng, block_gas_limit, base_price),
+            PackingStrategyKind::FairShare => {
+                FairShare.packing_order(pool, rng, block_gas_limit, base_price)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod strategy_tests {
+    use std::{collections::HashMap, sync::atomic::AtomicUsize};
+
+    use cfx_types::U256;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    use super::{PackingStrategy, PackingStrategyKind};
+    use crate::{
+        mock_tx::MockTransaction, transaction::PackingPoolTransaction,
+        PackingPool, PackingPoolConfig, SampleTag,
+    };
+
+    fn default_tx(sender: u64, nonce: u64, gas_price: u64) -> MockTransaction {
+        static ID: AtomicUsize = AtomicUsize::new(0);
+        MockTransaction {
+            sender,
+            nonce,
+            gas_price,
+            gas_limit: 21000,
+            id: ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst),
+        }
+    }
+
+    /// Sender `i` has `i + 1` transactions with gas price `10 + i`.
+    fn default_pool(accounts: u64) -> PackingPool<MockTransaction> {
+        let mut pool = PackingPool::new(PackingPoolConfig::new_for_test());
+        for sender in 0..accounts {
+            for nonce in 0..=sender {
+                pool.insert(default_tx(sender, nonce, 10 + sender))
+                    .1
+                    .unwrap();
+            }
+        }
+        pool
+    }
+
+    #[test]
+    fn test_parse_packing_strategy() {
+        assert_eq!(
+            "random".parse::<PackingStrategyKind>(),
+            Ok(PackingStrategyKind::Random)
+        );
+        assert_eq!(
+            "priority_fee".parse::<PackingStrategyKind>(),
+            Ok(PackingStrategyKind::PriorityFee)
+        );
+        assert_eq!(
+            "fair_share".parse::<PackingStrategyKind>(),
+            Ok(PackingStrategyKind::FairShare)
+        );
+        assert!("fifo".parse::<PackingStrategyKind>().is_err());
+    }
+
+    #[test]
+    fn test_priority_fee_ordering() {
+        let pool = default_pool(10);
+        let mut rng = XorShiftRng::from_entropy();
+        let base_price = U256::from(12);
+
+        let order: Vec<_> = PackingStrategyKind::PriorityFee
+            .packing_order(&pool, &mut rng, U256::from(21000), base_price)
+            .collect();
+        assert_eq!(order.len(), 10);
+        for (idx, (sender, txs, tag)) in order.iter().enumerate() {
+            assert_eq!(*sender, 9 - idx as u64);
+            assert_eq!(txs.len(), *sender as usize + 1);
+            assert_eq!(*tag, SampleTag::PriorityFee);
+        }
+        for pair in order.windows(2) {
+            assert!(
+                pair[0].1[0].priority_gas_price(&base_price)
+                    >= pair[1].1[0].priority_gas_price(&base_price)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fair_share() {
+        let pool = default_pool(10);
+        let mut rng = XorShiftRng::from_entropy();
+
+        let mut next_nonce = HashMap::new();
+        let mut last_round_len = usize::MAX;
+        let mut round_len = 0;
+        let mut last_sender = None;
+        for (sender, txs, tag) in PackingStrategyKind::FairShare.packing_order(
+            &pool,
+            &mut rng,
+            U256::from(21000),
+            U256::zero(),
+        ) {
+            assert_eq!(tag, SampleTag::FairShare);
+            assert_eq!(txs.len(), 1);
+            // The nonces of a sender are continuous across the rounds.
+            let nonce = next_nonce.entry(sender).or_insert(0u64);
+            assert_eq!(txs[0].nonce(), U256::from(*nonce));
+            *nonce += 1;
+
+            // A round starts over from the highest price.
+            if last_sender.map_or(false, |last| sender > last) {
+                assert!(round_len < last_round_len);
+                last_round_len = round_len;
+                round_len = 0;
+            }
+            round_len += 1;
+            last_sender = Some(sender);
+        }
+        assert_eq!(next_nonce.len(), 10);
+        for (sender, nonce) in next_nonce {
+            assert_eq!(nonce, sender + 1);
+        }
+    }
+}
@@ -13,6 +13,11 @@ pub trait PackingPoolTransaction: Clone {
     fn gas_price(&self) -> U256;
 
     fn gas_limit(&self) -> U256;
+
+    /// The gas price paid to the miner under the given base price.
+    fn priority_gas_price(&self, base_price: &U256) -> U256 {
+        self.gas_price().saturating_sub(*base_price)
+    }
 }
 
 impl PackingPoolTransaction for Arc<SignedTransaction> {
@@ -29,4 +34,12 @@ impl PackingPoolTransaction for Arc<SignedTransaction> {
 
     #[inline]
     fn gas_limit(&self) -> U256 { *SignedTransaction::gas_limit(&self) }
+
+    #[inline]
+    fn priority_gas_price(&self, base_price: &U256) -> U256 {
+        if SignedTransaction::gas_price(&self) < base_price {
+            return U256::zero();
+        }
+        self.transaction.priority_gas_price(base_price)
+    }
 }
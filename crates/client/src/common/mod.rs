@@ -215,6 +215,10 @@ pub fn initialize_common_modules(
     };
 
     metrics::initialize(conf.metrics_config());
+    // The PoS metrics are registered with the `prometheus` crate.
+    metrics::add_prometheus_exposition(|| {
+        diem_metrics::get_all_metrics_as_serialized_string().unwrap_or_default()
+    });
 
     let worker_thread_pool = Arc::new(Mutex::new(ThreadPool::with_name(
         "Tx Recover".into(),
@@ -378,20 +382,23 @@ pub fn initialize_common_modules(
                 .clone(),
             vrf_proposal_threshold: conf.raw_conf.vrf_proposal_threshold,
             pos_state_config: conf.pos_state_config(),
+            backup_service_address: conf.raw_conf.pos_backup_service_address,
         },
         conf.raw_conf.pos_reference_enable_height,
     ));
     let verification_config =
         conf.verification_config(machine.clone(), pos_verifier.clone());
+    let notifications = Notifications::init();
     let txpool = Arc::new(TransactionPool::new(
-        conf.txpool_config(),
+        conf.txpool_config(network_config.get_network_type())?,
         verification_config.clone(),
         data_man.clone(),
         machine.clone(),
+        notifications.clone(),
     ));
+    txpool.start_journal_thread();
 
     let statistics = Arc::new(Statistics::new());
-    let notifications = Notifications::init();
     let pivot_hint = if let Some(conf) = &consensus_conf.pivot_hint_conf {
         Some(Arc::new(PivotHint::new(conf)?))
     } else {
@@ -777,6 +784,7 @@ pub fn initialize_not_light_node_modules(
             sync.clone(),
             txpool.clone(),
             eth_rpc_http_server_addr,
+            conf.admin_node_config(),
         ))?;
 
     Ok((
@@ -2,9 +2,16 @@
 // Conflux is free software and distributed under GNU General Public License.
 // See http://www.gnu.org/licenses/
 
-use std::{collections::BTreeMap, convert::TryInto, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::TryInto,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+};
 
 use cfx_rpc_builder::RpcModuleSelection;
+use cfx_rpc_cfx_types::admin::NodeConfig;
 use lazy_static::*;
 use log::{error, warn};
 use parking_lot::RwLock;
@@ -23,7 +30,8 @@ use cfx_storage::{
     ConsensusParam, ProvideExtraSnapshotSyncConfig, StorageConfiguration,
 };
 use cfx_types::{
-    parse_hex_string, Address, AllChainID, Space, SpaceMap, H256, U256,
+    parse_hex_string, Address, AddressSpaceUtil, AllChainID, Space, SpaceMap,
+    H256, U256,
 };
 use cfxcore::{
     block_data_manager::{DataManagerConfiguration, DbType},
@@ -43,7 +51,9 @@ use cfxcore::{
     light_protocol::LightNodeConfiguration,
     sync::{ProtocolConfiguration, StateSyncConfiguration, SyncGraphConfig},
     sync_parameters::*,
-    transaction_pool::TxPoolConfig,
+    transaction_pool::{
+        PackingStrategyKind, TxPoolConfig, TxPoolJournalConfig,
+    },
     NodeType,
 };
 use diem_types::term_state::{
@@ -51,7 +61,7 @@ use diem_types::term_state::{
     OUT_QUEUE_LOCKED_VIEWS, ROUND_PER_TERM, TERM_ELECTED_SIZE, TERM_MAX_SIZE,
 };
 use metrics::MetricsConfiguration;
-use network::DiscoveryConfiguration;
+use network::{DiscoveryConfiguration, EncryptionMode};
 use primitives::block_header::CIP112_TRANSITION_HEIGHT;
 use txgen::TransactionGeneratorConfig;
 
@@ -133,6 +143,10 @@ build_config! {
         (metrics_influxdb_password, (Option<String>), None)
         (metrics_influxdb_node, (Option<String>), None)
         (metrics_output_file, (Option<String>), None)
+        // Serve the metrics, including the PoS metrics, at `/metrics` in the
+        // Prometheus text format, e.g. "127.0.0.1:9100". It takes effect only
+        // if `metrics_enabled` is true.
+        (metrics_prometheus_listen_address, (Option<SocketAddr>), None)
         (metrics_report_interval_ms, (u64), 3_000)
         (rocksdb_disable_wal, (bool), false)
         (txgen_account_count, (usize), 10)
@@ -174,6 +188,7 @@ build_config! {
         (next_hardfork_transition_height, (Option<u64>), None)
         (cip1559_transition_height, (Option<u64>), None)
         (cancun_opcodes_transition_number, (Option<u64>), None)
+        (crypto_builtins_transition_number, (Option<u64>), None)
         (referee_bound, (usize), REFEREE_DEFAULT_BOUND)
         (params_dao_vote_period, (u64), DAO_PARAMETER_VOTE_PERIOD)
         (timer_chain_beta, (u64), TIMER_CHAIN_DEFAULT_BETA)
@@ -278,6 +293,8 @@ build_config! {
         (node_table_promotion_timeout_s, (u64), 3 * 24 * 3600)
         (session_ip_limits, (String), "1,8,4,2".into())
         (subnet_quota, (usize), 128)
+        (p2p_compression, (bool), false)
+        (p2p_compression_threshold, (usize), 1024)
 
         // Transaction cache/transaction pool section.
         (tx_cache_index_maintain_timeout_ms, (u64), 300_000)
@@ -286,9 +303,18 @@ build_config! {
         (tx_pool_min_eth_tx_gas_price, (Option<u64>), None)
         (tx_pool_nonce_bits, (usize), TXPOOL_DEFAULT_NONCE_BITS)
         (tx_pool_allow_gas_over_half_block, (bool), false)
+        (tx_pool_journal_path, (Option<String>), None)
+        (tx_pool_journal_all, (bool), false)
+        (tx_pool_journal_interval_s, (u64), 600)
+        (tx_pool_journal_max_size_mb, (usize), 64)
+        (tx_pool_journal_rotations, (usize), 1)
+        (tx_pool_local_addresses, (Option<String>), None)
+        (tx_pool_local_evm_addresses, (Option<String>), None)
+        (tx_pool_private_tx_lifetime_epochs, (u64), 100)
         (max_packing_batch_gas_limit, (u64), 3_000_000)
         (max_packing_batch_size, (usize), 50)
         (packing_pool_degree, (u8), 4)
+        (packing_strategy, (String), "random".into())
 
 
         // Storage Section.
@@ -337,6 +363,7 @@ build_config! {
         (max_trans_count_received_in_catch_up, (u64), 60_000)
         (persist_tx_index, (bool), false)
         (persist_block_number_index, (bool), true)
+        (persist_log_index, (bool), false)
         (print_memory_usage_period_s, (Option<u64>), None)
         (target_block_gas_limit, (u64), DEFAULT_TARGET_BLOCK_GAS_LIMIT)
         (executive_trace, (bool), false)
@@ -371,6 +398,7 @@ build_config! {
         (pos_cip136_round_per_term, (u64), ROUND_PER_TERM)
         (dev_pos_private_key_encryption_password, (Option<String>), None)
         (pos_started_as_voter, (bool), true)
+        (pos_backup_service_address, (Option<SocketAddr>), None)
 
         // Light node section
         (ln_epoch_request_batch_size, (Option<usize>), None)
@@ -431,6 +459,7 @@ build_config! {
             "evm" => Ok(Space::Ethereum),
             _ =>  Err("Invalid single_mpt_space".to_owned()),
         })
+        (p2p_encryption, (EncryptionMode), EncryptionMode::Disabled, EncryptionMode::from_str)
     }
 }
 
@@ -444,12 +473,17 @@ macro_rules! set_conf {
 }
 pub struct Configuration {
     pub raw_conf: RawConfiguration,
+    /// Trust the `pow_hash` carried by the imported headers instead of
+    /// recomputing it. It cannot be set in the config file and is only set by
+    /// `import-blocks --skip-pow-check`.
+    pub skip_pow_hash_verification: bool,
 }
 
 impl Default for Configuration {
     fn default() -> Self {
         Configuration {
             raw_conf: Default::default(),
+            skip_pow_hash_verification: false,
         }
     }
 }
@@ -555,6 +589,10 @@ impl Configuration {
         network_config.max_outgoing_peers = self.raw_conf.max_outgoing_peers;
         network_config.max_outgoing_peers_archive =
             self.raw_conf.max_outgoing_peers_archive.unwrap_or(0);
+        network_config.encryption = self.raw_conf.p2p_encryption;
+        network_config.compression_enabled = self.raw_conf.p2p_compression;
+        network_config.compression_threshold =
+            self.raw_conf.p2p_compression_threshold;
         Ok(network_config)
     }
 
@@ -745,6 +783,7 @@ impl Configuration {
             self.raw_conf.max_block_size_in_bytes,
             self.raw_conf.transaction_epoch_bound,
             self.raw_conf.tx_pool_nonce_bits,
+            !self.skip_pow_hash_verification,
             machine,
             pos_verifier,
         )
@@ -997,6 +1036,7 @@ impl Configuration {
                 * self.raw_conf.era_epoch_count as f64)
                 as usize,
             strict_tx_index_gc: self.raw_conf.strict_tx_index_gc,
+            persist_log_index: self.raw_conf.persist_log_index,
         };
 
         // By default, we do not keep the block data for additional period,
@@ -1060,17 +1100,38 @@ impl Configuration {
                 .metrics_influxdb_password
                 .clone(),
             influxdb_report_node: self.raw_conf.metrics_influxdb_node.clone(),
+            prometheus_listen_address: self
+                .raw_conf
+                .metrics_prometheus_listen_address,
         }
     }
 
-    pub fn txpool_config(&self) -> TxPoolConfig {
+    pub fn txpool_config(
+        &self, network: &Network,
+    ) -> Result<TxPoolConfig, String> {
         let (min_native_tx_price_default, min_eth_tx_price_default) =
             if self.is_test_or_dev_mode() {
                 (1, 1)
             } else {
                 (ONE_GDRIP_IN_DRIP, 20 * ONE_GDRIP_IN_DRIP)
             };
-        TxPoolConfig {
+        let mut local_addresses = HashSet::new();
+        for addr in to_address_list(&self.raw_conf.tx_pool_local_addresses) {
+            local_addresses.insert(
+                parse_config_address_string(addr, network)?.with_native_space(),
+            );
+        }
+        for addr in to_address_list(&self.raw_conf.tx_pool_local_evm_addresses)
+        {
+            local_addresses.insert(
+                parse_hex_string::<Address>(addr)
+                    .map_err(|e| {
+                        format!("Invalid tx_pool_local_evm_addresses: {}", e)
+                    })?
+                    .with_evm_space(),
+            );
+        }
+        Ok(TxPoolConfig {
             capacity: self.raw_conf.tx_pool_size,
             half_block_gas_limit: RwLock::new(U256::from(
                 DEFAULT_TARGET_BLOCK_GAS_LIMIT / 2,
@@ -1092,7 +1153,28 @@ impl Configuration {
                 .max_packing_batch_gas_limit,
             max_packing_batch_size: self.raw_conf.max_packing_batch_size,
             packing_pool_degree: self.raw_conf.packing_pool_degree,
-        }
+            packing_strategy: self
+                .raw_conf
+                .packing_strategy
+                .parse::<PackingStrategyKind>()
+                .map_err(|e| format!("Invalid packing_strategy: {}", e))?,
+            journal: self.raw_conf.tx_pool_journal_path.as_ref().map(|path| {
+                TxPoolJournalConfig {
+                    path: path.into(),
+                    journal_all: self.raw_conf.tx_pool_journal_all,
+                    interval: Duration::from_secs(
+                        self.raw_conf.tx_pool_journal_interval_s,
+                    ),
+                    max_size: self.raw_conf.tx_pool_journal_max_size_mb
+                        * 1_000_000,
+                    rotations: self.raw_conf.tx_pool_journal_rotations,
+                }
+            }),
+            local_addresses,
+            private_tx_lifetime_epochs: self
+                .raw_conf
+                .tx_pool_private_tx_lifetime_epochs,
+        })
     }
 
     pub fn rpc_impl_config(&self) -> RpcImplConfiguration {
@@ -1115,6 +1197,40 @@ impl Configuration {
         }
     }
 
+    /// Data directories and P2P/RPC configuration returned by the `admin_`
+    /// RPCs.
+    pub fn admin_node_config(&self) -> NodeConfig {
+        let data_dir = Path::new(&self.raw_conf.conflux_data_dir);
+        let block_db_dir = match &self.raw_conf.block_db_dir {
+            Some(dir) => dir.clone(),
+            None => data_dir.join(BLOCK_DB_DIR_NAME).display().to_string(),
+        };
+        let net_config_dir = match &self.raw_conf.netconf_dir {
+            Some(dir) => dir.clone(),
+            None => data_dir.join(NET_CONFIG_DB_DIR_NAME).display().to_string(),
+        };
+        let api_names = |apis: &ApiSet| {
+            let mut names: Vec<String> =
+                apis.list_apis().iter().map(|api| api.to_string()).collect();
+            names.sort();
+            names
+        };
+
+        NodeConfig {
+            node_type: format!("{:?}", self.node_type()),
+            data_dir: self.raw_conf.conflux_data_dir.clone(),
+            block_db_dir,
+            net_config_dir,
+            tcp_port: self.raw_conf.tcp_port,
+            udp_port: self.raw_conf.udp_port,
+            public_address: self.raw_conf.public_address.clone(),
+            max_incoming_peers: self.raw_conf.max_incoming_peers,
+            max_outgoing_peers: self.raw_conf.max_outgoing_peers,
+            public_rpc_apis: api_names(&self.raw_conf.public_rpc_apis),
+            public_evm_rpc_apis: api_names(&self.raw_conf.public_evm_rpc_apis),
+        }
+    }
+
     pub fn local_http_config(&self) -> HttpConfiguration {
         HttpConfiguration::new(
             Some((127, 0, 0, 1)),
@@ -1473,6 +1589,14 @@ impl Configuration {
             .or(self.raw_conf.next_hardfork_transition_number)
             .unwrap_or(default_transition_time);
 
+        //
+        // BLS12-381 and secp256r1 builtins
+        //
+        params.transition_numbers.crypto_builtins = self
+            .raw_conf
+            .crypto_builtins_transition_number
+            .unwrap_or(default_transition_time);
+
         if params.transition_heights.cip1559
             < self.raw_conf.pos_reference_enable_height
         {
@@ -1505,6 +1629,18 @@ pub fn to_bootnodes(bootnodes: &Option<String>) -> Result<Vec<String>, String> {
     }
 }
 
+/// Splits a comma separated list of addresses, ignoring the empty ones.
+fn to_address_list(addresses: &Option<String>) -> Vec<&str> {
+    match addresses {
+        Some(addresses) => addresses
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None => vec![],
+    }
+}
+
 pub fn parse_config_address_string(
     addr: &str, network: &Network,
 ) -> Result<Address, String> {
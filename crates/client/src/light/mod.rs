@@ -15,8 +15,10 @@ use crate::{
     common::{initialize_common_modules, ClientComponents},
     configuration::Configuration,
     rpc::{
-        extractor::RpcExtractor, impls::light::RpcImpl,
-        setup_debug_rpc_apis_light, setup_public_rpc_apis_light,
+        extractor::RpcExtractor,
+        impls::{eth::light::RpcImpl as EthRpcImpl, light::RpcImpl},
+        setup_debug_rpc_apis_light, setup_public_eth_rpc_apis_light,
+        setup_public_rpc_apis_light,
     },
 };
 use blockgen::BlockGenerator;
@@ -31,6 +33,8 @@ pub struct LightClientExtraComponents {
     pub debug_rpc_http_server: Option<HttpServer>,
     pub debug_rpc_tcp_server: Option<TcpServer>,
     pub debug_rpc_ws_server: Option<WsServer>,
+    pub eth_rpc_http_server: Option<HttpServer>,
+    pub eth_rpc_ws_server: Option<WsServer>,
     pub light: Arc<LightQueryService>,
     pub rpc_http_server: Option<HttpServer>,
     pub rpc_tcp_server: Option<TcpServer>,
@@ -94,13 +98,18 @@ impl LightClient {
             accounts,
             consensus.clone(),
             data_man.clone(),
+            conf.rpc_impl_config().max_estimation_gas_limit,
         ));
 
+        let eth_rpc_impl =
+            Arc::new(EthRpcImpl::new(light.clone(), consensus.clone()));
+
         let debug_rpc_http_server = super::rpc::start_http(
             conf.local_http_config(),
             setup_debug_rpc_apis_light(
                 common_impl.clone(),
                 rpc_impl.clone(),
+                eth_rpc_impl.clone(),
                 pubsub.clone(),
                 eth_pubsub.clone(),
                 &conf,
@@ -112,6 +121,7 @@ impl LightClient {
             setup_debug_rpc_apis_light(
                 common_impl.clone(),
                 rpc_impl.clone(),
+                eth_rpc_impl.clone(),
                 pubsub.clone(),
                 eth_pubsub.clone(),
                 &conf,
@@ -124,6 +134,7 @@ impl LightClient {
             setup_public_rpc_apis_light(
                 common_impl.clone(),
                 rpc_impl.clone(),
+                eth_rpc_impl.clone(),
                 pubsub.clone(),
                 eth_pubsub.clone(),
                 &conf,
@@ -136,6 +147,7 @@ impl LightClient {
             setup_public_rpc_apis_light(
                 common_impl.clone(),
                 rpc_impl.clone(),
+                eth_rpc_impl.clone(),
                 pubsub.clone(),
                 eth_pubsub.clone(),
                 &conf,
@@ -148,6 +160,32 @@ impl LightClient {
             setup_public_rpc_apis_light(
                 common_impl.clone(),
                 rpc_impl.clone(),
+                eth_rpc_impl.clone(),
+                pubsub.clone(),
+                eth_pubsub.clone(),
+                &conf,
+            ),
+            RpcExtractor,
+        )?;
+
+        let eth_rpc_http_server = super::rpc::start_http(
+            conf.eth_http_config(),
+            setup_public_eth_rpc_apis_light(
+                common_impl.clone(),
+                rpc_impl.clone(),
+                eth_rpc_impl.clone(),
+                pubsub.clone(),
+                eth_pubsub.clone(),
+                &conf,
+            ),
+        )?;
+
+        let eth_rpc_ws_server = super::rpc::start_ws(
+            conf.eth_ws_config(),
+            setup_public_eth_rpc_apis_light(
+                common_impl.clone(),
+                rpc_impl.clone(),
+                eth_rpc_impl.clone(),
                 pubsub.clone(),
                 eth_pubsub.clone(),
                 &conf,
@@ -160,6 +198,7 @@ impl LightClient {
             setup_public_rpc_apis_light(
                 common_impl,
                 rpc_impl,
+                eth_rpc_impl,
                 pubsub.clone(),
                 eth_pubsub.clone(),
                 &conf,
@@ -177,6 +216,8 @@ impl LightClient {
                 debug_rpc_http_server,
                 debug_rpc_tcp_server,
                 debug_rpc_ws_server,
+                eth_rpc_http_server,
+                eth_rpc_ws_server,
                 light,
                 rpc_http_server,
                 rpc_tcp_server,
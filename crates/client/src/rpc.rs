@@ -6,6 +6,7 @@ use cfx_rpc_builder::{
     RpcModuleBuilder, RpcModuleSelection, RpcServerConfig, RpcServerHandle,
     TransportRpcModuleConfig,
 };
+use cfx_rpc_cfx_types::admin::NodeConfig;
 use cfxcore::{
     SharedConsensusGraph, SharedSynchronizationService, SharedTransactionPool,
 };
@@ -46,7 +47,10 @@ pub use errors::{error_codes, invalid_params};
 
 use self::{
     impls::{
-        cfx::{CfxHandler, LocalRpcImpl, RpcImpl, TestRpcImpl, TraceHandler},
+        cfx::{
+            AdminHandler, CfxHandler, LocalRpcImpl, RpcImpl, TestRpcImpl,
+            TraceHandler,
+        },
         cfx_filter::CfxFilterClient,
         common::RpcImpl as CommonImpl,
         eth_pubsub::PubSubClient as EthPubSubClient,
@@ -59,12 +63,13 @@ use self::{
         pubsub::PubSubClient,
     },
     traits::{
+        admin::Admin,
         cfx::Cfx,
         cfx_filter::CfxFilter,
         debug::LocalRpc,
         eth_space::{
             eth::Eth, eth_filter::EthFilter, eth_pubsub::EthPubSub,
-            trace::Trace as EthTrace,
+            light_eth::LightEth, trace::Trace as EthTrace,
         },
         pool::TransactionPool,
         pos::Pos,
@@ -79,7 +84,12 @@ use crate::{
     configuration::Configuration,
     rpc::{
         impls::{
-            eth::{EthHandler, EthTraceHandler, GethDebugHandler},
+            eth::{
+                light::{
+                    EthHandler as LightEthHandler, RpcImpl as LightEthImpl,
+                },
+                EthHandler, EthTraceHandler, GethDebugHandler,
+            },
             eth_filter::EthFilterHelper as EthFilterClient,
             RpcImplConfiguration,
         },
@@ -107,6 +117,8 @@ pub fn setup_public_rpc_apis(
         &conf.raw_conf.throttling_conf,
         "rpc",
         conf.raw_conf.public_rpc_apis.list_apis(),
+        conf.admin_node_config(),
+        false, /* local */
     )
 }
 
@@ -122,6 +134,8 @@ pub fn setup_public_eth_rpc_apis(
         &conf.raw_conf.throttling_conf,
         "rpc",
         conf.raw_conf.public_evm_rpc_apis.list_apis(),
+        conf.admin_node_config(),
+        false, /* local */
     )
 }
 
@@ -137,20 +151,23 @@ pub fn setup_debug_rpc_apis(
         &conf.raw_conf.throttling_conf,
         "rpc_local",
         ApiSet::All.list_apis(),
+        conf.admin_node_config(),
+        true, /* local */
     )
 }
 
 fn setup_rpc_apis(
     common: Arc<CommonImpl>, rpc: Arc<RpcImpl>, pubsub: PubSubClient,
     eth_pubsub: EthPubSubClient, throttling_conf: &Option<String>,
-    throttling_section: &str, apis: HashSet<Api>,
+    throttling_section: &str, apis: HashSet<Api>, admin_config: NodeConfig,
+    local: bool,
 ) -> MetaIoHandler<Metadata> {
     let mut handler = MetaIoHandler::default();
     for api in &apis {
         match api {
             Api::Cfx => {
-                let cfx =
-                    CfxHandler::new(common.clone(), rpc.clone()).to_delegate();
+                let cfx = CfxHandler::new(common.clone(), rpc.clone(), local)
+                    .to_delegate();
                 extend_with_interceptor(
                     &mut handler,
                     &rpc.config,
@@ -310,6 +327,15 @@ fn setup_rpc_apis(
                     PoSInterceptor::new(common.pos_handler.clone());
                 handler.extend_with(RpcProxy::new(pos, pos_interceptor));
             }
+            Api::Admin => {
+                handler.extend_with(
+                    AdminHandler::new(
+                        common.network.clone(),
+                        admin_config.clone(),
+                    )
+                    .to_delegate(),
+                );
+            }
         }
     }
 
@@ -365,41 +391,63 @@ fn add_meta_rpc_methods(
 }
 
 pub fn setup_public_rpc_apis_light(
-    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, pubsub: PubSubClient,
-    eth_pubsub: EthPubSubClient, conf: &Configuration,
+    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, eth_rpc: Arc<LightEthImpl>,
+    pubsub: PubSubClient, eth_pubsub: EthPubSubClient, conf: &Configuration,
 ) -> MetaIoHandler<Metadata> {
     setup_rpc_apis_light(
         common,
         rpc,
+        eth_rpc,
         pubsub,
         eth_pubsub,
         &conf.raw_conf.throttling_conf,
         "rpc",
         conf.raw_conf.public_rpc_apis.list_apis(),
+        conf.admin_node_config(),
+    )
+}
+
+pub fn setup_public_eth_rpc_apis_light(
+    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, eth_rpc: Arc<LightEthImpl>,
+    pubsub: PubSubClient, eth_pubsub: EthPubSubClient, conf: &Configuration,
+) -> MetaIoHandler<Metadata> {
+    setup_rpc_apis_light(
+        common,
+        rpc,
+        eth_rpc,
+        pubsub,
+        eth_pubsub,
+        &conf.raw_conf.throttling_conf,
+        "rpc",
+        conf.raw_conf.public_evm_rpc_apis.list_apis(),
+        conf.admin_node_config(),
     )
 }
 
 pub fn setup_debug_rpc_apis_light(
-    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, pubsub: PubSubClient,
-    eth_pubsub: EthPubSubClient, conf: &Configuration,
+    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, eth_rpc: Arc<LightEthImpl>,
+    pubsub: PubSubClient, eth_pubsub: EthPubSubClient, conf: &Configuration,
 ) -> MetaIoHandler<Metadata> {
     let mut light_debug_apis = ApiSet::All.list_apis();
     light_debug_apis.remove(&Api::Trace);
     setup_rpc_apis_light(
         common,
         rpc,
+        eth_rpc,
         pubsub,
         eth_pubsub,
         &conf.raw_conf.throttling_conf,
         "rpc_local",
         light_debug_apis,
+        conf.admin_node_config(),
     )
 }
 
 fn setup_rpc_apis_light(
-    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, pubsub: PubSubClient,
-    eth_pubsub: EthPubSubClient, throttling_conf: &Option<String>,
-    throttling_section: &str, apis: HashSet<Api>,
+    common: Arc<CommonImpl>, rpc: Arc<LightImpl>, eth_rpc: Arc<LightEthImpl>,
+    pubsub: PubSubClient, eth_pubsub: EthPubSubClient,
+    throttling_conf: &Option<String>, throttling_section: &str,
+    apis: HashSet<Api>, admin_config: NodeConfig,
 ) -> MetaIoHandler<Metadata> {
     let mut handler = MetaIoHandler::default();
     for api in apis {
@@ -414,10 +462,16 @@ fn setup_rpc_apis_light(
                 handler.extend_with(RpcProxy::new(cfx, interceptor));
             }
             Api::Eth => {
-                warn!("Light nodes do not support evm ports.");
+                info!("Add light EVM RPC");
+                let evm = LightEthHandler::new(eth_rpc.clone()).to_delegate();
+                let interceptor = ThrottleInterceptor::new(
+                    throttling_conf,
+                    throttling_section,
+                );
+                handler.extend_with(RpcProxy::new(evm, interceptor));
             }
             Api::EthDebug => {
-                warn!("Light nodes do not support evm ports.");
+                warn!("Light nodes do not support evm debug RPC");
             }
             Api::Debug => {
                 handler.extend_with(
@@ -444,6 +498,15 @@ fn setup_rpc_apis_light(
             Api::Pos => {
                 warn!("Light nodes do not support PoS RPC");
             }
+            Api::Admin => {
+                handler.extend_with(
+                    AdminHandler::new(
+                        common.network.clone(),
+                        admin_config.clone(),
+                    )
+                    .to_delegate(),
+                );
+            }
         }
     }
     handler
@@ -521,13 +584,15 @@ pub async fn launch_async_rpc_servers(
     config: RpcImplConfiguration, apis: RpcModuleSelection,
     consensus: SharedConsensusGraph, sync: SharedSynchronizationService,
     tx_pool: SharedTransactionPool, addr: Option<SocketAddr>,
+    admin_config: NodeConfig,
 ) -> Result<Option<RpcServerHandle>, String> {
     if addr.is_none() {
         return Ok(None);
     }
 
     let rpc_module_builder =
-        RpcModuleBuilder::new(config, consensus, sync, tx_pool);
+        RpcModuleBuilder::new(config, consensus, sync, tx_pool)
+            .with_node_config(admin_config);
 
     info!(
         "Enabled evm async rpc modules: {:?}",
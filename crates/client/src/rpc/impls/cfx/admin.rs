@@ -0,0 +1,39 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use crate::rpc::traits::admin::Admin;
+use cfx_rpc_cfx_impl::AdminHandler as CfxAdminHandler;
+use cfx_rpc_cfx_types::admin::{NodeConfig, NodeInfo, PeerInfo};
+use delegate::delegate;
+use jsonrpc_core::Result as JsonRpcResult;
+use network::NetworkService;
+use std::sync::Arc;
+
+/// Admin RPCs for both full and light nodes.
+pub struct AdminHandler {
+    inner: CfxAdminHandler,
+}
+
+impl AdminHandler {
+    pub fn new(network: Arc<NetworkService>, config: NodeConfig) -> Self {
+        AdminHandler {
+            inner: CfxAdminHandler::new(network, config),
+        }
+    }
+}
+
+impl Admin for AdminHandler {
+    delegate! {
+        to self.inner {
+            fn node_info(&self) -> JsonRpcResult<NodeInfo>;
+            fn peers(&self) -> JsonRpcResult<Vec<PeerInfo>>;
+            fn add_peer(&self, url: String) -> JsonRpcResult<bool>;
+            fn remove_peer(&self, url: String) -> JsonRpcResult<bool>;
+            fn add_trusted_peer(&self, url: String) -> JsonRpcResult<bool>;
+            fn remove_trusted_peer(&self, url: String) -> JsonRpcResult<bool>;
+            fn datadir(&self) -> JsonRpcResult<String>;
+            fn config(&self) -> JsonRpcResult<NodeConfig>;
+        }
+    }
+}
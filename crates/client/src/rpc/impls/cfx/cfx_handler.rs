@@ -18,11 +18,11 @@ use crate::rpc::{
     },
 };
 use blockgen::BlockGenerator;
-use cfx_execute_helper::estimation::{decode_error, EstimateExt};
+use cfx_execute_helper::estimation::EstimateExt;
 use cfx_executor::{
-    executive::{ExecutionError, ExecutionOutcome, TxDropError},
-    internal_contract::storage_point_prop,
+    executive::ExecutionOutcome, internal_contract::storage_point_prop,
 };
+use cfx_rpc::{decode_raw_transaction, EthApi};
 use cfx_statedb::{
     global_params::{
         AccumulateInterestRate, BaseFeeProp, DistributablePoSInterest,
@@ -36,7 +36,6 @@ use cfx_types::{
     U64,
 };
 use cfx_util_macros::bail;
-use cfx_vm_types::Error as VmError;
 use cfxcore::{
     block_data_manager::BlockExecutionResult,
     errors::account_result_to_rpc_result,
@@ -53,7 +52,7 @@ use jsonrpc_core::{BoxFuture, Error as JsonRpcError, Result as JsonRpcResult};
 use log::{debug, error, info, trace, warn};
 use network::{
     node_table::{Node, NodeId},
-    throttling, SessionDetails, UpdateNodeOperation,
+    throttling, PeerReputation, SessionDetails, UpdateNodeOperation,
 };
 use parking_lot::Mutex;
 use primitives::{
@@ -74,8 +73,8 @@ use crate::{
     common::delegate_convert,
     rpc::{
         errors::{
-            call_execution_error, internal_error, invalid_params,
-            pivot_assumption_failed, request_rejected_in_catch_up_mode,
+            internal_error, invalid_params, pivot_assumption_failed,
+            request_rejected_in_catch_up_mode,
         },
         impls::{
             common::{self, RpcImpl as CommonImpl},
@@ -86,12 +85,13 @@ use crate::{
             eth::Transaction as EthTransaction, pos::Block as PosBlock,
             Account as RpcAccount, AccountPendingInfo,
             AccountPendingTransactions, BlameInfo, Block as RpcBlock,
-            BlockHashOrEpochNumber, Bytes, CfxRpcLogFilter,
-            CheckBalanceAgainstTransactionResponse, ConsensusGraphStates,
-            EpochNumber, EstimateGasAndCollateralResponse, Log as RpcLog,
-            PackedOrExecuted, Receipt as RpcReceipt,
-            RewardInfo as RpcRewardInfo, Status as RpcStatus,
-            StorageCollateralInfo, SyncGraphStates,
+            BlockHashOrEpochNumber, Bytes, CfxAccountProof, CfxRpcLogFilter,
+            CfxStorageProof, CheckBalanceAgainstTransactionResponse,
+            ConsensusGraphStates, EpochNumber,
+            EstimateGasAndCollateralResponse, Log as RpcLog, PackedOrExecuted,
+            Receipt as RpcReceipt, RewardInfo as RpcRewardInfo,
+            StateCommitment, StateEntryProof, Status as RpcStatus,
+            StorageCollateralInfo, StorageEntryProof, SyncGraphStates,
             Transaction as RpcTransaction, TransactionRequest,
         },
         CoreResult,
@@ -107,12 +107,15 @@ use cfx_parameters::{
     },
     staking::{BLOCKS_PER_YEAR, DRIPS_PER_STORAGE_COLLATERAL_UNIT},
 };
-use cfx_storage::state::StateDbGetOriginalMethods;
+use cfx_storage::{
+    state::StateDbGetOriginalMethods, StorageState, StorageStateTrait,
+};
 use cfxcore::{
     consensus::{MaybeExecutedTxExtraInfo, TransactionInfo},
     consensus_parameters::DEFERRED_STATE_EPOCH_COUNT,
 };
 use diem_types::account_address::AccountAddress;
+use keccak_hash::KECCAK_EMPTY;
 use primitives::transaction::{
     eth_transaction::EthereumTransaction,
     native_transaction::TypedNativeTransaction,
@@ -475,10 +478,9 @@ impl RpcImpl {
         })
     }
 
-    fn send_raw_transaction(&self, raw: Bytes) -> CoreResult<H256> {
-        info!("RPC Request: cfx_sendRawTransaction len={:?}", raw.0.len());
-        debug!("RawTransaction bytes={:?}", raw);
-
+    fn decode_raw_transaction(
+        raw: Bytes,
+    ) -> CoreResult<TransactionWithSignature> {
         let tx: TransactionWithSignature = invalid_params_check(
             "raw",
             TransactionWithSignature::from_raw(&raw.into_vec()),
@@ -490,8 +492,34 @@ impl RpcImpl {
                 "Can not recover pubkey for Ethereum like tx"
             ));
         }
+        Ok(tx)
+    }
+
+    fn send_private_raw_transaction(&self, raw: Bytes) -> CoreResult<H256> {
+        info!(
+            "RPC Request: cfx_sendPrivateRawTransaction len={:?}",
+            raw.0.len()
+        );
+        debug!("RawTransaction bytes={:?}", raw);
+
+        let tx = Self::decode_raw_transaction(raw)?;
+        self.send_transaction_with_signature(
+            tx, /* local */ true, /* private */ true,
+        )
+    }
 
-        let r = self.send_transaction_with_signature(tx);
+    /// Only the transactions sent through the local RPC are treated as local.
+    fn send_raw_transaction(
+        &self, raw: Bytes, local: bool,
+    ) -> CoreResult<H256> {
+        info!("RPC Request: cfx_sendRawTransaction len={:?}", raw.0.len());
+        debug!("RawTransaction bytes={:?}", raw);
+
+        let tx = Self::decode_raw_transaction(raw)?;
+
+        let r = self.send_transaction_with_signature(
+            tx, local, /* private */ false,
+        );
         if r.is_ok() && self.config.dev_pack_tx_immediately {
             // Try to pack and execute this new tx.
             for _ in 0..DEFERRED_STATE_EPOCH_COUNT {
@@ -549,15 +577,22 @@ impl RpcImpl {
         })
     }
 
+    /// The local transactions are exempt from eviction and the minimum gas
+    /// price checks, and the private transactions are never propagated.
     fn send_transaction_with_signature(
-        &self, tx: TransactionWithSignature,
+        &self, tx: TransactionWithSignature, local: bool, private: bool,
     ) -> CoreResult<H256> {
         if self.sync.catch_up_mode() {
             warn!("Ignore send_transaction request {}. Cannot send transaction when the node is still in catch-up mode.", tx.hash());
             bail!(request_rejected_in_catch_up_mode(None));
         }
-        let (signed_trans, failed_trans) =
-            self.tx_pool.insert_new_transactions(vec![tx]);
+        let (signed_trans, failed_trans) = if private {
+            self.tx_pool.insert_new_private_transactions(vec![tx])
+        } else if local {
+            self.tx_pool.insert_new_local_transactions(vec![tx])
+        } else {
+            self.tx_pool.insert_new_transactions(vec![tx])
+        };
 
         match (signed_trans.len(), failed_trans.len()) {
             (0, 0) => {
@@ -574,7 +609,9 @@ impl RpcImpl {
             }
             (1, 0) => {
                 let tx_hash = signed_trans[0].hash();
-                self.sync.append_received_transactions(signed_trans);
+                if !private {
+                    self.sync.append_received_transactions(signed_trans);
+                }
                 Ok(tx_hash.into())
             }
             _ => {
@@ -649,8 +686,11 @@ impl RpcImpl {
     ) -> CoreResult<H256> {
         info!("RPC Request: cfx_sendTransaction, tx = {:?}", tx);
 
-        self.prepare_transaction(tx, password)
-            .and_then(|tx| self.send_transaction_with_signature(tx))
+        self.prepare_transaction(tx, password).and_then(|tx| {
+            self.send_transaction_with_signature(
+                tx, /* local */ true, /* private */ false,
+            )
+        })
     }
 
     pub fn sign_transaction(
@@ -684,6 +724,82 @@ impl RpcImpl {
         Ok(Some(root))
     }
 
+    fn account_proof(
+        &self, address: RpcAddress, epoch_num: Option<EpochNumber>,
+    ) -> CoreResult<CfxAccountProof> {
+        self.check_address_network(address.network)?;
+        let epoch_num = epoch_num.unwrap_or(EpochNumber::LatestState);
+
+        info!(
+            "RPC Request: cfx_getAccountProof address={:?} epoch={:?}",
+            address, epoch_num
+        );
+
+        let (state, commitment) = self.proof_state(epoch_num)?;
+        account_proof_in_state(&state, address, commitment)
+    }
+
+    fn storage_proof(
+        &self, address: RpcAddress, positions: Vec<U256>,
+        epoch_num: Option<EpochNumber>,
+    ) -> CoreResult<CfxStorageProof> {
+        self.check_address_network(address.network)?;
+        let epoch_num = epoch_num.unwrap_or(EpochNumber::LatestState);
+
+        info!(
+            "RPC Request: cfx_getStorageProof address={:?} positions={:?} epoch={:?}",
+            address, positions, epoch_num
+        );
+
+        let (state, commitment) = self.proof_state(epoch_num)?;
+        storage_proof_in_state(&state, address, positions, commitment)
+    }
+
+    /// Returns the provable state of `epoch_num` and the pivot block which
+    /// commits its state root.
+    fn proof_state(
+        &self, epoch_num: EpochNumber,
+    ) -> CoreResult<(StorageState, StateCommitment)> {
+        // Resolve the epoch tag once so that the state and the committing
+        // block are looked up for the same height.
+        let height = invalid_params_check(
+            "epoch_num",
+            self.consensus_graph()
+                .get_height_from_epoch_number(epoch_num.into_primitive()),
+        )?;
+
+        let state = self.consensus.get_storage_state_by_epoch_number(
+            EpochNumber::Num(height.into()).into(),
+            "epoch_num",
+        )?;
+        let state_root = state.get_state_root()?;
+
+        let data_man = self.consensus.get_data_manager();
+        let header = self
+            .consensus
+            .get_hash_from_epoch_number(
+                EpochNumber::Num((height + DEFERRED_STATE_EPOCH_COUNT).into())
+                    .into_primitive(),
+            )
+            .ok()
+            .and_then(|hash| data_man.block_header_by_hash(&hash));
+
+        let commitment = StateCommitment {
+            epoch_number: height.into(),
+            state_root,
+            block_hash: header.as_ref().map(|h| h.hash()),
+            block_header: header
+                .as_ref()
+                .map(|h| Bytes::new(rlp::encode(h.as_ref()))),
+            deferred_state_root: header
+                .as_ref()
+                .map(|h| *h.deferred_state_root()),
+            blame: header.as_ref().map(|h| h.blame()),
+        };
+
+        Ok((state, commitment))
+    }
+
     fn send_usable_genesis_accounts(
         &self, account_start_index: usize,
     ) -> CoreResult<Bytes> {
@@ -1301,47 +1417,7 @@ impl RpcImpl {
         );
         let (execution_outcome, _estimation) =
             self.exec_transaction(request, epoch)?;
-        match execution_outcome {
-            ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(
-                expected,
-                got,
-            )) => bail!(call_execution_error(
-                "Transaction can not be executed".into(),
-                format! {"nonce is too old expected {:?} got {:?}", expected, got}
-            )),
-            ExecutionOutcome::NotExecutedDrop(
-                TxDropError::InvalidRecipientAddress(recipient),
-            ) => bail!(call_execution_error(
-                "Transaction can not be executed".into(),
-                format! {"invalid recipient address {:?}", recipient}
-            )),
-            ExecutionOutcome::NotExecutedDrop(
-                TxDropError::NotEnoughGasLimit { expected, got },
-            ) => bail!(call_execution_error(
-                "Transaction can not be executed".into(),
-                format! {"not enough gas limit with respected to tx size: expected {:?} got {:?}", expected, got}
-            )),
-            ExecutionOutcome::NotExecutedToReconsiderPacking(e) => {
-                bail!(call_execution_error(
-                    "Transaction can not be executed".into(),
-                    format! {"{:?}", e}
-                ))
-            }
-            ExecutionOutcome::ExecutionErrorBumpNonce(
-                ExecutionError::VmError(VmError::Reverted),
-                executed,
-            ) => bail!(call_execution_error(
-                "Transaction reverted".into(),
-                format!("0x{}", executed.output.to_hex::<String>())
-            )),
-            ExecutionOutcome::ExecutionErrorBumpNonce(e, _) => {
-                bail!(call_execution_error(
-                    "Transaction execution failed".into(),
-                    format! {"{:?}", e}
-                ))
-            }
-            ExecutionOutcome::Finished(executed) => Ok(executed.output.into()),
-        }
+        common::call_output(execution_outcome)
     }
 
     fn estimate_gas_and_collateral(
@@ -1352,72 +1428,11 @@ impl RpcImpl {
         );
         let (execution_outcome, estimation) =
             self.exec_transaction(request, epoch)?;
-        match execution_outcome {
-            ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(
-                expected,
-                got,
-            )) => bail!(call_execution_error(
-                "Can not estimate: transaction can not be executed".into(),
-                format! {"nonce is too old expected {:?} got {:?}", expected, got}
-            )),
-            ExecutionOutcome::NotExecutedDrop(
-                TxDropError::InvalidRecipientAddress(recipient),
-            ) => bail!(call_execution_error(
-                "Can not estimate: transaction can not be executed".into(),
-                format! {"invalid recipient address {:?}", recipient}
-            )),
-            ExecutionOutcome::NotExecutedToReconsiderPacking(e) => {
-                bail!(call_execution_error(
-                    "Can not estimate: transaction can not be executed".into(),
-                    format! {"{:?}", e}
-                ))
-            }
-            ExecutionOutcome::NotExecutedDrop(
-                TxDropError::NotEnoughGasLimit { expected, got },
-            ) => bail!(call_execution_error(
-                "Can not estimate: transaction can not be executed".into(),
-                format! {"not enough gas limit with respected to tx size: expected {:?} got {:?}", expected, got}
-            )),
-            ExecutionOutcome::ExecutionErrorBumpNonce(
-                ExecutionError::VmError(VmError::Reverted),
-                executed,
-            ) => {
-                let network_type = *self.sync.network.get_network_type();
-                let (revert_error, innermost_error, errors) =
-                    decode_error(&executed, |addr| {
-                        RpcAddress::try_from_h160(addr.clone(), network_type)
-                            .unwrap()
-                            .base32_address
-                    });
-
-                bail!(call_execution_error(
-                    format!("Estimation isn't accurate: transaction is reverted{}{}",
-                        revert_error, innermost_error),
-                    errors.join("\n"),
-                ))
-            }
-            ExecutionOutcome::ExecutionErrorBumpNonce(e, _) => {
-                bail!(call_execution_error(
-                    format! {"Can not estimate: transaction execution failed, \
-                    all gas will be charged (execution error: {:?})", e}
-                    .into(),
-                    format! {"{:?}", e}
-                ))
-            }
-            ExecutionOutcome::Finished(executed) => executed,
-        };
-        let storage_collateralized =
-            U64::from(estimation.estimated_storage_limit);
-        let estimated_gas_used = estimation.estimated_gas_limit;
-        let response = EstimateGasAndCollateralResponse {
-            gas_limit: estimated_gas_used, /* gas_limit used to be 4/3 of
-                                            * gas_used due to inaccuracy,
-                                            * currently it's the same as gas
-                                            * used as it's more accurate */
-            gas_used: estimated_gas_used,
-            storage_collateralized,
-        };
-        Ok(response)
+        common::estimate_gas_and_collateral_response(
+            execution_outcome,
+            estimation,
+            *self.sync.network.get_network_type(),
+        )
     }
 
     fn check_balance_against_transaction(
@@ -1492,6 +1507,7 @@ impl RpcImpl {
             has_gas_price: request.has_gas_price(),
             has_nonce: request.nonce.is_some(),
             has_storage_limit: request.storage_limit.is_some(),
+            collect_access_list: false,
         };
 
         let epoch_height = consensus_graph
@@ -1504,7 +1520,12 @@ impl RpcImpl {
         )?;
         trace!("call tx {:?}", signed_tx);
 
-        consensus_graph.call_virtual(&signed_tx, epoch.into(), estimate_request)
+        consensus_graph.call_virtual(
+            &signed_tx,
+            epoch.into(),
+            estimate_request,
+            Default::default(),
+        )
     }
 
     fn current_sync_phase(&self) -> CoreResult<String> {
@@ -2282,15 +2303,99 @@ impl RpcImpl {
     }
 }
 
+/// Builds the proofs of the account entry, the code entry and the storage
+/// root of `address` in `state`.
+fn account_proof_in_state(
+    state: &StorageState, address: RpcAddress, commitment: StateCommitment,
+) -> CoreResult<CfxAccountProof> {
+    let hex_address = address.hex_address;
+
+    let (account_value, account_proof) = state.get_original_raw_with_proof(
+        StorageKey::new_account_key(&hex_address).with_native_space(),
+    )?;
+
+    let code_hash = match &account_value {
+        Some(raw) => {
+            Account::new_from_rlp(hex_address, &Rlp::new(raw))
+                .map_err(|e| internal_error(e))?
+                .code_hash
+        }
+        None => KECCAK_EMPTY,
+    };
+
+    let (code_value, code_proof) = state.get_original_raw_with_proof(
+        StorageKey::new_code_key(&hex_address, &code_hash).with_native_space(),
+    )?;
+
+    let (storage_root, storage_root_proof) = state
+        .get_original_storage_root_with_proof(
+            &hex_address.with_native_space(),
+        )?;
+
+    Ok(CfxAccountProof {
+        address,
+        account: StateEntryProof {
+            value: account_value.map(|v| Bytes::new(v.into_vec())),
+            proof: Bytes::new(rlp::encode(&account_proof)),
+        },
+        code: StateEntryProof {
+            value: code_value.map(|v| Bytes::new(v.into_vec())),
+            proof: Bytes::new(rlp::encode(&code_proof)),
+        },
+        storage_root,
+        storage_root_proof: Bytes::new(rlp::encode(&storage_root_proof)),
+        commitment,
+    })
+}
+
+/// Builds the proofs of the storage entries of `address` at `positions` in
+/// `state`.
+fn storage_proof_in_state(
+    state: &StorageState, address: RpcAddress, positions: Vec<U256>,
+    commitment: StateCommitment,
+) -> CoreResult<CfxStorageProof> {
+    let mut entries = Vec::with_capacity(positions.len());
+    for position in positions {
+        let key = H256::from_uint(&position);
+        let (value, proof) = state.get_original_raw_with_proof(
+            StorageKey::new_storage_key(&address.hex_address, key.as_ref())
+                .with_native_space(),
+        )?;
+
+        entries.push(StorageEntryProof {
+            position,
+            entry: StateEntryProof {
+                value: value.map(|v| Bytes::new(v.into_vec())),
+                proof: Bytes::new(rlp::encode(&proof)),
+            },
+        });
+    }
+
+    Ok(CfxStorageProof {
+        address,
+        entries,
+        commitment,
+    })
+}
+
 #[allow(dead_code)]
 pub struct CfxHandler {
     common: Arc<CommonImpl>,
     rpc_impl: Arc<RpcImpl>,
+    /// Whether this handler serves the local RPC, whose transactions are
+    /// treated as local by the transaction pool.
+    local: bool,
 }
 
 impl CfxHandler {
-    pub fn new(common: Arc<CommonImpl>, rpc_impl: Arc<RpcImpl>) -> Self {
-        CfxHandler { common, rpc_impl }
+    pub fn new(
+        common: Arc<CommonImpl>, rpc_impl: Arc<RpcImpl>, local: bool,
+    ) -> Self {
+        CfxHandler {
+            common,
+            rpc_impl,
+            local,
+        }
     }
 }
 
@@ -2340,27 +2445,34 @@ impl Cfx for CfxHandler {
             fn collateral_for_storage(&self, address: RpcAddress, num: Option<EpochNumber>)
                 -> BoxFuture<JsonRpcResult<U256>>;
             fn call(&self, request: TransactionRequest, block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>)
-                -> JsonRpcResult<Bytes>;
+                -> BoxFuture<JsonRpcResult<Bytes>>;
             fn estimate_gas_and_collateral(
                 &self, request: TransactionRequest, epoch_number: Option<EpochNumber>)
-                -> JsonRpcResult<EstimateGasAndCollateralResponse>;
+                -> BoxFuture<JsonRpcResult<EstimateGasAndCollateralResponse>>;
             fn check_balance_against_transaction(
                 &self, account_addr: RpcAddress, contract_addr: RpcAddress, gas_limit: U256, gas_price: U256, storage_limit: U256, epoch: Option<EpochNumber>,
             ) -> BoxFuture<JsonRpcResult<CheckBalanceAgainstTransactionResponse>>;
             fn get_logs(&self, filter: CfxRpcLogFilter) -> BoxFuture<JsonRpcResult<Vec<RpcLog>>>;
             fn get_block_reward_info(&self, num: EpochNumber) -> JsonRpcResult<Vec<RpcRewardInfo>>;
-            fn send_raw_transaction(&self, raw: Bytes) -> JsonRpcResult<H256>;
             fn storage_at(&self, addr: RpcAddress, pos: U256, block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>)
                 -> BoxFuture<JsonRpcResult<Option<H256>>>;
             fn transaction_by_hash(&self, hash: H256) -> BoxFuture<JsonRpcResult<Option<RpcTransaction>>>;
             fn transaction_receipt(&self, tx_hash: H256) -> BoxFuture<JsonRpcResult<Option<RpcReceipt>>>;
             fn storage_root(&self, address: RpcAddress, epoch_num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<Option<StorageRoot>>>;
+            fn account_proof(&self, address: RpcAddress, epoch_num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<CfxAccountProof>>;
+            fn storage_proof(&self, address: RpcAddress, positions: Vec<U256>, epoch_num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<CfxStorageProof>>;
             fn get_supply_info(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<TokenSupplyInfo>;
             fn get_collateral_info(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<StorageCollateralInfo>;
             fn get_vote_params(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<VoteParamsInfo>;
             fn get_fee_burnt(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<U256>;
         }
     }
+
+    fn send_raw_transaction(&self, raw: Bytes) -> JsonRpcResult<H256> {
+        self.rpc_impl
+            .send_raw_transaction(raw, self.local)
+            .map_err(Into::into)
+    }
 }
 
 #[allow(dead_code)]
@@ -2438,11 +2550,22 @@ impl TestRpc for TestRpcImpl {
 pub struct LocalRpcImpl {
     common: Arc<CommonImpl>,
     rpc_impl: Arc<RpcImpl>,
+    eth: EthApi,
 }
 
 impl LocalRpcImpl {
     pub fn new(common: Arc<CommonImpl>, rpc_impl: Arc<RpcImpl>) -> Self {
-        LocalRpcImpl { common, rpc_impl }
+        let eth = EthApi::new(
+            rpc_impl.config.clone(),
+            rpc_impl.consensus.clone(),
+            rpc_impl.sync.clone(),
+            rpc_impl.tx_pool.clone(),
+        );
+        LocalRpcImpl {
+            common,
+            rpc_impl,
+            eth,
+        }
     }
 }
 
@@ -2458,6 +2581,10 @@ impl LocalRpc for LocalRpcImpl {
             fn net_node(&self, id: NodeId) -> JsonRpcResult<Option<(String, Node)>>;
             fn net_disconnect_node(&self, id: NodeId, op: Option<UpdateNodeOperation>)
                 -> JsonRpcResult<bool>;
+            fn net_reputation(&self, id: NodeId) -> JsonRpcResult<Option<PeerReputation>>;
+            fn net_set_reputation(&self, id: NodeId, score: f64) -> JsonRpcResult<Option<PeerReputation>>;
+            fn net_ban_node(&self, id: NodeId, duration: Option<U64>) -> JsonRpcResult<bool>;
+            fn net_unban_node(&self, id: NodeId) -> JsonRpcResult<bool>;
             fn net_sessions(&self, node_id: Option<NodeId>) -> JsonRpcResult<Vec<SessionDetails>>;
             fn net_throttling(&self) -> JsonRpcResult<throttling::Service>;
             fn accounts(&self) -> JsonRpcResult<Vec<RpcAddress>>;
@@ -2480,9 +2607,189 @@ impl LocalRpc for LocalRpcImpl {
             fn sync_graph_state(&self) -> JsonRpcResult<SyncGraphStates>;
             fn send_transaction(
                 &self, tx: TransactionRequest, password: Option<String>) -> BoxFuture<JsonRpcResult<H256>>;
+            fn send_private_raw_transaction(&self, raw: Bytes) -> JsonRpcResult<H256>;
             fn sign_transaction(&self, tx: TransactionRequest, password: Option<String>) -> JsonRpcResult<String>;
             fn transactions_by_epoch(&self, epoch_number: U64) -> JsonRpcResult<Vec<WrapTransaction>>;
             fn transactions_by_block(&self, block_hash: H256) -> JsonRpcResult<Vec<WrapTransaction>>;
         }
     }
+
+    fn eth_send_private_raw_transaction(
+        &self, raw: Bytes,
+    ) -> JsonRpcResult<H256> {
+        info!(
+            "RPC Request: eth_sendPrivateRawTransaction len={:?}",
+            raw.0.len()
+        );
+        debug!("RawTransaction bytes={:?}", raw);
+
+        let tx = decode_raw_transaction(raw)?;
+        Ok(self
+            .eth
+            .send_transaction_with_signature(tx, true /* private */)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{account_proof_in_state, storage_proof_in_state};
+    use crate::rpc::types::{RpcAddress, StateCommitment};
+    use cfx_addr::Network;
+    use cfx_storage::{
+        new_storage_manager_for_testing, state_manager::StateManagerTrait,
+        verify_state_proof, verify_storage_root_proof, StateIndex, StateProof,
+        StorageRootProof,
+    };
+    use cfx_types::{
+        address_util::AddressUtil, AddressSpaceUtil, BigEndianHash, H160, H256,
+        U256,
+    };
+    use keccak_hash::keccak;
+    use primitives::{Account, CodeInfo, EpochId, StorageKey, StorageValue};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_state_proofs() {
+        let mut address = H160::from_low_u64_be(0x1234);
+        address.set_user_account_type_bits();
+        let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+        let position = U256::from(7);
+
+        let storage_manager = new_storage_manager_for_testing();
+        let mut state = storage_manager.get_state_for_genesis_write();
+        let mut account = Account::new_empty_with_balance(
+            &address.with_native_space(),
+            &U256::from(100),
+            &U256::from(3),
+        );
+        account.code_hash = keccak(&code);
+        state
+            .set(
+                StorageKey::new_account_key(&address).with_native_space(),
+                rlp::encode(&account).into(),
+            )
+            .unwrap();
+        let code_info = CodeInfo {
+            code: Arc::new(code),
+            owner: address,
+        };
+        state
+            .set(
+                StorageKey::new_code_key(&address, &account.code_hash)
+                    .with_native_space(),
+                rlp::encode(&code_info).into(),
+            )
+            .unwrap();
+        let storage_value = StorageValue {
+            value: U256::from(42),
+            owner: None,
+        };
+        state
+            .set(
+                StorageKey::new_storage_key(
+                    &address,
+                    H256::from_uint(&position).as_bytes(),
+                )
+                .with_native_space(),
+                rlp::encode(&storage_value).into(),
+            )
+            .unwrap();
+        let mut epoch_id = EpochId::default();
+        epoch_id.as_bytes_mut()[0] = 1;
+        state.compute_state_root().unwrap();
+        let root = state.commit(epoch_id).unwrap();
+        let root_hash = root.state_root.compute_state_root_hash();
+
+        let state = storage_manager
+            .get_state_no_commit_inner(
+                StateIndex::new_for_readonly(&epoch_id, &root),
+                /* try_open = */ false,
+                /* open_mpt_snapshot = */ false,
+            )
+            .unwrap()
+            .unwrap();
+        let commitment = StateCommitment {
+            epoch_number: Default::default(),
+            state_root: root.clone(),
+            block_hash: None,
+            block_header: None,
+            deferred_state_root: None,
+            blame: None,
+        };
+        let rpc_address =
+            RpcAddress::try_from_h160(address, Network::Main).unwrap();
+
+        // the account, its code and its storage root are proven
+        let proof = account_proof_in_state(
+            &state,
+            rpc_address.clone(),
+            commitment.clone(),
+        )
+        .unwrap();
+        let account_value = proof.account.value.unwrap().0;
+        assert_eq!(account_value, rlp::encode(&account).to_vec());
+        assert!(verify_state_proof(
+            &root_hash,
+            &proof.commitment.state_root,
+            StorageKey::new_account_key(&address).with_native_space(),
+            Some(account_value.as_slice()),
+            &rlp::decode::<StateProof>(&proof.account.proof.0).unwrap(),
+        ));
+        let code_value = proof.code.value.unwrap().0;
+        assert!(verify_state_proof(
+            &root_hash,
+            &root,
+            StorageKey::new_code_key(&address, &account.code_hash)
+                .with_native_space(),
+            Some(code_value.as_slice()),
+            &rlp::decode::<StateProof>(&proof.code.proof.0).unwrap(),
+        ));
+        assert!(verify_storage_root_proof(
+            &root_hash,
+            &root,
+            &address.with_native_space(),
+            &proof.storage_root,
+            &rlp::decode::<StorageRootProof>(&proof.storage_root_proof.0)
+                .unwrap(),
+        ));
+
+        // a tampered value is not proven
+        let tampered =
+            rlp::encode(&Account::new_empty(&address.with_native_space()));
+        assert!(!verify_state_proof(
+            &root_hash,
+            &root,
+            StorageKey::new_account_key(&address).with_native_space(),
+            Some(&tampered[..]),
+            &rlp::decode::<StateProof>(&proof.account.proof.0).unwrap(),
+        ));
+
+        // an existing and a missing storage entry are proven
+        let missing = U256::from(8);
+        let proof = storage_proof_in_state(
+            &state,
+            rpc_address,
+            vec![position, missing],
+            commitment,
+        )
+        .unwrap();
+        assert_eq!(proof.entries.len(), 2);
+        for entry in &proof.entries {
+            let key = H256::from_uint(&entry.position);
+            let value = entry.entry.value.as_ref().map(|v| v.0.as_slice());
+            assert_eq!(value.is_some(), entry.position == position);
+            assert!(verify_state_proof(
+                &root_hash,
+                &root,
+                StorageKey::new_storage_key(&address, key.as_bytes())
+                    .with_native_space(),
+                value,
+                &rlp::decode::<StateProof>(&entry.entry.proof.0).unwrap(),
+            ));
+        }
+        assert_eq!(
+            proof.entries[0].entry.value.as_ref().unwrap().0,
+            rlp::encode(&storage_value).to_vec()
+        );
+    }
 }
@@ -11,15 +11,16 @@ use std::{
 };
 
 use crate::rpc::{
-    errors::invalid_params_check,
+    errors::{call_execution_error, invalid_params, invalid_params_check},
     helpers::MAX_FEE_HISTORY_CACHE_BLOCK_COUNT,
     impls::pos::hash_value_to_h256,
     types::{
         cfx::check_rpc_address_network, pos::PoSEpochReward,
         AccountPendingInfo, AccountPendingTransactions, Block as RpcBlock,
         BlockHashOrEpochNumber, Bytes, CfxFeeHistory,
-        CheckBalanceAgainstTransactionResponse, EpochNumber, FeeHistory,
-        RpcAddress, Status as RpcStatus, Transaction as RpcTransaction,
+        CheckBalanceAgainstTransactionResponse, EpochNumber,
+        EstimateGasAndCollateralResponse, FeeHistory, RpcAddress,
+        Status as RpcStatus, Transaction as RpcTransaction,
         TxPoolPendingNonceRange, TxPoolStatus, TxWithPoolInfo, U64 as HexU64,
     },
     CoreResult,
@@ -36,6 +37,8 @@ use parking_lot::{Condvar, Mutex};
 
 use crate::rpc::types::pos::{Block as RpcPosBlock, Decision};
 use cfx_addr::Network;
+use cfx_execute_helper::estimation::{decode_error, EstimateExt};
+use cfx_executor::executive::{ExecutionError, ExecutionOutcome, TxDropError};
 use cfx_parameters::{
     rpc::GAS_PRICE_DEFAULT_VALUE, staking::DRIPS_PER_STORAGE_COLLATERAL_UNIT,
 };
@@ -43,6 +46,7 @@ use cfx_rpc_utils::error::jsonrpc_error_helpers::internal_rpc_err;
 use cfx_types::{
     Address, AddressSpaceUtil, Space, H160, H256, H520, U128, U256, U512, U64,
 };
+use cfx_vm_types::Error as VmError;
 use cfxcore::{
     consensus::pos_handler::PosVerifier, errors::Error as CoreError,
     genesis_block::register_transaction, BlockDataManager, ConsensusGraph,
@@ -59,9 +63,10 @@ use diem_types::{
 use network::{
     node_table::{Node, NodeEndpoint, NodeEntry, NodeId},
     throttling::{self, THROTTLING_SERVICE},
-    NetworkService, SessionDetails, UpdateNodeOperation,
+    NetworkService, PeerReputation, SessionDetails, UpdateNodeOperation,
 };
 use primitives::{Account, Action, Block, SignedTransaction, Transaction};
+use rustc_hex::ToHex;
 use storage_interface::DBReaderForPoW;
 
 fn grouped_txs<T, F>(
@@ -154,11 +159,132 @@ pub fn check_balance_against_transaction(
     }
 }
 
+/// Converts the outcome of a virtual call to the result of `cfx_call`.
+pub fn call_output(execution_outcome: ExecutionOutcome) -> CoreResult<Bytes> {
+    match execution_outcome {
+        ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(
+            expected,
+            got,
+        )) => bail!(call_execution_error(
+            "Transaction can not be executed".into(),
+            format! {"nonce is too old expected {:?} got {:?}", expected, got}
+        )),
+        ExecutionOutcome::NotExecutedDrop(
+            TxDropError::InvalidRecipientAddress(recipient),
+        ) => bail!(call_execution_error(
+            "Transaction can not be executed".into(),
+            format! {"invalid recipient address {:?}", recipient}
+        )),
+        ExecutionOutcome::NotExecutedDrop(TxDropError::NotEnoughGasLimit {
+            expected,
+            got,
+        }) => bail!(call_execution_error(
+            "Transaction can not be executed".into(),
+            format! {"not enough gas limit with respected to tx size: expected {:?} got {:?}", expected, got}
+        )),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(e) => {
+            bail!(call_execution_error(
+                "Transaction can not be executed".into(),
+                format! {"{:?}", e}
+            ))
+        }
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(VmError::Reverted),
+            executed,
+        ) => bail!(call_execution_error(
+            "Transaction reverted".into(),
+            format!("0x{}", executed.output.to_hex::<String>())
+        )),
+        ExecutionOutcome::ExecutionErrorBumpNonce(e, _) => {
+            bail!(call_execution_error(
+                "Transaction execution failed".into(),
+                format! {"{:?}", e}
+            ))
+        }
+        ExecutionOutcome::Finished(executed) => Ok(executed.output.into()),
+    }
+}
+
+/// Converts the outcome of a virtual call to the result of
+/// `cfx_estimateGasAndCollateral`.
+pub fn estimate_gas_and_collateral_response(
+    execution_outcome: ExecutionOutcome, estimation: EstimateExt,
+    network_type: Network,
+) -> CoreResult<EstimateGasAndCollateralResponse> {
+    match execution_outcome {
+        ExecutionOutcome::NotExecutedDrop(TxDropError::OldNonce(
+            expected,
+            got,
+        )) => bail!(call_execution_error(
+            "Can not estimate: transaction can not be executed".into(),
+            format! {"nonce is too old expected {:?} got {:?}", expected, got}
+        )),
+        ExecutionOutcome::NotExecutedDrop(
+            TxDropError::InvalidRecipientAddress(recipient),
+        ) => bail!(call_execution_error(
+            "Can not estimate: transaction can not be executed".into(),
+            format! {"invalid recipient address {:?}", recipient}
+        )),
+        ExecutionOutcome::NotExecutedToReconsiderPacking(e) => {
+            bail!(call_execution_error(
+                "Can not estimate: transaction can not be executed".into(),
+                format! {"{:?}", e}
+            ))
+        }
+        ExecutionOutcome::NotExecutedDrop(TxDropError::NotEnoughGasLimit {
+            expected,
+            got,
+        }) => bail!(call_execution_error(
+            "Can not estimate: transaction can not be executed".into(),
+            format! {"not enough gas limit with respected to tx size: expected {:?} got {:?}", expected, got}
+        )),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(VmError::Reverted),
+            executed,
+        ) => {
+            let (revert_error, innermost_error, errors) =
+                decode_error(&executed, |addr| {
+                    RpcAddress::try_from_h160(addr.clone(), network_type)
+                        .unwrap()
+                        .base32_address
+                });
+
+            bail!(call_execution_error(
+                format!(
+                    "Estimation isn't accurate: transaction is reverted{}{}",
+                    revert_error, innermost_error
+                ),
+                errors.join("\n"),
+            ))
+        }
+        ExecutionOutcome::ExecutionErrorBumpNonce(e, _) => {
+            bail!(call_execution_error(
+                format! {"Can not estimate: transaction execution failed, \
+                all gas will be charged (execution error: {:?})", e}
+                .into(),
+                format! {"{:?}", e}
+            ))
+        }
+        ExecutionOutcome::Finished(executed) => executed,
+    };
+    let storage_collateralized = U64::from(estimation.estimated_storage_limit);
+    let estimated_gas_used = estimation.estimated_gas_limit;
+    let response = EstimateGasAndCollateralResponse {
+        gas_limit: estimated_gas_used, /* gas_limit used to be 4/3 of
+                                        * gas_used due to inaccuracy,
+                                        * currently it's the same as gas
+                                        * used as it's more accurate */
+        gas_used: estimated_gas_used,
+        storage_collateralized,
+    };
+    Ok(response)
+}
+
 pub struct RpcImpl {
     exit: Arc<(Mutex<bool>, Condvar)>,
     consensus: SharedConsensusGraph,
     data_man: Arc<BlockDataManager>,
-    network: Arc<NetworkService>,
+    pub network: Arc<NetworkService>,
     tx_pool: SharedTransactionPool,
     accounts: Arc<AccountProvider>,
     pub pos_handler: Arc<PosVerifier>,
@@ -1079,6 +1205,32 @@ impl RpcImpl {
         Ok(self.network.disconnect_node(&id, op))
     }
 
+    pub fn net_reputation(
+        &self, id: NodeId,
+    ) -> JsonRpcResult<Option<PeerReputation>> {
+        Ok(self.network.get_peer_reputation(&id))
+    }
+
+    pub fn net_set_reputation(
+        &self, id: NodeId, score: f64,
+    ) -> JsonRpcResult<Option<PeerReputation>> {
+        if !score.is_finite() {
+            bail!(invalid_params("score", "score should be a finite number"));
+        }
+        Ok(self.network.set_peer_reputation(&id, score))
+    }
+
+    pub fn net_ban_node(
+        &self, id: NodeId, duration: Option<U64>,
+    ) -> JsonRpcResult<bool> {
+        let duration = duration.map(|d| Duration::from_secs(d.as_u64()));
+        Ok(self.network.ban_node(&id, duration))
+    }
+
+    pub fn net_unban_node(&self, id: NodeId) -> JsonRpcResult<bool> {
+        Ok(self.network.unban_node(&id))
+    }
+
     pub fn net_sessions(
         &self, node_id: Option<NodeId>,
     ) -> JsonRpcResult<Vec<SessionDetails>> {
@@ -1103,6 +1255,8 @@ impl RpcImpl {
             if self.tx_pool.check_tx_packed_in_deferred_pool(&hash) {
                 ret.packed = true;
             }
+            ret.is_local = self.tx_pool.is_local_transaction(&hash);
+            ret.is_private = self.tx_pool.is_private_transaction(&hash);
             let (local_nonce, local_balance) =
                 self.tx_pool.get_local_account_info(&tx.sender());
             let (state_nonce, state_balance) = self
@@ -1192,9 +1346,9 @@ impl RpcImpl {
             }
         };
 
-        let (ready_txs, deferred_txs) = self
-            .tx_pool
-            .content(address.map(AddressSpaceUtil::with_native_space));
+        let address = address.map(AddressSpaceUtil::with_native_space);
+        let (ready_txs, deferred_txs) = self.tx_pool.content(address);
+        let local_txs = self.tx_pool.local_transactions(address);
         let converter = |tx: Arc<SignedTransaction>| -> RpcTransaction {
             RpcTransaction::from_signed(&tx, None, *self.network.get_network_type())
                 .expect("transaction conversion with correct network id should not fail")
@@ -1206,6 +1360,7 @@ impl RpcImpl {
         > = BTreeMap::new();
         ret.insert("ready".into(), grouped_txs(ready_txs, converter));
         ret.insert("deferred".into(), grouped_txs(deferred_txs, converter));
+        ret.insert("local".into(), grouped_txs(local_txs, converter));
 
         Ok(ret)
     }
@@ -1260,6 +1415,7 @@ impl RpcImpl {
             ready: U64::from(ready_len),
             received: U64::from(received_len),
             unexecuted: U64::from(unexecuted_len),
+            private: U64::from(self.tx_pool.total_private()),
         })
     }
 
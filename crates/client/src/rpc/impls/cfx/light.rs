@@ -20,10 +20,10 @@ use delegate::delegate;
 use diem_types::transaction::TransactionPayload;
 use futures::future::{self, FutureExt};
 use jsonrpc_core::{BoxFuture, Error as RpcError, Result as JsonRpcResult};
-use log::{debug, info};
+use log::{debug, info, trace};
 use network::{
     node_table::{Node, NodeId},
-    throttling, SessionDetails, UpdateNodeOperation,
+    throttling, PeerReputation, SessionDetails, UpdateNodeOperation,
 };
 use primitives::{
     Account, DepositInfo, StorageRoot, TransactionWithSignature, VoteStakeInfo,
@@ -39,11 +39,14 @@ use crate::{
         impls::common::{self, RpcImpl as CommonImpl},
         traits::{cfx::Cfx, debug::LocalRpc, test::TestRpc},
         types::{
-            cfx::check_rpc_address_network,
+            cfx::{
+                check_rpc_address_network, check_two_rpc_address_network_match,
+            },
             pos::{Block as PosBlock, PoSEpochReward},
             Account as RpcAccount, AccountPendingInfo,
             AccountPendingTransactions, BlameInfo, Block as RpcBlock,
-            BlockHashOrEpochNumber, Bytes, CfxFeeHistory, CfxRpcLogFilter,
+            BlockHashOrEpochNumber, Bytes, CfxAccountProof, CfxFeeHistory,
+            CfxRpcLogFilter, CfxStorageProof,
             CheckBalanceAgainstTransactionResponse, ConsensusGraphStates,
             EpochNumber, EstimateGasAndCollateralResponse, FeeHistory,
             Log as RpcLog, PoSEconomics, Receipt as RpcReceipt,
@@ -56,6 +59,8 @@ use crate::{
     },
 };
 use cfx_addr::Network;
+use cfx_execute_helper::estimation::{EstimateExt, EstimateRequest};
+use cfx_executor::executive::ExecutionOutcome;
 use cfx_parameters::rpc::GAS_PRICE_DEFAULT_VALUE;
 use cfxcore::{errors::Error::LightProtocol, light_protocol::QueryService};
 use diem_types::account_address::AccountAddress;
@@ -95,18 +100,23 @@ pub struct RpcImpl {
 
     // helper API for retrieving verified information from peers
     light: Arc<LightQueryService>,
+
+    // the gas limit of virtual calls without a specified gas limit
+    max_estimation_gas_limit: Option<U256>,
 }
 
 impl RpcImpl {
     pub fn new(
         light: Arc<LightQueryService>, accounts: Arc<AccountProvider>,
         consensus: SharedConsensusGraph, data_man: Arc<BlockDataManager>,
+        max_estimation_gas_limit: Option<U256>,
     ) -> Self {
         RpcImpl {
             accounts,
             consensus,
             data_man,
             light,
+            max_estimation_gas_limit,
         }
     }
 
@@ -1091,6 +1101,115 @@ impl RpcImpl {
         fut.boxed()
     }
 
+    fn exec_transaction(
+        &self, request: TransactionRequest,
+        block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>,
+    ) -> CoreBoxFuture<(ExecutionOutcome, EstimateExt)> {
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus_graph = self.consensus.clone();
+        let light = self.light.clone();
+        let max_estimation_gas_limit = self.max_estimation_gas_limit;
+
+        let fut = async move {
+            let rpc_request_network = invalid_params_check(
+                "request",
+                check_two_rpc_address_network_match(
+                    request.from.as_ref(),
+                    request.to.as_ref(),
+                ),
+            )?;
+            invalid_params_check(
+                "request",
+                check_rpc_address_network(
+                    rpc_request_network,
+                    light.get_network_type(),
+                ),
+            )?;
+
+            let epoch = Self::get_epoch_number_with_pivot_check(
+                consensus_graph,
+                block_hash_or_epoch_number,
+            )?;
+            let epoch_height = invalid_params_check(
+                "epoch",
+                light.get_height_from_epoch_number(epoch.into()),
+            )?;
+
+            let estimate_request = EstimateRequest {
+                has_sender: request.from.is_some(),
+                has_gas_limit: request.gas.is_some(),
+                has_gas_price: request.has_gas_price(),
+                has_nonce: request.nonce.is_some(),
+                has_storage_limit: request.storage_limit.is_some(),
+                collect_access_list: false,
+            };
+
+            let chain_id = light.get_chain_id(epoch_height);
+            let signed_tx = request.sign_call(
+                epoch_height,
+                chain_id.in_native_space(),
+                max_estimation_gas_limit,
+            )?;
+            trace!("call tx {:?}", signed_tx);
+
+            light
+                .call_virtual(
+                    signed_tx,
+                    primitives::EpochNumber::Number(epoch_height),
+                    estimate_request,
+                )
+                .await
+        };
+
+        fut.boxed()
+    }
+
+    fn call(
+        &self, request: TransactionRequest,
+        block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>,
+    ) -> CoreBoxFuture<Bytes> {
+        info!(
+            "RPC Request: cfx_call request={:?} block_hash_or_epoch_number={:?}",
+            request, block_hash_or_epoch_number
+        );
+
+        let execution =
+            self.exec_transaction(request, block_hash_or_epoch_number);
+
+        let fut = async move {
+            let (execution_outcome, _estimation) = execution.await?;
+            common::call_output(execution_outcome)
+        };
+
+        fut.boxed()
+    }
+
+    fn estimate_gas_and_collateral(
+        &self, request: TransactionRequest, epoch: Option<EpochNumber>,
+    ) -> CoreBoxFuture<EstimateGasAndCollateralResponse> {
+        info!(
+            "RPC Request: cfx_estimateGasAndCollateral request={:?}, epoch={:?}",
+            request, epoch
+        );
+
+        let network_type = *self.light.get_network_type();
+        let execution = self.exec_transaction(
+            request,
+            epoch.map(BlockHashOrEpochNumber::EpochNumber),
+        );
+
+        let fut = async move {
+            let (execution_outcome, estimation) = execution.await?;
+            common::estimate_gas_and_collateral_response(
+                execution_outcome,
+                estimation,
+                network_type,
+            )
+        };
+
+        fut.boxed()
+    }
+
     fn fee_history(
         &self, mut block_count: HexU64, newest_block: EpochNumber,
         reward_percentiles: Option<Vec<f64>>,
@@ -1231,11 +1350,13 @@ impl Cfx for CfxHandler {
             fn block_by_hash_with_pivot_assumption(&self, block_hash: H256, pivot_hash: H256, epoch_number: U64) -> BoxFuture<JsonRpcResult<RpcBlock>>;
             fn block_by_hash(&self, hash: H256, include_txs: bool) -> BoxFuture<JsonRpcResult<Option<RpcBlock>>>;
             fn blocks_by_epoch(&self, num: EpochNumber) -> JsonRpcResult<Vec<H256>>;
+            fn call(&self, request: TransactionRequest, block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>) -> BoxFuture<JsonRpcResult<Bytes>>;
             fn check_balance_against_transaction(&self, account_addr: RpcAddress, contract_addr: RpcAddress, gas_limit: U256, gas_price: U256, storage_limit: U256, epoch: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<CheckBalanceAgainstTransactionResponse>>;
             fn code(&self, address: RpcAddress, block_hash_or_epoch_num: Option<BlockHashOrEpochNumber>) -> BoxFuture<JsonRpcResult<Bytes>>;
             fn collateral_for_storage(&self, address: RpcAddress, num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<U256>>;
             fn deposit_list(&self, address: RpcAddress, num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<Vec<DepositInfo>>>;
             fn epoch_number(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<U256>;
+            fn estimate_gas_and_collateral(&self, request: TransactionRequest, epoch_num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<EstimateGasAndCollateralResponse>>;
             fn gas_price(&self) -> BoxFuture<JsonRpcResult<U256>>;
             fn get_logs(&self, filter: CfxRpcLogFilter) -> BoxFuture<JsonRpcResult<Vec<RpcLog>>>;
             fn interest_rate(&self, num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<U256>>;
@@ -1256,9 +1377,8 @@ impl Cfx for CfxHandler {
     // TODO(thegaram): add support for these
     not_supported! {
         fn account_pending_transactions(&self, address: RpcAddress, maybe_start_nonce: Option<U256>, maybe_limit: Option<U64>) -> BoxFuture<JsonRpcResult<AccountPendingTransactions>>;
+        fn account_proof(&self, address: RpcAddress, epoch_num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<CfxAccountProof>>;
         fn block_by_block_number(&self, block_number: U64, include_txs: bool) -> BoxFuture<JsonRpcResult<Option<RpcBlock>>>;
-        fn call(&self, request: TransactionRequest, block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>) -> JsonRpcResult<Bytes>;
-        fn estimate_gas_and_collateral(&self, request: TransactionRequest, epoch_num: Option<EpochNumber>) -> JsonRpcResult<EstimateGasAndCollateralResponse>;
         fn get_block_reward_info(&self, num: EpochNumber) -> JsonRpcResult<Vec<RpcRewardInfo>>;
         fn get_supply_info(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<TokenSupplyInfo>;
         fn get_collateral_info(&self, epoch_num: Option<EpochNumber>) -> JsonRpcResult<StorageCollateralInfo>;
@@ -1266,6 +1386,7 @@ impl Cfx for CfxHandler {
         fn get_pos_reward_by_epoch(&self, epoch: EpochNumber) -> JsonRpcResult<Option<PoSEpochReward>>;
         fn get_fee_burnt(&self, epoch: Option<EpochNumber>) -> JsonRpcResult<U256>;
         fn max_priority_fee_per_gas(&self) -> BoxFuture<JsonRpcResult<U256>>;
+        fn storage_proof(&self, address: RpcAddress, positions: Vec<U256>, epoch_num: Option<EpochNumber>) -> BoxFuture<JsonRpcResult<CfxStorageProof>>;
     }
 }
 
@@ -1355,6 +1476,10 @@ impl LocalRpc for DebugRpcImpl {
             fn lock_account(&self, address: RpcAddress) -> JsonRpcResult<bool>;
             fn net_disconnect_node(&self, id: NodeId, op: Option<UpdateNodeOperation>) -> JsonRpcResult<bool>;
             fn net_node(&self, id: NodeId) -> JsonRpcResult<Option<(String, Node)>>;
+            fn net_reputation(&self, id: NodeId) -> JsonRpcResult<Option<PeerReputation>>;
+            fn net_set_reputation(&self, id: NodeId, score: f64) -> JsonRpcResult<Option<PeerReputation>>;
+            fn net_ban_node(&self, id: NodeId, duration: Option<U64>) -> JsonRpcResult<bool>;
+            fn net_unban_node(&self, id: NodeId) -> JsonRpcResult<bool>;
             fn net_sessions(&self, node_id: Option<NodeId>) -> JsonRpcResult<Vec<SessionDetails>>;
             fn net_throttling(&self) -> JsonRpcResult<throttling::Service>;
             fn new_account(&self, password: String) -> JsonRpcResult<RpcAddress>;
@@ -1372,6 +1497,8 @@ impl LocalRpc for DebugRpcImpl {
         fn current_sync_phase(&self) -> JsonRpcResult<String>;
         fn epoch_receipts(&self, epoch: BlockHashOrEpochNumber, include_eth_recepits: Option<bool>) -> JsonRpcResult<Option<Vec<Vec<RpcReceipt>>>>;
         fn epoch_receipt_proof_by_transaction(&self, tx_hash: H256) -> JsonRpcResult<Option<EpochReceiptProof>>;
+        fn eth_send_private_raw_transaction(&self, raw: Bytes) -> JsonRpcResult<H256>;
+        fn send_private_raw_transaction(&self, raw: Bytes) -> JsonRpcResult<H256>;
         fn stat_on_gas_load(&self, epoch: EpochNumber, time_window: U64) -> JsonRpcResult<Option<StatOnGasLoad>>;
         fn sign_transaction(&self, tx: TransactionRequest, password: Option<String>) -> JsonRpcResult<String>;
         fn sync_graph_state(&self) -> JsonRpcResult<SyncGraphStates>;
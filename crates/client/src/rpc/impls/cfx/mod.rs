@@ -1,3 +1,4 @@
+mod admin;
 pub mod cfx_filter;
 pub mod cfx_handler;
 pub mod common;
@@ -6,5 +7,6 @@ pub mod pool;
 pub mod pubsub;
 mod trace;
 
+pub use admin::AdminHandler;
 pub use cfx_handler::{CfxHandler, LocalRpcImpl, RpcImpl, TestRpcImpl};
 pub use trace::TraceHandler;
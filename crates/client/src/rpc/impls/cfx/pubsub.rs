@@ -9,7 +9,7 @@ use crate::rpc::{
     traits::pubsub::PubSub,
     types::{
         pubsub::{self, SubscriptionEpoch},
-        Header as RpcHeader, Log as RpcLog,
+        Header as RpcHeader, Log as RpcLog, Transaction as RpcTransaction,
     },
 };
 use cfx_addr::Network;
@@ -18,7 +18,9 @@ use cfx_parameters::{
     consensus_internal::REWARD_EPOCH_COUNT,
 };
 use cfx_types::{Space, H256};
-use cfxcore::{BlockDataManager, Notifications, SharedConsensusGraph};
+use cfxcore::{
+    sync::SyncPhaseType, BlockDataManager, Notifications, SharedConsensusGraph,
+};
 use futures::future::join_all;
 use itertools::zip;
 use jsonrpc_core::Result as RpcResult;
@@ -30,6 +32,7 @@ use log::{debug, error, trace, warn};
 use parking_lot::RwLock;
 use primitives::{
     filter::LogFilter, log_entry::LocalizedLogEntry, BlockReceipts,
+    SignedTransaction,
 };
 use std::{
     sync::{Arc, Weak},
@@ -46,6 +49,9 @@ pub struct PubSubClient {
     heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
     epochs_subscribers: Arc<RwLock<Subscribers<Client>>>,
     logs_subscribers: Arc<RwLock<Subscribers<(Client, LogFilter)>>>,
+    /// Each subscriber is with whether it receives full transactions.
+    pending_transactions_subscribers: Arc<RwLock<Subscribers<(Client, bool)>>>,
+    syncing_subscribers: Arc<RwLock<Subscribers<Client>>>,
     heads_loop_started: Arc<RwLock<bool>>,
     notifications: Arc<Notifications>,
     pub executor: Arc<Runtime>,
@@ -60,6 +66,9 @@ impl PubSubClient {
         let heads_subscribers = Arc::new(RwLock::new(Subscribers::default()));
         let epochs_subscribers = Arc::new(RwLock::new(Subscribers::default()));
         let logs_subscribers = Arc::new(RwLock::new(Subscribers::default()));
+        let pending_transactions_subscribers =
+            Arc::new(RwLock::new(Subscribers::default()));
+        let syncing_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 
         let handler = Arc::new(ChainNotificationHandler {
             consensus: consensus.clone(),
@@ -72,6 +81,8 @@ impl PubSubClient {
             heads_subscribers,
             epochs_subscribers,
             logs_subscribers,
+            pending_transactions_subscribers,
+            syncing_subscribers,
             heads_loop_started: Arc::new(RwLock::new(false)),
             notifications,
             executor,
@@ -284,6 +295,105 @@ impl PubSubClient {
 
         self.executor.spawn(fut);
     }
+
+    // Start an async loop that continuously receives the transactions inserted
+    // into the transaction pool and publishes the ones of the native space to
+    // subscriber `id`. The loop terminates when subscriber `id` unsubscribes.
+    fn start_pending_transactions_loop(&self, id: SubscriberId) {
+        trace!("start_pending_transactions_loop({:?})", id);
+
+        // clone everything we use in our async loop
+        let subscribers = self.pending_transactions_subscribers.clone();
+        let new_pending_transactions =
+            self.notifications.new_pending_transactions.clone();
+        let handler = self.handler.clone();
+
+        // subscribe to the `new_pending_transactions` channel
+        let mut receiver = new_pending_transactions.subscribe();
+
+        // loop asynchronously
+        let fut = async move {
+            while let Some(transactions) = receiver.recv().await {
+                // retrieve subscriber
+                let (sub, full_transactions) = match subscribers.read().get(&id)
+                {
+                    Some(sub) => sub.clone(),
+                    None => {
+                        // unsubscribed, terminate loop
+                        new_pending_transactions.unsubscribe(receiver.id);
+                        return;
+                    }
+                };
+
+                let send_res = handler.notify_pending_transactions(
+                    &sub,
+                    transactions,
+                    full_transactions,
+                );
+                if let Err(err) = send_res {
+                    if err.is_disconnected() {
+                        new_pending_transactions.unsubscribe(receiver.id);
+                        subscribers
+                            .write()
+                            .remove(&SubscriptionId::String(id.as_string()));
+                        return;
+                    }
+                }
+            }
+        };
+
+        self.executor.spawn(fut);
+    }
+
+    // Start an async loop that continuously receives the sync phase changes
+    // and publishes the sync status to subscriber `id`. The loop terminates
+    // when subscriber `id` unsubscribes.
+    fn start_syncing_loop(&self, id: SubscriberId) {
+        trace!("start_syncing_loop({:?})", id);
+
+        // clone everything we use in our async loop
+        let subscribers = self.syncing_subscribers.clone();
+        let sync_phase_changes = self.notifications.sync_phase_changes.clone();
+
+        // subscribe to the `sync_phase_changes` channel
+        let mut receiver = sync_phase_changes.subscribe();
+
+        // loop asynchronously
+        let fut = async move {
+            while let Some(phase) = receiver.recv().await {
+                trace!("syncing_loop({:?}): {:?}", id, phase);
+
+                // retrieve subscriber
+                let sub = match subscribers.read().get(&id) {
+                    Some(sub) => sub.clone(),
+                    None => {
+                        // unsubscribed, terminate loop
+                        sync_phase_changes.unsubscribe(receiver.id);
+                        return;
+                    }
+                };
+
+                let send_res = notify(
+                    &sub,
+                    pubsub::Result::SyncState {
+                        syncing: phase != SyncPhaseType::Normal,
+                        phase: format!("{:?}", phase),
+                    },
+                );
+                if let Err(err) = send_res {
+                    if err.is_disconnected() {
+                        sync_phase_changes.unsubscribe(receiver.id);
+                        subscribers
+                            .write()
+                            .remove(&SubscriptionId::String(id.as_string()));
+                        return;
+                    }
+                }
+            }
+        };
+
+        self.executor.spawn(fut);
+    }
 }
 
 /// PubSub notification handler.
@@ -333,6 +443,20 @@ impl ChainNotificationHandler {
         );
     }
 
+    fn notify_pending_transactions(
+        &self, subscriber: &Client, transactions: Vec<Arc<SignedTransaction>>,
+        full_transactions: bool,
+    ) -> SinkResult {
+        for result in pending_transaction_results(
+            transactions,
+            full_transactions,
+            self.network,
+        ) {
+            notify(subscriber, result)?;
+        }
+        Ok(())
+    }
+
     async fn notify_logs(
         &self, subscriber: &Client, filter: LogFilter, epoch: (u64, Vec<H256>),
     ) -> SinkResult {
@@ -558,7 +682,41 @@ impl PubSub for PubSubClient {
             (pubsub::Kind::Logs, _) => {
                 errors::invalid_params("logs", "Expected filter parameter.")
             }
-            _ => errors::unimplemented(None),
+            // --------- newPendingTransactions ---------
+            (pubsub::Kind::NewPendingTransactions, None) => {
+                let id = self
+                    .pending_transactions_subscribers
+                    .write()
+                    .push(subscriber, false);
+                self.start_pending_transactions_loop(id);
+                return;
+            }
+            (
+                pubsub::Kind::NewPendingTransactions,
+                Some(pubsub::Params::FullTransactions(full_transactions)),
+            ) => {
+                let id = self
+                    .pending_transactions_subscribers
+                    .write()
+                    .push(subscriber, full_transactions);
+                self.start_pending_transactions_loop(id);
+                return;
+            }
+            (pubsub::Kind::NewPendingTransactions, _) => {
+                errors::invalid_params(
+                    "newPendingTransactions",
+                    "Expected no parameters or a boolean.",
+                )
+            }
+            // --------- syncing ---------
+            (pubsub::Kind::Syncing, None) => {
+                let id = self.syncing_subscribers.write().push(subscriber);
+                self.start_syncing_loop(id);
+                return;
+            }
+            (pubsub::Kind::Syncing, _) => {
+                errors::invalid_params("syncing", "Expected no parameters.")
+            }
         };
 
         let _ = subscriber.reject(error);
@@ -570,11 +728,121 @@ impl PubSub for PubSubClient {
         let res0 = self.heads_subscribers.write().remove(&id).is_some();
         let res1 = self.epochs_subscribers.write().remove(&id).is_some();
         let res2 = self.logs_subscribers.write().remove(&id).is_some();
-
-        Ok(res0 || res1 || res2)
+        let res3 = self
+            .pending_transactions_subscribers
+            .write()
+            .remove(&id)
+            .is_some();
+        let res4 = self.syncing_subscribers.write().remove(&id).is_some();
+
+        Ok(res0 || res1 || res2 || res3 || res4)
     }
 }
 
 fn notify(subscriber: &Client, result: pubsub::Result) -> SinkResult {
     subscriber.notify(Ok(result))
 }
+
+/// The results of the native space transactions among the new pending ones.
+fn pending_transaction_results(
+    transactions: Vec<Arc<SignedTransaction>>, full_transactions: bool,
+    network: Network,
+) -> Vec<pubsub::Result> {
+    transactions
+        .into_iter()
+        .filter(|tx| tx.space() == Space::Native)
+        .filter_map(|tx| {
+            if !full_transactions {
+                return Some(pubsub::Result::TransactionHash(tx.hash()));
+            }
+            match RpcTransaction::from_signed(&tx, None, network) {
+                Ok(tx) => Some(pubsub::Result::Transaction(tx)),
+                Err(e) => {
+                    error!(
+                        "Unexpected error while constructing RpcTransaction: {:?}",
+                        e
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pending_transaction_results;
+    use crate::rpc::types::pubsub::Result as PubSubResult;
+    use cfx_addr::Network;
+    use cfx_types::{Address, U256};
+    use cfxkey::{Generator, Random};
+    use primitives::{
+        transaction::{
+            native_transaction::NativeTransaction, Eip155Transaction,
+        },
+        Action, SignedTransaction, Transaction,
+    };
+    use std::sync::Arc;
+
+    fn new_test_tx(nonce: u64, native: bool) -> Arc<SignedTransaction> {
+        let tx: Transaction = if native {
+            NativeTransaction {
+                nonce: U256::from(nonce),
+                gas_price: U256::one(),
+                gas: U256::from(21000),
+                action: Action::Call(Address::random()),
+                value: U256::zero(),
+                storage_limit: 0,
+                epoch_height: 0,
+                chain_id: 1,
+                data: Vec::new(),
+            }
+            .into()
+        } else {
+            Eip155Transaction {
+                nonce: U256::from(nonce),
+                gas_price: U256::one(),
+                gas: U256::from(21000),
+                action: Action::Call(Address::random()),
+                value: U256::zero(),
+                chain_id: Some(1),
+                data: Vec::new(),
+            }
+            .into()
+        };
+        Arc::new(tx.sign(Random.generate().unwrap().secret()))
+    }
+
+    #[test]
+    fn test_pending_transaction_results() {
+        let txs = vec![
+            new_test_tx(0, true),
+            new_test_tx(1, false),
+            new_test_tx(2, true),
+        ];
+
+        // only the native space transactions are published
+        let results =
+            pending_transaction_results(txs.clone(), false, Network::Main);
+        assert_eq!(
+            results,
+            vec![
+                PubSubResult::TransactionHash(txs[0].hash()),
+                PubSubResult::TransactionHash(txs[2].hash()),
+            ]
+        );
+
+        let results =
+            pending_transaction_results(txs.clone(), true, Network::Main);
+        assert_eq!(results.len(), 2);
+        for (result, tx) in results.iter().zip([&txs[0], &txs[2]]) {
+            match result {
+                PubSubResult::Transaction(rpc_tx) => {
+                    assert_eq!(rpc_tx.hash, tx.hash());
+                    assert_eq!(rpc_tx.nonce, *tx.nonce());
+                }
+                _ => panic!("expected a full transaction, got {:?}", result),
+            }
+        }
+    }
+}
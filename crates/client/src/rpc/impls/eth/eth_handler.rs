@@ -3,27 +3,27 @@
 // See http://www.gnu.org/licenses/
 
 use crate::rpc::{
-    errors::{internal_error, EthApiError},
+    errors::internal_error,
     impls::RpcImplConfiguration,
     traits::eth_space::eth::Eth,
     types::{
         eth::{
-            AccountPendingTransactions, Block as RpcBlock, BlockNumber,
-            EthRpcLogFilter, Log, Receipt, SyncStatus, Transaction,
-            TransactionRequest,
+            AccessListResult, AccountPendingTransactions, AccountProof,
+            Block as RpcBlock, BlockNumber, BlockOverrides, Bundle,
+            EthCallResponse, EthRpcLogFilter, EvmOverrides, Log, Receipt,
+            SimulatePayload, SimulatedBlock, StateContext, StateOverride,
+            SyncStatus, Transaction, TransactionRequest,
         },
         Bytes, FeeHistory, Index, U64 as HexU64,
     },
 };
-use cfx_rpc::EthApi;
-use cfx_types::{Address, AddressSpaceUtil, Space, H160, H256, U256, U64};
-use cfx_util_macros::bail;
+use cfx_rpc::{decode_raw_transaction, EthApi};
+use cfx_types::{Address, AddressSpaceUtil, H160, H256, U256, U64};
 use cfxcore::{
     SharedConsensusGraph, SharedSynchronizationService, SharedTransactionPool,
 };
 use jsonrpc_core::Result as RpcResult;
 use log::debug;
-use primitives::TransactionWithSignature;
 
 pub struct EthHandler {
     inner: EthApi,
@@ -232,27 +232,23 @@ impl Eth for EthHandler {
             .map_err(|err| err.into())
     }
 
-    fn send_raw_transaction(&self, raw: Bytes) -> RpcResult<H256> {
-        debug!("RPC Request: eth_sendRawTransaction(raw={:?})", raw,);
-        let tx = if let Ok(tx) =
-            TransactionWithSignature::from_raw(&raw.into_vec())
-        {
-            tx
-        } else {
-            bail!(EthApiError::FailedToDecodeSignedTransaction)
-        };
-
-        if tx.space() != Space::Ethereum {
-            bail!(EthApiError::Other(
-                "Incorrect transaction space".to_string()
-            ));
-        }
+    fn proof(
+        &self, address: H160, keys: Vec<U256>, block_num: Option<BlockNumber>,
+    ) -> RpcResult<AccountProof> {
+        debug!(
+            "RPC Request: eth_getProof(address={:?}, keys={:?}, block_num={:?})",
+            address, keys, block_num
+        );
 
-        if tx.recover_public().is_err() {
-            bail!(EthApiError::InvalidTransactionSignature);
-        }
+        self.inner
+            .get_proof(address, keys, block_num)
+            .map_err(|err| err.into())
+    }
 
-        let r = self.inner.send_transaction_with_signature(tx)?;
+    fn send_raw_transaction(&self, raw: Bytes) -> RpcResult<H256> {
+        debug!("RPC Request: eth_sendRawTransaction(raw={:?})", raw,);
+        let tx = decode_raw_transaction(raw)?;
+        let r = self.inner.send_transaction_with_signature(tx, false)?;
         Ok(r)
     }
 
@@ -263,14 +259,20 @@ impl Eth for EthHandler {
     fn call(
         &self, request: TransactionRequest,
         block_number_or_hash: Option<BlockNumber>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes> {
+        let overrides = EvmOverrides::new(state_overrides, block_overrides);
         debug!(
-            "RPC Request: eth_call(request={:?}, block_num={:?})",
-            request, block_number_or_hash
+            "RPC Request: eth_call(request={:?}, block_num={:?}, overrides={:?})",
+            request, block_number_or_hash, overrides
         );
 
-        let (execution, _estimation) =
-            self.inner.exec_transaction(request, block_number_or_hash)?;
+        let (execution, _estimation) = self.inner.exec_transaction(
+            request,
+            block_number_or_hash,
+            overrides,
+        )?;
 
         Ok(execution.output.into())
     }
@@ -278,17 +280,64 @@ impl Eth for EthHandler {
     fn estimate_gas(
         &self, request: TransactionRequest,
         block_number_or_hash: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<U256> {
+        let overrides = EvmOverrides::new(state_override, None);
         debug!(
-            "RPC Request: eth_estimateGas(request={:?}, block_num={:?})",
-            request, block_number_or_hash
+            "RPC Request: eth_estimateGas(request={:?}, block_num={:?}, overrides={:?})",
+            request, block_number_or_hash, overrides
         );
-        let (_, estimated_gas) =
-            self.inner.exec_transaction(request, block_number_or_hash)?;
+        let (_, estimated_gas) = self.inner.exec_transaction(
+            request,
+            block_number_or_hash,
+            overrides,
+        )?;
 
         Ok(estimated_gas)
     }
 
+    fn create_access_list(
+        &self, request: TransactionRequest,
+        block_number_or_hash: Option<BlockNumber>,
+    ) -> RpcResult<AccessListResult> {
+        debug!(
+            "RPC Request: eth_createAccessList(request={:?}, block_num={:?})",
+            request, block_number_or_hash
+        );
+
+        self.inner
+            .create_access_list(request, block_number_or_hash)
+            .map_err(|err| err.into())
+    }
+
+    fn call_many(
+        &self, bundle: Bundle, state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<EthCallResponse>> {
+        debug!(
+            "RPC Request: eth_callMany(bundle={:?}, state_context={:?}, state_override={:?})",
+            bundle, state_context, state_override
+        );
+
+        self.inner
+            .call_many(bundle, state_context, state_override)
+            .map_err(|err| err.into())
+    }
+
+    fn simulate_v1(
+        &self, payload: SimulatePayload,
+        block_number_or_hash: Option<BlockNumber>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        debug!(
+            "RPC Request: eth_simulateV1(payload={:?}, block_num={:?})",
+            payload, block_number_or_hash
+        );
+
+        self.inner
+            .simulate_v1(payload, block_number_or_hash)
+            .map_err(|err| err.into())
+    }
+
     fn fee_history(
         &self, block_count: HexU64, newest_block: BlockNumber,
         reward_percentiles: Option<Vec<f64>>,
@@ -0,0 +1,410 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use std::sync::Arc;
+
+use cfx_rpc::{decode_raw_transaction, EthApi};
+use cfx_rpc_cfx_types::{traits::BlockProvider, PhantomBlock};
+use cfx_types::{BigEndianHash, H160, H256, U256, U64};
+use cfx_util_macros::bail;
+use cfxcore::{
+    light_protocol::{self, Error as LightError},
+    LightQueryService, SharedConsensusGraph,
+};
+use delegate::delegate;
+use futures::future::FutureExt;
+use jsonrpc_core::{BoxFuture, Error as RpcError, Result as JsonRpcResult};
+use log::info;
+use primitives::EpochNumber;
+
+// To convert from CoreResult to BoxFuture by delegate! macro automatically.
+use crate::{
+    common::delegate_convert,
+    rpc::{
+        errors::{invalid_params, invalid_params_check},
+        traits::eth_space::light_eth::LightEth,
+        types::{
+            eth::{
+                Block as RpcBlock, BlockNumber, EthRpcLogFilter, Log, Receipt,
+                Transaction,
+            },
+            Bytes,
+        },
+        CoreBoxFuture, CoreResult,
+    },
+};
+use cfxcore::errors::Error::LightProtocol;
+
+/// The eth rpc of light nodes. The eSpace blocks are rebuilt from the verified
+/// receipts and transactions of the epochs, see
+/// [`LightQueryService::get_phantom_block`].
+#[derive(Clone)]
+pub struct RpcImpl {
+    // consensus graph
+    consensus: SharedConsensusGraph,
+
+    // helper API for retrieving verified information from peers
+    light: Arc<LightQueryService>,
+}
+
+impl RpcImpl {
+    pub fn new(
+        light: Arc<LightQueryService>, consensus: SharedConsensusGraph,
+    ) -> Self {
+        RpcImpl { consensus, light }
+    }
+
+    /// Returns the epoch of `hash` if it is a pivot block, as the eSpace blocks
+    /// correspond to the epochs.
+    fn pivot_epoch_number(
+        consensus: &SharedConsensusGraph, hash: &H256,
+    ) -> Option<u64> {
+        let epoch = consensus.get_block_epoch_number(hash)?;
+        let hashes = consensus
+            .get_block_hashes_by_epoch(EpochNumber::Number(epoch))
+            .ok()?;
+        (hashes.last() == Some(hash)).then_some(epoch)
+    }
+
+    fn get_epoch_number(
+        consensus: &SharedConsensusGraph, block: Option<BlockNumber>,
+    ) -> CoreResult<EpochNumber> {
+        match block.unwrap_or_default() {
+            BlockNumber::Hash { hash, .. } => {
+                match Self::pivot_epoch_number(consensus, &hash) {
+                    Some(epoch) => Ok(EpochNumber::Number(epoch)),
+                    None => bail!(invalid_params("block", "Unknown block")),
+                }
+            }
+            block => Ok(block.try_into()?),
+        }
+    }
+
+    async fn get_phantom_block(
+        light: &LightQueryService, epoch: EpochNumber,
+    ) -> CoreResult<PhantomBlock> {
+        let epoch = invalid_params_check(
+            "block",
+            light.get_height_from_epoch_number(epoch),
+        )?;
+
+        Ok(light.get_phantom_block(epoch).await?)
+    }
+
+    /// Returns the eSpace block containing the transaction `hash` and the
+    /// index of the transaction in it.
+    async fn get_eth_tx_info(
+        light: &LightQueryService, hash: H256,
+    ) -> CoreResult<Option<(PhantomBlock, usize)>> {
+        match light.get_eth_tx_info(hash).await {
+            Ok(info) => Ok(info),
+            // return `null` on timeout, as peers cannot provide
+            // non-existence proofs for transactions
+            Err(LightError::Timeout(_)) => Ok(None),
+            Err(e) => bail!(RpcError::invalid_params(e.to_string())),
+        }
+    }
+
+    fn client_version(&self) -> CoreResult<String> {
+        info!("RPC Request: web3_clientVersion()");
+        Ok(parity_version::conflux_client_version!())
+    }
+
+    fn net_version(&self) -> CoreResult<String> {
+        info!("RPC Request: net_version()");
+        Ok(format!("{}", self.evm_chain_id()?))
+    }
+
+    fn evm_chain_id(&self) -> CoreResult<u32> {
+        Ok(invalid_params_check(
+            "chain_id",
+            self.light.get_latest_verifiable_chain_id(),
+        )?
+        .in_evm_space())
+    }
+
+    fn chain_id(&self) -> CoreResult<Option<U64>> {
+        info!("RPC Request: eth_chainId()");
+        Ok(Some(self.evm_chain_id()?.into()))
+    }
+
+    fn block_number(&self) -> CoreResult<U256> {
+        info!("RPC Request: eth_blockNumber()");
+        Ok(invalid_params_check(
+            "block",
+            self.light.get_latest_verifiable_epoch_number(),
+        )?
+        .into())
+    }
+
+    fn balance(
+        &self, address: H160, block: Option<BlockNumber>,
+    ) -> CoreBoxFuture<U256> {
+        info!(
+            "RPC Request: eth_getBalance address={:?} block={:?}",
+            address, block
+        );
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus = self.consensus.clone();
+        let light = self.light.clone();
+
+        let fut = async move {
+            let epoch = Self::get_epoch_number(&consensus, block)?;
+
+            let account = invalid_params_check(
+                "address",
+                light.get_eth_account(epoch, address).await,
+            )?;
+
+            Ok(account.map(|account| account.balance).unwrap_or_default())
+        };
+
+        fut.boxed()
+    }
+
+    fn storage_at(
+        &self, address: H160, position: U256, block: Option<BlockNumber>,
+    ) -> CoreBoxFuture<H256> {
+        let position = H256::from_uint(&position);
+
+        info!(
+            "RPC Request: eth_getStorageAt address={:?} position={:?} block={:?}",
+            address, position, block
+        );
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus = self.consensus.clone();
+        let light = self.light.clone();
+
+        let fut = async move {
+            let epoch = Self::get_epoch_number(&consensus, block)?;
+
+            let maybe_entry =
+                light.get_eth_storage(epoch, address, position).await?;
+
+            Ok(maybe_entry.unwrap_or_default())
+        };
+
+        fut.boxed()
+    }
+
+    fn transaction_count(
+        &self, address: H160, block: Option<BlockNumber>,
+    ) -> CoreBoxFuture<U256> {
+        info!(
+            "RPC Request: eth_getTransactionCount address={:?} block={:?}",
+            address, block
+        );
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus = self.consensus.clone();
+        let light = self.light.clone();
+
+        let fut = async move {
+            let epoch = Self::get_epoch_number(&consensus, block)?;
+
+            let account = invalid_params_check(
+                "address",
+                light.get_eth_account(epoch, address).await,
+            )?;
+
+            Ok(account.map(|account| account.nonce).unwrap_or_default())
+        };
+
+        fut.boxed()
+    }
+
+    fn code_at(
+        &self, address: H160, block: Option<BlockNumber>,
+    ) -> CoreBoxFuture<Bytes> {
+        info!(
+            "RPC Request: eth_getCode address={:?} block={:?}",
+            address, block
+        );
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus = self.consensus.clone();
+        let light = self.light.clone();
+
+        let fut = async move {
+            let epoch = Self::get_epoch_number(&consensus, block)?;
+
+            Ok(Bytes::new(
+                invalid_params_check(
+                    "address",
+                    light.get_eth_code(epoch, address).await,
+                )?
+                .unwrap_or_default(),
+            ))
+        };
+
+        fut.boxed()
+    }
+
+    fn send_raw_transaction(&self, raw: Bytes) -> CoreResult<H256> {
+        info!("RPC Request: eth_sendRawTransaction bytes={:?}", raw);
+
+        // decode tx so that we have its hash
+        // this way we also avoid spamming peers with invalid txs
+        let tx = decode_raw_transaction(raw.clone()).map_err(RpcError::from)?;
+
+        match /* success = */ self.light.send_raw_tx(raw.into_vec()) {
+            true => Ok(tx.hash()),
+            false => bail!(LightProtocol(light_protocol::Error::InternalError("Unable to relay tx".into()).into())),
+        }
+    }
+
+    fn block_by_hash(
+        &self, hash: H256, include_txs: bool,
+    ) -> CoreBoxFuture<Option<RpcBlock>> {
+        info!(
+            "RPC Request: eth_getBlockByHash hash={:?} include_txs={:?}",
+            hash, include_txs
+        );
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus = self.consensus.clone();
+        let light = self.light.clone();
+
+        let fut = async move {
+            let epoch = match Self::pivot_epoch_number(&consensus, &hash) {
+                Some(epoch) => EpochNumber::Number(epoch),
+                None => return Ok(None),
+            };
+
+            let block = Self::get_phantom_block(&light, epoch).await?;
+            Ok(Some(RpcBlock::from_phantom(&block, include_txs)))
+        };
+
+        fut.boxed()
+    }
+
+    fn block_by_number(
+        &self, block_num: BlockNumber, include_txs: bool,
+    ) -> CoreBoxFuture<Option<RpcBlock>> {
+        info!(
+            "RPC Request: eth_getBlockByNumber block_num={:?} include_txs={:?}",
+            block_num, include_txs
+        );
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let consensus = self.consensus.clone();
+        let light = self.light.clone();
+
+        let fut = async move {
+            let epoch = Self::get_epoch_number(&consensus, Some(block_num))?;
+            let block = Self::get_phantom_block(&light, epoch).await?;
+            Ok(Some(RpcBlock::from_phantom(&block, include_txs)))
+        };
+
+        fut.boxed()
+    }
+
+    fn transaction_by_hash(
+        &self, hash: H256,
+    ) -> CoreBoxFuture<Option<Transaction>> {
+        info!("RPC Request: eth_getTransactionByHash hash={:?}", hash);
+
+        // clone `self.light` to avoid lifetime issues due to capturing `self`
+        let light = self.light.clone();
+
+        let fut = async move {
+            Ok(Self::get_eth_tx_info(&light, hash).await?.and_then(
+                |(block, idx)| EthApi::block_tx_by_index(Some(block), idx),
+            ))
+        };
+
+        fut.boxed()
+    }
+
+    fn transaction_receipt(
+        &self, hash: H256,
+    ) -> CoreBoxFuture<Option<Receipt>> {
+        info!("RPC Request: eth_getTransactionReceipt hash={:?}", hash);
+
+        // clone `self.light` to avoid lifetime issues due to capturing `self`
+        let light = self.light.clone();
+
+        let fut = async move {
+            let (block, idx) = match Self::get_eth_tx_info(&light, hash).await?
+            {
+                Some(info) => info,
+                None => return Ok(None),
+            };
+
+            let mut prior_log_index: usize =
+                block.receipts[..idx].iter().map(|r| r.logs.len()).sum();
+
+            Ok(Some(EthApi::construct_rpc_receipt(
+                &block,
+                idx,
+                &mut prior_log_index,
+            )?))
+        };
+
+        fut.boxed()
+    }
+
+    fn logs(&self, filter: EthRpcLogFilter) -> CoreBoxFuture<Vec<Log>> {
+        info!("RPC Request: eth_getLogs filter={:?}", filter);
+
+        // clone to avoid lifetime issues due to capturing `self`
+        let rpc = self.clone();
+
+        let fut = async move {
+            let filter = filter.into_primitive(&rpc)?;
+
+            let logs = rpc.light.get_eth_logs(filter).await?;
+
+            Ok(logs
+                .into_iter()
+                .map(|l| Log::try_from_localized(l, &rpc, false))
+                .collect::<Result<_, _>>()?)
+        };
+
+        fut.boxed()
+    }
+}
+
+impl BlockProvider for &RpcImpl {
+    fn get_block_epoch_number(&self, hash: &H256) -> Option<u64> {
+        self.consensus.get_block_epoch_number(hash)
+    }
+
+    fn get_block_hashes_by_epoch(
+        &self, epoch_number: EpochNumber,
+    ) -> Result<Vec<H256>, String> {
+        self.consensus.get_block_hashes_by_epoch(epoch_number)
+    }
+}
+
+pub struct EthHandler {
+    rpc_impl: Arc<RpcImpl>,
+}
+
+impl EthHandler {
+    pub fn new(rpc_impl: Arc<RpcImpl>) -> Self { EthHandler { rpc_impl } }
+}
+
+impl LightEth for EthHandler {
+    delegate! {
+        to self.rpc_impl {
+            fn client_version(&self) -> JsonRpcResult<String>;
+            fn net_version(&self) -> JsonRpcResult<String>;
+            fn chain_id(&self) -> JsonRpcResult<Option<U64>>;
+            fn block_number(&self) -> JsonRpcResult<U256>;
+            fn balance(&self, address: H160, block: Option<BlockNumber>) -> BoxFuture<JsonRpcResult<U256>>;
+            fn storage_at(&self, address: H160, storage_slot: U256, block: Option<BlockNumber>) -> BoxFuture<JsonRpcResult<H256>>;
+            fn transaction_count(&self, address: H160, block: Option<BlockNumber>) -> BoxFuture<JsonRpcResult<U256>>;
+            fn code_at(&self, address: H160, block: Option<BlockNumber>) -> BoxFuture<JsonRpcResult<Bytes>>;
+            fn send_raw_transaction(&self, transaction: Bytes) -> JsonRpcResult<H256>;
+            fn block_by_hash(&self, hash: H256, include_txs: bool) -> BoxFuture<JsonRpcResult<Option<RpcBlock>>>;
+            fn block_by_number(&self, block_num: BlockNumber, include_txs: bool) -> BoxFuture<JsonRpcResult<Option<RpcBlock>>>;
+            fn transaction_by_hash(&self, transaction_hash: H256) -> BoxFuture<JsonRpcResult<Option<Transaction>>>;
+            fn transaction_receipt(&self, transaction_hash: H256) -> BoxFuture<JsonRpcResult<Option<Receipt>>>;
+            fn logs(&self, filter: EthRpcLogFilter) -> BoxFuture<JsonRpcResult<Vec<Log>>>;
+        }
+    }
+}
@@ -3,6 +3,7 @@ pub mod eth_filter;
 pub mod eth_handler;
 pub mod eth_pubsub;
 pub mod eth_trace;
+pub mod light;
 
 pub use debug::GethDebugHandler;
 pub use eth_handler::EthHandler;
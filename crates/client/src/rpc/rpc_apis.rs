@@ -20,6 +20,7 @@ pub enum Api {
     Pos,
     EthPubsub,
     EthDebug,
+    Admin,
 }
 
 impl FromStr for Api {
@@ -38,6 +39,7 @@ impl FromStr for Api {
             "pos" => Ok(Pos),
             "ethpubsub" => Ok(EthPubsub),
             "ethdebug" => Ok(EthDebug),
+            "admin" => Ok(Admin),
             _ => Err("Unknown api type".into()),
         }
     }
@@ -56,6 +58,7 @@ impl Display for Api {
             Api::Pos => write!(f, "pos"),
             Api::EthPubsub => write!(f, "ethpubsub"),
             Api::EthDebug => write!(f, "ethdebug"),
+            Api::Admin => write!(f, "admin"),
         }
     }
 }
@@ -80,6 +83,7 @@ impl ApiSet {
                 Api::Trace,
                 Api::Pos,
                 Api::TxPool,
+                Api::Admin,
             ]
             .iter()
             .cloned()
@@ -0,0 +1,43 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_rpc_cfx_types::admin::{NodeConfig, NodeInfo, PeerInfo};
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_derive::rpc;
+
+/// Admin rpc interface, which should not be exposed on public interfaces.
+#[rpc(server)]
+pub trait Admin {
+    /// Returns information of the local node, including the node url.
+    #[rpc(name = "admin_nodeInfo")]
+    fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the connected peers with session details.
+    #[rpc(name = "admin_peers")]
+    fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+
+    /// Adds a node to connect with url "cfxnode://ID@IP:PORT".
+    #[rpc(name = "admin_addPeer")]
+    fn add_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Removes a node from node database and disconnects it.
+    #[rpc(name = "admin_removePeer")]
+    fn remove_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Adds a node which is always connected and persisted in node database.
+    #[rpc(name = "admin_addTrustedPeer")]
+    fn add_trusted_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Removes a node added by `admin_addTrustedPeer`.
+    #[rpc(name = "admin_removeTrustedPeer")]
+    fn remove_trusted_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Returns the data directory of the node.
+    #[rpc(name = "admin_datadir")]
+    fn datadir(&self) -> RpcResult<String>;
+
+    /// Returns the data directories and P2P/RPC configuration of the node.
+    #[rpc(name = "admin_config")]
+    fn config(&self) -> RpcResult<NodeConfig>;
+}
@@ -5,8 +5,9 @@
 use crate::rpc::types::{
     pos::PoSEpochReward, Account as RpcAccount, AccountPendingInfo,
     AccountPendingTransactions, Block, BlockHashOrEpochNumber, Bytes,
-    CfxFeeHistory, CfxRpcLogFilter, CheckBalanceAgainstTransactionResponse,
-    EpochNumber, EstimateGasAndCollateralResponse, Log as RpcLog, PoSEconomics,
+    CfxAccountProof, CfxFeeHistory, CfxRpcLogFilter, CfxStorageProof,
+    CheckBalanceAgainstTransactionResponse, EpochNumber,
+    EstimateGasAndCollateralResponse, Log as RpcLog, PoSEconomics,
     Receipt as RpcReceipt, RewardInfo as RpcRewardInfo, RpcAddress,
     SponsorInfo, Status as RpcStatus, StorageCollateralInfo, TokenSupplyInfo,
     Transaction, TransactionRequest, VoteParamsInfo, U64 as HexU64,
@@ -111,6 +112,21 @@ pub trait Cfx {
         &self, address: RpcAddress, epoch_num: Option<EpochNumber>,
     ) -> BoxFuture<JsonRpcResult<Option<StorageRoot>>>;
 
+    /// Returns the account, code and storage root of a given address with
+    /// Merkle proofs against the state root of the given epoch.
+    #[rpc(name = "cfx_getAccountProof")]
+    fn account_proof(
+        &self, address: RpcAddress, epoch_num: Option<EpochNumber>,
+    ) -> BoxFuture<JsonRpcResult<CfxAccountProof>>;
+
+    /// Returns storage entries of a given contract with Merkle proofs against
+    /// the state root of the given epoch.
+    #[rpc(name = "cfx_getStorageProof")]
+    fn storage_proof(
+        &self, address: RpcAddress, positions: Vec<U256>,
+        epoch_num: Option<EpochNumber>,
+    ) -> BoxFuture<JsonRpcResult<CfxStorageProof>>;
+
     /// Returns block with given hash.
     #[rpc(name = "cfx_getBlockByHash")]
     fn block_by_hash(
@@ -169,7 +185,7 @@ pub trait Cfx {
     fn call(
         &self, tx: TransactionRequest,
         block_hash_or_epoch_number: Option<BlockHashOrEpochNumber>,
-    ) -> JsonRpcResult<Bytes>;
+    ) -> BoxFuture<JsonRpcResult<Bytes>>;
 
     /// Returns logs matching the filter provided.
     #[rpc(name = "cfx_getLogs")]
@@ -200,7 +216,7 @@ pub trait Cfx {
     #[rpc(name = "cfx_estimateGasAndCollateral")]
     fn estimate_gas_and_collateral(
         &self, request: TransactionRequest, epoch_number: Option<EpochNumber>,
-    ) -> JsonRpcResult<EstimateGasAndCollateralResponse>;
+    ) -> BoxFuture<JsonRpcResult<EstimateGasAndCollateralResponse>>;
 
     #[rpc(name = "cfx_feeHistory")]
     fn fee_history(
@@ -14,7 +14,7 @@ use jsonrpc_core::{BoxFuture, Result as JsonRpcResult};
 use jsonrpc_derive::rpc;
 use network::{
     node_table::{Node, NodeId},
-    throttling, SessionDetails, UpdateNodeOperation,
+    throttling, PeerReputation, SessionDetails, UpdateNodeOperation,
 };
 use std::collections::BTreeMap;
 
@@ -60,6 +60,28 @@ pub trait LocalRpc {
         &self, id: NodeId, op: Option<UpdateNodeOperation>,
     ) -> JsonRpcResult<bool>;
 
+    #[rpc(name = "debug_getNetReputation")]
+    fn net_reputation(
+        &self, id: NodeId,
+    ) -> JsonRpcResult<Option<PeerReputation>>;
+
+    /// Overrides the reputation score of the node.
+    #[rpc(name = "debug_setNetReputation")]
+    fn net_set_reputation(
+        &self, id: NodeId, score: f64,
+    ) -> JsonRpcResult<Option<PeerReputation>>;
+
+    /// Bans the node for the given duration in seconds, or permanently if the
+    /// duration is not specified.
+    #[rpc(name = "debug_banNetNode")]
+    fn net_ban_node(
+        &self, id: NodeId, duration: Option<U64>,
+    ) -> JsonRpcResult<bool>;
+
+    /// Clears the reputation and ban of the node.
+    #[rpc(name = "debug_unbanNetNode")]
+    fn net_unban_node(&self, id: NodeId) -> JsonRpcResult<bool>;
+
     #[rpc(name = "debug_getNetSessions")]
     fn net_sessions(
         &self, node_id: Option<NodeId>,
@@ -79,6 +101,18 @@ pub trait LocalRpc {
         &self, tx: TransactionRequest, password: Option<String>,
     ) -> BoxFuture<JsonRpcResult<H256>>;
 
+    /// Sends signed transaction which is only packed by the local block
+    /// generator and never propagated, returning its hash.
+    #[rpc(name = "cfx_sendPrivateRawTransaction")]
+    fn send_private_raw_transaction(&self, raw: Bytes) -> JsonRpcResult<H256>;
+
+    /// Sends signed eSpace transaction which is only packed by the local
+    /// block generator and never propagated, returning its hash.
+    #[rpc(name = "eth_sendPrivateRawTransaction")]
+    fn eth_send_private_raw_transaction(
+        &self, raw: Bytes,
+    ) -> JsonRpcResult<H256>;
+
     /// Returns accounts list.
     #[rpc(name = "cfx_accounts")]
     fn accounts(&self) -> JsonRpcResult<Vec<RpcAddress>>;
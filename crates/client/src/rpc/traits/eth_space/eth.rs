@@ -26,8 +26,10 @@ use jsonrpc_derive::rpc;
 
 use crate::rpc::types::{
     eth::{
-        AccountPendingTransactions, Block, BlockNumber, EthRpcLogFilter, Log,
-        Receipt, SyncStatus, Transaction, TransactionRequest,
+        AccessListResult, AccountPendingTransactions, AccountProof, Block,
+        BlockNumber, BlockOverrides, Bundle, EthCallResponse, EthRpcLogFilter,
+        Log, Receipt, SimulatePayload, SimulatedBlock, StateContext,
+        StateOverride, SyncStatus, Transaction, TransactionRequest,
     },
     Bytes, FeeHistory, Index,
 };
@@ -150,6 +152,13 @@ pub trait Eth {
         &self, address: H160, block: Option<BlockNumber>,
     ) -> Result<Bytes>;
 
+    /// Returns the account and storage values of the given account together
+    /// with their Merkle proofs.
+    #[rpc(name = "eth_getProof")]
+    fn proof(
+        &self, address: H160, keys: Vec<U256>, block: Option<BlockNumber>,
+    ) -> Result<AccountProof>;
+
     /// Sends signed transaction, returning its hash.
     #[rpc(name = "eth_sendRawTransaction")]
     fn send_raw_transaction(&self, transaction: Bytes) -> Result<H256>;
@@ -159,18 +168,42 @@ pub trait Eth {
     fn submit_transaction(&self, transaction: Bytes) -> Result<H256>;
 
     /// Call contract, returning the output data.
-    /// TODO support state_overrides and block_overrides
     #[rpc(name = "eth_call")]
     fn call(
         &self, transaction: TransactionRequest, block: Option<BlockNumber>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes>;
 
     /// Estimate gas needed for execution of given contract.
     #[rpc(name = "eth_estimateGas")]
     fn estimate_gas(
         &self, transaction: TransactionRequest, block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256>;
 
+    /// Returns the access list of the given transaction, with the gas needed
+    /// with and without the access list.
+    #[rpc(name = "eth_createAccessList")]
+    fn create_access_list(
+        &self, transaction: TransactionRequest, block: Option<BlockNumber>,
+    ) -> Result<AccessListResult>;
+
+    /// Executes a bundle of transactions in sequence, each of which observes
+    /// the state changes of the previous ones.
+    #[rpc(name = "eth_callMany")]
+    fn call_many(
+        &self, bundle: Bundle, state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> Result<Vec<EthCallResponse>>;
+
+    /// Executes calls in a sequence of simulated blocks on top of the given
+    /// block.
+    #[rpc(name = "eth_simulateV1")]
+    fn simulate_v1(
+        &self, payload: SimulatePayload, block: Option<BlockNumber>,
+    ) -> Result<Vec<SimulatedBlock>>;
+
     /// Get transaction by its hash.
     #[rpc(name = "eth_getTransactionByHash")]
     fn transaction_by_hash(
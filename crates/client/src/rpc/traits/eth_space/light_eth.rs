@@ -0,0 +1,90 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! The eth rpc interface of light nodes.
+use cfx_types::{H160, H256, U256, U64};
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+
+use crate::rpc::types::{
+    eth::{Block, BlockNumber, EthRpcLogFilter, Log, Receipt, Transaction},
+    Bytes,
+};
+
+/// The subset of the eth rpc interface served by light nodes. The results are
+/// retrieved from the full node peers and verified.
+#[rpc(server)]
+pub trait LightEth {
+    #[rpc(name = "web3_clientVersion")]
+    fn client_version(&self) -> Result<String>;
+
+    #[rpc(name = "net_version")]
+    fn net_version(&self) -> Result<String>;
+
+    /// Returns the chain ID used for transaction signing at the latest
+    /// verifiable block.
+    #[rpc(name = "eth_chainId")]
+    fn chain_id(&self) -> Result<Option<U64>>;
+
+    /// Returns the latest verifiable block number.
+    #[rpc(name = "eth_blockNumber")]
+    fn block_number(&self) -> Result<U256>;
+
+    /// Returns balance of the given account.
+    #[rpc(name = "eth_getBalance")]
+    fn balance(
+        &self, address: H160, block: Option<BlockNumber>,
+    ) -> BoxFuture<Result<U256>>;
+
+    /// Returns content of the storage at given address.
+    #[rpc(name = "eth_getStorageAt")]
+    fn storage_at(
+        &self, address: H160, storage_slot: U256, block: Option<BlockNumber>,
+    ) -> BoxFuture<Result<H256>>;
+
+    /// Returns the number of transactions sent from given address at given time
+    /// (block number).
+    #[rpc(name = "eth_getTransactionCount")]
+    fn transaction_count(
+        &self, address: H160, block: Option<BlockNumber>,
+    ) -> BoxFuture<Result<U256>>;
+
+    /// Returns the code at given address at given time (block number).
+    #[rpc(name = "eth_getCode")]
+    fn code_at(
+        &self, address: H160, block: Option<BlockNumber>,
+    ) -> BoxFuture<Result<Bytes>>;
+
+    /// Sends signed transaction, returning its hash.
+    #[rpc(name = "eth_sendRawTransaction")]
+    fn send_raw_transaction(&self, transaction: Bytes) -> Result<H256>;
+
+    /// Returns block with given hash.
+    #[rpc(name = "eth_getBlockByHash")]
+    fn block_by_hash(
+        &self, hash: H256, include_txs: bool,
+    ) -> BoxFuture<Result<Option<Block>>>;
+
+    /// Returns block with given number.
+    #[rpc(name = "eth_getBlockByNumber")]
+    fn block_by_number(
+        &self, block_num: BlockNumber, include_txs: bool,
+    ) -> BoxFuture<Result<Option<Block>>>;
+
+    /// Get transaction by its hash.
+    #[rpc(name = "eth_getTransactionByHash")]
+    fn transaction_by_hash(
+        &self, transaction_hash: H256,
+    ) -> BoxFuture<Result<Option<Transaction>>>;
+
+    /// Returns transaction receipt by transaction hash.
+    #[rpc(name = "eth_getTransactionReceipt")]
+    fn transaction_receipt(
+        &self, transaction_hash: H256,
+    ) -> BoxFuture<Result<Option<Receipt>>>;
+
+    /// Returns logs matching given filter object.
+    #[rpc(name = "eth_getLogs")]
+    fn logs(&self, filter: EthRpcLogFilter) -> BoxFuture<Result<Vec<Log>>>;
+}
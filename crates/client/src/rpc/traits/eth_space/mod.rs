@@ -2,4 +2,5 @@ pub mod debug;
 pub mod eth;
 pub mod eth_filter;
 pub mod eth_pubsub;
+pub mod light_eth;
 pub mod trace;
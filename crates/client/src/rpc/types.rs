@@ -25,6 +25,10 @@ pub use self::{
         receipt::Receipt,
         reward_info::RewardInfo,
         stat_on_gas_load::StatOnGasLoad,
+        state_proof::{
+            AccountProof as CfxAccountProof, StateCommitment, StateEntryProof,
+            StorageEntryProof, StorageProof as CfxStorageProof,
+        },
         status::Status,
         storage_collateral_info::StorageCollateralInfo,
         sync_graph_states::SyncGraphStates,
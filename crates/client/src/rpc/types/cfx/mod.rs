@@ -11,6 +11,7 @@ pub mod receipt;
 pub mod reward_info;
 pub mod sponsor_info;
 pub mod stat_on_gas_load;
+pub mod state_proof;
 pub mod status;
 pub mod storage_collateral_info;
 pub mod sync_graph_states;
@@ -20,14 +20,14 @@
 
 //! Pub-Sub types.
 
-use crate::rpc::types::{CfxRpcLogFilter, Header, Log};
+use crate::rpc::types::{CfxRpcLogFilter, Header, Log, Transaction};
 use cfx_types::{H256, U256};
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use serde_json::{from_value, Value};
 
 /// Subscription result.
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged, rename_all = "camelCase")]
 // NOTE: rename_all does not apply to enum member fields
 // see: https://github.com/serde-rs/serde/issues/1061
@@ -41,6 +41,16 @@ pub enum Result {
     /// Transaction hash
     TransactionHash(H256),
 
+    /// Transaction
+    Transaction(Transaction),
+
+    /// Sync status
+    SyncState {
+        syncing: bool,
+        /// The current sync phase of the node.
+        phase: String,
+    },
+
     /// Epoch
     #[serde(rename_all = "camelCase")]
     Epoch {
@@ -90,6 +100,8 @@ pub enum Params {
     Logs(CfxRpcLogFilter),
     /// Epoch parameters.
     Epochs(SubscriptionEpoch),
+    /// Whether to return full transactions instead of transaction hashes.
+    FullTransactions(bool),
 }
 
 impl Default for Params {
@@ -107,6 +119,10 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Params::None);
         }
 
+        if let Value::Bool(full_transactions) = v {
+            return Ok(Params::FullTransactions(full_transactions));
+        }
+
         // try to interpret as a log filter
         if let Ok(v) = from_value(v.clone()).map(Params::Logs) {
             return Ok(v);
@@ -118,3 +134,30 @@ impl<'a> Deserialize<'a> for Params {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Params, SubscriptionEpoch};
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_params() {
+        let params: Params = serde_json::from_str("null").unwrap();
+        assert_eq!(params, Params::None);
+
+        let params: Params = serde_json::from_str("true").unwrap();
+        assert_eq!(params, Params::FullTransactions(true));
+
+        let params: Params = serde_json::from_str("false").unwrap();
+        assert_eq!(params, Params::FullTransactions(false));
+
+        let params: Params = serde_json::from_str(r#""latest_state""#).unwrap();
+        assert_eq!(params, Params::Epochs(SubscriptionEpoch::LatestState));
+
+        let params: Params = serde_json::from_str("{}").unwrap();
+        assert!(matches!(params, Params::Logs(_)));
+
+        assert!(serde_json::from_str::<Params>("1").is_err());
+        assert!(serde_json::from_str::<Params>(r#""true""#).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use crate::rpc::types::{Bytes, RpcAddress};
+use cfx_internal_common::StateRootWithAuxInfo;
+use cfx_types::{H256, U256, U64};
+use primitives::StorageRoot;
+use serde::Serialize;
+
+/// A raw state trie entry together with its RLP encoded `StateProof`.
+///
+/// `value` is `None` for a non-existent entry, in which case `proof` is a
+/// non-existence proof.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StateEntryProof {
+    pub value: Option<Bytes>,
+    pub proof: Bytes,
+}
+
+/// The state the proofs are checked against and the pivot block committing
+/// it.
+///
+/// The hash of `stateRoot.stateRoot` is the `deferredStateRoot` of the pivot
+/// block `DEFERRED_STATE_EPOCH_COUNT` epochs later, as long as that block
+/// does not blame its ancestors (`blame == 0`). The block fields are `None`
+/// if the committing block is not on the pivot chain yet.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StateCommitment {
+    pub epoch_number: U64,
+    pub state_root: StateRootWithAuxInfo,
+    pub block_hash: Option<H256>,
+    /// RLP encoded header of the committing block, so that `blockHash` and
+    /// `deferredStateRoot` can be checked.
+    pub block_header: Option<Bytes>,
+    pub deferred_state_root: Option<H256>,
+    pub blame: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: RpcAddress,
+    /// Proof of the RLP encoded `Account`.
+    pub account: StateEntryProof,
+    /// Proof of the RLP encoded `CodeInfo` under the account's code hash.
+    pub code: StateEntryProof,
+    pub storage_root: StorageRoot,
+    /// RLP encoded `NodeMerkleProof` of `storageRoot`.
+    pub storage_root_proof: Bytes,
+    pub commitment: StateCommitment,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntryProof {
+    pub position: U256,
+    /// Proof of the RLP encoded `StorageValue`.
+    #[serde(flatten)]
+    pub entry: StateEntryProof,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+    pub address: RpcAddress,
+    pub entries: Vec<StorageEntryProof>,
+    pub commitment: StateCommitment,
+}
@@ -13,6 +13,11 @@ pub struct TxWithPoolInfo {
     pub state_balance: U256,
     pub local_balance_enough: bool,
     pub state_balance_enough: bool,
+    /// Whether the transaction is submitted through the local RPC or sent
+    /// from a configured local address.
+    pub is_local: bool,
+    /// Whether the transaction is submitted privately and never propagated.
+    pub is_private: bool,
 }
 
 #[derive(Default, Serialize)]
@@ -44,4 +49,6 @@ pub struct TxPoolStatus {
     pub ready: U64,
     pub received: U64,
     pub unexecuted: U64,
+    /// The number of the privately submitted transactions.
+    pub private: U64,
 }
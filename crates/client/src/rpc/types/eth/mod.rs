@@ -3,9 +3,12 @@
 // See http://www.gnu.org/licenses/
 
 pub use cfx_rpc_eth_types::{
-    eth_pubsub, trace_filter::TraceFilter, AccountPendingTransactions, Block,
-    BlockNumber, EthRpcLogFilter, FilterChanges, Header, Log, Receipt,
-    SyncInfo, SyncStatus, Transaction, TransactionRequest,
+    eth_pubsub, trace_filter::TraceFilter, AccessListResult,
+    AccountPendingTransactions, AccountProof, Block, BlockNumber,
+    BlockOverrides, Bundle, EthCallResponse, EthRpcLogFilter, EvmOverrides,
+    FilterChanges, Header, Log, Receipt, SimulatePayload, SimulatedBlock,
+    StateContext, StateOverride, StorageProof, SyncInfo, SyncStatus,
+    Transaction, TransactionRequest,
 };
 
 pub use cfx_rpc_cfx_types::trace_eth::{LocalizedTrace, Res};
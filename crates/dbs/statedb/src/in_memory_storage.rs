@@ -28,6 +28,10 @@ impl InmemoryStorage {
             })
         })
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.inner.iter().map(|(k, v)| (k.as_slice(), &**v))
+    }
 }
 
 impl StorageTrait for InmemoryStorage {
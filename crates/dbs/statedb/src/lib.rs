@@ -23,6 +23,8 @@ pub use self::{
     statedb_ext::StateDbExt,
 };
 pub use cfx_storage::utils::access_mode;
+#[cfg(feature = "testonly_code")]
+pub use in_memory_storage::InmemoryStorage;
 pub type StateDb = StateDbGeneric;
 
 // Put StateDb in mod to make sure that methods from statedb_ext don't access
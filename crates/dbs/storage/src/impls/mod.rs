@@ -7,6 +7,7 @@ pub(super) mod merkle_patricia_trie;
 pub(super) mod delta_mpt;
 pub(super) mod node_merkle_proof;
 pub(super) mod proof_merger;
+pub(super) mod proof_verifier;
 pub(super) mod recording_storage;
 pub(super) mod replicated_state;
 pub(super) mod single_mpt_state;
@@ -0,0 +1,77 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Standalone verification of state proofs for clients which only trust a
+//! block header.
+//!
+//! A block header commits to the state of an earlier epoch through
+//! `deferred_state_root`, which is the hash of the three trie roots
+//! (snapshot, intermediate delta and delta) in `StateRoot`. The functions
+//! here first check that the `StateRootWithAuxInfo` returned along with a
+//! proof matches that hash, and then check the proof against the roots.
+
+/// Check that `root` is the state committed by `state_root_hash` and that
+/// `proof` proves `key` maps to `value` in that state. A `None` value checks
+/// the non-existence of `key`.
+///
+/// `value` is the raw entry in the state trie, e.g. the RLP encoding of an
+/// `Account` or of a `StorageValue`.
+pub fn verify_state_proof(
+    state_root_hash: &MerkleHash, root: &StateRootWithAuxInfo,
+    key: StorageKeyWithSpace, value: Option<&[u8]>, proof: &StateProof,
+) -> bool {
+    if !is_committed_root(state_root_hash, root) {
+        return false;
+    }
+
+    proof.is_valid_kv(
+        &key.to_key_bytes(),
+        value,
+        root.state_root.clone(),
+        root.aux_info.maybe_intermediate_mpt_key_padding.clone(),
+    )
+}
+
+/// Check that `root` is the state committed by `state_root_hash` and that
+/// `proof` proves `storage_root` is the storage root of `address` in that
+/// state.
+pub fn verify_storage_root_proof(
+    state_root_hash: &MerkleHash, root: &StateRootWithAuxInfo,
+    address: &AddressWithSpace, storage_root: &StorageRoot,
+    proof: &StorageRootProof,
+) -> bool {
+    if !is_committed_root(state_root_hash, root) {
+        return false;
+    }
+
+    let key = StorageKey::new_storage_root_key(&address.address)
+        .with_space(address.space)
+        .to_key_bytes();
+
+    proof.is_valid(
+        &key,
+        storage_root,
+        root.state_root.clone(),
+        root.aux_info.maybe_intermediate_mpt_key_padding.clone(),
+    )
+}
+
+fn is_committed_root(
+    state_root_hash: &MerkleHash, root: &StateRootWithAuxInfo,
+) -> bool {
+    let computed = root.state_root.compute_state_root_hash();
+    if computed != *state_root_hash {
+        debug!(
+            "State root mismatch: expected {:?}, computed {:?}",
+            state_root_hash, computed
+        );
+        return false;
+    }
+    true
+}
+
+use super::{node_merkle_proof::StorageRootProof, state_proof::StateProof};
+use cfx_internal_common::StateRootWithAuxInfo;
+use cfx_types::AddressWithSpace;
+use primitives::{MerkleHash, StorageKey, StorageKeyWithSpace, StorageRoot};
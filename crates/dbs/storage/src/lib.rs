@@ -206,6 +206,7 @@ pub use self::{
         },
         node_merkle_proof::{NodeMerkleProof, StorageRootProof},
         proof_merger::StateProofMerger,
+        proof_verifier::{verify_state_proof, verify_storage_root_proof},
         recording_storage::RecordingStorage,
         snapshot_sync::{FullSyncVerifier, MptSlicer},
         state_proof::StateProof,
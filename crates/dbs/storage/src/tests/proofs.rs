@@ -604,6 +604,222 @@ fn test_invalid_node_merkle_proof() {
     }
 }
 
+#[test]
+fn test_verify_proofs_against_state_root_hash() {
+    let mut rng = get_rng_for_test();
+
+    // note: do not drop state_manager (_mgr)
+    let (_mgr, state, padding, keys) = generate_random_state(&mut rng);
+    let mut root = state.get_state_root().unwrap();
+    root.aux_info.maybe_intermediate_mpt_key_padding = Some(padding.clone());
+    let root_hash = root.state_root.compute_state_root_hash();
+
+    let mut invalid_root_hash = root_hash.clone();
+    invalid_root_hash.as_bytes_mut()[0] ^= 0x01;
+
+    for key in keys.iter().take(100) {
+        let storage_key = StorageKey::AccountKey(key).with_native_space();
+        let (value, proof) = state
+            .get_with_proof(storage_key)
+            .expect("kv lookup should succeed");
+        let value = value.as_ref().map(|b| &**b);
+
+        assert!(verify_state_proof(
+            &root_hash,
+            &root,
+            storage_key,
+            value,
+            &proof
+        ));
+
+        // a proof for a state which is not committed by the header
+        // should be rejected
+        assert!(!verify_state_proof(
+            &invalid_root_hash,
+            &root,
+            storage_key,
+            value,
+            &proof
+        ));
+
+        assert!(!verify_state_proof(
+            &root_hash,
+            &root,
+            storage_key,
+            None,
+            &proof
+        ));
+    }
+
+    for key in generate_nonexistent_keys(&mut rng, &keys).iter().take(100) {
+        let storage_key = StorageKey::AccountKey(key).with_native_space();
+        let (value, proof) = state
+            .get_with_proof(storage_key)
+            .expect("kv lookup should succeed");
+
+        assert_eq!(value, None);
+        assert!(verify_state_proof(
+            &root_hash,
+            &root,
+            storage_key,
+            None,
+            &proof
+        ));
+    }
+}
+
+#[test]
+fn test_verify_storage_root_proof() {
+    let mut rng = get_rng_for_test();
+    let snapshot_epoch_count = 1;
+    let state_manager =
+        new_state_manager_for_unit_test_with_snapshot_epoch_count(
+            snapshot_epoch_count,
+        );
+
+    let addresses: Vec<Address> = generate_account_keys(TEST_NUMBER_OF_KEYS)
+        .iter()
+        .take(200)
+        .map(|key| Address::from_slice(key))
+        .collect();
+    let slot = H256::from_low_u64_be(1);
+
+    // the storage of the first half of accounts is set in state-0 and the
+    // second half in state-1, so that the proofs go through both the snapshot
+    // and the intermediate trie
+    let mut state_0 = state_manager.get_state_for_genesis_write();
+    for address in portion(&addresses, 0, 2) {
+        state_0
+            .set(
+                StorageKey::new_storage_key(address, slot.as_bytes())
+                    .with_native_space(),
+                address.as_bytes().into(),
+            )
+            .expect("Inserting k-v should succeed");
+    }
+
+    let mut epoch_id_0 = H256::default();
+    epoch_id_0.as_bytes_mut()[0] = 1;
+    let root_0 = state_0.compute_state_root().unwrap();
+    state_0.commit(epoch_id_0).unwrap();
+
+    let mut state_1 = state_manager
+        .get_state_for_next_epoch_inner(
+            StateIndex::new_for_next_epoch(
+                &epoch_id_0,
+                &root_0,
+                1,
+                snapshot_epoch_count,
+            ),
+            true,
+            false,
+        )
+        .unwrap()
+        .unwrap();
+
+    for address in portion(&addresses, 1, 2) {
+        state_1
+            .set(
+                StorageKey::new_storage_key(address, slot.as_bytes())
+                    .with_native_space(),
+                address.as_bytes().into(),
+            )
+            .expect("Inserting k-v should succeed");
+    }
+
+    let mut epoch_id_1 = H256::default();
+    epoch_id_1.as_bytes_mut()[0] = 2;
+    let root_1 = state_1.compute_state_root().unwrap();
+    state_1.commit(epoch_id_1).unwrap();
+
+    let padding = StorageKeyWithSpace::delta_mpt_padding(
+        &root_1.state_root.snapshot_root,
+        &root_1.state_root.intermediate_delta_root,
+    );
+
+    let state = state_manager
+        .get_state_for_next_epoch_inner(
+            StateIndex::new_for_next_epoch(
+                &epoch_id_1,
+                &root_1,
+                2,
+                snapshot_epoch_count,
+            ),
+            true,
+            false,
+        )
+        .unwrap()
+        .unwrap();
+
+    let mut root = state.get_state_root().unwrap();
+    root.aux_info.maybe_intermediate_mpt_key_padding = Some(padding);
+    let root_hash = root.state_root.compute_state_root_hash();
+    let invalid_root_hash = get_invalid_hash(&mut rng, root_hash);
+
+    for (i, address) in addresses.iter().enumerate() {
+        let address = address.with_native_space();
+        let (storage_root, proof) = state
+            .get_node_merkle_all_versions::<WithProof>(
+                StorageKey::new_storage_root_key(&address.address)
+                    .with_native_space(),
+            )
+            .expect("node merkle lookup should succeed");
+
+        assert!(
+            storage_root.delta.is_some()
+                || storage_root.intermediate.is_some()
+                || storage_root.snapshot.is_some()
+        );
+
+        assert!(verify_storage_root_proof(
+            &root_hash,
+            &root,
+            &address,
+            &storage_root,
+            &proof
+        ));
+
+        // a proof for a state which is not committed by the header should be
+        // rejected
+        assert!(!verify_storage_root_proof(
+            &invalid_root_hash,
+            &root,
+            &address,
+            &storage_root,
+            &proof
+        ));
+
+        // a tampered storage root should be rejected
+        let invalid_storage_root =
+            get_invalid_merkle_triplet(&mut rng, &storage_root);
+        assert!(!verify_storage_root_proof(
+            &root_hash,
+            &root,
+            &address,
+            &invalid_storage_root,
+            &proof
+        ));
+
+        // the proof should not work for other accounts or the other space
+        let other = addresses[(i + 1) % addresses.len()].with_native_space();
+        assert!(!verify_storage_root_proof(
+            &root_hash,
+            &root,
+            &other,
+            &storage_root,
+            &proof
+        ));
+
+        assert!(!verify_storage_root_proof(
+            &root_hash,
+            &root,
+            &address.address.with_evm_space(),
+            &storage_root,
+            &proof
+        ));
+    }
+}
+
 #[test]
 fn test_recording_storage() {
     let mut rng = get_rng_for_test();
@@ -702,9 +918,9 @@ use crate::{
         new_state_manager_for_unit_test_with_snapshot_epoch_count,
         FakeStateManager, TEST_NUMBER_OF_KEYS,
     },
-    RecordingStorage,
+    verify_state_proof, verify_storage_root_proof, RecordingStorage,
 };
-use cfx_types::H256;
+use cfx_types::{Address, AddressSpaceUtil, H256};
 use primitives::{
     DeltaMptKeyPadding, MptValue, NodeMerkleTriplet, StateRoot, StorageKey,
     StorageKeyWithSpace,
@@ -0,0 +1,132 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Snappy compression of the protocol packets.
+//!
+//! Compression is advertised as an extension of the Hello packet, and only
+//! protocol packets that are not smaller than the configured threshold are
+//! compressed once both peers advertised it. Compressed packets are marked by
+//! a packet extension, so that small packets are still sent as is.
+
+use crate::Error;
+use lazy_static::lazy_static;
+use log::debug;
+use metrics::{
+    register_meter_with_group, Histogram, Meter, MeterTimer, Sample,
+};
+use std::sync::Arc;
+
+lazy_static! {
+    static ref COMPRESS_RAW_METER: Arc<dyn Meter> =
+        register_meter_with_group("network_compression", "compress_raw");
+    static ref COMPRESS_COMPRESSED_METER: Arc<dyn Meter> =
+        register_meter_with_group("network_compression", "compress_compressed");
+    static ref DECOMPRESS_RAW_METER: Arc<dyn Meter> =
+        register_meter_with_group("network_compression", "decompress_raw");
+    static ref DECOMPRESS_COMPRESSED_METER: Arc<dyn Meter> =
+        register_meter_with_group(
+            "network_compression",
+            "decompress_compressed"
+        );
+    /// Compressed size in percentage of the raw size.
+    static ref COMPRESSION_RATIO: Arc<dyn Histogram> =
+        Sample::ExpDecay(0.015).register_with_group(
+            "network_compression",
+            "compression_ratio",
+            1024
+        );
+    static ref COMPRESSION_SKIPPED_METER: Arc<dyn Meter> =
+        register_meter_with_group("network_compression", "skipped");
+    static ref COMPRESS_TIMER: Arc<dyn Meter> =
+        register_meter_with_group("timer", "network_compression::compress");
+    static ref DECOMPRESS_TIMER: Arc<dyn Meter> =
+        register_meter_with_group("timer", "network_compression::decompress");
+}
+
+/// Compression algorithm advertised in the Hello packet.
+pub const COMPRESSION_SNAPPY: u8 = 1;
+
+/// Default minimum size of packets to compress.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Maximum size of decompressed packets, which is the same as the maximum
+/// packet size on wire.
+const MAX_DECOMPRESSED_SIZE: usize = (1 << 24) - 1;
+
+/// Compresses the packet data. Returns `None` if the data is not smaller
+/// after compressed, in which case it should be sent as is.
+pub fn compress(data: &[u8]) -> Option<Vec<u8>> {
+    let _timer = MeterTimer::time_func(COMPRESS_TIMER.as_ref());
+
+    let compressed = match snap::raw::Encoder::new().compress_vec(data) {
+        Ok(compressed) => compressed,
+        Err(e) => {
+            debug!("failed to compress packet, err = {:?}", e);
+            return None;
+        }
+    };
+
+    if compressed.len() >= data.len() {
+        COMPRESSION_SKIPPED_METER.mark(1);
+        return None;
+    }
+
+    COMPRESS_RAW_METER.mark(data.len());
+    COMPRESS_COMPRESSED_METER.mark(compressed.len());
+    COMPRESSION_RATIO.update((compressed.len() * 100 / data.len()) as u64);
+
+    Some(compressed)
+}
+
+/// Decompresses the packet data received from remote peer.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let _timer = MeterTimer::time_func(DECOMPRESS_TIMER.as_ref());
+
+    // check the declared size in advance to avoid allocating too much memory
+    let len = snap::raw::decompress_len(data).map_err(|e| {
+        Error::Decoder(format!("invalid compressed packet: {}", e))
+    })?;
+    if len > MAX_DECOMPRESSED_SIZE {
+        debug!("failed to decompress packet, oversized, len = {}", len);
+        return Err(Error::OversizedPacket);
+    }
+
+    let decompressed =
+        snap::raw::Decoder::new()
+            .decompress_vec(data)
+            .map_err(|e| {
+                Error::Decoder(format!("invalid compressed packet: {}", e))
+            })?;
+
+    DECOMPRESS_COMPRESSED_METER.mark(data.len());
+    DECOMPRESS_RAW_METER.mark(decompressed.len());
+
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_and_decompress() {
+        let data = vec![7u8; 4096];
+        let compressed = compress(&data).unwrap();
+        assert!(compressed.len() < data.len());
+        assert_eq!(decompress(&compressed).unwrap(), data);
+
+        // incompressible
+        assert!(compress(&[1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn test_decompress_invalid() {
+        let mut compressed = compress(&vec![7u8; 4096]).unwrap();
+        compressed.truncate(compressed.len() - 1);
+        assert!(decompress(&compressed).is_err());
+
+        // declared size is too large
+        assert!(decompress(&[0xff, 0xff, 0xff, 0xff, 0x0f]).is_err());
+    }
+}
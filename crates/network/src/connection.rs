@@ -3,6 +3,7 @@
 // See http://www.gnu.org/licenses/
 
 use crate::{
+    encryption::{FrameSealer, FRAME_OVERHEAD},
     iolib::{IoContext, StreamToken},
     throttling::THROTTLING_SERVICE,
     Error,
@@ -188,6 +189,9 @@ pub struct GenericConnection<Socket: GenericSocket> {
     registered: AtomicBool,
     /// Assemble packet with extra information before sending out.
     assembler: Box<dyn PacketAssembler>,
+    /// Encrypt packets before assembling once the session negotiated
+    /// encryption.
+    sealer: Option<FrameSealer>,
 }
 
 impl<Socket: GenericSocket> GenericConnection<Socket> {
@@ -251,6 +255,9 @@ impl<Socket: GenericSocket> GenericConnection<Socket> {
             data
         );
 
+        if let Some(ref mut sealer) = self.sealer {
+            sealer.seal(&mut data)?;
+        }
         self.assembler.assemble(&mut data)?;
         let size = self.socket.write(&data)?;
 
@@ -275,6 +282,12 @@ impl<Socket: GenericSocket> GenericConnection<Socket> {
                 None => return Ok(WriteStatus::Complete),
             };
 
+            // Packets are sealed when dequeued rather than enqueued, so that
+            // the encryption nonces follow the order written to socket.
+            if let Some(ref mut sealer) = self.sealer {
+                sealer.seal(&mut packet.data)?;
+            }
+
             // assemble packet to send, e.g. prefix length to packet
             self.assembler.assemble(&mut packet.data)?;
 
@@ -338,7 +351,9 @@ impl<Socket: GenericSocket> GenericConnection<Socket> {
     ) -> Result<SendQueueStatus, Error> {
         if !data.is_empty() {
             let size = data.len();
-            if self.assembler.is_oversized(size) {
+            // reserve space for encryption, which may be enabled before the
+            // packet is dequeued
+            if self.assembler.is_oversized(size + FRAME_OVERHEAD) {
                 return Err(Error::OversizedPacket.into());
             }
 
@@ -372,6 +387,12 @@ impl<Socket: GenericSocket> GenericConnection<Socket> {
     }
 
     pub fn is_sending(&self) -> bool { self.interest.is_writable() }
+
+    /// Encrypt the packets that are not sent out yet and all subsequent
+    /// packets.
+    pub fn set_sealer(&mut self, sealer: FrameSealer) {
+        self.sealer = Some(sealer);
+    }
 }
 
 pub type Connection = GenericConnection<TcpStream>;
@@ -387,6 +408,7 @@ impl Connection {
             interest: Ready::hup() | Ready::readable(),
             registered: AtomicBool::new(false),
             assembler: Box::new(PacketWithLenAssembler::default()),
+            sealer: None,
         }
     }
 
@@ -675,6 +697,7 @@ mod tests {
                 interest: Ready::hup() | Ready::readable(),
                 registered: AtomicBool::new(false),
                 assembler: Box::new(PacketWithLenAssembler::new(1, None)),
+                sealer: None,
             }
         }
     }
@@ -0,0 +1,266 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Optional authenticated encryption of the session packets.
+//!
+//! The nonces exchanged in the handshake are encrypted with the node keys of
+//! the recipients, so they are only known by the 2 peers and are used to
+//! derive a key for each direction of the session. Packets are then sealed
+//! with AES-256-GCM, and the AEAD nonce is a counter of the packets sent in
+//! the direction, which is never transferred.
+//!
+//! The encrypted frame format is:
+//!     [ciphertext || tag (16 bytes) || PACKET_ENCRYPTED]
+//!
+//! `PACKET_ENCRYPTED` takes the place of the packet id of plaintext packets,
+//! so that the receiver could tell encrypted frames from the plaintext ones
+//! sent before the encryption is negotiated.
+
+use crate::Error;
+use aes_gcm::{
+    aead::{generic_array::GenericArray, AeadInPlace, NewAead},
+    Aes256Gcm,
+};
+use bytes::Bytes;
+use cfx_types::H256;
+use keccak_hash::keccak;
+use log::debug;
+use std::str::FromStr;
+
+/// Packet id of the encrypted frames, which is not used by any plaintext
+/// packet.
+pub const PACKET_ENCRYPTED: u8 = 0x81;
+
+/// Cipher suite advertised in the HELLO packet.
+pub const CIPHER_AES_256_GCM: u8 = 1;
+
+/// Bytes appended to a packet when it is sealed.
+pub const FRAME_OVERHEAD: usize = AES_GCM_TAG_LEN + 1;
+
+const AES_GCM_TAG_LEN: usize = 16;
+const AES_GCM_NONCE_LEN: usize = 12;
+
+const INITIATOR_KEY_LABEL: &[u8] = b"conflux-p2p-initiator";
+const RECIPIENT_KEY_LABEL: &[u8] = b"conflux-p2p-recipient";
+
+/// Node-level setting of the session encryption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionMode {
+    /// Neither advertise nor accept encryption.
+    Disabled,
+    /// Encrypt the sessions with peers that support it, and fall back to
+    /// plaintext otherwise.
+    Enabled,
+    /// Disconnect peers that do not support encryption.
+    Required,
+}
+
+impl Default for EncryptionMode {
+    fn default() -> Self { EncryptionMode::Disabled }
+}
+
+impl FromStr for EncryptionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disabled" => Ok(EncryptionMode::Disabled),
+            "enabled" => Ok(EncryptionMode::Enabled),
+            "required" => Ok(EncryptionMode::Required),
+            _ => Err(format!(
+                "invalid encryption mode {}, expected one of disabled, enabled \
+                 and required",
+                s
+            )),
+        }
+    }
+}
+
+/// Creates the sealer and opener of a session from the local and remote
+/// nonces of the handshake.
+pub fn session_ciphers(
+    local_nonce: &H256, remote_nonce: &H256, originated: bool,
+) -> (FrameSealer, FrameOpener) {
+    let (initiator_nonce, recipient_nonce) = if originated {
+        (local_nonce, remote_nonce)
+    } else {
+        (remote_nonce, local_nonce)
+    };
+    let initiator_key =
+        derive_key(INITIATOR_KEY_LABEL, initiator_nonce, recipient_nonce);
+    let recipient_key =
+        derive_key(RECIPIENT_KEY_LABEL, initiator_nonce, recipient_nonce);
+
+    let (seal_key, open_key) = if originated {
+        (initiator_key, recipient_key)
+    } else {
+        (recipient_key, initiator_key)
+    };
+
+    (
+        FrameSealer {
+            cipher: FrameCipher::new(&seal_key),
+        },
+        FrameOpener {
+            cipher: FrameCipher::new(&open_key),
+        },
+    )
+}
+
+fn derive_key(
+    label: &[u8], initiator_nonce: &H256, recipient_nonce: &H256,
+) -> H256 {
+    let mut data = Vec::with_capacity(label.len() + 2 * H256::len_bytes());
+    data.extend_from_slice(label);
+    data.extend_from_slice(initiator_nonce.as_bytes());
+    data.extend_from_slice(recipient_nonce.as_bytes());
+    keccak(data)
+}
+
+/// Check if the received frame is encrypted.
+pub fn is_encrypted(frame: &[u8]) -> bool {
+    frame.last() == Some(&PACKET_ENCRYPTED)
+}
+
+struct FrameCipher {
+    aead: Aes256Gcm,
+    /// Number of frames processed, which is the AEAD nonce of next frame.
+    counter: u64,
+}
+
+impl FrameCipher {
+    fn new(key: &H256) -> Self {
+        FrameCipher {
+            aead: Aes256Gcm::new(GenericArray::from_slice(key.as_bytes())),
+            counter: 0,
+        }
+    }
+
+    fn next_nonce(&mut self) -> Result<[u8; AES_GCM_NONCE_LEN], Error> {
+        let mut nonce = [0u8; AES_GCM_NONCE_LEN];
+        nonce[AES_GCM_NONCE_LEN - 8..]
+            .copy_from_slice(&self.counter.to_be_bytes());
+        // never reuse the nonce with the same key
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| Error::Msg("encryption nonce exhausted".into()))?;
+        Ok(nonce)
+    }
+}
+
+/// Encrypts the packets sent to remote peer.
+pub struct FrameSealer {
+    cipher: FrameCipher,
+}
+
+impl FrameSealer {
+    /// Encrypts the assembled session packet in place. Packets must be sealed
+    /// in the same order as they are written to socket.
+    pub fn seal(&mut self, data: &mut Vec<u8>) -> Result<(), Error> {
+        let nonce = self.cipher.next_nonce()?;
+        self.cipher
+            .aead
+            .encrypt_in_place(GenericArray::from_slice(&nonce), b"", data)
+            .map_err(|_| Error::Msg("failed to encrypt packet".into()))?;
+        data.push(PACKET_ENCRYPTED);
+        Ok(())
+    }
+}
+
+/// Decrypts the packets received from remote peer.
+pub struct FrameOpener {
+    cipher: FrameCipher,
+}
+
+impl FrameOpener {
+    /// Decrypts the received frame, which must be checked by `is_encrypted`
+    /// in advance.
+    pub fn open(&mut self, frame: &[u8]) -> Result<Bytes, Error> {
+        if frame.len() < FRAME_OVERHEAD {
+            debug!("failed to open frame, too short, len = {}", frame.len());
+            return Err(Error::BadProtocol);
+        }
+
+        let nonce = self.cipher.next_nonce()?;
+        let mut data = frame[..frame.len() - 1].to_vec();
+        self.cipher
+            .aead
+            .decrypt_in_place(GenericArray::from_slice(&nonce), b"", &mut data)
+            .map_err(|_| {
+                debug!("failed to open frame, authentication failed");
+                Error::Auth
+            })?;
+        Ok(data.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_pair() -> ((FrameSealer, FrameOpener), (FrameSealer, FrameOpener))
+    {
+        let initiator_nonce = H256::random();
+        let recipient_nonce = H256::random();
+        (
+            session_ciphers(&initiator_nonce, &recipient_nonce, true),
+            session_ciphers(&recipient_nonce, &initiator_nonce, false),
+        )
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let ((mut sealer1, mut opener1), (mut sealer2, mut opener2)) =
+            session_pair();
+
+        for i in 0..3u8 {
+            let mut data = vec![i; 10];
+            sealer1.seal(&mut data).unwrap();
+            assert_eq!(data.len(), 10 + FRAME_OVERHEAD);
+            assert!(is_encrypted(&data));
+            assert_eq!(&opener2.open(&data).unwrap()[..], &[i; 10][..]);
+        }
+
+        let mut data = vec![];
+        sealer2.seal(&mut data).unwrap();
+        assert!(opener1.open(&data).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_open_invalid_frame() {
+        let mut data = vec![1, 2, 3];
+
+        // tampered
+        let ((mut sealer, _), (_, mut opener)) = session_pair();
+        let mut frame = data.clone();
+        sealer.seal(&mut frame).unwrap();
+        frame[0] ^= 1;
+        assert!(opener.open(&frame).is_err());
+
+        // replayed
+        let ((mut sealer, _), (_, mut opener)) = session_pair();
+        sealer.seal(&mut data).unwrap();
+        assert!(opener.open(&data).is_ok());
+        assert!(opener.open(&data).is_err());
+
+        // reflected to the sender
+        let ((mut sealer, mut opener), _) = session_pair();
+        let mut frame = vec![1, 2, 3];
+        sealer.seal(&mut frame).unwrap();
+        assert!(opener.open(&frame).is_err());
+
+        // too short
+        assert!(opener.open(&[PACKET_ENCRYPTED]).is_err());
+    }
+
+    #[test]
+    fn test_encryption_mode_from_str() {
+        assert_eq!(
+            EncryptionMode::from_str("required"),
+            Ok(EncryptionMode::Required)
+        );
+        assert!(EncryptionMode::from_str("on").is_err());
+    }
+}
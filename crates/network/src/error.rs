@@ -15,6 +15,7 @@ pub enum DisconnectReason {
     IpLimited,
     UpdateNodeIdFailed,
     Blacklisted,
+    EncryptionRequired,
     Custom(String),
     Unknown,
 }
@@ -28,6 +29,7 @@ impl DisconnectReason {
             DisconnectReason::IpLimited => 3,
             DisconnectReason::UpdateNodeIdFailed => 4,
             DisconnectReason::Blacklisted => 5,
+            DisconnectReason::EncryptionRequired => 6,
             DisconnectReason::Custom(_) => 100,
             DisconnectReason::Unknown => 0xff,
         }
@@ -61,6 +63,7 @@ impl Decodable for DisconnectReason {
             3 => Ok(DisconnectReason::IpLimited),
             4 => Ok(DisconnectReason::UpdateNodeIdFailed),
             5 => Ok(DisconnectReason::Blacklisted),
+            6 => Ok(DisconnectReason::EncryptionRequired),
             100 => match std::str::from_utf8(&raw[1..]) {
                 Err(_) => {
                     Err(DecoderError::Custom("Unable to decode message part"))
@@ -81,6 +84,7 @@ impl fmt::Display for DisconnectReason {
             DisconnectReason::IpLimited => "IP limited",
             DisconnectReason::UpdateNodeIdFailed => "Update node id failed",
             DisconnectReason::Blacklisted => "blacklisted",
+            DisconnectReason::EncryptionRequired => "encryption required",
             DisconnectReason::Custom(ref msg) => &msg[..],
             DisconnectReason::Unknown => "unknown",
         };
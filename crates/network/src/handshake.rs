@@ -67,6 +67,9 @@ pub struct Handshake {
     pub state: HandshakeState,
     /// nonce for verification
     nonce: H256,
+    /// nonce of remote node, which is unknown in test mode that bypasses the
+    /// cryptography
+    remote_nonce: Option<H256>,
 }
 
 impl Handshake {
@@ -79,6 +82,7 @@ impl Handshake {
             connection: Connection::new(token, socket),
             state: HandshakeState::New,
             nonce: H256::random(),
+            remote_nonce: None,
         }
     }
 
@@ -101,6 +105,13 @@ impl Handshake {
     /// Check if handshake is complete
     pub fn done(&self) -> bool { self.state == HandshakeState::StartSession }
 
+    /// Get the local and remote nonces, which are only known by the 2 peers
+    /// and used to derive the session encryption keys.
+    pub fn nonces(&self) -> Option<(H256, H256)> {
+        self.remote_nonce
+            .map(|remote_nonce| (self.nonce, remote_nonce))
+    }
+
     /// Readable IO handler. Drives the state change.
     pub fn readable<Message>(
         &mut self, io: &IoContext<Message>, host: &HostMetadata,
@@ -192,6 +203,7 @@ impl Handshake {
 
         let (remote_public, remote_nonce) = auth.split_at(NodeId::len_bytes());
         self.id.assign_from_slice(remote_public);
+        self.remote_nonce = Some(H256::from_slice(remote_nonce));
 
         self.write_ack_of_auth(io, remote_nonce)
     }
@@ -267,6 +279,7 @@ impl Handshake {
             debug!("failed to read ack of auth, nonce mismatch");
             return Err(Error::BadProtocol.into());
         }
+        self.remote_nonce = Some(H256::from_slice(remote_nonce));
 
         self.write_ack_of_ack(io, remote_nonce)
     }
@@ -12,29 +12,35 @@ pub type ProtocolId = [u8; PROTOCOL_ID_SIZE];
 pub type HandlerWorkType = u8;
 pub type PeerId = usize;
 
+mod compression;
 mod connection;
 mod discovery;
+mod encryption;
 mod error;
 mod handshake;
 mod ip;
 mod ip_utils;
 mod node_database;
 pub mod node_table;
+mod reputation;
 pub mod service;
 mod session;
 mod session_manager;
 pub mod throttling;
 
 pub use crate::{
+    encryption::EncryptionMode,
     error::{DisconnectReason, Error, ThrottlingReason},
     ip::SessionIpLimitConfig,
     node_table::Node,
+    reputation::{PeerReputation, ReputationChange},
     service::NetworkService,
     session::SessionDetails,
 };
 pub use io::TimerToken;
 
 use crate::{
+    compression::DEFAULT_COMPRESSION_THRESHOLD,
     node_table::NodeId,
     service::{
         ProtocolVersion, DEFAULT_CONNECTION_LIFETIME_FOR_PROMOTION,
@@ -114,6 +120,12 @@ pub struct NetworkConfiguration {
     /// Maximum number of P2P nodes for subnet B (ip/16).
     pub subnet_quota: usize,
     pub session_ip_limit_config: SessionIpLimitConfig,
+    /// Whether to encrypt the P2P sessions after handshake
+    pub encryption: EncryptionMode,
+    /// Whether to compress protocol packets if the remote peer supports it
+    pub compression_enabled: bool,
+    /// Minimum size of protocol packets to compress
+    pub compression_threshold: usize,
 
     pub discovery_config: DiscoveryConfiguration,
 }
@@ -151,6 +163,9 @@ impl NetworkConfiguration {
             test_mode: false,
             subnet_quota: 32,
             session_ip_limit_config: SessionIpLimitConfig::default(),
+            encryption: EncryptionMode::default(),
+            compression_enabled: false,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
             discovery_config,
         }
     }
@@ -287,6 +302,13 @@ pub trait NetworkContext {
         &self, node_id: &NodeId, op: Option<UpdateNodeOperation>, reason: &str,
     );
 
+    /// Update the reputation of the specified peer, and disconnect the peer
+    /// if it is banned due to low reputation. Returns `true` if the peer is
+    /// banned and disconnected.
+    fn report_peer(
+        &self, node_id: &NodeId, change: ReputationChange, reason: &str,
+    ) -> bool;
+
     /// Register a new IO timer. 'IoHandler::timeout' will be called with the
     /// token.
     fn register_timer(
@@ -5,6 +5,7 @@
 use crate::{
     ip::{NodeIpLimit, NodeTagIndex, ValidateInsertResult},
     node_table::{Node, NodeContact, NodeEntry, NodeId, NodeTable},
+    reputation::{PeerReputation, ReputationChange, ReputationTable},
     IpFilter,
 };
 use io::StreamToken;
@@ -13,6 +14,7 @@ use std::{collections::HashSet, net::IpAddr, time::Duration};
 const TRUSTED_NODES_FILE: &str = "trusted_nodes.json";
 const UNTRUSTED_NODES_FILE: &str = "untrusted_nodes.json";
 const BLACKLISTED_NODES_FILE: &str = "blacklisted_nodes.json";
+const RESERVED_NODES_FILE: &str = "reserved_nodes.json";
 
 /// Node database maintains all P2P nodes in trusted and untrusted node tables,
 /// and supports to limit the number of nodes for the same IP address.
@@ -30,6 +32,9 @@ const BLACKLISTED_NODES_FILE: &str = "blacklisted_nodes.json";
 /// 3. RPC explicitly add a trusted node. If the node is an existing
 /// untrusted one, promote it to trusted.
 ///
+/// Besides, RPC could add reserved nodes, which are persisted in a separate
+/// table and always connected after restart.
+///
 /// # Update node information
 ///
 /// ## note_success
@@ -76,6 +81,14 @@ const BLACKLISTED_NODES_FILE: &str = "blacklisted_nodes.json";
 /// following: 1. Select untrusted node prior to trusted node.
 /// 2. Select node that has been contacted long time ago.
 /// 3. Randomly select one without "fresher" bias.
+///
+/// # Reputation
+///
+/// Protocol handlers report the misbehavior of peers, which lowers the
+/// reputation score of peers. Peers with low score are banned temporarily or
+/// permanently, and removed from the trusted and untrusted node tables. Like
+/// blacklisted nodes, banned nodes are refused to connect or update via UDP
+/// discovery until the ban expires.
 pub struct NodeDatabase {
     // Trusted nodes to establish outgoing connections.
     // A trusted node comes in 2 ways:
@@ -92,6 +105,13 @@ pub struct NodeDatabase {
     // Maximum duration to blacklist a node since last contact.
     blacklisted_lifetime: Duration,
 
+    // Reputation scores and bans of peers.
+    reputations: ReputationTable,
+
+    // Reserved nodes added via RPC, which are also added as trusted nodes
+    // when the database is loaded.
+    reserved_nodes: NodeTable,
+
     // IP address/subnet index for trusted and untrusted nodes.
     ip_limit: NodeIpLimit,
 
@@ -116,8 +136,13 @@ impl NodeDatabase {
         let mut db = NodeDatabase {
             trusted_nodes,
             untrusted_nodes,
-            blacklisted_nodes: NodeTable::new(path, BLACKLISTED_NODES_FILE),
+            blacklisted_nodes: NodeTable::new(
+                path.clone(),
+                BLACKLISTED_NODES_FILE,
+            ),
             blacklisted_lifetime: Duration::from_secs(7 * 24 * 3600),
+            reputations: ReputationTable::new(path.clone()),
+            reserved_nodes: NodeTable::new(path, RESERVED_NODES_FILE),
             ip_limit,
             trusted_node_tag_index,
         };
@@ -125,6 +150,10 @@ impl NodeDatabase {
         db.init(false /* trusted */);
         db.init(true /* trusted */);
 
+        for entry in db.reserved_nodes.entries() {
+            db.insert_trusted(entry);
+        }
+
         db
     }
 
@@ -400,6 +429,10 @@ impl NodeDatabase {
         self.untrusted_nodes.clear_useless();
 
         self.blacklisted_nodes.save();
+
+        self.reputations.save();
+
+        self.reserved_nodes.save();
     }
 
     /// Promote untrusted nodes to trusted with the given duration.
@@ -581,10 +614,69 @@ impl NodeDatabase {
         }
     }
 
-    /// Check if the specified node is blacklisted.
+    /// Apply the reputation change to the specified node. Returns `true` if
+    /// the node is banned due to low reputation, in which case it is removed
+    /// from the trusted and untrusted node tables.
+    pub fn note_reputation(
+        &mut self, id: &NodeId, change: ReputationChange,
+    ) -> bool {
+        let banned = self.reputations.note(id, change);
+        if banned {
+            self.remove(id);
+        }
+        banned
+    }
+
+    pub fn get_reputation(&self, id: &NodeId) -> Option<PeerReputation> {
+        self.reputations.get(id)
+    }
+
+    /// Override the reputation score of the specified node.
+    pub fn set_reputation_score(
+        &mut self, id: &NodeId, score: f64,
+    ) -> PeerReputation {
+        self.reputations.set_score(id, score)
+    }
+
+    /// Ban the specified node for the given duration, or permanently if
+    /// `duration` is `None`.
+    pub fn ban(&mut self, id: &NodeId, duration: Option<Duration>) {
+        self.reputations.ban(id, duration);
+        self.remove(id);
+    }
+
+    /// Clear the reputation and ban of the specified node.
+    pub fn unban(&mut self, id: &NodeId) -> bool { self.reputations.unban(id) }
+
+    /// Add a reserved node, which is also added as a trusted node to connect.
+    pub fn insert_reserved(&mut self, entry: NodeEntry) {
+        let node = Node::new(entry.id.clone(), entry.endpoint.clone());
+        self.reserved_nodes
+            .add_node(node, false /* preserve_last_contact */);
+        self.insert_trusted(entry);
+    }
+
+    /// Remove the specified reserved node, and demote it to untrusted if it is
+    /// trusted, so that it is not persisted in the trusted node table either.
+    /// Returns `true` if it is a persisted reserved node.
+    pub fn remove_reserved(&mut self, id: &NodeId) -> bool {
+        let removed = self.reserved_nodes.remove_with_id(id).is_some();
+        self.demote(id);
+        removed
+    }
+
+    pub fn reserved_nodes(&self) -> Vec<NodeEntry> {
+        self.reserved_nodes.entries()
+    }
+
+    /// Check if the specified node is blacklisted or banned.
     /// If blacklisted for a long time, it will be removed from blacklisted node
     /// table.
     pub fn evaluate_blacklisted(&mut self, id: &NodeId) -> bool {
+        if self.reputations.is_banned(id) {
+            return true;
+        }
+
         let node = match self.blacklisted_nodes.get_mut(id) {
             Some(node) => node,
             None => return false,
@@ -615,8 +707,11 @@ impl NodeDatabase {
 #[cfg(test)]
 mod tests {
     use super::NodeDatabase;
-    use crate::node_table::{NodeEndpoint, NodeEntry, NodeId};
-    use std::{str::FromStr, time::Duration};
+    use crate::{
+        node_table::{NodeEndpoint, NodeEntry, NodeId},
+        reputation::ReputationChange,
+    };
+    use std::{fs, str::FromStr, time::Duration};
 
     fn new_entry(addr: &str) -> NodeEntry {
         NodeEntry {
@@ -797,4 +892,86 @@ mod tests {
         assert_eq!(db.evaluate_blacklisted(&n.id), false);
         assert_eq!(db.get(&n.id, false), None);
     }
+
+    #[test]
+    fn test_banned_by_reputation() {
+        let mut db = NodeDatabase::new(None, 2);
+
+        let n = new_entry("127.0.0.1:999");
+        db.insert_trusted(n.clone());
+
+        assert_eq!(
+            db.note_reputation(&n.id, ReputationChange::ProtocolViolation),
+            false
+        );
+        assert_eq!(db.evaluate_blacklisted(&n.id), false);
+        assert_eq!(db.get(&n.id, true /* trusted_only */).unwrap().id, n.id);
+
+        // banned and removed
+        assert_eq!(
+            db.note_reputation(&n.id, ReputationChange::InvalidBlock),
+            true
+        );
+        assert_eq!(db.evaluate_blacklisted(&n.id), true);
+        assert_eq!(db.get(&n.id, false), None);
+
+        // refused to add again until unbanned
+        db.insert_trusted(n.clone());
+        assert_eq!(db.get(&n.id, false), None);
+        assert_eq!(db.unban(&n.id), true);
+        db.insert_trusted(n.clone());
+        assert_eq!(db.get(&n.id, true /* trusted_only */).unwrap().id, n.id);
+    }
+
+    #[test]
+    fn test_reserved_nodes() {
+        let mut db = NodeDatabase::new(None, 2);
+
+        let n = new_entry("127.0.0.1:999");
+        db.insert_reserved(n.clone());
+        let reserved: Vec<NodeId> =
+            db.reserved_nodes().into_iter().map(|e| e.id).collect();
+        assert_eq!(reserved, vec![n.id]);
+        assert_eq!(db.get(&n.id, true /* trusted_only */).unwrap().id, n.id);
+
+        // demoted to untrusted after removed from reserved nodes
+        assert_eq!(db.remove_reserved(&n.id), true);
+        assert_eq!(db.remove_reserved(&n.id), false);
+        assert!(db.reserved_nodes().is_empty());
+        assert_eq!(db.get(&n.id, true /* trusted_only */), None);
+        assert_eq!(db.get(&n.id, false /* trusted_only */).unwrap().id, n.id);
+    }
+
+    #[test]
+    fn test_reserved_nodes_persisted() {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("node_db_{:x}", NodeId::random().to_low_u64_be()));
+        let path = dir.to_str().map(|s| s.to_string());
+
+        let n = new_entry("127.0.0.1:999");
+        {
+            let mut db = NodeDatabase::new(path.clone(), 2);
+            db.insert_reserved(n.clone());
+            db.save();
+        }
+
+        // reserved nodes are trusted after restart
+        {
+            let mut db = NodeDatabase::new(path.clone(), 2);
+            assert_eq!(db.reserved_nodes().len(), 1);
+            assert_eq!(
+                db.get(&n.id, true /* trusted_only */).unwrap().id,
+                n.id
+            );
+            assert_eq!(db.remove_reserved(&n.id), true);
+            db.save();
+        }
+
+        // and neither reserved nor trusted after removed
+        let db = NodeDatabase::new(path, 2);
+        assert!(db.reserved_nodes().is_empty());
+        assert_eq!(db.get(&n.id, true /* trusted_only */), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
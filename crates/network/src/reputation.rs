@@ -0,0 +1,397 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+//! Reputation of the remote peers.
+//!
+//! Protocol handlers report the misbehavior of peers, e.g. invalid blocks or
+//! request timeouts, and each report lowers the score of the peer by the
+//! weight of the behavior. Scores decay exponentially towards 0 over time, so
+//! that occasional faults are forgiven. Once the score drops to
+//! `BAN_THRESHOLD`, the peer is banned temporarily. After `MAX_TEMPORARY_BANS`
+//! temporary bans, the next ban is permanent if it is caused by a severe
+//! misbehavior, e.g. an invalid block, but not by slow or useless responses,
+//! which may be caused by network issues.
+
+use crate::node_table::NodeId;
+use log::{debug, warn};
+use serde::Deserialize;
+use serde_derive::Serialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const PEER_REPUTATION_FILE: &str = "peer_reputation.json";
+
+/// Score to ban a peer.
+pub const BAN_THRESHOLD: f64 = -100.0;
+const MIN_SCORE: f64 = -1000.0;
+const MAX_SCORE: f64 = 100.0;
+
+/// Time for a score to decay by half.
+const SCORE_HALF_LIFE: Duration = Duration::from_secs(30 * 60);
+
+/// Duration of temporary bans.
+pub const TEMPORARY_BAN_DURATION: Duration = Duration::from_secs(3600);
+/// Number of temporary bans before a peer is banned permanently.
+const MAX_TEMPORARY_BANS: u32 = 3;
+
+/// Behavior of peers that changes the reputation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReputationChange {
+    /// Did not respond to a request in time.
+    RequestTimeout,
+    /// Responded with data that is not requested or cannot be used.
+    UselessResponse,
+    /// Sent malformed or unexpected messages.
+    ProtocolViolation,
+    /// Relayed an invalid block.
+    InvalidBlock,
+}
+
+impl ReputationChange {
+    pub fn score(&self) -> f64 {
+        match self {
+            ReputationChange::RequestTimeout => -5.0,
+            ReputationChange::UselessResponse => -10.0,
+            ReputationChange::ProtocolViolation => -40.0,
+            ReputationChange::InvalidBlock => -60.0,
+        }
+    }
+
+    /// Whether the behavior is malicious rather than caused by network
+    /// issues or an overloaded peer, so that it may lead to a permanent ban.
+    pub fn is_severe(&self) -> bool {
+        match self {
+            ReputationChange::RequestTimeout
+            | ReputationChange::UselessResponse => false,
+            ReputationChange::ProtocolViolation
+            | ReputationChange::InvalidBlock => true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerReputation {
+    pub score: f64,
+    /// Unix time in seconds when the score was updated, which is used to
+    /// decay the score.
+    pub updated_at: u64,
+    /// Number of times the peer has been banned.
+    pub ban_count: u32,
+    /// Unix time in seconds until when the peer is banned, or `u64::MAX` if
+    /// banned permanently.
+    pub banned_until: Option<u64>,
+}
+
+impl PeerReputation {
+    fn decay(&mut self, now: u64) {
+        if now > self.updated_at {
+            let elapsed = (now - self.updated_at) as f64;
+            self.score *=
+                0.5f64.powf(elapsed / SCORE_HALF_LIFE.as_secs() as f64);
+        }
+        self.updated_at = now;
+    }
+
+    pub fn is_banned(&self, now: u64) -> bool {
+        self.banned_until.map_or(false, |until| until > now)
+    }
+
+    pub fn is_permanently_banned(&self) -> bool {
+        self.banned_until == Some(u64::MAX)
+    }
+
+    fn ban(&mut self, now: u64, duration: Option<Duration>) {
+        self.ban_count += 1;
+        self.banned_until = Some(match duration {
+            Some(duration) => now.saturating_add(duration.as_secs()),
+            None => u64::MAX,
+        });
+    }
+
+    /// Useless entries that need not to be persisted.
+    fn is_neutral(&self, now: u64) -> bool {
+        self.score.abs() < 1.0 && self.banned_until.map_or(true, |u| u <= now)
+    }
+}
+
+/// Reputation of peers backed by disk file.
+pub struct ReputationTable {
+    peers: HashMap<NodeId, PeerReputation>,
+    path: Option<PathBuf>,
+}
+
+impl ReputationTable {
+    pub fn new(dir: Option<String>) -> Self {
+        let path = dir.map(|dir| {
+            let mut buf = PathBuf::from(dir);
+            buf.push(PEER_REPUTATION_FILE);
+            buf
+        });
+
+        let mut table = ReputationTable {
+            peers: HashMap::new(),
+            path,
+        };
+        table.load_from_file();
+        table
+    }
+
+    /// Get the reputation of the specified peer with score decayed.
+    pub fn get(&self, id: &NodeId) -> Option<PeerReputation> {
+        let mut reputation = self.peers.get(id)?.clone();
+        reputation.decay(now_secs());
+        Some(reputation)
+    }
+
+    /// Apply the change to the reputation of the specified peer. Returns
+    /// `true` if the peer is banned because of this change.
+    pub fn note(&mut self, id: &NodeId, change: ReputationChange) -> bool {
+        self.note_at(id, change, now_secs())
+    }
+
+    fn note_at(
+        &mut self, id: &NodeId, change: ReputationChange, now: u64,
+    ) -> bool {
+        let reputation = self.peers.entry(*id).or_default();
+        reputation.decay(now);
+        reputation.score =
+            (reputation.score + change.score()).clamp(MIN_SCORE, MAX_SCORE);
+
+        if reputation.score > BAN_THRESHOLD || reputation.is_banned(now) {
+            return false;
+        }
+
+        let duration = if reputation.ban_count >= MAX_TEMPORARY_BANS
+            && change.is_severe()
+        {
+            None
+        } else {
+            Some(TEMPORARY_BAN_DURATION)
+        };
+        reputation.ban(now, duration);
+        debug!(
+            "peer banned due to low reputation, id = {:?}, reputation = {:?}",
+            id, reputation
+        );
+
+        true
+    }
+
+    /// Override the score of the specified peer. Note, it does not ban or
+    /// unban the peer.
+    pub fn set_score(&mut self, id: &NodeId, score: f64) -> PeerReputation {
+        let reputation = self.peers.entry(*id).or_default();
+        reputation.score = score.clamp(MIN_SCORE, MAX_SCORE);
+        reputation.updated_at = now_secs();
+        reputation.clone()
+    }
+
+    /// Ban the specified peer for the given duration, or permanently if
+    /// `duration` is `None`.
+    pub fn ban(&mut self, id: &NodeId, duration: Option<Duration>) {
+        self.peers.entry(*id).or_default().ban(now_secs(), duration);
+    }
+
+    /// Clear the reputation of the specified peer, including the ban.
+    pub fn unban(&mut self, id: &NodeId) -> bool {
+        self.peers.remove(id).is_some()
+    }
+
+    pub fn is_banned(&self, id: &NodeId) -> bool {
+        self.peers
+            .get(id)
+            .map_or(false, |reputation| reputation.is_banned(now_secs()))
+    }
+
+    fn load_from_file(&mut self) {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return,
+        };
+
+        let file = match fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                debug!("peer reputation file not found: {:?}", e);
+                return;
+            }
+        };
+
+        match serde_json::from_reader::<_, json::ReputationTable>(file) {
+            Ok(table) => {
+                for peer in table.peers {
+                    self.peers.insert(peer.id, peer.reputation);
+                }
+            }
+            Err(e) => warn!("Error reading peer reputation file: {:?}", e),
+        }
+    }
+
+    /// Save the peer_reputation.json file, and neutral peers are skipped.
+    pub fn save(&self) {
+        let path = match self.path {
+            Some(ref path) => Path::new(path),
+            None => return,
+        };
+
+        if let Some(dir) = path.parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                warn!("Error creating peer reputation directory: {:?}", e);
+                return;
+            }
+        }
+
+        let now = now_secs();
+        let peers = self
+            .peers
+            .iter()
+            .filter(|(_, reputation)| !reputation.is_neutral(now))
+            .map(|(id, reputation)| json::PeerEntry {
+                id: *id,
+                reputation: reputation.clone(),
+            })
+            .collect();
+        let table = json::ReputationTable { peers };
+
+        match fs::File::create(&path) {
+            Ok(file) => {
+                if let Err(e) = serde_json::to_writer_pretty(file, &table) {
+                    warn!("Error writing peer reputation file: {:?}", e);
+                }
+            }
+            Err(e) => {
+                warn!("Error creating peer reputation file: {:?}", e);
+            }
+        }
+    }
+}
+
+impl Drop for ReputationTable {
+    fn drop(&mut self) { self.save(); }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+mod json {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct ReputationTable {
+        pub peers: Vec<PeerEntry>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct PeerEntry {
+        pub id: NodeId,
+        pub reputation: PeerReputation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay() {
+        let mut reputation = PeerReputation {
+            score: -80.0,
+            updated_at: 1000,
+            ..Default::default()
+        };
+        reputation.decay(1000 + SCORE_HALF_LIFE.as_secs());
+        assert!((reputation.score + 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_temporary_and_permanent_ban() {
+        let mut table = ReputationTable::new(None);
+        let id = NodeId::random();
+        let mut now = 1000;
+
+        for ban in 1..=MAX_TEMPORARY_BANS + 1 {
+            assert!(!table.note_at(&id, ReputationChange::InvalidBlock, now));
+            assert!(table.note_at(&id, ReputationChange::InvalidBlock, now));
+            // no more bans until expired
+            assert!(!table.note_at(&id, ReputationChange::InvalidBlock, now));
+
+            let reputation = table.peers[&id].clone();
+            assert_eq!(reputation.ban_count, ban);
+            assert!(reputation.is_banned(now));
+
+            if ban <= MAX_TEMPORARY_BANS {
+                assert!(!reputation.is_permanently_banned());
+                now += TEMPORARY_BAN_DURATION.as_secs();
+                assert!(!reputation.is_banned(now));
+                // forgive the score, so that the next ban is caused by the
+                // new changes only
+                table.peers.get_mut(&id).unwrap().score = 0.0;
+            } else {
+                assert!(reputation.is_permanently_banned());
+            }
+        }
+
+        assert!(table.unban(&id));
+        assert!(!table.is_banned(&id));
+    }
+
+    #[test]
+    fn test_timeouts_only_ban_temporarily() {
+        let mut table = ReputationTable::new(None);
+        let id = NodeId::random();
+        let mut now = 1000;
+
+        for ban in 1..=MAX_TEMPORARY_BANS + 2 {
+            while !table.note_at(&id, ReputationChange::RequestTimeout, now) {}
+
+            let reputation = table.peers[&id].clone();
+            assert_eq!(reputation.ban_count, ban);
+            assert!(reputation.is_banned(now));
+            assert!(!reputation.is_permanently_banned());
+            now += TEMPORARY_BAN_DURATION.as_secs();
+            table.peers.get_mut(&id).unwrap().score = 0.0;
+        }
+
+        // A severe misbehavior after the temporary bans is banned permanently.
+        while !table.note_at(&id, ReputationChange::InvalidBlock, now) {}
+        assert!(table.peers[&id].is_permanently_banned());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!(
+            "peer_reputation_{:x}",
+            NodeId::random().to_low_u64_be()
+        ));
+        let dir_str = dir.to_str().map(|s| s.to_string());
+
+        let banned = NodeId::random();
+        let scored = NodeId::random();
+        let neutral = NodeId::random();
+        {
+            let mut table = ReputationTable::new(dir_str.clone());
+            table.ban(&banned, None);
+            table.set_score(&scored, -50.0);
+            table.set_score(&neutral, 0.0);
+            table.save();
+        }
+
+        let table = ReputationTable::new(dir_str);
+        assert!(table.is_banned(&banned));
+        assert!(table.get(&scored).unwrap().score < -49.0);
+        assert!(table.get(&neutral).is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -46,6 +46,7 @@ use crate::{
     node_database::NodeDatabase,
     node_table::*,
     parse_msg_id_leb128_2_bytes_at_most,
+    reputation::{PeerReputation, ReputationChange},
     session::{self, Session, SessionData, SessionDetails},
     session_manager::SessionManager,
     Error, HandlerWorkType, IpFilter, NatType, NetworkConfiguration,
@@ -408,6 +409,81 @@ impl NetworkService {
         true
     }
 
+    pub fn get_peer_reputation(&self, id: &NodeId) -> Option<PeerReputation> {
+        self.inner.as_ref()?.node_db.read().get_reputation(id)
+    }
+
+    pub fn set_peer_reputation(
+        &self, id: &NodeId, score: f64,
+    ) -> Option<PeerReputation> {
+        let inner = self.inner.as_ref()?;
+        let reputation = inner.node_db.write().set_reputation_score(id, score);
+        Some(reputation)
+    }
+
+    /// Ban the specified node for the given duration, or permanently if
+    /// `duration` is `None`, and disconnect it if connected. Returns `true` if
+    /// the ban is recorded, whether the node is connected or not.
+    pub fn ban_node(&self, id: &NodeId, duration: Option<Duration>) -> bool {
+        match self.inner {
+            Some(ref inner) => inner.node_db.write().ban(id, duration),
+            None => return false,
+        }
+        self.disconnect_node(id, None /* op */);
+        true
+    }
+
+    pub fn unban_node(&self, id: &NodeId) -> bool {
+        match self.inner {
+            Some(ref inner) => inner.node_db.write().unban(id),
+            None => false,
+        }
+    }
+
+    /// Add a P2P peer as reserved node, which is always connected and
+    /// persisted in node database.
+    pub fn add_reserved_peer(&self, node: NodeEntry) -> Result<(), Error> {
+        if let Some(ref inner) = self.inner {
+            inner.add_reserved_peer(node);
+            Ok(())
+        } else {
+            Err("Network service not started yet!".into())
+        }
+    }
+
+    /// Remove a reserved node. Note, it does not disconnect the node.
+    pub fn remove_reserved_peer(&self, id: &NodeId) -> Result<bool, Error> {
+        if let Some(ref inner) = self.inner {
+            Ok(inner.remove_reserved_peer(id))
+        } else {
+            Err("Network service not started yet!".into())
+        }
+    }
+
+    pub fn is_reserved_peer(&self, id: &NodeId) -> bool {
+        match self.inner {
+            Some(ref inner) => inner.reserved_nodes.read().contains(id),
+            None => false,
+        }
+    }
+
+    /// Return the local node with public endpoint
+    pub fn local_node(&self) -> Option<Node> {
+        let inner = self.inner.as_ref()?;
+        Some(Node::new(
+            inner.metadata.id().clone(),
+            inner.metadata.public_endpoint.clone(),
+        ))
+    }
+
+    /// Return the protocols registered
+    pub fn protocols(&self) -> Vec<ProtocolInfo> {
+        match self.inner {
+            Some(ref inner) => inner.metadata.protocols.read().clone(),
+            None => Vec::new(),
+        }
+    }
+
     pub fn save_node_db(&self) {
         if let Some(inner) = &self.inner {
             inner.node_db.write().save();
@@ -665,6 +741,13 @@ impl NetworkServiceInner {
             }
         }
 
+        // reserved nodes added via RPC are persisted in node database
+        let persisted_reserved_nodes = inner.node_db.read().reserved_nodes();
+        inner
+            .reserved_nodes
+            .write()
+            .extend(persisted_reserved_nodes.into_iter().map(|n| n.id));
+
         Ok(inner)
     }
 
@@ -724,6 +807,19 @@ impl NetworkServiceInner {
         Ok(())
     }
 
+    /// Add a reserved node, which is persisted in node database.
+    fn add_reserved_peer(&self, entry: NodeEntry) {
+        let id = entry.id;
+        self.node_db.write().insert_reserved(entry);
+        self.reserved_nodes.write().insert(id);
+    }
+
+    fn remove_reserved_peer(&self, id: &NodeId) -> bool {
+        let persisted = self.node_db.write().remove_reserved(id);
+        let removed = self.reserved_nodes.write().remove(id);
+        persisted || removed
+    }
+
     fn initialize_udp_protocols(
         &self, io: &IoContext<NetworkIoMessage>,
     ) -> Result<(), Error> {
@@ -1394,6 +1490,27 @@ impl NetworkServiceInner {
         deregister
     }
 
+    fn report_peer(
+        &self, node_id: &NodeId, io: &IoContext<NetworkIoMessage>,
+        change: ReputationChange, reason: &str,
+    ) -> bool {
+        debug!(
+            "report peer, id = {:?}, change = {:?}, reason = {:?}",
+            node_id, change, reason
+        );
+
+        // Note, the node database lock must be released before killing the
+        // connection.
+        let banned = self.node_db.write().note_reputation(node_id, change);
+        if banned {
+            self.kill_connection(
+                node_id, io, true, /* remote */
+                None, reason,
+            );
+        }
+        banned
+    }
+
     pub fn with_context<F, R>(
         &self, handler: Arc<dyn NetworkProtocolHandler + Sync>,
         protocol: ProtocolId, io: &IoContext<NetworkIoMessage>, action: F,
@@ -2042,6 +2159,13 @@ impl<'a> NetworkContextTrait for NetworkContext<'a> {
             .kill_connection(node_id, self.io, true, op, reason);
     }
 
+    fn report_peer(
+        &self, node_id: &NodeId, change: ReputationChange, reason: &str,
+    ) -> bool {
+        self.network_service
+            .report_peer(node_id, self.io, change, reason)
+    }
+
     fn register_timer(
         &self, token: TimerToken, delay: Duration,
     ) -> Result<(), Error> {
@@ -3,19 +3,23 @@
 // See http://www.gnu.org/licenses/
 
 use crate::{
+    compression::{self, COMPRESSION_SNAPPY},
     connection::{Connection, ConnectionDetails, SendQueueStatus, WriteStatus},
+    encryption::{self, FrameOpener, FrameSealer, CIPHER_AES_256_GCM},
     handshake::Handshake,
     node_table::{NodeEndpoint, NodeEntry, NodeId},
     parse_msg_id_leb128_2_bytes_at_most,
     service::{NetworkServiceInner, ProtocolVersion},
-    DisconnectReason, Error, ProtocolId, ProtocolInfo, SessionMetadata,
-    UpdateNodeOperation, PROTOCOL_ID_SIZE,
+    DisconnectReason, EncryptionMode, Error, ProtocolId, ProtocolInfo,
+    SessionMetadata, UpdateNodeOperation, PROTOCOL_ID_SIZE,
 };
 use bytes::Bytes;
+use cfx_types::H256;
 use cfx_util_macros::bail;
 use diem_crypto::{bls::BLS_PUBLIC_KEY_LENGTH, ValidCryptoMaterial};
 use diem_types::validator_config::{ConsensusPublicKey, ConsensusVRFPublicKey};
 use io::*;
+use keccak_hash::keccak;
 use log::{debug, trace};
 use mio::{tcp::*, *};
 use priority_send_queue::SendQueuePriority;
@@ -37,9 +41,14 @@ use std::{
 /// packet to exchange the supported protocols. Then, session is ready to send
 /// and receive protocol packets.
 ///
-/// Conflux do not use AES based encrypted connection to send protocol packets.
-/// This is because that Conflux has high TPS, and the encryption/decryption
-/// workloads are very heavy (about 20% CPU time in 3000 TPS).
+/// By default, Conflux do not encrypt the protocol packets, because Conflux
+/// has high TPS and the encryption/decryption workloads are heavy. Nodes could
+/// enable the AES-GCM encryption in `NetworkConfiguration`, which is
+/// advertised as an extension of the Hello packet. Once both peers advertised
+/// it, all subsequent packets are encrypted with the keys derived from the
+/// handshake nonces, see the `encryption` module. As the Hello packets are
+/// sent in plaintext, the session is ready only after both peers confirmed
+/// the digest of the Hello packets with the encrypted HELLO_DIGEST packet.
 pub struct Session {
     /// Session information
     pub metadata: SessionMetadata,
@@ -58,6 +67,24 @@ pub struct Session {
     last_read: Instant,
     last_write: (Instant, WriteStatus),
     pos_public_key: Option<(ConsensusPublicKey, ConsensusVRFPublicKey)>,
+
+    // Encryption is advertised if the ciphers are created after handshake,
+    // and the sealer is moved to the connection once the remote peer also
+    // advertised it in Hello packet.
+    sealer: Option<FrameSealer>,
+    opener: Option<FrameOpener>,
+    encrypted: bool,
+    /// Minimum size of protocol packets to compress, which is set once both
+    /// peers advertised compression in Hello packet.
+    compression_threshold: Option<usize>,
+    /// Hash of the Hello packet sent to remote peer.
+    sent_hello_hash: Option<H256>,
+    /// Digest of both Hello packets that remote peer should confirm once
+    /// encryption negotiated.
+    hello_digest: Option<H256>,
+    /// PoS public keys in the Hello packet of remote peer, which are reported
+    /// once the Hello packets are confirmed.
+    peer_pos_public_key: Option<(ConsensusPublicKey, ConsensusVRFPublicKey)>,
 }
 
 /// Session state.
@@ -94,6 +121,8 @@ pub struct SessionDataWithDisconnectInfo {
 const PACKET_HELLO: u8 = 0x80;
 // id for Disconnect packet
 const PACKET_DISCONNECT: u8 = 0x01;
+// id for the packet to confirm the digest of Hello packets in encrypted session
+const PACKET_HELLO_DIGEST: u8 = 0x82;
 // id for protocol packet
 pub const PACKET_USER: u8 = 0x10;
 /// header_version for protocol packet.
@@ -101,6 +130,11 @@ pub const PACKET_USER: u8 = 0x10;
 pub const PACKET_HEADER_VERSION: u8 = 0;
 /// The header version where extension is introduced.
 const HEADER_VERSION_WITH_EXTENSION: u8 = 0;
+// id for the extension of Hello packet to advertise the encryption cipher
+const EXTENSION_ENCRYPTION: u8 = 0x01;
+// id for the extension of Hello packet to advertise the compression algorithm,
+// and of protocol packets to mark the compressed data
+const EXTENSION_COMPRESSION: u8 = 0x02;
 
 impl Session {
     /// Create a new instance of `Session`, which starts to handshake with
@@ -131,6 +165,13 @@ impl Session {
             last_read: Instant::now(),
             last_write: (Instant::now(), WriteStatus::Complete),
             pos_public_key,
+            sealer: None,
+            opener: None,
+            encrypted: false,
+            compression_threshold: None,
+            sent_hello_hash: None,
+            hello_digest: None,
+            peer_pos_public_key: None,
         })
     }
 
@@ -203,7 +244,8 @@ impl Session {
     /// Complete the handshake process:
     /// 1. For incoming session, check if the remote peer is blacklisted.
     /// 2. Change the session state to `State::Session`.
-    /// 3. Send Hello packet to remote peer.
+    /// 3. Create the encryption ciphers if enabled.
+    /// 4. Send Hello packet to remote peer.
     fn complete_handshake<Message>(
         &mut self, io: &IoContext<Message>, host: &NetworkServiceInner,
     ) -> Result<(), Error>
@@ -225,8 +267,22 @@ impl Session {
             self.metadata.id = Some(id);
         }
 
+        let handshake = wrapper.take();
+        if host.config.encryption != EncryptionMode::Disabled {
+            // nonces are unavailable if the cryptography is bypassed in test
+            if let Some((local_nonce, remote_nonce)) = handshake.nonces() {
+                let (sealer, opener) = encryption::session_ciphers(
+                    &local_nonce,
+                    &remote_nonce,
+                    self.metadata.originated,
+                );
+                self.sealer = Some(sealer);
+                self.opener = Some(opener);
+            }
+        }
+
         // write HELLO packet to remote peer
-        self.state = State::Session(wrapper.take().connection);
+        self.state = State::Session(handshake.connection);
         self.write_hello(io, host)?;
 
         Ok(())
@@ -271,7 +327,7 @@ impl Session {
                 })
             }
             State::Session(ref mut c) => match c.readable()? {
-                Some(data) => Ok(self.read_packet(data, host)?),
+                Some(data) => Ok(self.read_packet(io, data, host)?),
                 None => Ok(SessionDataWithDisconnectInfo {
                     session_data: SessionData::None,
                     token_to_disconnect: None,
@@ -280,15 +336,47 @@ impl Session {
         }
     }
 
+    /// Decrypt the packet from underlying connection if it is encrypted, and
+    /// parse it. Returns the plaintext together with the parsed packet.
+    fn open_packet(
+        &mut self, data: Bytes,
+    ) -> Result<(Bytes, SessionPacket), Error> {
+        let encrypted = encryption::is_encrypted(&data);
+        let data = if !encrypted {
+            data
+        } else if let Some(ref mut opener) = self.opener {
+            opener.open(&data)?
+        } else {
+            debug!("encryption not advertised, session = {:?}", self);
+            return Err(Error::BadProtocol.into());
+        };
+
+        let packet = SessionPacket::parse(data.clone())?;
+
+        // Once encryption negotiated, only the Disconnect packet is allowed in
+        // plaintext, which may be sent before the remote peer received Hello.
+        if self.encrypted && !encrypted && packet.id != PACKET_DISCONNECT {
+            debug!(
+                "plaintext packet in encrypted session, packet_id = {}, session = {:?}",
+                packet.id, self
+            );
+            return Err(Error::BadProtocol.into());
+        }
+
+        Ok((data, packet))
+    }
+
     /// Handle the packet from underlying connection.
-    fn read_packet(
-        &mut self, data: Bytes, host: &NetworkServiceInner,
+    fn read_packet<Message: Send + Sync + Clone>(
+        &mut self, io: &IoContext<Message>, data: Bytes,
+        host: &NetworkServiceInner,
     ) -> Result<SessionDataWithDisconnectInfo, Error> {
-        let packet = SessionPacket::parse(data)?;
+        let (data, packet) = self.open_packet(data)?;
 
         // For protocol packet, the Hello packet should already been received.
         // So that dispatch it to the corresponding protocol handler.
         if packet.id != PACKET_HELLO
+            && packet.id != PACKET_HELLO_DIGEST
             && packet.id != PACKET_DISCONNECT
             && self.had_hello.is_none()
         {
@@ -310,14 +398,43 @@ impl Session {
                     None => None,
                 };
 
+                self.negotiate_encryption(
+                    &packet.extensions,
+                    host.config.encryption,
+                )?;
+                self.negotiate_compression(&packet.extensions, host);
+
                 // Handle Hello packet to exchange protocols
                 let rlp = Rlp::new(&packet.data);
                 let pos_public_key = self.read_hello(&rlp, host)?;
+
+                // The Hello packets are not authenticated, so wait for remote
+                // peer to confirm them with the session keys.
+                let session_data = if self.encrypted {
+                    self.send_hello_digest(io, &data)?;
+                    self.peer_pos_public_key = pos_public_key;
+                    SessionData::Continue
+                } else {
+                    self.had_hello = Some(Instant::now());
+                    SessionData::Ready { pos_public_key }
+                };
+
                 Ok(SessionDataWithDisconnectInfo {
-                    session_data: SessionData::Ready { pos_public_key },
+                    session_data,
                     token_to_disconnect,
                 })
             }
+            PACKET_HELLO_DIGEST => {
+                debug!("Read HELLO_DIGEST in session {:?}", self);
+                self.check_hello_digest(&packet.data)?;
+                self.had_hello = Some(Instant::now());
+                Ok(SessionDataWithDisconnectInfo {
+                    session_data: SessionData::Ready {
+                        pos_public_key: self.peer_pos_public_key.take(),
+                    },
+                    token_to_disconnect: None,
+                })
+            }
             PACKET_DISCONNECT => {
                 let rlp = Rlp::new(&packet.data);
                 let reason: DisconnectReason = rlp.as_val()?;
@@ -327,15 +444,30 @@ impl Session {
                 );
                 Err(Error::Disconnect(reason).into())
             }
-            PACKET_USER => Ok(SessionDataWithDisconnectInfo {
-                session_data: SessionData::Message {
-                    data: packet.data.to_vec(),
-                    protocol: packet
-                        .protocol
-                        .expect("protocol should available for USER packet"),
-                },
-                token_to_disconnect: None,
-            }),
+            PACKET_USER => {
+                let compressed = has_extension(
+                    &packet.extensions,
+                    &[EXTENSION_COMPRESSION, COMPRESSION_SNAPPY],
+                );
+                let data = if !compressed {
+                    packet.data.to_vec()
+                } else if host.config.compression_enabled {
+                    compression::decompress(&packet.data)?
+                } else {
+                    debug!("compression not advertised, session = {:?}", self);
+                    return Err(Error::BadProtocol.into());
+                };
+
+                Ok(SessionDataWithDisconnectInfo {
+                    session_data: SessionData::Message {
+                        data,
+                        protocol: packet.protocol.expect(
+                            "protocol should available for USER packet",
+                        ),
+                    },
+                    token_to_disconnect: None,
+                })
+            }
             _ => {
                 debug!(
                     "read packet UNKNOWN, packet_id = {:?}, session = {:?}",
@@ -372,9 +504,125 @@ impl Session {
             })
     }
 
-    /// Read Hello packet to exchange the supported protocols, and set the
-    /// `had_hello` flag to indicates that session is ready to send/receive
-    /// protocol packets.
+    /// Start to encrypt packets if both peers advertised encryption in Hello
+    /// packet. Otherwise, disconnect the remote peer if encryption is
+    /// required.
+    fn negotiate_encryption(
+        &mut self, extensions: &[Vec<u8>], mode: EncryptionMode,
+    ) -> Result<(), Error> {
+        if self.encrypted {
+            return Ok(());
+        }
+
+        let peer_advertised = has_extension(
+            extensions,
+            &[EXTENSION_ENCRYPTION, CIPHER_AES_256_GCM],
+        );
+        if peer_advertised {
+            if let Some(sealer) = self.sealer.take() {
+                debug!("Encryption negotiated, session = {:?}", self);
+                self.connection_mut().set_sealer(sealer);
+                self.encrypted = true;
+                return Ok(());
+            }
+        }
+
+        if mode == EncryptionMode::Required {
+            debug!(
+                "failed to negotiate encryption, peer_advertised = {}, session = {:?}",
+                peer_advertised, self
+            );
+            return Err(
+                self.send_disconnect(DisconnectReason::EncryptionRequired)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Start to compress large protocol packets if both peers advertised
+    /// compression in Hello packet.
+    fn negotiate_compression(
+        &mut self, extensions: &[Vec<u8>], host: &NetworkServiceInner,
+    ) {
+        if host.config.compression_enabled
+            && has_extension(
+                extensions,
+                &[EXTENSION_COMPRESSION, COMPRESSION_SNAPPY],
+            )
+        {
+            debug!("Compression negotiated, session = {:?}", self);
+            self.compression_threshold =
+                Some(host.config.compression_threshold);
+        }
+    }
+
+    /// Send the digest of both Hello packets to remote peer once encryption
+    /// negotiated, so that the Hello packets tampered in plaintext, e.g. the
+    /// encryption extension stripped, are detected by both peers.
+    fn send_hello_digest<Message: Send + Sync + Clone>(
+        &mut self, io: &IoContext<Message>, received_hello: &[u8],
+    ) -> Result<(), Error> {
+        let sent_hello_hash = match self.sent_hello_hash {
+            Some(hash) => hash,
+            None => {
+                debug!("Hello not sent, session = {:?}", self);
+                return Err(Error::BadProtocol.into());
+            }
+        };
+        let received_hello_hash = keccak(received_hello);
+
+        // Hash the Hello packet of the originated peer first, so that both
+        // peers get the same digest.
+        let (egress_hash, ingress_hash) = if self.metadata.originated {
+            (sent_hello_hash, received_hello_hash)
+        } else {
+            (received_hello_hash, sent_hello_hash)
+        };
+        let mut hashes = Vec::with_capacity(2 * H256::len_bytes());
+        hashes.extend_from_slice(egress_hash.as_bytes());
+        hashes.extend_from_slice(ingress_hash.as_bytes());
+        let digest = keccak(hashes);
+        self.hello_digest = Some(digest);
+
+        let packet = self.prepare_packet(
+            None,
+            PACKET_HELLO_DIGEST,
+            rlp::encode(&digest),
+        )?;
+        self.connection_mut()
+            .send(io, packet, SendQueuePriority::High)
+            .map(|_| ())
+    }
+
+    /// Check the digest of both Hello packets confirmed by remote peer, and
+    /// disconnect it if mismatched.
+    fn check_hello_digest(&mut self, data: &[u8]) -> Result<(), Error> {
+        let expected = match self.hello_digest.take() {
+            Some(digest) => digest,
+            None => {
+                debug!("unexpected Hello digest, session = {:?}", self);
+                return Err(Error::BadProtocol.into());
+            }
+        };
+
+        let digest: H256 = Rlp::new(data).as_val()?;
+        if digest != expected {
+            debug!(
+                "Hello digest mismatch, expected = {:?}, actual = {:?}, session = {:?}",
+                expected, digest, self
+            );
+            return Err(self.send_disconnect(DisconnectReason::Custom(
+                "Hello digest mismatch".into(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Read Hello packet to exchange the supported protocols. The session is
+    /// ready to send/receive protocol packets once the `had_hello` flag is
+    /// set by the caller.
     ///
     /// Besides, the node endpoint of remote peer will be added or updated in
     /// node database, which is used to establish outgoing connections.
@@ -458,7 +706,6 @@ impl Session {
             host.node_db.write().insert_with_token(entry, self.token());
         }
 
-        self.had_hello = Some(Instant::now());
         match rlp.item_count()? {
             3 => Ok(None),
             4 => {
@@ -490,8 +737,30 @@ impl Session {
     /// Assemble a packet with specified protocol id, packet id and data.
     /// Return concrete error if session is expired or the protocol id is
     /// invalid.
+    /// Protocol packets are compressed if not smaller than the negotiated
+    /// threshold.
     fn prepare_packet(
         &self, protocol: Option<ProtocolId>, packet_id: u8, data: Vec<u8>,
+    ) -> Result<Vec<u8>, Error> {
+        if let Some(threshold) = self.compression_threshold {
+            if protocol.is_some() && data.len() >= threshold {
+                if let Some(compressed) = compression::compress(&data) {
+                    return self.prepare_packet_with_extensions(
+                        protocol,
+                        packet_id,
+                        compressed,
+                        &[vec![EXTENSION_COMPRESSION, COMPRESSION_SNAPPY]],
+                    );
+                }
+            }
+        }
+
+        self.prepare_packet_with_extensions(protocol, packet_id, data, &[])
+    }
+
+    fn prepare_packet_with_extensions(
+        &self, protocol: Option<ProtocolId>, packet_id: u8, data: Vec<u8>,
+        extensions: &[Vec<u8>],
     ) -> Result<Vec<u8>, Error> {
         if protocol.is_some() && self.had_hello.is_none() {
             debug!(
@@ -514,6 +783,7 @@ impl Session {
             self.metadata.peer_header_version,
             protocol,
             data,
+            extensions,
         ))
     }
 
@@ -602,15 +872,32 @@ impl Session {
             &mut self.pos_public_key.as_ref().unwrap().1.to_bytes().to_vec(),
         );
         rlp.append(&key_bytes);
-        self.send_packet(
-            io,
+
+        let mut extensions = Vec::new();
+        if self.opener.is_some() {
+            extensions.push(vec![EXTENSION_ENCRYPTION, CIPHER_AES_256_GCM]);
+        }
+        if host.config.compression_enabled {
+            extensions.push(vec![EXTENSION_COMPRESSION, COMPRESSION_SNAPPY]);
+        }
+
+        self.send_hello(io, rlp.drain(), &extensions)
+    }
+
+    fn send_hello<Message: Send + Sync + Clone>(
+        &mut self, io: &IoContext<Message>, data: Vec<u8>,
+        extensions: &[Vec<u8>],
+    ) -> Result<(), Error> {
+        let packet = self.prepare_packet_with_extensions(
             None,
-            ProtocolVersion::default(),
             PACKET_HELLO,
-            rlp.drain(),
-            SendQueuePriority::High,
-        )
-        .map(|_| ())
+            data,
+            extensions,
+        )?;
+        self.sent_hello_hash = Some(keccak(&packet));
+        self.connection_mut()
+            .send(io, packet, SendQueuePriority::High)
+            .map(|_| ())
     }
 
     /// Writable IO handler. Sends pending packets.
@@ -689,6 +976,10 @@ pub struct SessionDetails {
     pub last_write_status: String,
 }
 
+fn has_extension(extensions: &[Vec<u8>], extension: &[u8]) -> bool {
+    extensions.iter().any(|e| e[..] == *extension)
+}
+
 /// MovableWrapper is a util to move a value out of a struct.
 /// It is used to move the `Connection` instance when session state changed.
 struct MovableWrapper<T> {
@@ -751,18 +1042,27 @@ struct SessionPacket {
 }
 
 impl SessionPacket {
-    // data + Option<protocol> + protocol_flag + packet_id
+    // data + extensions + Option<protocol> + header_byte + packet_id
     fn assemble(
         id: u8, header_version: u8, protocol: Option<ProtocolId>,
-        mut data: Vec<u8>,
+        mut data: Vec<u8>, extensions: &[Vec<u8>],
     ) -> Vec<u8> {
+        // extensions are parsed from the end
+        for (i, extension) in extensions.iter().rev().enumerate() {
+            assert!(extension.len() < 0x80, "extension too large");
+            data.extend_from_slice(extension);
+            let has_next = if i == 0 { 0 } else { 1 };
+            data.push(((extension.len() as u8) << 1) | has_next);
+        }
+        let has_extension = if extensions.is_empty() { 0 } else { 0x10 };
+
         let mut protocol_flag = 0;
         if let Some(protocol) = protocol {
             data.extend_from_slice(&protocol);
             protocol_flag = 1;
         }
 
-        let header_byte = (header_version << 1) + protocol_flag;
+        let header_byte = has_extension + (header_version << 1) + protocol_flag;
         data.push(header_byte);
         data.push(id);
 
@@ -845,6 +1145,10 @@ impl SessionPacket {
     ) -> Result<(Bytes, Vec<Vec<u8>>), Error> {
         let mut extensions = Vec::new();
         while has_extension {
+            if data.is_empty() {
+                debug!("failed to parse session packet, extension missed");
+                bail!(Error::BadProtocol);
+            }
             let extension_byte = data.split_off(data.len() - 1)[0];
             let extension_len = (extension_byte >> 1) as usize;
             has_extension = (extension_byte & 1) != 0;
@@ -875,11 +1179,269 @@ impl fmt::Debug for SessionPacket {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encryption::session_ciphers;
+    use std::{
+        net::{TcpListener as StdTcpListener, TcpStream as StdTcpStream},
+        thread,
+    };
+
+    const ENCRYPTION: [u8; 2] = [EXTENSION_ENCRYPTION, CIPHER_AES_256_GCM];
+
+    fn test_io() -> IoContext<i32> {
+        IoContext::new(IoChannel::disconnected(), 0)
+    }
+
+    fn test_session(
+        socket: StdTcpStream, originated: bool,
+        ciphers: Option<(FrameSealer, FrameOpener)>,
+    ) -> Session {
+        let (sealer, opener) = match ciphers {
+            Some((sealer, opener)) => (Some(sealer), Some(opener)),
+            None => (None, None),
+        };
+        Session {
+            metadata: SessionMetadata {
+                id: None,
+                peer_protocols: Vec::new(),
+                originated,
+                peer_header_version: PACKET_HEADER_VERSION,
+            },
+            address: socket.peer_addr().unwrap(),
+            state: State::Session(Connection::new(
+                0,
+                TcpStream::from_stream(socket).unwrap(),
+            )),
+            sent_hello: Instant::now(),
+            had_hello: None,
+            expired: None,
+            last_read: Instant::now(),
+            last_write: (Instant::now(), WriteStatus::Complete),
+            pos_public_key: None,
+            sealer,
+            opener,
+            encrypted: false,
+            compression_threshold: None,
+            sent_hello_hash: None,
+            hello_digest: None,
+            peer_pos_public_key: None,
+        }
+    }
+
+    /// Creates the egress and ingress sessions connected over loopback, which
+    /// create the ciphers after handshake if encryption is enabled.
+    fn session_pair(
+        egress_encryption: bool, ingress_encryption: bool,
+    ) -> (Session, Session) {
+        let listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+        let egress =
+            StdTcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (ingress, _) = listener.accept().unwrap();
+
+        let egress_nonce = H256::random();
+        let ingress_nonce = H256::random();
+        let egress_ciphers = if egress_encryption {
+            Some(session_ciphers(&egress_nonce, &ingress_nonce, true))
+        } else {
+            None
+        };
+        let ingress_ciphers = if ingress_encryption {
+            Some(session_ciphers(&ingress_nonce, &egress_nonce, false))
+        } else {
+            None
+        };
+
+        (
+            test_session(egress, true, egress_ciphers),
+            test_session(ingress, false, ingress_ciphers),
+        )
+    }
+
+    /// Writes out the packets queued in `from`, and reads `count` packets
+    /// from `to`.
+    fn transfer(
+        from: &mut Session, to: &mut Session, io: &IoContext<i32>, count: usize,
+    ) -> Vec<Bytes> {
+        while from.connection().is_sending() {
+            from.writable(io).unwrap();
+        }
+
+        let mut packets = Vec::new();
+        for _ in 0..100 {
+            while let Some(data) = to.connection_mut().readable().unwrap() {
+                packets.push(data);
+            }
+            if packets.len() >= count {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(packets.len(), count);
+        packets
+    }
+
+    /// Handles the Hello packet the same as `read_packet`, except the protocols
+    /// exchange that requires the network service.
+    fn receive_hello(
+        session: &mut Session, io: &IoContext<i32>, data: Bytes,
+        mode: EncryptionMode,
+    ) -> Result<(), Error> {
+        let (data, packet) = session.open_packet(data)?;
+        assert_eq!(packet.id, PACKET_HELLO);
+        session.negotiate_encryption(&packet.extensions, mode)?;
+        if session.encrypted {
+            session.send_hello_digest(io, &data)?;
+        }
+        Ok(())
+    }
+
+    fn receive_hello_digest(
+        session: &mut Session, data: Bytes,
+    ) -> Result<(), Error> {
+        assert!(encryption::is_encrypted(&data));
+        let (_, packet) = session.open_packet(data)?;
+        assert_eq!(packet.id, PACKET_HELLO_DIGEST);
+        session.check_hello_digest(&packet.data)
+    }
+
+    #[test]
+    fn test_encryption_negotiated() {
+        let io = test_io();
+        let (mut egress, mut ingress) = session_pair(true, true);
+        egress
+            .send_hello(&io, vec![1], &[ENCRYPTION.to_vec()])
+            .unwrap();
+        ingress
+            .send_hello(&io, vec![2], &[ENCRYPTION.to_vec()])
+            .unwrap();
+
+        let hello = transfer(&mut egress, &mut ingress, &io, 1).remove(0);
+        receive_hello(&mut ingress, &io, hello, EncryptionMode::Enabled)
+            .unwrap();
+        assert!(ingress.encrypted);
+
+        // The Hello packet of ingress session is not sent out yet, so it is
+        // encrypted as well.
+        let mut packets = transfer(&mut ingress, &mut egress, &io, 2);
+        assert!(encryption::is_encrypted(&packets[0]));
+        receive_hello(
+            &mut egress,
+            &io,
+            packets.remove(0),
+            EncryptionMode::Enabled,
+        )
+        .unwrap();
+        assert!(egress.encrypted);
+        receive_hello_digest(&mut egress, packets.remove(0)).unwrap();
+
+        let digest = transfer(&mut egress, &mut ingress, &io, 1).remove(0);
+        receive_hello_digest(&mut ingress, digest).unwrap();
+
+        // Hello digest is confirmed only once.
+        assert!(egress.hello_digest.is_none());
+        assert!(ingress.hello_digest.is_none());
+    }
+
+    #[test]
+    fn test_encryption_fallback() {
+        let io = test_io();
+        let (mut egress, mut ingress) = session_pair(true, false);
+        egress
+            .send_hello(&io, vec![1], &[ENCRYPTION.to_vec()])
+            .unwrap();
+        ingress.send_hello(&io, vec![2], &[]).unwrap();
+
+        let hello = transfer(&mut egress, &mut ingress, &io, 1).remove(0);
+        receive_hello(&mut ingress, &io, hello, EncryptionMode::Disabled)
+            .unwrap();
+        let hello = transfer(&mut ingress, &mut egress, &io, 1).remove(0);
+        assert!(!encryption::is_encrypted(&hello));
+        receive_hello(&mut egress, &io, hello, EncryptionMode::Enabled)
+            .unwrap();
+
+        assert!(!egress.encrypted && !ingress.encrypted);
+        assert!(egress.hello_digest.is_none());
+        assert!(ingress.hello_digest.is_none());
+    }
+
+    #[test]
+    fn test_encryption_required() {
+        let io = test_io();
+        let (mut egress, mut ingress) = session_pair(true, false);
+        ingress.send_hello(&io, vec![2], &[]).unwrap();
+
+        let hello = transfer(&mut ingress, &mut egress, &io, 1).remove(0);
+        match receive_hello(&mut egress, &io, hello, EncryptionMode::Required) {
+            Err(Error::Disconnect(DisconnectReason::EncryptionRequired)) => {}
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert!(!egress.encrypted);
+
+        // Disconnect packet is sent immediately in plaintext.
+        let data = transfer(&mut egress, &mut ingress, &io, 1).remove(0);
+        let (_, packet) = ingress.open_packet(data).unwrap();
+        assert_eq!(packet.id, PACKET_DISCONNECT);
+        assert_eq!(
+            Rlp::new(&packet.data).as_val::<DisconnectReason>().unwrap(),
+            DisconnectReason::EncryptionRequired
+        );
+    }
+
+    #[test]
+    fn test_tampered_hello_detected() {
+        let io = test_io();
+        let (mut egress, mut ingress) = session_pair(true, true);
+        egress
+            .send_hello(&io, vec![1], &[ENCRYPTION.to_vec()])
+            .unwrap();
+        ingress
+            .send_hello(&io, vec![2], &[ENCRYPTION.to_vec()])
+            .unwrap();
+
+        // The Hello packet of egress session is modified in transit.
+        let hello = transfer(&mut egress, &mut ingress, &io, 1).remove(0);
+        let mut tampered = hello.to_vec();
+        tampered[0] = 3;
+        receive_hello(
+            &mut ingress,
+            &io,
+            tampered.into(),
+            EncryptionMode::Enabled,
+        )
+        .unwrap();
+
+        let mut packets = transfer(&mut ingress, &mut egress, &io, 2);
+        receive_hello(
+            &mut egress,
+            &io,
+            packets.remove(0),
+            EncryptionMode::Enabled,
+        )
+        .unwrap();
+        match receive_hello_digest(&mut egress, packets.remove(0)) {
+            Err(Error::Disconnect(DisconnectReason::Custom(_))) => {}
+            r => panic!("unexpected result {:?}", r),
+        }
+
+        // Hello digest is not accepted in plaintext.
+        let digest = SessionPacket::assemble(
+            PACKET_HELLO_DIGEST,
+            PACKET_HEADER_VERSION,
+            None,
+            rlp::encode(&H256::zero()),
+            &[],
+        );
+        assert!(ingress.open_packet(digest.into()).is_err());
+    }
 
     #[test]
     fn test_packet_assemble() {
-        let packet =
-            SessionPacket::assemble(5, PACKET_HEADER_VERSION, None, vec![1, 3]);
+        let packet = SessionPacket::assemble(
+            5,
+            PACKET_HEADER_VERSION,
+            None,
+            vec![1, 3],
+            &[],
+        );
         assert_eq!(packet, vec![1, 3, 0, 5]);
 
         let packet = SessionPacket::assemble(
@@ -887,6 +1449,7 @@ mod tests {
             PACKET_HEADER_VERSION,
             Some([8; 3]),
             vec![2, 4],
+            &[],
         );
         assert_eq!(packet, vec![2, 4, 8, 8, 8, 1, 6]);
     }
@@ -941,4 +1504,34 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_packet_extensions() {
+        let extensions = vec![vec![1, 1], vec![9]];
+        let packet = SessionPacket::assemble(
+            PACKET_HELLO,
+            PACKET_HEADER_VERSION,
+            None,
+            vec![7],
+            &extensions,
+        );
+        assert_eq!(packet, vec![7, 9, 2, 1, 1, 5, 0x10, PACKET_HELLO]);
+        assert_eq!(
+            SessionPacket::parse(packet.into()).unwrap(),
+            SessionPacket {
+                id: PACKET_HELLO,
+                header_version: 0,
+                protocol: None,
+                data: vec![7].into(),
+                extensions,
+            }
+        );
+
+        // extension missed
+        assert!(SessionPacket::parse(vec![0x10, PACKET_HELLO].into()).is_err());
+
+        // extension length is not enough
+        assert!(SessionPacket::parse(vec![1, 4, 0x10, PACKET_HELLO].into())
+            .is_err());
+    }
 }
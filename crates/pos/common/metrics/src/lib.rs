@@ -138,7 +138,7 @@ pub fn gather_metrics() -> Vec<prometheus::proto::MetricFamily> {
     metric_families
 }
 
-fn get_all_metrics_as_serialized_string() -> Result<Vec<u8>> {
+pub fn get_all_metrics_as_serialized_string() -> Result<Vec<u8>> {
     let all_metrics = gather_metrics();
 
     let encoder = TextEncoder::new();
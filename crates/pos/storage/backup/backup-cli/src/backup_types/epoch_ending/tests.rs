@@ -26,7 +26,7 @@ use diem_types::{
     proptest_types::{AccountInfoUniverse, LedgerInfoWithSignaturesGen},
     waypoint::Waypoint,
 };
-use pos_ledger_db::DiemDB;
+use pos_ledger_db::PosLedgerDB;
 use proptest::{collection::vec, prelude::*, std_facade::BTreeMap};
 use std::{
     convert::TryInto,
@@ -106,7 +106,7 @@ fn end_to_end() {
         .map(|li| li.ledger_info().next_block_epoch())
         .unwrap_or(0);
 
-    let tgt_db = DiemDB::open(
+    let tgt_db = PosLedgerDB::open(
         &tgt_db_dir,
         true, /* read_only */
         None, /* pruner */
@@ -20,7 +20,7 @@ use crate::{
 use diem_config::config::RocksdbConfig;
 use diem_temppath::TempPath;
 use diem_types::transaction::PRE_GENESIS_VERSION;
-use pos_ledger_db::DiemDB;
+use pos_ledger_db::PosLedgerDB;
 use std::{convert::TryInto, sync::Arc};
 use storage_interface::DbReader;
 use tokio::time::Duration;
@@ -81,7 +81,7 @@ fn end_to_end() {
     )
     .unwrap();
 
-    let tgt_db = DiemDB::open(
+    let tgt_db = PosLedgerDB::open(
         &tgt_db_dir,
         true, /* read_only */
         None, /* pruner */
@@ -26,7 +26,7 @@ use crate::{
 use diem_config::config::RocksdbConfig;
 use diem_temppath::TempPath;
 use diem_types::transaction::Version;
-use pos_ledger_db::DiemDB;
+use pos_ledger_db::PosLedgerDB;
 use executor_test_helpers::integration_test_impl::test_execution_with_storage_impl;
 use proptest::prelude::*;
 use std::{convert::TryInto, sync::Arc};
@@ -35,7 +35,7 @@ use tokio::time::Duration;
 
 #[derive(Debug)]
 struct TestData {
-    db: Arc<DiemDB>,
+    db: Arc<PosLedgerDB>,
     txn_start_ver: Version,
     state_snapshot_ver: Option<Version>,
     target_ver: Version,
@@ -153,7 +153,7 @@ fn test_end_to_end_impl(d: TestData) {
     .unwrap();
 
     // Check
-    let tgt_db = DiemDB::open(
+    let tgt_db = PosLedgerDB::open(
         &tgt_db_dir,
         false, /* read_only */
         None,  /* pruner */
@@ -20,7 +20,7 @@ use crate::{
 use diem_config::config::RocksdbConfig;
 use diem_temppath::TempPath;
 use diem_types::transaction::Version;
-use pos_ledger_db::DiemDB;
+use pos_ledger_db::PosLedgerDB;
 use std::{convert::TryInto, mem::size_of, sync::Arc};
 use storage_interface::DbReader;
 use tokio::time::Duration;
@@ -102,7 +102,7 @@ fn end_to_end() {
     // We don't write down any ledger infos when recovering transactions. State-sync needs to take
     // care of it before running consensus. The latest transactions are deemed "synced" instead of
     // "committed" most likely.
-    let tgt_db = DiemDB::open(
+    let tgt_db = PosLedgerDB::open(
         &tgt_db_dir,
         true, /* read_only */
         None, /* pruner */
@@ -51,6 +51,10 @@ impl MetadataCacheOpt {
     // in cache we save things other than the cached files.
     const SUB_DIR: &'static str = "cache";
 
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
     fn cache_dir(&self) -> PathBuf {
         self.dir
             .clone()
@@ -10,7 +10,7 @@ use diem_config::utils::get_available_port;
 use diem_proptest_helpers::ValueGenerator;
 use diem_temppath::TempPath;
 use diem_types::{ledger_info::LedgerInfoWithSignatures, transaction::TransactionToCommit};
-use pos_ledger_db::{test_helper::arb_blocks_to_commit, DiemDB};
+use pos_ledger_db::{test_helper::arb_blocks_to_commit, PosLedgerDB};
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
@@ -18,16 +18,16 @@ use std::{
 use storage_interface::DbWriter;
 use tokio::runtime::Runtime;
 
-pub fn tmp_db_empty() -> (TempPath, Arc<DiemDB>) {
+pub fn tmp_db_empty() -> (TempPath, Arc<PosLedgerDB>) {
     let tmpdir = TempPath::new();
-    let db = Arc::new(DiemDB::new_for_test(&tmpdir));
+    let db = Arc::new(PosLedgerDB::new_for_test(&tmpdir));
 
     (tmpdir, db)
 }
 
 pub fn tmp_db_with_random_content() -> (
     TempPath,
-    Arc<DiemDB>,
+    Arc<PosLedgerDB>,
     Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
 ) {
     let (tmpdir, db) = tmp_db_empty();
@@ -46,7 +46,7 @@ pub fn tmp_db_with_random_content() -> (
     (tmpdir, db, blocks)
 }
 
-pub fn start_local_backup_service(db: Arc<DiemDB>) -> (Runtime, u16) {
+pub fn start_local_backup_service(db: Arc<PosLedgerDB>) -> (Runtime, u16) {
     let port = get_available_port();
     let rt = start_backup_service(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port), db);
     (rt, port)
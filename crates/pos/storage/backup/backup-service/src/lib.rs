@@ -9,11 +9,11 @@ mod handlers;
 
 use crate::handlers::get_routes;
 use diem_logger::prelude::*;
-use pos_ledger_db::DiemDB;
+use pos_ledger_db::PosLedgerDB;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::runtime::{Builder, Runtime};
 
-pub fn start_backup_service(address: SocketAddr, db: Arc<DiemDB>) -> Runtime {
+pub fn start_backup_service(address: SocketAddr, db: Arc<PosLedgerDB>) -> Runtime {
     let backup_handler = db.get_backup_handler();
     let routes = get_routes(backup_handler);
 
@@ -54,7 +54,7 @@ mod tests {
     #[test]
     fn routing_and_error_codes() {
         let tmpdir = TempPath::new();
-        let db = Arc::new(DiemDB::new_for_test(&tmpdir));
+        let db = Arc::new(PosLedgerDB::new_for_test(&tmpdir));
         let port = get_available_port();
         let _rt = start_backup_service(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port), db);
 
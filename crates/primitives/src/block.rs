@@ -183,6 +183,51 @@ impl Block {
             Some(rlp.as_raw().len()),
         ))
     }
+
+    /// Encode the block together with the `pow_hash` of its header, so that
+    /// the proof of work does not need to be recomputed when it is decoded.
+    /// Transactions are encoded without "sender" and "public" fields.
+    pub fn encode_with_pow_hash(&self) -> Vec<u8> {
+        let mut header_stream = RlpStream::new();
+        self.block_header
+            .stream_rlp_with_pow_hash(&mut header_stream);
+
+        let mut stream = RlpStream::new();
+        stream
+            .begin_list(2)
+            .append_raw(&header_stream.drain(), 1)
+            .begin_list(self.transactions.len());
+        for tx in &self.transactions {
+            stream.append(&tx.transaction);
+        }
+        stream.drain()
+    }
+
+    /// Decode a block encoded by `encode_with_pow_hash`. The public keys of
+    /// the transactions need to be recovered later.
+    pub fn decode_with_pow_hash(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.as_raw().len() != rlp.payload_info()?.total() {
+            return Err(DecoderError::RlpIsTooBig);
+        }
+        if rlp.item_count()? != 2 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        let block_header =
+            BlockHeader::decode_with_pow_hash(rlp.at(0)?.as_raw())?;
+        let transactions = rlp
+            .list_at::<TransactionWithSignature>(1)?
+            .into_iter()
+            .map(|tx| Arc::new(SignedTransaction::new_unsigned(tx)))
+            .collect();
+
+        Ok(Block::new_with_rlp_size(
+            block_header,
+            transactions,
+            None,
+            None,
+        ))
+    }
 }
 
 // The encode of Block only serializes TransactionWithSignature
@@ -358,3 +403,56 @@ impl Decodable for CompactBlock {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Block;
+    use crate::{
+        keylib::{Generator, Random},
+        transaction::{native_transaction::NativeTransaction, Transaction},
+        BlockHeaderBuilder,
+    };
+    use cfx_types::{H256, U256};
+    use rlp::Rlp;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_encode_with_pow_hash_roundtrip() {
+        let keypair = Random.generate().unwrap();
+        let tx = Transaction::from(NativeTransaction {
+            nonce: U256::from(1),
+            gas: U256::from(21_000),
+            gas_price: U256::one(),
+            value: U256::from(7),
+            chain_id: 1,
+            ..Default::default()
+        })
+        .sign(keypair.secret());
+        let mut header = BlockHeaderBuilder::new()
+            .with_height(3)
+            .with_nonce(U256::from(11))
+            .build();
+        header.pow_hash = Some(H256::repeat_byte(0xab));
+        let block = Block::new(header, vec![Arc::new(tx)]);
+
+        let encoded = block.encode_with_pow_hash();
+        let decoded = Block::decode_with_pow_hash(&Rlp::new(&encoded)).unwrap();
+
+        assert_eq!(decoded.hash(), block.hash());
+        assert_eq!(
+            decoded.block_header.pow_hash,
+            Some(H256::repeat_byte(0xab))
+        );
+        assert_eq!(decoded.transactions.len(), 1);
+        assert_eq!(
+            decoded.transactions[0].hash(),
+            block.transactions[0].hash()
+        );
+        assert!(decoded.transactions[0].public().is_none());
+
+        // Trailing bytes are rejected.
+        let mut padded = encoded.clone();
+        padded.push(0);
+        assert!(Block::decode_with_pow_hash(&Rlp::new(&padded)).is_err());
+    }
+}
@@ -36,7 +36,8 @@ pub use id_provider::EthSubscriptionIdProvider;
 pub use module::{EthRpcModule, RpcModuleSelection};
 
 use cfx_rpc::{helpers::ChainInfo, *};
-use cfx_rpc_cfx_types::RpcImplConfiguration;
+use cfx_rpc_cfx_impl::AdminHandler;
+use cfx_rpc_cfx_types::{admin::NodeConfig, RpcImplConfiguration};
 use cfx_rpc_eth_api::*;
 use cfxcore::{
     SharedConsensusGraph, SharedSynchronizationService, SharedTransactionPool,
@@ -70,6 +71,7 @@ pub struct RpcModuleBuilder {
     consensus: SharedConsensusGraph,
     sync: SharedSynchronizationService,
     tx_pool: SharedTransactionPool,
+    node_config: NodeConfig,
 }
 
 impl RpcModuleBuilder {
@@ -82,9 +84,16 @@ impl RpcModuleBuilder {
             consensus,
             sync,
             tx_pool,
+            node_config: Default::default(),
         }
     }
 
+    /// Configure the node configuration returned by the `admin_` module.
+    pub fn with_node_config(mut self, node_config: NodeConfig) -> Self {
+        self.node_config = node_config;
+        self
+    }
+
     /// Configures all [`RpcModule`]s specific to the given
     /// [`TransportRpcModuleConfig`] which can be used to start the
     /// transport server(s).
@@ -101,10 +110,12 @@ impl RpcModuleBuilder {
                 consensus,
                 sync,
                 tx_pool,
+                node_config,
             } = self;
 
             let mut registry =
-                RpcRegistryInner::new(config, consensus, sync, tx_pool);
+                RpcRegistryInner::new(config, consensus, sync, tx_pool)
+                    .with_node_config(node_config);
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -122,6 +133,7 @@ pub struct RpcRegistryInner {
     config: RpcImplConfiguration,
     sync: SharedSynchronizationService,
     tx_pool: SharedTransactionPool,
+    node_config: NodeConfig,
     modules: HashMap<EthRpcModule, Methods>,
 }
 
@@ -135,10 +147,16 @@ impl RpcRegistryInner {
             config,
             sync,
             tx_pool,
+            node_config: Default::default(),
             modules: Default::default(),
         }
     }
 
+    pub fn with_node_config(mut self, node_config: NodeConfig) -> Self {
+        self.node_config = node_config;
+        self
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -182,6 +200,13 @@ impl RpcRegistryInner {
         NetApi::new(Box::new(ChainInfo::new(self.consensus.clone())))
     }
 
+    pub fn admin_api(&self) -> AdminApi {
+        AdminApi::new(AdminHandler::new(
+            self.sync.network.clone(),
+            self.node_config.clone(),
+        ))
+    }
+
     /// Helper function to create a [`RpcModule`] if it's not `None`
     fn maybe_module(
         &mut self, config: Option<&RpcModuleSelection>,
@@ -213,6 +238,12 @@ impl RpcRegistryInner {
             self.modules
                 .entry(namespace)
                 .or_insert_with(|| match namespace {
+                    EthRpcModule::Admin => AdminApi::new(AdminHandler::new(
+                        self.sync.network.clone(),
+                        self.node_config.clone(),
+                    ))
+                    .into_rpc()
+                    .into(),
                     EthRpcModule::Debug => DebugApi::new(
                         self.consensus.clone(),
                         self.config.max_estimation_gas_limit,
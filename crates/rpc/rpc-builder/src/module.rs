@@ -249,8 +249,8 @@ impl fmt::Display for RpcModuleSelection {
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "kebab-case")]
 pub enum EthRpcModule {
-    /// `admin_` module
-    // Admin,
+    /// `admin_` module, which is not included in the standard modules.
+    Admin,
     /// `debug_` module
     Debug,
     /// `eth_` module
@@ -299,7 +299,7 @@ impl FromStr for EthRpcModule {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
-            // "admin" => Self::Admin,
+            "admin" => Self::Admin,
             "debug" => Self::Debug,
             "eth" => Self::Eth,
             "net" => Self::Net,
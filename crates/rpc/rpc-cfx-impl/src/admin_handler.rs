@@ -0,0 +1,232 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_rpc_cfx_types::{
+    admin::{
+        NodeConfig, NodeInfo, PeerInfo, PeerNetworkInfo, PeerSessionInfo, Ports,
+    },
+    ChainStaticMeta,
+};
+use jsonrpc_core::{Error as JsonRpcError, Result as JsonRpcResult};
+use log::info;
+use network::{
+    node_table::{Node, NodeEndpoint, NodeEntry},
+    NetworkService, ProtocolInfo,
+};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+/// Implementation of the `admin_` RPCs shared by the core space and eSpace
+/// servers.
+#[derive(Clone)]
+pub struct AdminHandler {
+    network: Arc<NetworkService>,
+    config: NodeConfig,
+}
+
+impl AdminHandler {
+    pub fn new(network: Arc<NetworkService>, config: NodeConfig) -> Self {
+        AdminHandler { network, config }
+    }
+
+    pub fn node_info(&self) -> JsonRpcResult<NodeInfo> {
+        let node = self.network.local_node().ok_or_else(network_not_started)?;
+
+        Ok(NodeInfo {
+            id: node.id,
+            name: ChainStaticMeta::client_version(),
+            enode: node.to_string(),
+            ip: node.endpoint.address.ip().to_string(),
+            ports: Ports {
+                discovery: node.endpoint.udp_port,
+                listener: node.endpoint.address.port(),
+            },
+            listen_addr: self.network.local_addr().map(|a| a.to_string()),
+            network_id: self.network.network_id().into(),
+            protocols: protocol_versions(&self.network.protocols()),
+        })
+    }
+
+    pub fn peers(&self) -> JsonRpcResult<Vec<PeerInfo>> {
+        let peers = self.network.get_peer_info().unwrap_or_default();
+
+        Ok(peers
+            .into_iter()
+            .map(|peer| {
+                let session = self
+                    .network
+                    .get_detailed_sessions(Some(peer.nodeid))
+                    .and_then(|sessions| sessions.into_iter().next());
+
+                // prefer the endpoint in node database, since the remote
+                // port of ingress connections is not the listening one
+                let (trusted, node) = match self.network.get_node(&peer.nodeid)
+                {
+                    Some((trusted, node)) => (trusted, node),
+                    None => (
+                        false,
+                        Node::new(
+                            peer.nodeid,
+                            NodeEndpoint {
+                                address: peer.addr,
+                                udp_port: peer.addr.port(),
+                            },
+                        ),
+                    ),
+                };
+
+                PeerInfo {
+                    id: peer.nodeid,
+                    enode: node.to_string(),
+                    network: PeerNetworkInfo {
+                        remote_address: peer.addr.to_string(),
+                        inbound: session
+                            .as_ref()
+                            .map_or(false, |session| !session.originated),
+                        trusted,
+                        reserved: self.network.is_reserved_peer(&peer.nodeid),
+                    },
+                    protocols: protocol_versions(&peer.protocols),
+                    session: session.map(|session| PeerSessionInfo {
+                        status: session.status,
+                        last_read: session.last_read,
+                        last_write: session.last_write,
+                        last_write_status: session.last_write_status,
+                    }),
+                }
+            })
+            .collect())
+    }
+
+    /// Add a trusted node to connect, which may be evicted later like the
+    /// nodes from discovery.
+    pub fn add_peer(&self, url: String) -> JsonRpcResult<bool> {
+        let entry = parse_node_url(&url)?;
+        info!("RPC Request: admin_addPeer({})", url);
+        self.network
+            .add_peer(entry)
+            .map_err(|_| network_not_started())?;
+        Ok(true)
+    }
+
+    /// Remove the node from node database and disconnect it.
+    pub fn remove_peer(&self, url: String) -> JsonRpcResult<bool> {
+        let entry = parse_node_url(&url)?;
+        info!("RPC Request: admin_removePeer({})", url);
+        let id = entry.id;
+        self.network
+            .drop_peer(entry)
+            .map_err(|_| network_not_started())?;
+        Ok(self.network.disconnect_node(&id, None /* op */))
+    }
+
+    /// Add a reserved node, which is always connected and persisted in node
+    /// database.
+    pub fn add_trusted_peer(&self, url: String) -> JsonRpcResult<bool> {
+        let entry = parse_node_url(&url)?;
+        info!("RPC Request: admin_addTrustedPeer({})", url);
+        self.network
+            .add_reserved_peer(entry)
+            .map_err(|_| network_not_started())?;
+        Ok(true)
+    }
+
+    /// Remove a reserved node, which is demoted to untrusted in node database
+    /// but not disconnected.
+    pub fn remove_trusted_peer(&self, url: String) -> JsonRpcResult<bool> {
+        let entry = parse_node_url(&url)?;
+        info!("RPC Request: admin_removeTrustedPeer({})", url);
+        self.network
+            .remove_reserved_peer(&entry.id)
+            .map_err(|_| network_not_started())
+    }
+
+    pub fn datadir(&self) -> JsonRpcResult<String> {
+        Ok(self.config.data_dir.clone())
+    }
+
+    pub fn config(&self) -> JsonRpcResult<NodeConfig> {
+        Ok(self.config.clone())
+    }
+}
+
+/// Parse node url with format "cfxnode://ID@IP:PORT", in which the node id is
+/// required.
+fn parse_node_url(url: &str) -> JsonRpcResult<NodeEntry> {
+    let node = Node::from_str(url).map_err(|e| {
+        JsonRpcError::invalid_params(format!("invalid node url: {:?}", e))
+    })?;
+
+    if node.id.is_zero() {
+        return Err(JsonRpcError::invalid_params(
+            "node id is required, expected format cfxnode://ID@IP:PORT",
+        ));
+    }
+
+    Ok(NodeEntry {
+        id: node.id,
+        endpoint: node.endpoint,
+    })
+}
+
+fn protocol_versions(protocols: &[ProtocolInfo]) -> BTreeMap<String, u8> {
+    protocols
+        .iter()
+        .map(|p| {
+            (
+                String::from_utf8_lossy(&p.protocol).into_owned(),
+                p.version.0,
+            )
+        })
+        .collect()
+}
+
+fn network_not_started() -> JsonRpcError {
+    let mut error = JsonRpcError::internal_error();
+    error.message = "Network service not started yet".into();
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::ErrorCode;
+
+    const NODE_ID: &str = "a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c";
+
+    fn assert_invalid_params(url: &str) {
+        let error = parse_node_url(url).unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidParams, "url = {}", url);
+    }
+
+    #[test]
+    fn test_parse_node_url() {
+        let entry =
+            parse_node_url(&format!("cfxnode://{}@127.0.0.1:32323", NODE_ID))
+                .unwrap();
+        assert_eq!(format!("{:x}", entry.id), NODE_ID);
+        assert_eq!(entry.endpoint.address, "127.0.0.1:32323".parse().unwrap());
+        assert_eq!(entry.endpoint.udp_port, 32323);
+    }
+
+    #[test]
+    fn test_parse_node_url_without_id() {
+        assert_invalid_params("127.0.0.1:32323");
+        assert_invalid_params("cfxnode://127.0.0.1:32323");
+        assert_invalid_params(&format!(
+            "cfxnode://{}@127.0.0.1:32323",
+            "0".repeat(128)
+        ));
+        assert_invalid_params("cfxnode://abc@127.0.0.1:32323");
+    }
+
+    #[test]
+    fn test_parse_node_url_with_bad_endpoint() {
+        assert_invalid_params(&format!("cfxnode://{}@127.0.0.1", NODE_ID));
+        assert_invalid_params(&format!("cfxnode://{}@", NODE_ID));
+        assert_invalid_params(&format!(
+            "cfxnode://{}@127.0.0.1:99999",
+            NODE_ID
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+mod admin_handler;
 mod trace_handler;
 
+pub use admin_handler::AdminHandler;
 pub use trace_handler::TraceHandler;
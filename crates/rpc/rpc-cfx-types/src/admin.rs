@@ -0,0 +1,86 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_types::{H512, U64};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Information of the local node returned by `admin_nodeInfo`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    pub id: H512,
+    /// Client version of the node.
+    pub name: String,
+    /// Node url with format "cfxnode://ID@IP:PORT".
+    pub enode: String,
+    /// Public IP address of the node.
+    pub ip: String,
+    pub ports: Ports,
+    /// Local address to listen for TCP connections.
+    pub listen_addr: Option<String>,
+    pub network_id: U64,
+    /// Versions of the registered P2P protocols.
+    pub protocols: BTreeMap<String, u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ports {
+    /// UDP port for node discovery.
+    pub discovery: u16,
+    /// TCP port for P2P connections.
+    pub listener: u16,
+}
+
+/// Connected peer returned by `admin_peers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerInfo {
+    pub id: H512,
+    /// Node url with format "cfxnode://ID@IP:PORT".
+    pub enode: String,
+    pub network: PeerNetworkInfo,
+    /// Versions of the P2P protocols supported by the peer.
+    pub protocols: BTreeMap<String, u8>,
+    pub session: Option<PeerSessionInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerNetworkInfo {
+    pub remote_address: String,
+    pub inbound: bool,
+    /// Whether the peer is in the trusted node table, which is used to
+    /// sample outgoing connections.
+    pub trusted: bool,
+    /// Whether the peer is reserved, which is always connected.
+    pub reserved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerSessionInfo {
+    pub status: String,
+    pub last_read: String,
+    pub last_write: String,
+    pub last_write_status: String,
+}
+
+/// Data directories and P2P/RPC configuration returned by `admin_config`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeConfig {
+    pub node_type: String,
+    pub data_dir: String,
+    pub block_db_dir: String,
+    pub net_config_dir: String,
+    pub tcp_port: u16,
+    pub udp_port: Option<u16>,
+    pub public_address: Option<String>,
+    pub max_incoming_peers: usize,
+    pub max_outgoing_peers: usize,
+    pub public_rpc_apis: Vec<String>,
+    pub public_evm_rpc_apis: Vec<String>,
+}
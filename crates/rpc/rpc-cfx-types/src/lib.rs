@@ -1,4 +1,5 @@
 pub mod address;
+pub mod admin;
 mod chain_meta;
 pub mod epoch_number;
 mod fee_history;
@@ -0,0 +1,38 @@
+use cfx_rpc_eth_types::{NodeConfig, NodeInfo, PeerInfo};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// Admin rpc interface, which should not be exposed on public interfaces.
+#[rpc(server, namespace = "admin")]
+pub trait AdminApi {
+    /// Returns information of the local node, including the node url.
+    #[method(name = "nodeInfo")]
+    fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the connected peers with session details.
+    #[method(name = "peers")]
+    fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+
+    /// Adds a node to connect with url "cfxnode://ID@IP:PORT".
+    #[method(name = "addPeer")]
+    fn add_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Removes a node from node database and disconnects it.
+    #[method(name = "removePeer")]
+    fn remove_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Adds a node which is always connected and persisted in node database.
+    #[method(name = "addTrustedPeer")]
+    fn add_trusted_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Removes a node added by `admin_addTrustedPeer`.
+    #[method(name = "removeTrustedPeer")]
+    fn remove_trusted_peer(&self, url: String) -> RpcResult<bool>;
+
+    /// Returns the data directory of the node.
+    #[method(name = "datadir")]
+    fn datadir(&self) -> RpcResult<String>;
+
+    /// Returns the data directories and P2P/RPC configuration of the node.
+    #[method(name = "config")]
+    fn config(&self) -> RpcResult<NodeConfig>;
+}
@@ -1,6 +1,8 @@
 use cfx_rpc_eth_types::{
-    Block, BlockNumber as BlockId, EthRpcLogFilter as Filter, FeeHistory,
-    Header, Log, Receipt, SyncStatus, Transaction, TransactionRequest,
+    AccessListResult, AccountProof, Block, BlockNumber as BlockId,
+    BlockOverrides, Bundle, EthCallResponse, EthRpcLogFilter as Filter,
+    FeeHistory, Header, Log, Receipt, SimulatePayload, SimulatedBlock,
+    StateContext, StateOverride, SyncStatus, Transaction, TransactionRequest,
 };
 use cfx_rpc_primitives::{Bytes, Index};
 use cfx_types::{Address, H256, H64, U256, U64};
@@ -188,33 +190,27 @@ pub trait EthApi {
     /// `eth_simulateV1` executes an arbitrary number of transactions on top of
     /// the requested state. The transactions are packed into individual
     /// blocks. Overrides can be provided.
-    // #[method(name = "simulateV1")]
-    // async fn simulate_v1(
-    //     &self,
-    //     opts: SimBlock,
-    //     block_number: Option<BlockId>,
-    // ) -> RpcResult<Vec<SimulatedBlock>>;
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self, payload: SimulatePayload, block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
 
     /// Executes a new message call immediately without creating a transaction
     /// on the block chain.
     #[method(name = "call")]
     async fn call(
-        &self,
-        request: TransactionRequest,
-        block_number: Option<BlockId>,
-        // state_overrides: Option<StateOverride>,
-        // block_overrides: Option<Box<BlockOverrides>>,
+        &self, request: TransactionRequest, block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes>;
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain
     /// index, with the optionality of state overrides
-    // #[method(name = "callMany")]
-    // async fn call_many(
-    //     &self,
-    //     bundle: Bundle,
-    //     state_context: Option<StateContext>,
-    //     state_override: Option<StateOverride>,
-    // ) -> RpcResult<Vec<EthCallResponse>>;
+    #[method(name = "callMany")]
+    async fn call_many(
+        &self, bundle: Bundle, state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<EthCallResponse>>;
 
     /// Generates an access list for a transaction.
     ///
@@ -232,21 +228,17 @@ pub trait EthApi {
     /// could change when the transaction is actually mined. Adding an
     /// accessList to your transaction does not necessary result in lower
     /// gas usage compared to a transaction without an access list.
-    // #[method(name = "createAccessList")]
-    // async fn create_access_list(
-    //     &self,
-    //     request: TransactionRequest,
-    //     block_number: Option<BlockId>,
-    // ) -> RpcResult<AccessListResult>;
+    #[method(name = "createAccessList")]
+    async fn create_access_list(
+        &self, request: TransactionRequest, block_number: Option<BlockId>,
+    ) -> RpcResult<AccessListResult>;
 
     /// Generates and returns an estimate of how much gas is necessary to allow
     /// the transaction to complete.
     #[method(name = "estimateGas")]
     async fn estimate_gas(
-        &self,
-        request: TransactionRequest,
-        block_number: Option<BlockId>,
-        // state_override: Option<StateOverride>,
+        &self, request: TransactionRequest, block_number: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
     /// Returns the current price per gas in wei.
@@ -345,17 +337,16 @@ pub trait EthApi {
     // async fn sign_typed_data(&self, address: Address, data: TypedData) ->
     // RpcResult<Bytes>;
 
-    // Returns the account and storage values of the specified account including
-    // the Merkle-proof. This call can be used to verify that the data you
-    // are pulling from is not tampered with.
-
-    // #[method(name = "getProof")]
-    // async fn get_proof(
-    //     &self,
-    //     address: Address,
-    //     keys: Vec<JsonStorageKey>,
-    //     block_number: Option<BlockId>,
-    // ) -> RpcResult<EIP1186AccountProofResponse>;
+    /// Returns the account and storage values of the specified account
+    /// including the Merkle-proof. This call can be used to verify that the
+    /// data you are pulling from is not tampered with.
+    ///
+    /// See [`AccountProof`] for the encoding of the proofs.
+    #[method(name = "getProof")]
+    async fn get_proof(
+        &self, address: Address, keys: Vec<JsonStorageKey>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<AccountProof>;
 
     /// Returns logs matching given filter object.
     #[method(name = "getLogs")]
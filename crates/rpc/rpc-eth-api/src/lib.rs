@@ -1,3 +1,4 @@
+mod admin;
 mod debug;
 mod eth;
 mod filter;
@@ -7,6 +8,7 @@ mod rpc;
 mod trace;
 mod web3;
 
+pub use admin::AdminApiServer;
 pub use debug::DebugApiServer;
 pub use eth::EthApiServer;
 pub use filter::EthFilterApiServer;
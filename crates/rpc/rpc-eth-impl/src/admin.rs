@@ -0,0 +1,63 @@
+use cfx_rpc_cfx_impl::AdminHandler;
+use cfx_rpc_eth_api::AdminApiServer;
+use cfx_rpc_eth_types::{NodeConfig, NodeInfo, PeerInfo};
+use cfx_rpc_utils::error::jsonrpsee_error_helpers::jsonrpc_error_to_error_object_owned;
+use jsonrpsee::core::RpcResult;
+
+pub struct AdminApi {
+    inner: AdminHandler,
+}
+
+impl AdminApi {
+    pub fn new(inner: AdminHandler) -> Self { AdminApi { inner } }
+}
+
+impl AdminApiServer for AdminApi {
+    fn node_info(&self) -> RpcResult<NodeInfo> {
+        self.inner
+            .node_info()
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+        self.inner
+            .peers()
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn add_peer(&self, url: String) -> RpcResult<bool> {
+        self.inner
+            .add_peer(url)
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn remove_peer(&self, url: String) -> RpcResult<bool> {
+        self.inner
+            .remove_peer(url)
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn add_trusted_peer(&self, url: String) -> RpcResult<bool> {
+        self.inner
+            .add_trusted_peer(url)
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn remove_trusted_peer(&self, url: String) -> RpcResult<bool> {
+        self.inner
+            .remove_trusted_peer(url)
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn datadir(&self) -> RpcResult<String> {
+        self.inner
+            .datadir()
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+
+    fn config(&self) -> RpcResult<NodeConfig> {
+        self.inner
+            .config()
+            .map_err(jsonrpc_error_to_error_object_owned)
+    }
+}
@@ -1,6 +1,5 @@
 use alloy_rpc_types_trace::geth::{
-    GethDebugBuiltInTracerType,
-    GethDebugTracerType::{BuiltInTracer, JsTracer},
+    GethDebugBuiltInTracerType, GethDebugTracerType::BuiltInTracer,
     GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame,
     TraceResult,
 };
@@ -13,7 +12,7 @@ use cfxcore::{
     errors::Error as CoreError, ConsensusGraph, ConsensusGraphTrait,
     SharedConsensusGraph,
 };
-use geth_tracer::to_alloy_h256;
+use geth_tracer::{check_tracing_options, to_alloy_h256};
 use jsonrpsee::core::RpcResult;
 use primitives::{
     Block, BlockHashOrEpochNumber, BlockHeaderBuilder, EpochNumber,
@@ -82,6 +81,7 @@ impl DebugApi {
         }
 
         let opts = opts.unwrap_or_default();
+        check_tracing_options(&opts.tracing_options).map_err(CoreError::Msg)?;
         let block_num = block_number.unwrap_or_default();
 
         let epoch_num = self
@@ -151,16 +151,18 @@ impl DebugApi {
         )?;
 
         let res = traces
-            .first()
+            .into_iter()
+            .next()
             .ok_or(CoreError::Msg("trace generation failed".to_string()))?;
 
-        Ok(res.trace.clone())
+        res.trace.map_err(CoreError::Msg)
     }
 
     pub fn trace_block_by_num(
         &self, block_num: u64, opts: Option<GethDebugTracingOptions>,
     ) -> Result<Vec<TraceResult>, CoreError> {
         let opts = opts.unwrap_or_default();
+        check_tracing_options(&opts).map_err(CoreError::Msg)?;
         let epoch_traces = self
             .consensus_graph()
             .collect_epoch_geth_trace(block_num, None, opts)?;
@@ -168,9 +170,15 @@ impl DebugApi {
         let result = epoch_traces
             .into_iter()
             .filter(|val| val.space == Space::Ethereum)
-            .map(|val| TraceResult::Success {
-                result: val.trace,
-                tx_hash: Some(to_alloy_h256(val.tx_hash)),
+            .map(|val| match val.trace {
+                Ok(result) => TraceResult::Success {
+                    result,
+                    tx_hash: Some(to_alloy_h256(val.tx_hash)),
+                },
+                Err(error) => TraceResult::Error {
+                    error,
+                    tx_hash: Some(to_alloy_h256(val.tx_hash)),
+                },
             })
             .collect();
         Ok(result)
@@ -182,41 +190,11 @@ impl DebugApi {
         let opts = opts.unwrap_or_default();
 
         // early return if tracer is not supported or NoopTracer is requested
-        if let Some(tracer_type) = &opts.tracer {
-            match tracer_type {
-                BuiltInTracer(builtin_tracer) => match builtin_tracer {
-                    GethDebugBuiltInTracerType::FourByteTracer => (),
-                    GethDebugBuiltInTracerType::CallTracer => {
-                        // pre check config
-                        let _ = opts
-                            .tracer_config
-                            .clone()
-                            .into_call_config()
-                            .map_err(|err| {
-                            CoreError::Msg(err.to_string())
-                        })?;
-                        ()
-                    }
-                    GethDebugBuiltInTracerType::PreStateTracer => {
-                        // pre check config
-                        let _ = opts
-                            .tracer_config
-                            .clone()
-                            .into_pre_state_config()
-                            .map_err(|err| CoreError::Msg(err.to_string()))?;
-                        ()
-                    }
-                    GethDebugBuiltInTracerType::NoopTracer => {
-                        return Ok(GethTrace::NoopTracer(NoopFrame::default()))
-                    }
-                    GethDebugBuiltInTracerType::MuxTracer => {
-                        return Err(CoreError::Msg("not supported".to_string()))
-                    }
-                },
-                JsTracer(_) => {
-                    return Err(CoreError::Msg("not supported".to_string()))
-                }
-            }
+        check_tracing_options(&opts).map_err(CoreError::Msg)?;
+        if let Some(BuiltInTracer(GethDebugBuiltInTracerType::NoopTracer)) =
+            opts.tracer
+        {
+            return Ok(GethTrace::NoopTracer(NoopFrame::default()));
         }
 
         let tx_index = self
@@ -243,7 +221,7 @@ impl DebugApi {
             .map(|val| val.trace)
             .ok_or(CoreError::Msg("trace generation failed".to_string()))?;
 
-        Ok(trace)
+        trace.map_err(CoreError::Msg)
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::helpers::{FeeHistoryCache, MAX_FEE_HISTORY_CACHE_BLOCK_COUNT};
 use async_trait::async_trait;
-use cfx_execute_helper::estimation::EstimateRequest;
+use cfx_execute_helper::estimation::{EstimateExt, EstimateRequest};
 use cfx_executor::executive::{
     Executed, ExecutionError, ExecutionOutcome, TxDropError,
 };
@@ -10,9 +10,12 @@ use cfx_rpc_cfx_types::{
 };
 use cfx_rpc_eth_api::EthApiServer;
 use cfx_rpc_eth_types::{
-    Block, BlockNumber as BlockId, EthRpcLogFilter, EthRpcLogFilter as Filter,
-    FeeHistory, Header, Log, Receipt, SyncInfo, SyncStatus, Transaction,
-    TransactionRequest,
+    AccessListResult, AccountProof, Block, BlockNumber as BlockId,
+    BlockOverrides, BlockTransactions, Bundle, EthCallResponse,
+    EthRpcLogFilter, EthRpcLogFilter as Filter, EvmOverrides, FeeHistory,
+    Header, Log, Receipt, SimCallResult, SimulateError, SimulatePayload,
+    SimulatedBlock, StateContext, StateOverride, StorageProof, SyncInfo,
+    SyncStatus, Transaction, TransactionRequest,
 };
 use cfx_rpc_primitives::{Bytes, Index, U64 as HexU64};
 use cfx_rpc_utils::error::{
@@ -20,23 +23,28 @@ use cfx_rpc_utils::error::{
     jsonrpsee_error_helpers::internal_error as jsonrpsee_internal_error,
 };
 use cfx_statedb::StateDbExt;
+use cfx_storage::{state::StateDbGetOriginalMethods, StorageStateTrait};
 use cfx_types::{
     Address, AddressSpaceUtil, BigEndianHash, Space, H160, H256, H64, U256, U64,
 };
 use cfx_util_macros::bail;
 use cfx_vm_types::Error as VmError;
 use cfxcore::{
+    consensus::consensus_inner::consensus_executor::VirtualBlock,
     errors::{Error as CoreError, Result as CoreResult},
     ConsensusGraph, ConsensusGraphTrait, SharedConsensusGraph,
     SharedSynchronizationService, SharedTransactionPool,
 };
 use jsonrpc_core::Error as RpcError;
 use jsonrpsee::core::RpcResult;
+use keccak_hash::KECCAK_EMPTY;
 use primitives::{
-    filter::LogFilter, receipt::EVM_SPACE_SUCCESS, Action,
-    BlockHashOrEpochNumber, EpochNumber, StorageKey, StorageValue,
-    TransactionStatus, TransactionWithSignature,
+    filter::LogFilter,
+    receipt::{EVM_SPACE_FAIL, EVM_SPACE_SUCCESS},
+    Account, Action, BlockHashOrEpochNumber, EpochNumber, SignedTransaction,
+    StorageKey, StorageValue, TransactionStatus, TransactionWithSignature,
 };
+use rlp::Rlp;
 use rustc_hex::ToHex;
 use solidity_abi::string_revert_reason_decode;
 
@@ -97,12 +105,54 @@ impl EthApi {
             .ok_or("Specified block header does not exist".into())
     }
 
+    /// Resolve an eth RPC block parameter into the epoch it refers to. Block
+    /// hashes are accepted as long as they refer to a pivot block.
+    pub fn epoch_by_block_number(
+        &self, block_number_or_hash: Option<BlockNumber>,
+    ) -> CoreResult<EpochNumber> {
+        let consensus_graph = self.consensus_graph();
+
+        let epoch = match block_number_or_hash.unwrap_or_default() {
+            BlockNumber::Hash { hash, .. } => {
+                match consensus_graph.get_block_epoch_number(&hash) {
+                    Some(e) => {
+                        // do not expose non-pivot blocks in eth RPC
+                        let pivot = consensus_graph
+                            .get_block_hashes_by_epoch(EpochNumber::Number(e))?
+                            .last()
+                            .cloned();
+
+                        if Some(hash) != pivot {
+                            bail!("Block {:?} not found", hash);
+                        }
+
+                        EpochNumber::Number(e)
+                    }
+                    None => bail!("Block {:?} not found", hash),
+                }
+            }
+            epoch => epoch.try_into()?,
+        };
+
+        Ok(epoch)
+    }
+
     pub fn exec_transaction(
-        &self, mut request: TransactionRequest,
-        block_number_or_hash: Option<BlockNumber>,
+        &self, request: TransactionRequest,
+        block_number_or_hash: Option<BlockNumber>, overrides: EvmOverrides,
     ) -> CoreResult<(Executed, U256)> {
-        let consensus_graph = self.consensus_graph();
+        let epoch = self.epoch_by_block_number(block_number_or_hash)?;
+        let (executed, estimation) =
+            self.exec_transaction_at_epoch(request, epoch, false, overrides)?;
 
+        Ok((executed, estimation.estimated_gas_limit))
+    }
+
+    /// Checks the fee fields of a call request and signs it with a fake
+    /// signature for virtual execution.
+    fn sign_call_request(
+        &self, mut request: TransactionRequest,
+    ) -> CoreResult<(SignedTransaction, EstimateRequest)> {
         if request.gas_price.is_some()
             && request.max_priority_fee_per_gas.is_some()
         {
@@ -125,28 +175,6 @@ impl EthApi {
             }
         }
 
-        let epoch = match block_number_or_hash.unwrap_or_default() {
-            BlockNumber::Hash { hash, .. } => {
-                match consensus_graph.get_block_epoch_number(&hash) {
-                    Some(e) => {
-                        // do not expose non-pivot blocks in eth RPC
-                        let pivot = consensus_graph
-                            .get_block_hashes_by_epoch(EpochNumber::Number(e))?
-                            .last()
-                            .cloned();
-
-                        if Some(hash) != pivot {
-                            bail!("Block {:?} not found", hash);
-                        }
-
-                        EpochNumber::Number(e)
-                    }
-                    None => bail!("Block {:?} not found", hash),
-                }
-            }
-            epoch => epoch.try_into()?,
-        };
-
         // if gas_price and gas is zero, it is considered as not set
         request.unset_zero_gas_and_price();
 
@@ -156,6 +184,7 @@ impl EthApi {
             has_gas_price: request.has_gas_price(),
             has_nonce: request.nonce.is_some(),
             has_storage_limit: false,
+            collect_access_list: false,
         };
 
         let chain_id = self.consensus.best_chain_id();
@@ -163,10 +192,24 @@ impl EthApi {
         let max_gas = self.config.max_estimation_gas_limit;
         let signed_tx = request.sign_call(chain_id.in_evm_space(), max_gas)?;
 
+        Ok((signed_tx, estimate_request))
+    }
+
+    fn exec_transaction_at_epoch(
+        &self, request: TransactionRequest, epoch: EpochNumber,
+        collect_access_list: bool, overrides: EvmOverrides,
+    ) -> CoreResult<(Executed, EstimateExt)> {
+        let consensus_graph = self.consensus_graph();
+
+        let (signed_tx, mut estimate_request) =
+            self.sign_call_request(request)?;
+        estimate_request.collect_access_list = collect_access_list;
+
         let (execution_outcome, estimation) = consensus_graph.call_virtual(
             &signed_tx,
             epoch,
             estimate_request,
+            overrides,
         )?;
 
         let executed = match execution_outcome {
@@ -221,17 +264,250 @@ impl EthApi {
             ExecutionOutcome::Finished(executed) => executed,
         };
 
-        Ok((executed, estimation.estimated_gas_limit))
+        Ok((executed, estimation))
+    }
+
+    pub fn create_access_list(
+        &self, request: TransactionRequest,
+        block_number_or_hash: Option<BlockNumber>,
+    ) -> CoreResult<AccessListResult> {
+        // Pin the epoch so that both executions run against the same state.
+        let epoch = self.epoch_by_block_number(block_number_or_hash)?;
+        let epoch = EpochNumber::Number(
+            self.consensus_graph().get_height_from_epoch_number(epoch)?,
+        );
+
+        let mut request_with_list = request.clone();
+        let (_, estimation) = self.exec_transaction_at_epoch(
+            request,
+            epoch.clone(),
+            true,
+            Default::default(),
+        )?;
+        let access_list = estimation.access_list.unwrap_or_default();
+
+        request_with_list.access_list = Some(access_list.clone());
+        let (_, estimation_with_list) = self.exec_transaction_at_epoch(
+            request_with_list,
+            epoch,
+            false,
+            Default::default(),
+        )?;
+
+        Ok(AccessListResult {
+            access_list,
+            gas_used: estimation_with_list.estimated_gas_limit,
+            gas_used_without_access_list: estimation.estimated_gas_limit,
+        })
+    }
+
+    pub fn call_many(
+        &self, bundle: Bundle, state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> CoreResult<Vec<EthCallResponse>> {
+        let StateContext {
+            block_number,
+            transaction_index,
+        } = state_context.unwrap_or_default();
+        // The state is only available at the end of an epoch.
+        if transaction_index.map_or(false, |index| !index.is_all()) {
+            bail!(invalid_params(
+                "stateContext.transactionIndex",
+                "only -1 is supported"
+            ));
+        }
+        let epoch = self.epoch_by_block_number(block_number)?;
+
+        let transactions = bundle
+            .transactions
+            .into_iter()
+            .map(|request| self.sign_call_request(request))
+            .collect::<CoreResult<Vec<_>>>()?;
+        let hashes: Vec<H256> =
+            transactions.iter().map(|(tx, _)| tx.hash()).collect();
+        let block = VirtualBlock {
+            transactions,
+            overrides: EvmOverrides::new(
+                state_override,
+                bundle.block_override.map(Box::new),
+            ),
+        };
+
+        let block = self
+            .consensus_graph()
+            .call_virtual_bundle(epoch, Space::Ethereum, vec![block], false)?
+            .pop()
+            .expect("one block is simulated");
+        let block_number = U256::from(block.env.epoch_height);
+
+        let mut log_index = 0;
+        Ok(block
+            .outcomes
+            .into_iter()
+            .zip(hashes)
+            .enumerate()
+            .map(|(idx, (outcome, tx_hash))| {
+                let (executed, error) = bundle_call_result(outcome);
+                let logs = executed.as_ref().map_or(vec![], |executed| {
+                    simulated_logs(
+                        executed,
+                        block_number,
+                        tx_hash,
+                        idx,
+                        &mut log_index,
+                    )
+                });
+                EthCallResponse {
+                    value: executed
+                        .as_ref()
+                        .filter(|_| error.is_none())
+                        .map(|executed| executed.output.clone().into()),
+                    error: error.map(|error| error.message),
+                    gas_used: executed
+                        .map_or(U256::zero(), |executed| executed.gas_used),
+                    logs,
+                }
+            })
+            .collect())
+    }
+
+    pub fn simulate_v1(
+        &self, payload: SimulatePayload,
+        block_number_or_hash: Option<BlockNumber>,
+    ) -> CoreResult<Vec<SimulatedBlock>> {
+        if payload.trace_transfers {
+            bail!(invalid_params(
+                "traceTransfers",
+                "tracing transfers as logs is not supported"
+            ));
+        }
+        let return_full_transactions = payload.return_full_transactions;
+        let epoch = self.epoch_by_block_number(block_number_or_hash)?;
+
+        let mut block_txs = Vec::with_capacity(payload.block_state_calls.len());
+        let mut blocks = Vec::with_capacity(payload.block_state_calls.len());
+        for sim_block in payload.block_state_calls {
+            let transactions = sim_block
+                .calls
+                .into_iter()
+                .map(|request| self.sign_call_request(request))
+                .collect::<CoreResult<Vec<_>>>()?;
+            block_txs.push(
+                transactions
+                    .iter()
+                    .map(|(tx, _)| tx.clone())
+                    .collect::<Vec<_>>(),
+            );
+            blocks.push(VirtualBlock {
+                transactions,
+                overrides: EvmOverrides::new(
+                    sim_block.state_overrides,
+                    sim_block.block_overrides.map(Box::new),
+                ),
+            });
+        }
+
+        let outcomes = self.consensus_graph().call_virtual_bundle(
+            epoch,
+            Space::Ethereum,
+            blocks,
+            payload.validation,
+        )?;
+
+        Ok(outcomes
+            .into_iter()
+            .zip(block_txs)
+            .map(|(block, txs)| {
+                let number = U256::from(block.env.epoch_height);
+                let mut log_index = 0;
+                let calls = block
+                    .outcomes
+                    .into_iter()
+                    .zip(&txs)
+                    .enumerate()
+                    .map(|(idx, (outcome, tx))| {
+                        let (executed, error) = bundle_call_result(outcome);
+                        let logs =
+                            executed.as_ref().map_or(vec![], |executed| {
+                                simulated_logs(
+                                    executed,
+                                    number,
+                                    tx.hash(),
+                                    idx,
+                                    &mut log_index,
+                                )
+                            });
+                        let status = if error.is_none() {
+                            EVM_SPACE_SUCCESS
+                        } else {
+                            EVM_SPACE_FAIL
+                        };
+                        SimCallResult {
+                            return_data: executed
+                                .as_ref()
+                                .map(|executed| executed.output.clone())
+                                .unwrap_or_default()
+                                .into(),
+                            logs,
+                            gas_used: executed
+                                .map_or(U256::zero(), |executed| {
+                                    executed.gas_used
+                                }),
+                            status: status.into(),
+                            error,
+                        }
+                    })
+                    .collect();
+
+                let transactions = if return_full_transactions {
+                    BlockTransactions::Full(
+                        txs.iter()
+                            .enumerate()
+                            .map(|(idx, tx)| {
+                                Transaction::from_signed(
+                                    tx,
+                                    (None, Some(number), Some(idx.into())),
+                                    (None, None),
+                                )
+                            })
+                            .collect(),
+                    )
+                } else {
+                    BlockTransactions::Hashes(
+                        txs.iter().map(|tx| tx.hash()).collect(),
+                    )
+                };
+
+                SimulatedBlock {
+                    number,
+                    timestamp: block.env.timestamp.into(),
+                    gas_limit: block.env.gas_limit,
+                    gas_used: block.env.accumulated_gas_used,
+                    miner: block.env.author,
+                    base_fee_per_gas: Some(
+                        block.env.base_gas_price[Space::Ethereum],
+                    ),
+                    transactions,
+                    calls,
+                }
+            })
+            .collect())
     }
 
+    /// The private transactions are only packed by the local block generator
+    /// and never propagated. The eSpace RPC is not served on the local ports,
+    /// so the other transactions are not treated as local.
     pub fn send_transaction_with_signature(
-        &self, tx: TransactionWithSignature,
+        &self, tx: TransactionWithSignature, private: bool,
     ) -> CoreResult<H256> {
         if self.sync.catch_up_mode() {
             bail!(request_rejected_in_catch_up_mode(None));
         }
-        let (signed_trans, failed_trans) =
-            self.tx_pool.insert_new_transactions(vec![tx]);
+        let (signed_trans, failed_trans) = if private {
+            self.tx_pool.insert_new_private_transactions(vec![tx])
+        } else {
+            self.tx_pool.insert_new_transactions(vec![tx])
+        };
         if signed_trans.len() + failed_trans.len() > 1 {
             // This should never happen
             Ok(H256::zero().into())
@@ -245,13 +521,15 @@ impl EthApi {
             bail!(RpcError::from(EthApiError::from(tx_err)))
         } else {
             let tx_hash = signed_trans[0].hash();
-            self.sync.append_received_transactions(signed_trans);
+            if !private {
+                self.sync.append_received_transactions(signed_trans);
+            }
             Ok(tx_hash.into())
         }
     }
 
     pub fn construct_rpc_receipt(
-        &self, b: &PhantomBlock, idx: usize, prior_log_index: &mut usize,
+        b: &PhantomBlock, idx: usize, prior_log_index: &mut usize,
     ) -> CoreResult<Receipt> {
         if b.transactions.len() != b.receipts.len() {
             return Err(internal_error(
@@ -421,7 +699,7 @@ impl EthApi {
         let mut prior_log_index = 0;
 
         for idx in 0..b.receipts.len() {
-            block_receipts.push(self.construct_rpc_receipt(
+            block_receipts.push(Self::construct_rpc_receipt(
                 &b,
                 idx,
                 &mut prior_log_index,
@@ -730,6 +1008,70 @@ impl EthApi {
         Ok(Bytes::new(code))
     }
 
+    pub fn get_proof(
+        &self, address: H160, keys: Vec<JsonStorageKey>,
+        block_num: Option<BlockNumber>,
+    ) -> CoreResult<AccountProof> {
+        let epoch_num = self.epoch_by_block_number(block_num)?;
+
+        let state = self
+            .consensus
+            .get_storage_state_by_epoch_number(epoch_num, "block_number")?;
+        let state_root = state.get_state_root()?;
+
+        let address_with_space = address.with_evm_space();
+
+        let (account_value, account_proof) = state
+            .get_original_raw_with_proof(
+                StorageKey::new_account_key(&address).with_evm_space(),
+            )?;
+
+        let account = match &account_value {
+            Some(raw) => Some(
+                Account::new_from_rlp(address, &Rlp::new(raw))
+                    .map_err(|e| internal_error(e))?,
+            ),
+            None => None,
+        };
+
+        let (storage_root, storage_root_proof) =
+            state.get_original_storage_root_with_proof(&address_with_space)?;
+
+        let mut storage_proof = Vec::with_capacity(keys.len());
+        for key in keys {
+            let position: H256 = H256::from_uint(&key);
+            let (raw_value, proof) = state.get_original_raw_with_proof(
+                StorageKey::new_storage_key(&address, position.as_ref())
+                    .with_evm_space(),
+            )?;
+
+            let value = match &raw_value {
+                Some(raw) => rlp::decode::<StorageValue>(raw)?.value,
+                None => U256::zero(),
+            };
+
+            storage_proof.push(StorageProof {
+                key,
+                value,
+                raw_value: raw_value.map(|v| Bytes::new(v.into_vec())),
+                proof: Bytes::new(rlp::encode(&proof)),
+            });
+        }
+
+        Ok(AccountProof {
+            address,
+            balance: account.as_ref().map_or(U256::zero(), |a| a.balance),
+            code_hash: account.as_ref().map_or(KECCAK_EMPTY, |a| a.code_hash),
+            nonce: account.as_ref().map_or(U256::zero(), |a| a.nonce),
+            account_value: account_value.map(|v| Bytes::new(v.into_vec())),
+            account_proof: Bytes::new(rlp::encode(&account_proof)),
+            storage_root,
+            storage_root_proof: Bytes::new(rlp::encode(&storage_root_proof)),
+            storage_proof,
+            state_root,
+        })
+    }
+
     pub fn fee_history(
         &self, mut block_count: HexU64, newest_block: BlockNumber,
         reward_percentiles: Option<Vec<f64>>,
@@ -925,7 +1267,7 @@ impl EthApi {
 
         for (idx, tx) in phantom_block.transactions.iter().enumerate() {
             if tx.hash() == tx_hash {
-                let receipt = self.construct_rpc_receipt(
+                let receipt = Self::construct_rpc_receipt(
                     &phantom_block,
                     idx,
                     &mut prior_log_index,
@@ -993,6 +1335,126 @@ impl EthApi {
     }
 }
 
+/// Error code of a reverted call in a bundle, the same as `eth_call`.
+const BUNDLE_CALL_REVERTED: i32 = 3;
+/// Error code of a call in a bundle which fails for other VM errors.
+const BUNDLE_CALL_VM_ERROR: i32 = -32015;
+/// Error code of a call in a bundle which is not executed.
+const BUNDLE_CALL_NOT_EXECUTED: i32 = -32000;
+
+/// Splits the outcome of a call in a bundle into the execution result and the
+/// error if the call fails. A call which is not executed has no execution
+/// result.
+fn bundle_call_result(
+    outcome: ExecutionOutcome,
+) -> (Option<Executed>, Option<SimulateError>) {
+    match outcome {
+        ExecutionOutcome::Finished(executed) => (Some(executed), None),
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(VmError::Reverted),
+            executed,
+        ) => {
+            let error = SimulateError {
+                code: BUNDLE_CALL_REVERTED,
+                message: format!(
+                    "execution reverted: revert: {}",
+                    string_revert_reason_decode(&executed.output)
+                ),
+            };
+            (Some(executed), Some(error))
+        }
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            ExecutionError::VmError(e),
+            executed,
+        ) => {
+            let error = SimulateError {
+                code: BUNDLE_CALL_VM_ERROR,
+                message: format!("execution reverted: {}", e),
+            };
+            (Some(executed), Some(error))
+        }
+        ExecutionOutcome::ExecutionErrorBumpNonce(
+            e @ ExecutionError::NotEnoughCash { .. },
+            executed,
+        ) => {
+            let error = SimulateError {
+                code: BUNDLE_CALL_VM_ERROR,
+                message: format!(
+                    "insufficient funds for gas * price + value: {:?}",
+                    e
+                ),
+            };
+            (Some(executed), Some(error))
+        }
+        ExecutionOutcome::NotExecutedDrop(e) => {
+            let error = SimulateError {
+                code: BUNDLE_CALL_NOT_EXECUTED,
+                message: format!("transaction not executed: {:?}", e),
+            };
+            (None, Some(error))
+        }
+        ExecutionOutcome::NotExecutedToReconsiderPacking(e) => {
+            let error = SimulateError {
+                code: BUNDLE_CALL_NOT_EXECUTED,
+                message: format!("transaction not executed: {:?}", e),
+            };
+            (None, Some(error))
+        }
+    }
+}
+
+/// Converts the logs of a call in a bundle to RPC logs. Simulated blocks are
+/// never sealed, so the block hash is zero.
+fn simulated_logs(
+    executed: &Executed, block_number: U256, tx_hash: H256, tx_index: usize,
+    log_index: &mut usize,
+) -> Vec<Log> {
+    executed
+        .logs
+        .iter()
+        .enumerate()
+        .map(|(tx_log_index, log)| {
+            let rpc_log = Log {
+                address: log.address,
+                topics: log.topics.clone(),
+                data: log.data.clone().into(),
+                block_hash: H256::zero(),
+                block_number,
+                transaction_hash: tx_hash,
+                transaction_index: tx_index.into(),
+                log_index: Some((*log_index).into()),
+                transaction_log_index: Some(tx_log_index.into()),
+                removed: false,
+            };
+            *log_index += 1;
+            rpc_log
+        })
+        .collect()
+}
+
+/// Decodes a signed eSpace transaction and checks its signature.
+pub fn decode_raw_transaction(
+    bytes: Bytes,
+) -> Result<TransactionWithSignature, EthApiError> {
+    let tx =
+        if let Ok(tx) = TransactionWithSignature::from_raw(&bytes.into_vec()) {
+            tx
+        } else {
+            bail!(EthApiError::FailedToDecodeSignedTransaction)
+        };
+
+    if tx.space() != Space::Ethereum {
+        bail!(EthApiError::Other(
+            "Incorrect transaction space".to_string()
+        ));
+    }
+
+    if tx.recover_public().is_err() {
+        bail!(EthApiError::InvalidTransactionSignature);
+    }
+    Ok(tx)
+}
+
 impl BlockProvider for &EthApi {
     fn get_block_epoch_number(&self, hash: &H256) -> Option<u64> {
         self.consensus_graph().get_block_epoch_number(hash)
@@ -1227,35 +1689,38 @@ impl EthApiServer for EthApi {
     /// `eth_simulateV1` executes an arbitrary number of transactions on top of
     /// the requested state. The transactions are packed into individual
     /// blocks. Overrides can be provided.
-    // async fn simulate_v1(
-    //     &self,
-    //     opts: SimBlock,
-    //     block_number: Option<BlockId>,
-    // ) -> RpcResult<Vec<SimulatedBlock>>;
+    async fn simulate_v1(
+        &self, payload: SimulatePayload, block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        self.simulate_v1(payload, block_number)
+            .map_err(|err| err.into())
+    }
 
     /// Executes a new message call immediately without creating a transaction
     /// on the block chain.
     async fn call(
-        &self,
-        request: TransactionRequest,
-        block_number: Option<BlockId>,
-        // state_overrides: Option<StateOverride>,
-        // block_overrides: Option<Box<BlockOverrides>>,
+        &self, request: TransactionRequest, block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes> {
-        let (execution, _estimation) =
-            self.exec_transaction(request, block_number)?;
+        let (execution, _estimation) = self.exec_transaction(
+            request,
+            block_number,
+            EvmOverrides::new(state_overrides, block_overrides),
+        )?;
 
         Ok(execution.output.into())
     }
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain
     /// index, with the optionality of state overrides
-    // async fn call_many(
-    //     &self,
-    //     bundle: Bundle,
-    //     state_context: Option<StateContext>,
-    //     state_override: Option<StateOverride>,
-    // ) -> RpcResult<Vec<EthCallResponse>>;
+    async fn call_many(
+        &self, bundle: Bundle, state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<EthCallResponse>> {
+        self.call_many(bundle, state_context, state_override)
+            .map_err(|err| err.into())
+    }
 
     /// Generates an access list for a transaction.
     ///
@@ -1273,22 +1738,24 @@ impl EthApiServer for EthApi {
     /// could change when the transaction is actually mined. Adding an
     /// accessList to your transaction does not necessary result in lower
     /// gas usage compared to a transaction without an access list.
-    // async fn create_access_list(
-    //     &self,
-    //     request: TransactionRequest,
-    //     block_number: Option<BlockId>,
-    // ) -> RpcResult<AccessListResult>;
+    async fn create_access_list(
+        &self, request: TransactionRequest, block_number: Option<BlockId>,
+    ) -> RpcResult<AccessListResult> {
+        self.create_access_list(request, block_number)
+            .map_err(|err| err.into())
+    }
 
     /// Generates and returns an estimate of how much gas is necessary to allow
     /// the transaction to complete.
     async fn estimate_gas(
-        &self,
-        request: TransactionRequest,
-        block_number: Option<BlockId>,
-        // state_override: Option<StateOverride>,
+        &self, request: TransactionRequest, block_number: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> RpcResult<U256> {
-        let (_, estimated_gas) =
-            self.exec_transaction(request, block_number)?;
+        let (_, estimated_gas) = self.exec_transaction(
+            request,
+            block_number,
+            EvmOverrides::new(state_override, None),
+        )?;
 
         Ok(estimated_gas)
     }
@@ -1376,25 +1843,8 @@ impl EthApiServer for EthApi {
 
     /// Sends signed transaction, returning its hash.
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
-        let tx = if let Ok(tx) =
-            TransactionWithSignature::from_raw(&bytes.into_vec())
-        {
-            tx
-        } else {
-            bail!(EthApiError::FailedToDecodeSignedTransaction)
-        };
-
-        if tx.space() != Space::Ethereum {
-            bail!(EthApiError::Other(
-                "Incorrect transaction space".to_string()
-            ));
-        }
-
-        if tx.recover_public().is_err() {
-            bail!(EthApiError::InvalidTransactionSignature);
-        }
-
-        let r = self.send_transaction_with_signature(tx)?;
+        let tx = decode_raw_transaction(bytes)?;
+        let r = self.send_transaction_with_signature(tx, false)?;
         Ok(r)
     }
 
@@ -1415,6 +1865,16 @@ impl EthApiServer for EthApi {
         Err(jsonrpsee_internal_error("Not implemented"))
     }
 
+    /// Returns the account and storage values of the specified account
+    /// including the Merkle-proof.
+    async fn get_proof(
+        &self, address: Address, keys: Vec<JsonStorageKey>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<AccountProof> {
+        self.get_proof(address, keys, block_number)
+            .map_err(|err| err.into())
+    }
+
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
         self.logs(filter).map_err(|err| err.into())
     }
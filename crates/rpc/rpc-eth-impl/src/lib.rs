@@ -1,3 +1,4 @@
+mod admin;
 mod debug;
 mod eth;
 mod filter;
@@ -9,8 +10,9 @@ mod trace;
 pub mod traits;
 mod web3;
 
+pub use admin::AdminApi;
 pub use debug::DebugApi;
-pub use eth::EthApi;
+pub use eth::{decode_raw_transaction, EthApi};
 pub use filter::EthFilterApi;
 pub use net::NetApi;
 pub use pubsub::PubSubApi;
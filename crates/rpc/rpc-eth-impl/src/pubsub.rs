@@ -7,12 +7,13 @@ use cfx_rpc_cfx_types::{traits::BlockProvider, PhantomBlock};
 use cfx_rpc_eth_api::EthPubSubApiServer;
 use cfx_rpc_eth_types::{
     eth_pubsub::{Kind as SubscriptionKind, Params, Result as PubSubResult},
-    Header, Log,
+    Header, Log, Transaction,
 };
 use cfx_rpc_utils::error::jsonrpsee_error_helpers::internal_rpc_err;
 use cfx_types::{Space, H256};
 use cfxcore::{
-    BlockDataManager, ConsensusGraph, Notifications, SharedConsensusGraph,
+    channel::Receiver, sync::SyncPhaseType, BlockDataManager, ConsensusGraph,
+    Notifications, SharedConsensusGraph,
 };
 use futures::StreamExt;
 use jsonrpsee::{
@@ -22,7 +23,8 @@ use jsonrpsee::{
 use log::{debug, error, info, trace, warn};
 use parking_lot::RwLock;
 use primitives::{
-    filter::LogFilter, log_entry::LocalizedLogEntry, BlockReceipts, EpochNumber,
+    filter::LogFilter, log_entry::LocalizedLogEntry, BlockReceipts,
+    EpochNumber, SignedTransaction,
 };
 use serde::Serialize;
 use std::{
@@ -312,6 +314,54 @@ impl EthPubSubApiServer for PubSubApi {
                 self.start_logs_loop(filter);
                 Ok(())
             }
+            (SubscriptionKind::NewPendingTransactions, params) => {
+                let full_transactions = match params {
+                    None => false,
+                    Some(Params::FullTransactions(full)) => full,
+                    Some(_) => {
+                        // reject
+                        return Err(
+                            "Params should be empty or a boolean".into()
+                        );
+                    }
+                };
+
+                let channel =
+                    self.notifications.new_pending_transactions.clone();
+                let receiver = channel.subscribe();
+                let receiver_id = receiver.id;
+                let stream =
+                    receiver_stream(receiver).flat_map(move |transactions| {
+                        futures::stream::iter(pending_transaction_results(
+                            transactions,
+                            full_transactions,
+                        ))
+                    });
+
+                let sink = pending.accept().await?;
+                self.executor.spawn(async move {
+                    let _ = pipe_from_stream(sink, stream).await;
+                    channel.unsubscribe(receiver_id);
+                });
+                Ok(())
+            }
+            (SubscriptionKind::Syncing, None) => {
+                let channel = self.notifications.sync_phase_changes.clone();
+                let receiver = channel.subscribe();
+                let receiver_id = receiver.id;
+                let stream = receiver_stream(receiver).map(|phase| {
+                    PubSubResult::SyncState {
+                        syncing: phase != SyncPhaseType::Normal,
+                    }
+                });
+
+                let sink = pending.accept().await?;
+                self.executor.spawn(async move {
+                    let _ = pipe_from_stream(sink, stream).await;
+                    channel.unsubscribe(receiver_id);
+                });
+                Ok(())
+            }
             (_, _) => {
                 // reject
                 Err("Not supported".into())
@@ -320,6 +370,37 @@ impl EthPubSubApiServer for PubSubApi {
     }
 }
 
+/// Turns a receiver of the `Notifications` channels into a stream.
+fn receiver_stream<T>(receiver: Receiver<T>) -> impl Stream<Item = T> + Unpin {
+    Box::pin(futures::stream::unfold(
+        receiver,
+        |mut receiver| async move {
+            receiver.recv().await.map(|item| (item, receiver))
+        },
+    ))
+}
+
+/// The results of the ethereum space transactions among the new pending ones.
+fn pending_transaction_results(
+    transactions: Vec<Arc<SignedTransaction>>, full_transactions: bool,
+) -> Vec<PubSubResult> {
+    transactions
+        .into_iter()
+        .filter(|tx| tx.space() == Space::Ethereum)
+        .map(|tx| {
+            if full_transactions {
+                PubSubResult::Transaction(Transaction::from_signed(
+                    &tx,
+                    (None, None, None),
+                    (None, None),
+                ))
+            } else {
+                PubSubResult::TransactionHash(tx.hash())
+            }
+        })
+        .collect()
+}
+
 pub struct ChainDataProvider {
     consensus: SharedConsensusGraph,
     data_man: Arc<BlockDataManager>,
@@ -595,3 +676,78 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::pending_transaction_results;
+    use cfx_rpc_eth_types::eth_pubsub::Result as PubSubResult;
+    use cfx_types::{Address, U256};
+    use cfxkey::{Generator, Random};
+    use primitives::{
+        transaction::{
+            native_transaction::NativeTransaction, Eip155Transaction,
+        },
+        Action, SignedTransaction, Transaction,
+    };
+    use std::sync::Arc;
+
+    fn new_test_tx(nonce: u64, native: bool) -> Arc<SignedTransaction> {
+        let tx: Transaction = if native {
+            NativeTransaction {
+                nonce: U256::from(nonce),
+                gas_price: U256::one(),
+                gas: U256::from(21000),
+                action: Action::Call(Address::random()),
+                value: U256::zero(),
+                storage_limit: 0,
+                epoch_height: 0,
+                chain_id: 1,
+                data: Vec::new(),
+            }
+            .into()
+        } else {
+            Eip155Transaction {
+                nonce: U256::from(nonce),
+                gas_price: U256::one(),
+                gas: U256::from(21000),
+                action: Action::Call(Address::random()),
+                value: U256::zero(),
+                chain_id: Some(1),
+                data: Vec::new(),
+            }
+            .into()
+        };
+        Arc::new(tx.sign(Random.generate().unwrap().secret()))
+    }
+
+    #[test]
+    fn test_pending_transaction_results() {
+        let txs = vec![
+            new_test_tx(0, false),
+            new_test_tx(1, true),
+            new_test_tx(2, false),
+        ];
+
+        // only the ethereum space transactions are published
+        let results = pending_transaction_results(txs.clone(), false);
+        assert_eq!(
+            results,
+            vec![
+                PubSubResult::TransactionHash(txs[0].hash()),
+                PubSubResult::TransactionHash(txs[2].hash()),
+            ]
+        );
+
+        let results = pending_transaction_results(txs.clone(), true);
+        assert_eq!(results.len(), 2);
+        for (result, tx) in results.iter().zip([&txs[0], &txs[2]]) {
+            match result {
+                PubSubResult::Transaction(rpc_tx) => {
+                    assert_eq!(rpc_tx.hash, tx.hash());
+                    assert_eq!(rpc_tx.nonce, *tx.nonce());
+                }
+                _ => panic!("expected a full transaction, got {:?}", result),
+            }
+        }
+    }
+}
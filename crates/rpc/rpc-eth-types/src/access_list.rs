@@ -0,0 +1,20 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_types::U256;
+use primitives::AccessList;
+use serde::Serialize;
+
+/// Response of `eth_createAccessList`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    /// Addresses and storage keys touched by the transaction, excluding the
+    /// sender and the precompiles
+    pub access_list: AccessList,
+    /// Estimated gas of the transaction with `access_list` attached
+    pub gas_used: U256,
+    /// Estimated gas of the transaction without an access list
+    pub gas_used_without_access_list: U256,
+}
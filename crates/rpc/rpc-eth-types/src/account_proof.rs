@@ -0,0 +1,69 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_internal_common::StateRootWithAuxInfo;
+use cfx_rpc_primitives::Bytes;
+use cfx_types::{H160, H256, U256};
+use primitives::StorageRoot;
+use serde::Serialize;
+
+/// Response of `eth_getProof`.
+///
+/// The shape follows EIP-1186, but Conflux keeps its state in up to three
+/// Merkle Patricia tries (snapshot, intermediate delta and delta) and stores
+/// contract storage in the same tries as accounts, so the proofs are encoded
+/// differently:
+///
+/// * `accountProof` and `storageProof[i].proof` are the RLP encoding of a
+///   `StateProof`, i.e. `[delta_proof, intermediate_proof, snapshot_proof]`
+///   where each item is either an empty list or a `TrieProof`.
+/// * `accountValue` and `storageProof[i].rawValue` are the raw trie entries the
+///   proofs are checked against (absent for non-existent entries).
+/// * `storageHash` is replaced by `storageRoot`, the node merkle of the
+///   account's storage subtree in each trie, which is proved by
+///   `storageRootProof` (RLP encoded `NodeMerkleProof`).
+/// * `stateRoot` contains the trie roots the proofs are checked against. Its
+///   hash is committed as `stateRoot` (`deferred_state_root`) of a later block,
+///   usually the block `DEFERRED_STATE_EPOCH_COUNT` blocks after the requested
+///   one.
+///
+/// See `cfx_storage::verify_state_proof` for verification.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    /// The requested account
+    pub address: H160,
+    /// Account balance
+    pub balance: U256,
+    /// Hash of the account code
+    pub code_hash: H256,
+    /// Account nonce
+    pub nonce: U256,
+    /// Raw state entry of the account
+    pub account_value: Option<Bytes>,
+    /// RLP encoded proof of the account entry
+    pub account_proof: Bytes,
+    /// Node merkle of the account storage in each trie
+    pub storage_root: StorageRoot,
+    /// RLP encoded proof of `storage_root`
+    pub storage_root_proof: Bytes,
+    /// Proofs of the requested storage entries
+    pub storage_proof: Vec<StorageProof>,
+    /// Roots of the state the proofs are checked against
+    pub state_root: StateRootWithAuxInfo,
+}
+
+/// Proof of a single storage entry, see [`AccountProof`].
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+    /// Storage key
+    pub key: U256,
+    /// Storage value
+    pub value: U256,
+    /// Raw state entry of the storage slot
+    pub raw_value: Option<Bytes>,
+    /// RLP encoded proof of the storage entry
+    pub proof: Bytes,
+}
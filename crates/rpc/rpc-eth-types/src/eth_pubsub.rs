@@ -4,14 +4,14 @@
 
 //! Pub-Sub types.
 
-use super::{EthRpcLogFilter, Header, Log};
+use super::{EthRpcLogFilter, Header, Log, Transaction};
 use cfx_types::H256;
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use serde_json::{from_value, Value};
 
 /// Subscription result.
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged, rename_all = "camelCase")]
 // NOTE: rename_all does not apply to enum member fields
 // see: https://github.com/serde-rs/serde/issues/1061
@@ -24,6 +24,12 @@ pub enum Result {
 
     /// Transaction hash
     TransactionHash(H256),
+
+    /// Transaction
+    Transaction(Transaction),
+
+    /// Sync status
+    SyncState { syncing: bool },
 }
 
 /// Subscription kind.
@@ -48,6 +54,8 @@ pub enum Params {
     None,
     /// Log parameters.
     Logs(EthRpcLogFilter),
+    /// Whether to return full transactions instead of transaction hashes.
+    FullTransactions(bool),
 }
 
 impl Default for Params {
@@ -65,9 +73,37 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Params::None);
         }
 
+        if let Value::Bool(full_transactions) = v {
+            return Ok(Params::FullTransactions(full_transactions));
+        }
+
         // try to interpret as a log filter
         from_value(v.clone()).map(Params::Logs).map_err(|e| {
             D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e))
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Params;
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_params() {
+        let params: Params = serde_json::from_str("null").unwrap();
+        assert_eq!(params, Params::None);
+
+        let params: Params = serde_json::from_str("true").unwrap();
+        assert_eq!(params, Params::FullTransactions(true));
+
+        let params: Params = serde_json::from_str("false").unwrap();
+        assert_eq!(params, Params::FullTransactions(false));
+
+        let params: Params = serde_json::from_str("{}").unwrap();
+        assert!(matches!(params, Params::Logs(_)));
+
+        assert!(serde_json::from_str::<Params>("1").is_err());
+        assert!(serde_json::from_str::<Params>(r#""true""#).is_err());
+    }
+}
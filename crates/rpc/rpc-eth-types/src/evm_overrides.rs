@@ -0,0 +1,129 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use cfx_rpc_primitives::Bytes;
+use cfx_types::{Address, H256, U256, U64};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A set of account overrides, keyed by address.
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+/// Custom account fields used in `eth_call` and `eth_estimateGas`, compatible
+/// with Geth.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountOverride {
+    /// Fake balance to set for the account before executing the call
+    pub balance: Option<U256>,
+    /// Fake nonce to set for the account before executing the call
+    pub nonce: Option<U64>,
+    /// Fake EVM bytecode to inject into the account before executing the call
+    pub code: Option<Bytes>,
+    /// Fake key-value mapping to override all slots in the account storage
+    pub state: Option<HashMap<H256, H256>>,
+    /// Fake key-value mapping to override individual slots in the account
+    /// storage
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+/// Custom block fields used in `eth_call` and `eth_estimateGas`, compatible
+/// with Geth.
+///
+/// The overridden fields only change what the executed code observes, the
+/// hardfork rules are still decided by the requested block.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlockOverrides {
+    /// Overrides the block number, i.e. the epoch number in Conflux
+    pub number: Option<U256>,
+    /// Overrides the difficulty of the block
+    pub difficulty: Option<U256>,
+    /// Overrides the timestamp of the block
+    pub time: Option<U64>,
+    /// Overrides the gas limit of the block
+    pub gas_limit: Option<U64>,
+    /// Overrides the coinbase address of the block
+    #[serde(alias = "feeRecipient")]
+    pub coinbase: Option<Address>,
+    /// Overrides the prevrandao of the block, which is the difficulty in
+    /// Conflux
+    #[serde(alias = "prevRandao")]
+    pub random: Option<H256>,
+    /// Overrides the base fee of the block
+    #[serde(alias = "baseFeePerGas")]
+    pub base_fee: Option<U256>,
+}
+
+/// Overrides applied to the state and the block environment of a virtual
+/// call.
+#[derive(Debug, Default, Clone)]
+pub struct EvmOverrides {
+    pub state: Option<StateOverride>,
+    pub block: Option<Box<BlockOverrides>>,
+}
+
+impl EvmOverrides {
+    pub fn new(
+        state: Option<StateOverride>, block: Option<Box<BlockOverrides>>,
+    ) -> Self {
+        EvmOverrides { state, block }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.state.is_none() && self.block.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn state_override_deserialization() {
+        let s = r#"{
+            "0x0000000000000000000000000000000000000124": {
+                "balance": "0x10",
+                "nonce": "0x1",
+                "code": "0x6080",
+                "stateDiff": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                }
+            }
+        }"#;
+        let state_override: StateOverride = serde_json::from_str(s).unwrap();
+        let address =
+            Address::from_str("0000000000000000000000000000000000000124")
+                .unwrap();
+        let account = &state_override[&address];
+
+        assert_eq!(account.balance, Some(U256::from(0x10)));
+        assert_eq!(account.nonce, Some(U64::from(1)));
+        assert_eq!(account.code, Some(Bytes::new(vec![0x60, 0x80])));
+        assert_eq!(account.state, None);
+        assert_eq!(
+            account.state_diff.as_ref().unwrap()[&H256::from_low_u64_be(1)],
+            H256::from_low_u64_be(2)
+        );
+    }
+
+    #[test]
+    fn block_overrides_deserialization() {
+        let s = r#"{"number": "0x10", "time": "0x20", "feeRecipient": "0x0000000000000000000000000000000000000124"}"#;
+        let block_overrides: BlockOverrides = serde_json::from_str(s).unwrap();
+
+        assert_eq!(block_overrides.number, Some(U256::from(0x10)));
+        assert_eq!(block_overrides.time, Some(U64::from(0x20)));
+        assert_eq!(
+            block_overrides.coinbase,
+            Some(Address::from_low_u64_be(0x124))
+        );
+        assert_eq!(block_overrides.base_fee, None);
+
+        assert!(
+            serde_json::from_str::<BlockOverrides>(r#"{"foo": 1}"#).is_err()
+        );
+    }
+}
@@ -1,27 +1,43 @@
+mod access_list;
+mod account_proof;
 mod block;
 mod block_number;
 mod errors;
 pub mod eth_pubsub;
+mod evm_overrides;
 mod fee_history;
 mod filter;
 mod log;
 mod receipt;
+mod simulate;
 mod sync;
 pub mod trace_filter;
 mod transaction;
 mod transaction_request;
 mod tx_pool;
 
-pub use block::{Block, Header};
+pub use access_list::AccessListResult;
+pub use account_proof::{AccountProof, StorageProof};
+pub use block::{Block, BlockTransactions, Header};
 pub use block_number::BlockNumber;
-pub use cfx_rpc_cfx_types::trace_eth::*;
+pub use cfx_rpc_cfx_types::{
+    admin::{NodeConfig, NodeInfo, PeerInfo},
+    trace_eth::*,
+};
 pub use cfx_rpc_primitives::{Bytes, U64};
 pub use errors::Error;
 pub use eth_pubsub::*;
+pub use evm_overrides::{
+    AccountOverride, BlockOverrides, EvmOverrides, StateOverride,
+};
 pub use fee_history::FeeHistory;
 pub use filter::*;
 pub use log::Log;
 pub use receipt::Receipt;
+pub use simulate::{
+    Bundle, EthCallResponse, SimBlock, SimCallResult, SimulateError,
+    SimulatePayload, SimulatedBlock, StateContext, TransactionIndex,
+};
 pub use sync::{SyncInfo, SyncStatus};
 pub use trace_filter::TraceFilter;
 pub use transaction::Transaction;
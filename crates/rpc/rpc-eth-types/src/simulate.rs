@@ -0,0 +1,203 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use crate::{
+    block::BlockTransactions, BlockNumber, BlockOverrides, Log, StateOverride,
+    TransactionRequest,
+};
+use cfx_rpc_primitives::Bytes;
+use cfx_types::{H160, U256, U64};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// Bundle of transactions executed in sequence by `eth_callMany`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Bundle {
+    /// All transactions to execute
+    pub transactions: Vec<TransactionRequest>,
+    /// Block overrides to apply
+    pub block_override: Option<BlockOverrides>,
+}
+
+/// State context of `eth_callMany`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StateContext {
+    /// Block number, the latest state is used if not specified
+    pub block_number: Option<BlockNumber>,
+    /// Position of the bundle within the transactions of the block
+    pub transaction_index: Option<TransactionIndex>,
+}
+
+/// Position of a bundle within the transactions of a block. `-1` means the
+/// bundle is executed after all transactions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionIndex {
+    #[default]
+    All,
+    Index(usize),
+}
+
+impl TransactionIndex {
+    pub fn is_all(&self) -> bool { matches!(self, TransactionIndex::All) }
+}
+
+impl<'a> Deserialize<'a> for TransactionIndex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'a> {
+        match i64::deserialize(deserializer)? {
+            -1 => Ok(TransactionIndex::All),
+            index if index >= 0 => Ok(TransactionIndex::Index(index as usize)),
+            index => Err(D::Error::custom(format!(
+                "invalid transaction index {}, expected -1 or a non-negative integer",
+                index
+            ))),
+        }
+    }
+}
+
+/// Result of a transaction in `eth_callMany`.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallResponse {
+    /// Return data if the transaction succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// Error message if the transaction failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Gas used by the transaction
+    pub gas_used: U256,
+    /// Logs emitted by the transaction
+    pub logs: Vec<Log>,
+}
+
+/// Request of `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// Blocks to simulate in sequence
+    pub block_state_calls: Vec<SimBlock>,
+    /// Adds ETH transfers as logs, which is not supported yet
+    pub trace_transfers: bool,
+    /// Checks the nonce, the balance and the base fee of the calls like
+    /// executing real transactions
+    pub validation: bool,
+    /// Returns full transactions instead of hashes in the simulated blocks
+    pub return_full_transactions: bool,
+}
+
+/// A block of calls in `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Block overrides to apply
+    pub block_overrides: Option<BlockOverrides>,
+    /// State overrides applied before the calls of the block
+    pub state_overrides: Option<StateOverride>,
+    /// Calls to execute in sequence
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// A simulated block returned by `eth_simulateV1`.
+///
+/// Simulated blocks are never sealed, so they have no hash and no state
+/// root.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// Block number, i.e. the epoch number in Conflux
+    pub number: U256,
+    /// Timestamp
+    pub timestamp: U256,
+    /// Gas Limit
+    pub gas_limit: U256,
+    /// Gas Used
+    pub gas_used: U256,
+    /// Authors address
+    pub miner: H160,
+    /// Base fee
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    /// Transactions
+    pub transactions: BlockTransactions,
+    /// Results of the calls
+    pub calls: Vec<SimCallResult>,
+}
+
+/// Result of a call in `eth_simulateV1`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// Return data
+    pub return_data: Bytes,
+    /// Logs emitted by the call
+    pub logs: Vec<Log>,
+    /// Gas used by the call
+    pub gas_used: U256,
+    /// Status, 1 for success and 0 for failure
+    pub status: U64,
+    /// Error if the call failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+/// Error of a failed call in `eth_simulateV1`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulateError {
+    /// Error code
+    pub code: i32,
+    /// Error message
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_context_deserialization() {
+        let s = r#"{"blockNumber": "0x10", "transactionIndex": -1}"#;
+        let context: StateContext = serde_json::from_str(s).unwrap();
+        assert_eq!(context.block_number, Some(BlockNumber::Num(0x10)));
+        assert_eq!(context.transaction_index, Some(TransactionIndex::All));
+
+        let s = r#"{"transactionIndex": 2}"#;
+        let context: StateContext = serde_json::from_str(s).unwrap();
+        assert_eq!(context.block_number, None);
+        assert_eq!(context.transaction_index, Some(TransactionIndex::Index(2)));
+
+        let s = r#"{"transactionIndex": -2}"#;
+        assert!(serde_json::from_str::<StateContext>(s).is_err());
+    }
+
+    #[test]
+    fn simulate_payload_deserialization() {
+        let s = r#"{
+            "blockStateCalls": [{
+                "blockOverrides": {"number": "0x20"},
+                "stateOverrides": {
+                    "0x0000000000000000000000000000000000000124": {"balance": "0x10"}
+                },
+                "calls": [{"to": "0x0000000000000000000000000000000000000124"}]
+            }, {}],
+            "validation": true
+        }"#;
+        let payload: SimulatePayload = serde_json::from_str(s).unwrap();
+
+        assert!(payload.validation);
+        assert!(!payload.trace_transfers);
+        assert!(!payload.return_full_transactions);
+        assert_eq!(payload.block_state_calls.len(), 2);
+
+        let block = &payload.block_state_calls[0];
+        assert_eq!(
+            block.block_overrides.as_ref().unwrap().number,
+            Some(U256::from(0x20))
+        );
+        assert_eq!(block.state_overrides.as_ref().unwrap().len(), 1);
+        assert_eq!(block.calls.len(), 1);
+        assert!(payload.block_state_calls[1].calls.is_empty());
+    }
+}
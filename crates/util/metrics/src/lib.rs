@@ -13,6 +13,7 @@ mod queue;
 mod registry;
 mod report;
 mod report_influxdb;
+mod report_prometheus;
 mod timer;
 
 pub use self::{
@@ -27,5 +28,9 @@ pub use self::{
         GroupingRegistry, Registry, DEFAULT_GROUPING_REGISTRY, DEFAULT_REGISTRY,
     },
     report::{report_async, Reportable, Reporter},
+    report_prometheus::{
+        add_prometheus_exposition, prometheus_text, MetricFamilies,
+        PrometheusReportable,
+    },
     timer::{register_timer, register_timer_with_group, ScopeTimer, Timer},
 };
@@ -5,10 +5,12 @@
 use crate::{
     report::{report_async, FileReporter, Reportable},
     report_influxdb::{InfluxdbReportable, InfluxdbReporter},
+    report_prometheus::{start_prometheus_server, PrometheusReportable},
 };
 use duration_str::deserialize_duration;
 use serde::{Deserialize, Serialize};
 use std::{
+    net::SocketAddr,
     sync::atomic::{AtomicBool, Ordering},
     time::Duration,
 };
@@ -21,7 +23,9 @@ pub fn is_enabled() -> bool { ENABLED.load(ORDER) }
 
 fn enable() { ENABLED.store(true, ORDER); }
 
-pub trait Metric: Send + Sync + Reportable + InfluxdbReportable {
+pub trait Metric:
+    Send + Sync + Reportable + InfluxdbReportable + PrometheusReportable
+{
     fn get_type(&self) -> &str;
 }
 
@@ -40,6 +44,8 @@ pub struct MetricsConfiguration {
     pub influxdb_report_username: Option<String>,
     pub influxdb_report_password: Option<String>,
     pub influxdb_report_node: Option<String>,
+
+    pub prometheus_listen_address: Option<SocketAddr>,
 }
 
 impl Default for MetricsConfiguration {
@@ -53,6 +59,7 @@ impl Default for MetricsConfiguration {
             influxdb_report_username: None,
             influxdb_report_password: None,
             influxdb_report_node: None,
+            prometheus_listen_address: None,
         }
     }
 }
@@ -96,4 +103,9 @@ pub fn initialize(config: MetricsConfiguration) {
 
         report_async(reporter, config.report_interval);
     }
+
+    // prometheus endpoint
+    if let Some(address) = config.prometheus_listen_address {
+        start_prometheus_server(address);
+    }
 }
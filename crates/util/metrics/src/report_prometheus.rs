@@ -0,0 +1,496 @@
+// Copyright 2024 Conflux Foundation. All rights reserved.
+// Conflux is free software and distributed under GNU General Public License.
+// See http://www.gnu.org/licenses/
+
+use crate::{
+    counter::{Counter, CounterUsize},
+    gauge::{Gauge, GaugeUsize},
+    histogram::Histogram,
+    meter::{Meter, StandardMeter},
+    registry::{DEFAULT_GROUPING_REGISTRY, DEFAULT_REGISTRY},
+};
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use parking_lot::RwLock;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Write as _},
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+const METRIC_NAME_PREFIX: &str = "conflux_";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+const REQUEST_TIMEOUT_SECONDS: u64 = 10;
+const QUANTILES: [(f64, &str); 6] = [
+    (0.5, "0.5"),
+    (0.75, "0.75"),
+    (0.9, "0.9"),
+    (0.95, "0.95"),
+    (0.99, "0.99"),
+    (0.999, "0.999"),
+];
+
+type Exposition = Box<dyn Fn() -> Vec<u8> + Send + Sync>;
+
+lazy_static! {
+    static ref EXTERNAL_EXPOSITIONS: RwLock<Vec<Exposition>> =
+        RwLock::new(Vec::new());
+}
+
+/// Adds the metrics outside the registries to the `/metrics` endpoint, e.g.
+/// the ones registered with the `prometheus` crate. `exposition` returns them
+/// in the Prometheus text format.
+pub fn add_prometheus_exposition(
+    exposition: impl Fn() -> Vec<u8> + Send + Sync + 'static,
+) {
+    EXTERNAL_EXPOSITIONS.write().push(Box::new(exposition));
+}
+
+/// Renders all the metrics in the registries in the Prometheus text format.
+/// The metrics registered in a group are labeled with the group name.
+pub fn prometheus_text() -> String {
+    let mut families = MetricFamilies::default();
+
+    for (name, metric) in DEFAULT_REGISTRY.read().get_all() {
+        metric.add_samples(&metric_name(name), &[], &mut families);
+    }
+
+    for (group_name, metrics) in DEFAULT_GROUPING_REGISTRY.read().get_all() {
+        for (name, metric) in metrics {
+            metric.add_samples(
+                &metric_name(name),
+                &[("group", group_name.as_str())],
+                &mut families,
+            );
+        }
+    }
+
+    families.render()
+}
+
+/// Serves `/metrics` on `address` in a background thread.
+pub fn start_prometheus_server(address: SocketAddr) {
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("failed to bind prometheus endpoint {}: {:?}", address, e);
+            return;
+        }
+    };
+    info!("Prometheus endpoint started on {}", address);
+
+    thread::Builder::new()
+        .name("prometheus".into())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        if let Err(e) = handle_request(stream) {
+                            debug!("failed to serve metrics, {:?}", e);
+                        }
+                    }
+                    Err(e) => debug!("failed to accept connection, {:?}", e),
+                }
+            }
+        })
+        .expect("spawn prometheus thread");
+}
+
+fn handle_request(mut stream: TcpStream) -> std::io::Result<()> {
+    stream
+        .set_read_timeout(Some(Duration::from_secs(REQUEST_TIMEOUT_SECONDS)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The headers are consumed before the response.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let mut body = prometheus_text().into_bytes();
+            for exposition in EXTERNAL_EXPOSITIONS.read().iter() {
+                body.extend(exposition());
+            }
+            ("200 OK", body)
+        }
+        _ => ("404 Not Found", Vec::new()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        CONTENT_TYPE,
+        body.len()
+    )?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
+
+/// Metric names may only contain ASCII letters, digits and underscores.
+fn metric_name(name: &str) -> String {
+    let mut metric_name = String::from(METRIC_NAME_PREFIX);
+    metric_name.extend(name.chars().map(|c| {
+        if c.is_ascii_alphanumeric() {
+            c
+        } else {
+            '_'
+        }
+    }));
+    metric_name
+}
+
+/// The samples of the metrics, grouped by the metric families, as a family
+/// must be rendered in a single block.
+#[derive(Default)]
+pub struct MetricFamilies {
+    families: BTreeMap<String, MetricFamily>,
+    /// The family of each sample name, e.g. `x_sum` and `x_count` belong to
+    /// the summary `x`, so no other family may be named after them.
+    sample_names: HashMap<String, String>,
+}
+
+struct MetricFamily {
+    metric_type: &'static str,
+    samples: Vec<String>,
+}
+
+impl MetricFamilies {
+    fn add_sample(
+        &mut self, family: &str, metric_type: &'static str, suffix: &str,
+        labels: &[(&str, &str)], value: impl Display,
+    ) {
+        // Different metrics may be sanitized to the same name. The samples
+        // that conflict with an existing family are dropped, as a family
+        // can only have one type.
+        if let Some(existing) = self.families.get(family) {
+            if existing.metric_type != metric_type {
+                debug!(
+                    "drop {} sample of {}, already reported as {}",
+                    metric_type, family, existing.metric_type
+                );
+                return;
+            }
+        }
+        let sample_name = format!("{}{}", family, suffix);
+        match self.sample_names.get(&sample_name) {
+            Some(owner) if owner != family => {
+                debug!(
+                    "drop sample {} of {}, already reported by {}",
+                    sample_name, family, owner
+                );
+                return;
+            }
+            Some(_) => {}
+            None => {
+                self.sample_names.insert(sample_name.clone(), family.into());
+            }
+        }
+
+        let mut sample = sample_name;
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(name, value)| {
+                    format!(
+                        "{}=\"{}\"",
+                        name,
+                        value
+                            .replace('\\', "\\\\")
+                            .replace('"', "\\\"")
+                            .replace('\n', "\\n")
+                    )
+                })
+                .collect();
+            write!(sample, "{{{}}}", labels.join(",")).unwrap();
+        }
+        write!(sample, " {}", value).unwrap();
+
+        self.families
+            .entry(family.into())
+            .or_insert_with(|| MetricFamily {
+                metric_type,
+                samples: Vec::new(),
+            })
+            .samples
+            .push(sample);
+    }
+
+    fn render(&self) -> String {
+        let mut text = String::new();
+        for (name, family) in &self.families {
+            writeln!(text, "# TYPE {} {}", name, family.metric_type).unwrap();
+            for sample in &family.samples {
+                writeln!(text, "{}", sample).unwrap();
+            }
+        }
+        text
+    }
+}
+
+pub trait PrometheusReportable {
+    fn add_samples(
+        &self, name: &str, labels: &[(&str, &str)],
+        families: &mut MetricFamilies,
+    );
+}
+
+impl PrometheusReportable for CounterUsize {
+    fn add_samples(
+        &self, name: &str, labels: &[(&str, &str)],
+        families: &mut MetricFamilies,
+    ) {
+        families.add_sample(name, "counter", "", labels, self.count());
+    }
+}
+
+impl PrometheusReportable for GaugeUsize {
+    fn add_samples(
+        &self, name: &str, labels: &[(&str, &str)],
+        families: &mut MetricFamilies,
+    ) {
+        families.add_sample(name, "gauge", "", labels, self.value());
+    }
+}
+
+impl PrometheusReportable for StandardMeter {
+    fn add_samples(
+        &self, name: &str, labels: &[(&str, &str)],
+        families: &mut MetricFamilies,
+    ) {
+        add_meter_samples(&*self.snapshot(), name, labels, families);
+    }
+}
+
+fn add_meter_samples(
+    snapshot: &dyn Meter, name: &str, labels: &[(&str, &str)],
+    families: &mut MetricFamilies,
+) {
+    families.add_sample(
+        &format!("{}_count", name),
+        "counter",
+        "",
+        labels,
+        snapshot.count(),
+    );
+
+    let rate_family = format!("{}_rate", name);
+    for (window, rate) in [
+        ("1m", snapshot.rate1()),
+        ("5m", snapshot.rate5()),
+        ("15m", snapshot.rate15()),
+        ("mean", snapshot.rate_mean()),
+    ] {
+        let mut rate_labels = labels.to_vec();
+        rate_labels.push(("window", window));
+        families.add_sample(&rate_family, "gauge", "", &rate_labels, rate);
+    }
+}
+
+/// Histograms are reported as summaries with the quantiles of their samples.
+impl<T: Histogram> PrometheusReportable for T {
+    fn add_samples(
+        &self, name: &str, labels: &[(&str, &str)],
+        families: &mut MetricFamilies,
+    ) {
+        let snapshot = self.snapshot();
+        for (quantile, quantile_label) in QUANTILES {
+            let mut quantile_labels = labels.to_vec();
+            quantile_labels.push(("quantile", quantile_label));
+            families.add_sample(
+                name,
+                "summary",
+                "",
+                &quantile_labels,
+                snapshot.percentile(quantile),
+            );
+        }
+        // The sum is estimated from the samples to be consistent with the
+        // count of all the updates.
+        families.add_sample(
+            name,
+            "summary",
+            "_sum",
+            labels,
+            snapshot.mean() * snapshot.count() as f64,
+        );
+        families.add_sample(
+            name,
+            "summary",
+            "_count",
+            labels,
+            snapshot.count(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct FixedMeter;
+
+    impl Meter for FixedMeter {
+        fn count(&self) -> usize { 7 }
+
+        fn rate1(&self) -> f64 { 1.5 }
+
+        fn rate5(&self) -> f64 { 2.0 }
+
+        fn rate15(&self) -> f64 { 0.25 }
+
+        fn rate_mean(&self) -> f64 { 3.0 }
+    }
+
+    #[derive(Clone)]
+    struct FixedHistogram;
+
+    impl Histogram for FixedHistogram {
+        fn count(&self) -> usize { 4 }
+
+        fn mean(&self) -> f64 { 2.5 }
+
+        fn percentile(&self, p: f64) -> u64 { (p * 1000.0).round() as u64 }
+
+        fn snapshot(&self) -> Arc<dyn Histogram> { Arc::new(self.clone()) }
+    }
+
+    #[test]
+    fn test_metric_name() {
+        assert_eq!(metric_name("tx_pool"), "conflux_tx_pool");
+        assert_eq!(
+            metric_name("sync::recv-block.size"),
+            "conflux_sync__recv_block_size"
+        );
+        assert_eq!(metric_name("größe"), "conflux_gr__e");
+    }
+
+    #[test]
+    fn test_label_escaping() {
+        let mut families = MetricFamilies::default();
+        families.add_sample(
+            "conflux_x",
+            "gauge",
+            "",
+            &[
+                ("a", "back\\slash"),
+                ("b", "\"quoted\""),
+                ("c", "new\nline"),
+            ],
+            1,
+        );
+        assert_eq!(
+            families.render(),
+            "# TYPE conflux_x gauge\n\
+             conflux_x{a=\"back\\\\slash\",b=\"\\\"quoted\\\"\",c=\"new\\nline\"} 1\n"
+        );
+    }
+
+    #[test]
+    fn test_counter_and_gauge() {
+        let counter = CounterUsize::default();
+        counter.inc(3);
+        let gauge = GaugeUsize::default();
+        gauge.update(42);
+
+        let mut families = MetricFamilies::default();
+        counter.add_samples("conflux_counter", &[], &mut families);
+        gauge.add_samples("conflux_gauge", &[("k", "v")], &mut families);
+        assert_eq!(
+            families.render(),
+            "# TYPE conflux_counter counter\n\
+             conflux_counter 3\n\
+             # TYPE conflux_gauge gauge\n\
+             conflux_gauge{k=\"v\"} 42\n"
+        );
+    }
+
+    #[test]
+    fn test_meter() {
+        let mut families = MetricFamilies::default();
+        add_meter_samples(&FixedMeter, "conflux_meter", &[], &mut families);
+        assert_eq!(
+            families.render(),
+            "# TYPE conflux_meter_count counter\n\
+             conflux_meter_count 7\n\
+             # TYPE conflux_meter_rate gauge\n\
+             conflux_meter_rate{window=\"1m\"} 1.5\n\
+             conflux_meter_rate{window=\"5m\"} 2\n\
+             conflux_meter_rate{window=\"15m\"} 0.25\n\
+             conflux_meter_rate{window=\"mean\"} 3\n"
+        );
+    }
+
+    #[test]
+    fn test_histogram_as_summary() {
+        let mut families = MetricFamilies::default();
+        FixedHistogram.add_samples("conflux_histogram", &[], &mut families);
+        assert_eq!(
+            families.render(),
+            "# TYPE conflux_histogram summary\n\
+             conflux_histogram{quantile=\"0.5\"} 500\n\
+             conflux_histogram{quantile=\"0.75\"} 750\n\
+             conflux_histogram{quantile=\"0.9\"} 900\n\
+             conflux_histogram{quantile=\"0.95\"} 950\n\
+             conflux_histogram{quantile=\"0.99\"} 990\n\
+             conflux_histogram{quantile=\"0.999\"} 999\n\
+             conflux_histogram_sum 10\n\
+             conflux_histogram_count 4\n"
+        );
+    }
+
+    #[test]
+    fn test_conflicting_families() {
+        let mut families = MetricFamilies::default();
+        // `a.b` and `a_b` are sanitized to the same name.
+        let counter = CounterUsize::default();
+        counter.inc(1);
+        counter.add_samples(&metric_name("a.b"), &[], &mut families);
+        let gauge = GaugeUsize::default();
+        gauge.update(2);
+        gauge.add_samples(&metric_name("a_b"), &[], &mut families);
+        // The meter `x` has a family named like the count of the summary `x`.
+        add_meter_samples(&FixedMeter, "conflux_x", &[], &mut families);
+        FixedHistogram.add_samples("conflux_x", &[], &mut families);
+
+        let text = families.render();
+        assert_eq!(text.matches("# TYPE conflux_a_b ").count(), 1);
+        assert!(text.contains("# TYPE conflux_a_b counter\nconflux_a_b 1\n"));
+        assert_eq!(
+            text.lines()
+                .filter(|line| line.starts_with("conflux_x_count"))
+                .count(),
+            1
+        );
+        assert!(text.contains("conflux_x_count 7\n"));
+        assert!(text.contains("conflux_x_sum 10\n"));
+    }
+
+    #[test]
+    fn test_grouped_metrics_of_different_types() {
+        let mut families = MetricFamilies::default();
+        let counter = CounterUsize::default();
+        counter.inc(1);
+        counter.add_samples("conflux_n", &[("group", "a")], &mut families);
+        counter.add_samples("conflux_n", &[("group", "b")], &mut families);
+        // The same name in another group with another type is dropped.
+        let gauge = GaugeUsize::default();
+        gauge.update(2);
+        gauge.add_samples("conflux_n", &[("group", "c")], &mut families);
+
+        assert_eq!(
+            families.render(),
+            "# TYPE conflux_n counter\n\
+             conflux_n{group=\"a\"} 1\n\
+             conflux_n{group=\"b\"} 1\n"
+        );
+    }
+}